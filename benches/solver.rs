@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Benchmarks of the board operations and the solver, run them with `cargo bench`
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use std::time::Duration;
+use wiggers_graaf::board::{
+    collect_valid_moves, get_solved_board, get_start_board, get_valid_moves, is_solution,
+    make_move, to_id, Coordinates, SlideDirection, SlideMove,
+};
+use wiggers_graaf::graph::Graph;
+use wiggers_graaf::solver::Solver;
+
+fn board_benchmarks(c: &mut Criterion) {
+    let board = get_start_board();
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+
+    c.bench_function("get_valid_moves", |b| {
+        b.iter(|| collect_valid_moves(black_box(&board)))
+    });
+    c.bench_function("make_move", |b| {
+        b.iter(|| make_move(black_box(&board), black_box(&slide_move)))
+    });
+    c.bench_function("to_id", |b| b.iter(|| to_id(black_box(&board))));
+}
+
+/// Build the graph of the standard puzzle like the solver does, but leave it unanalyzed
+fn build_graph() -> Graph {
+    let mut graph = Graph::new();
+    let solution = get_solved_board();
+    graph.add_node(solution).unwrap();
+    graph.add_node(get_start_board()).unwrap();
+
+    let mut inspection_queue = vec![get_start_board()];
+    while let Some(board) = inspection_queue.pop() {
+        for (slide_move, new_board) in get_valid_moves(&board) {
+            if !graph.contains_node(&new_board) {
+                inspection_queue.push(new_board);
+            }
+            graph.add_edge(&board, &new_board, &slide_move).unwrap();
+        }
+        if is_solution(&board) {
+            let slide_move = SlideMove {
+                start: Coordinates { x: 1, y: 0 },
+                direction: SlideDirection::Down,
+                distance: 1,
+            };
+            graph.add_edge(&board, &solution, &slide_move).unwrap();
+            graph.add_edge(&solution, &board, &slide_move).unwrap();
+        }
+    }
+    graph
+}
+
+fn solver_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solver");
+    // Solving takes seconds, so take the fewest samples that criterion allows
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(60));
+
+    group.bench_function("Solver::new", |b| b.iter(Solver::new));
+
+    let graph = build_graph();
+    group.bench_function("Graph::analyze", |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |mut graph| graph.analyze(&get_start_board(), &get_solved_board()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, board_benchmarks, solver_benchmarks);
+criterion_main!(benches);
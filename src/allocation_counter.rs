@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! A global allocator that counts heap allocations, only installed for native builds with the
+//! count-allocations feature enabled.
+
+#[cfg(all(feature = "count-allocations", not(target_arch = "wasm32")))]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Forwards to the system allocator, while counting the number of allocations
+#[cfg(all(feature = "count-allocations", not(target_arch = "wasm32")))]
+pub struct CountingAllocator;
+
+#[cfg(all(feature = "count-allocations", not(target_arch = "wasm32")))]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[cfg(all(feature = "count-allocations", not(target_arch = "wasm32")))]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Get the total number of allocations so far, None if the counting allocator is not installed
+pub fn allocation_count() -> Option<u64> {
+    if cfg!(all(
+        feature = "count-allocations",
+        not(target_arch = "wasm32")
+    )) {
+        Some(ALLOCATIONS.load(Ordering::Relaxed))
+    } else {
+        None
+    }
+}
@@ -7,22 +7,24 @@ mod unittest;
 use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use std::cmp::{Ordering, PartialEq};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::ops::Range;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Coordinates {
-    pub x: i32,
-    pub y: i32,
+    pub x: i8,
+    pub y: i8,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Size {
-    pub x: i32,
-    pub y: i32,
+    pub x: u8,
+    pub y: u8,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Axis {
     Horizontal,
     Vertical,
@@ -43,7 +45,7 @@ pub struct SlideMove {
     pub distance: u8,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Piece {
     /// The coordinates of the piece's bottom left most tile
     pub position: Coordinates,
@@ -51,11 +53,49 @@ pub struct Piece {
     pub size: Size,
 }
 
+/// What a cell of the board does to the pieces that slide over it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Normal,
+    /// Pieces may only slide over the cell in its direction
+    OneWay(SlideDirection),
+    /// Pieces can't stop on ice, they slide on until they are blocked
+    Ice,
+}
+
+/// The cells of a board that aren't normal, as bit masks with a bit per cell
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Cells {
+    /// The one-way cells, for each direction in the order of DIRECTIONS
+    pub(crate) one_way: [u32; 4],
+    pub(crate) ice: u32,
+}
+
+/// The most cells that a board with special cells can have, one for each bit of the masks
+pub const MAX_CELLS: usize = 32;
+
+/// The most checkpoints that a puzzle can have
+pub const MAX_CHECKPOINTS: usize = 4;
+
+/// The checkpoints of a puzzle, sub-goals that must be reached in order before reaching the goal
+/// counts, and how many of them were reached. A checkpoint is reached when a piece of its size
+/// is at its position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct GoalProgress {
+    checkpoints: [Piece; MAX_CHECKPOINTS],
+    count: u8,
+    reached: u8,
+}
+
 /// A game board filled with all tiles
 #[derive(Debug, Clone, Copy)]
 pub struct Board {
     pub size: Size,
     pub pieces: [Piece; 10],
+    pub cells: Cells,
+    /// The checkpoints that this state has reached, the same position is a different state
+    /// before and after reaching a checkpoint
+    pub progress: GoalProgress,
 }
 
 /// An efficient way to identify a board
@@ -68,9 +108,20 @@ pub fn to_id(board: &Board) -> BoardId {
     hasher.finish()
 }
 
+/// Get the BoardId that represents a board and its mirror image, as both play out identically
+pub fn to_canonical_id(board: &Board) -> BoardId {
+    to_id(board).min(to_id(&board.mirrored()))
+}
+
 /// Standard Klotski board is 4 by 5 tiles
 const SIZE: Size = Size { x: 4, y: 5 };
 
+/// The puzzle is solved when the 2 by 2 piece reaches the bottom center
+pub(crate) const GOAL: Piece = Piece {
+    position: Coordinates { x: 1, y: 0 },
+    size: Size { x: 2, y: 2 },
+};
+
 impl fmt::Display for Coordinates {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -82,6 +133,30 @@ impl fmt::Display for Coordinates {
     }
 }
 
+impl FromStr for Coordinates {
+    type Err = anyhow::Error;
+
+    /// Parse a tile name like "B2", the inverse of Display
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        let column = chars
+            .next()
+            .filter(char::is_ascii_uppercase)
+            .with_context(|| format!("Invalid column in \"{s}\""))?;
+        let row: i8 = chars
+            .as_str()
+            .parse()
+            .with_context(|| format!("Invalid row in \"{s}\""))?;
+        if row < 1 {
+            return Err(anyhow!("Invalid row in \"{s}\""));
+        }
+        Ok(Coordinates {
+            x: (column as u8 - b'A') as i8,
+            y: row - 1,
+        })
+    }
+}
+
 impl Coordinates {
     pub fn axis_to_string(axis: Axis, coordinate: u8) -> String {
         match axis {
@@ -91,16 +166,45 @@ impl Coordinates {
     }
 }
 
+/// The notation of a move names the tile of the piece's bottom left corner before and after the move,
+/// e.g. "B2⮕D2" slides the piece at B2 two steps right. Columns are lettered from A on the left,
+/// rows are numbered from 1 at the bottom. The alternate form "{:#}" writes "B2>D2", for plain ASCII.
 impl fmt::Display for SlideMove {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}⮕{}", self.start, self.get_endpoint())
+        let separator = if f.alternate() { '>' } else { '⮕' };
+        write!(f, "{}{separator}{}", self.start, self.get_endpoint())
+    }
+}
+
+impl FromStr for SlideMove {
+    type Err = anyhow::Error;
+
+    /// Parse a move in either form of its notation, like "B2⮕B1" or "B2>B1"
+    fn from_str(s: &str) -> Result<Self> {
+        let (start, end) = s
+            .split_once(['⮕', '>'])
+            .with_context(|| format!("Missing separator in move \"{s}\""))?;
+        let start: Coordinates = start.trim().parse()?;
+        let end: Coordinates = end.trim().parse()?;
+        let (direction, distance) = match (end.x - start.x, end.y - start.y) {
+            (0, dy) if dy > 0 => (SlideDirection::Up, dy),
+            (0, dy) if dy < 0 => (SlideDirection::Down, -dy),
+            (dx, 0) if dx < 0 => (SlideDirection::Left, -dx),
+            (dx, 0) if dx > 0 => (SlideDirection::Right, dx),
+            _ => return Err(anyhow!("Move \"{s}\" is not a straight slide")),
+        };
+        Ok(SlideMove {
+            start,
+            direction,
+            distance: distance.try_into()?,
+        })
     }
 }
 
 impl SlideMove {
-    fn get_endpoint(&self) -> Coordinates {
+    pub fn get_endpoint(&self) -> Coordinates {
         let mut end = self.start;
-        let distance = self.distance as i32;
+        let distance = self.distance as i8;
         match self.direction {
             SlideDirection::Up => end.y += distance,
             SlideDirection::Down => end.y -= distance,
@@ -111,11 +215,556 @@ impl SlideMove {
     }
 }
 
+impl Piece {
+    /// The coordinates just past the piece's top right most tile
+    pub fn end(&self) -> Coordinates {
+        Coordinates {
+            x: self.position.x + self.size.x as i8,
+            y: self.position.y + self.size.y as i8,
+        }
+    }
+}
+
+impl Cells {
+    /// Whether all cells are normal
+    pub fn is_empty(&self) -> bool {
+        *self == Cells::default()
+    }
+}
+
+impl GoalProgress {
+    pub fn new(checkpoints: &[Piece]) -> Result<Self> {
+        if checkpoints.len() > MAX_CHECKPOINTS {
+            return Err(anyhow!(
+                "Puzzles have at most {MAX_CHECKPOINTS} checkpoints, found {}",
+                checkpoints.len()
+            ));
+        }
+        let mut progress = GoalProgress {
+            count: checkpoints.len() as u8,
+            ..Default::default()
+        };
+        progress.checkpoints[..checkpoints.len()].copy_from_slice(checkpoints);
+        Ok(progress)
+    }
+
+    /// Whether the puzzle has no checkpoints
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn checkpoints(&self) -> &[Piece] {
+        &self.checkpoints[..self.count as usize]
+    }
+
+    /// The number of checkpoints that were reached
+    pub fn reached(&self) -> usize {
+        self.reached as usize
+    }
+
+    /// The checkpoint to reach next, if any are left
+    pub fn next(&self) -> Option<Piece> {
+        self.checkpoints().get(self.reached()).copied()
+    }
+
+    /// Whether all checkpoints were reached, so that reaching the goal solves the puzzle
+    pub fn is_complete(&self) -> bool {
+        self.reached == self.count
+    }
+
+    /// The same checkpoints, with a number of them reached
+    pub(crate) fn with_reached(&self, reached: usize) -> Result<Self> {
+        if reached > self.count as usize {
+            return Err(anyhow!(
+                "Reached {reached} of only {} checkpoints",
+                self.count
+            ));
+        }
+        Ok(GoalProgress {
+            reached: reached as u8,
+            ..*self
+        })
+    }
+
+    /// Count the checkpoints that the pieces reach, in order
+    fn advance(&mut self, pieces: &[Piece]) {
+        while self.next().is_some_and(|next| pieces.contains(&next)) {
+            self.reached += 1;
+        }
+    }
+}
+
+/// The index of a direction in DIRECTIONS
+fn direction_index(direction: SlideDirection) -> usize {
+    match direction {
+        SlideDirection::Up => 0,
+        SlideDirection::Down => 1,
+        SlideDirection::Left => 2,
+        SlideDirection::Right => 3,
+    }
+}
+
+impl Board {
+    /// The bit of a cell in the cells' masks, cells past MAX_CELLS have none as they are all normal
+    fn cell_bit(&self, coordinates: Coordinates) -> u32 {
+        let index = coordinates.y as u32 * self.size.x as u32 + coordinates.x as u32;
+        1u32.checked_shl(index).unwrap_or(0)
+    }
+
+    /// The bits of the cells that a piece covers
+    fn cells_mask(&self, piece: &Piece) -> u32 {
+        self.covered_cells(piece)
+            .fold(0, |mask, cell| mask | self.cell_bit(cell))
+    }
+
+    pub fn cell(&self, coordinates: Coordinates) -> Cell {
+        if self.cells.is_empty() {
+            return Cell::Normal;
+        }
+        let bit = self.cell_bit(coordinates);
+        if self.cells.ice & bit != 0 {
+            return Cell::Ice;
+        }
+        DIRECTIONS
+            .into_iter()
+            .find(|direction| self.cells.one_way[direction_index(*direction)] & bit != 0)
+            .map_or(Cell::Normal, Cell::OneWay)
+    }
+
+    /// Change what a cell does, only boards of up to MAX_CELLS cells can have special cells
+    pub fn set_cell(&mut self, coordinates: Coordinates, cell: Cell) -> Result<()> {
+        let cell_count = self.size.x as usize * self.size.y as usize;
+        if cell != Cell::Normal && cell_count > MAX_CELLS {
+            return Err(anyhow!(
+                "Boards with special cells have at most {MAX_CELLS} cells, this one has {cell_count}"
+            ));
+        }
+        let bit = self.cell_bit(coordinates);
+        self.cells.ice &= !bit;
+        self.cells.one_way.iter_mut().for_each(|mask| *mask &= !bit);
+        match cell {
+            Cell::Normal => {}
+            Cell::OneWay(direction) => self.cells.one_way[direction_index(direction)] |= bit,
+            Cell::Ice => self.cells.ice |= bit,
+        }
+        Ok(())
+    }
+
+    /// Whether the cells under a piece let it slide in a direction
+    fn cells_allow(&self, piece: &Piece, direction: SlideDirection) -> bool {
+        if self.cells.is_empty() {
+            return true;
+        }
+        let mask = self.cells_mask(piece);
+        DIRECTIONS
+            .into_iter()
+            .filter(|other| *other != direction)
+            .all(|other| self.cells.one_way[direction_index(other)] & mask == 0)
+    }
+
+    /// The cells that a piece covers
+    fn covered_cells(&self, piece: &Piece) -> impl Iterator<Item = Coordinates> {
+        let end = piece.end();
+        (piece.position.y..end.y)
+            .cartesian_product(piece.position.x..end.x)
+            .map(|(y, x)| Coordinates { x, y })
+    }
+
+    /// The first cell under a piece that is one-way in another direction, see cells_allow
+    fn one_way_cell_against(
+        &self,
+        piece: &Piece,
+        direction: SlideDirection,
+    ) -> Option<Coordinates> {
+        if self.cells_allow(piece, direction) {
+            return None;
+        }
+        self.covered_cells(piece)
+            .find(|cell| matches!(self.cell(*cell), Cell::OneWay(other) if other != direction))
+    }
+
+    /// Whether a piece covers any ice
+    fn is_on_ice(&self, piece: &Piece) -> bool {
+        self.cells.ice != 0 && self.cells.ice & self.cells_mask(piece) != 0
+    }
+
+    /// Mirror the board horizontally, i.e. swap left and right
+    pub fn mirrored(&self) -> Board {
+        self.transformed(true, false)
+    }
+
+    /// Turn the board upside down, i.e. rotate it by half a turn. Unlike the mirror image, this plays
+    /// out differently, as the goal stays at the bottom. Quarter turns would change the board's size.
+    pub fn rotated(&self) -> Board {
+        self.transformed(true, true)
+    }
+
+    /// Flip the board along either axis, the pieces, cells and checkpoints all move to the
+    /// flipped places, and the one-way cells turn around
+    fn transformed(&self, flip_x: bool, flip_y: bool) -> Board {
+        let flip_piece = |piece: &mut Piece| {
+            let end = piece.end();
+            if flip_x {
+                piece.position.x = self.size.x as i8 - end.x;
+            }
+            if flip_y {
+                piece.position.y = self.size.y as i8 - end.y;
+            }
+        };
+
+        let mut new_board = *self;
+        new_board.pieces.iter_mut().for_each(flip_piece);
+        new_board.progress.checkpoints[..self.progress.count as usize]
+            .iter_mut()
+            .for_each(flip_piece);
+        if !self.cells.is_empty() {
+            new_board.cells = Cells::default();
+            for (y, x) in (0..self.size.y as i8).cartesian_product(0..self.size.x as i8) {
+                let mut cell = Piece {
+                    position: Coordinates { x, y },
+                    size: Size { x: 1, y: 1 },
+                };
+                flip_piece(&mut cell);
+                let attribute = match self.cell(Coordinates { x, y }) {
+                    Cell::OneWay(direction) => Cell::OneWay(match direction {
+                        SlideDirection::Up if flip_y => SlideDirection::Down,
+                        SlideDirection::Down if flip_y => SlideDirection::Up,
+                        SlideDirection::Left if flip_x => SlideDirection::Right,
+                        SlideDirection::Right if flip_x => SlideDirection::Left,
+                        direction => direction,
+                    }),
+                    attribute => attribute,
+                };
+                new_board
+                    .set_cell(cell.position, attribute)
+                    .expect("The board already has these cells");
+            }
+        }
+
+        // After modifying the board, we need to sort it to ensure correct ID calculation.
+        new_board.pieces.sort();
+        new_board
+    }
+}
+
+/// Write the board's layout, in the form that FromStr parses. The pieces are lettered in their sorted order.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rows = vec![vec!['.'; self.size.x as usize]; self.size.y as usize];
+        for (piece, name) in self.pieces.iter().zip('A'..) {
+            let end = piece.end();
+            for y in piece.position.y..end.y {
+                for x in piece.position.x..end.x {
+                    rows[(self.size.y as i8 - 1 - y) as usize][x as usize] = name;
+                }
+            }
+        }
+        let rows: Vec<String> = rows.into_iter().map(String::from_iter).collect();
+        write!(f, "{}", rows.join("/"))
+    }
+}
+
+impl FromStr for Board {
+    type Err = anyhow::Error;
+
+    /// Parse a board from its layout, top row first, with rows separated by newlines or '/'.
+    /// Every piece is drawn with a letter of its own and empty tiles are '.' or a space,
+    /// e.g. the start board is "ABBC/ABBC/DEEF/DGHF/I..J".
+    fn from_str(s: &str) -> Result<Self> {
+        let rows: Vec<&str> = s
+            .trim_matches('\n')
+            .split(['\n', '/'])
+            .map(|row| row.trim_end_matches('\r'))
+            .collect();
+        Board::from_rows(&rows, SIZE)
+    }
+}
+
+/// Parse a position given either in notation or as a layout, see Board::from_notation and FromStr
+pub fn parse_position(s: &str) -> Result<Board> {
+    // Only the notation names a goal, layouts don't contain its '@'
+    match s.contains('@') {
+        true => Board::from_notation(s.trim()),
+        false => s.parse(),
+    }
+}
+
+/// Parse a size like "4x5", width first
+fn parse_size(s: &str) -> Result<Size> {
+    let (x, y) = s
+        .split_once('x')
+        .with_context(|| format!("Invalid size \"{s}\""))?;
+    Ok(Size {
+        x: x.parse()
+            .with_context(|| format!("Invalid width in \"{s}\""))?,
+        y: y.parse()
+            .with_context(|| format!("Invalid height in \"{s}\""))?,
+    })
+}
+
+/// Write a goal as the size and position of the piece that must reach it, like "2x2@B1"
+fn goal_to_string(goal: &Piece) -> String {
+    format!("{}x{}@{}", goal.size.x, goal.size.y, goal.position)
+}
+
+/// Parse a goal, see goal_to_string
+fn parse_goal(s: &str) -> Result<Piece> {
+    let (size, position) = s
+        .split_once('@')
+        .with_context(|| format!("Invalid goal \"{s}\""))?;
+    Ok(Piece {
+        position: position.parse()?,
+        size: parse_size(size)?,
+    })
+}
+
+impl Board {
+    /// Write the position on a single line, in a notation like FEN in chess. It consists of the layout
+    /// with runs of empty tiles counted, the board's size, and the goal as the size and position of
+    /// the piece that must reach it, e.g. the start is "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1".
+    /// Puzzles with checkpoints list them before the goal, the reached ones marked with a '!',
+    /// e.g. "1x1@A5!,2x1@B3,2x2@B1". Boards with special cells add their rows, top row first, with
+    /// '.' for normal cells, '^', 'v', '<' and '>' for one-way cells and '*' for ice.
+    pub fn to_notation(&self) -> String {
+        let mut layout = String::new();
+        let mut empty = 0;
+        for tile in self.to_string().chars().chain(['/']) {
+            // Runs longer than 9 are split, as every digit counts on its own
+            if tile == '.' && empty < 9 {
+                empty += 1;
+                continue;
+            }
+            if empty > 0 {
+                layout.push(char::from_digit(empty, 10).expect("Runs are at most 9 long"));
+                empty = 0;
+            }
+            match tile {
+                '.' => empty = 1,
+                tile => layout.push(tile),
+            }
+        }
+        layout.pop();
+        let goals: Vec<String> = self
+            .progress
+            .checkpoints()
+            .iter()
+            .enumerate()
+            .map(|(index, checkpoint)| {
+                let reached = if index < self.progress.reached() {
+                    "!"
+                } else {
+                    ""
+                };
+                format!("{}{reached}", goal_to_string(checkpoint))
+            })
+            .chain([goal_to_string(&GOAL)])
+            .collect();
+        let notation = format!(
+            "{layout} {}x{} {}",
+            self.size.x,
+            self.size.y,
+            goals.join(",")
+        );
+        match self.cells.is_empty() {
+            true => notation,
+            false => format!("{notation} {}", self.cells_to_string()),
+        }
+    }
+
+    /// Write the cells' rows, top row first, see to_notation
+    fn cells_to_string(&self) -> String {
+        let rows: Vec<String> = (0..self.size.y as i8)
+            .rev()
+            .map(|y| {
+                (0..self.size.x as i8)
+                    .map(|x| match self.cell(Coordinates { x, y }) {
+                        Cell::Normal => '.',
+                        Cell::OneWay(SlideDirection::Up) => '^',
+                        Cell::OneWay(SlideDirection::Down) => 'v',
+                        Cell::OneWay(SlideDirection::Left) => '<',
+                        Cell::OneWay(SlideDirection::Right) => '>',
+                        Cell::Ice => '*',
+                    })
+                    .collect()
+            })
+            .collect();
+        rows.join("/")
+    }
+
+    /// Set the cells from their rows, top row first, see to_notation
+    fn set_cells_from_str(&mut self, s: &str) -> Result<()> {
+        let rows: Vec<&str> = s.split('/').collect();
+        if rows.len() != self.size.y as usize {
+            return Err(anyhow!(
+                "Expected {} rows of cells, found {}",
+                self.size.y,
+                rows.len()
+            ));
+        }
+        for (row_index, row) in rows.iter().enumerate() {
+            if row.chars().count() != self.size.x as usize {
+                return Err(anyhow!(
+                    "Row {} of the cells is not {} cells wide",
+                    row_index + 1,
+                    self.size.x
+                ));
+            }
+            let y = (self.size.y as usize - 1 - row_index) as i8;
+            for (x, cell) in row.chars().enumerate() {
+                let cell = match cell {
+                    '.' => Cell::Normal,
+                    '^' => Cell::OneWay(SlideDirection::Up),
+                    'v' => Cell::OneWay(SlideDirection::Down),
+                    '<' => Cell::OneWay(SlideDirection::Left),
+                    '>' => Cell::OneWay(SlideDirection::Right),
+                    '*' => Cell::Ice,
+                    cell => return Err(anyhow!("Invalid cell '{cell}'")),
+                };
+                self.set_cell(Coordinates { x: x as i8, y }, cell)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a position from its notation, see to_notation. The layout may also write empty tiles as '.'
+    pub fn from_notation(notation: &str) -> Result<Self> {
+        let fields: Vec<&str> = notation.split_whitespace().collect();
+        let (layout, size, goal, cells) = match fields[..] {
+            [layout, size, goal] => (layout, size, goal, None),
+            [layout, size, goal, cells] => (layout, size, goal, Some(cells)),
+            _ => {
+                return Err(anyhow!(
+                    "Expected a layout, a size, a goal and optionally cells in \"{notation}\""
+                ))
+            }
+        };
+        let size = parse_size(size)?;
+        let mut goals: Vec<&str> = goal.split(',').collect();
+        let goal = goals.pop().expect("Splitting yields at least one part");
+        if parse_goal(goal)? != GOAL {
+            return Err(anyhow!(
+                "Unsupported goal \"{goal}\", the puzzle's goal is {}x{}@{}",
+                GOAL.size.x,
+                GOAL.size.y,
+                GOAL.position
+            ));
+        }
+
+        let rows: Vec<String> = layout
+            .split('/')
+            .map(|row| {
+                row.chars()
+                    .flat_map(|tile| match tile.to_digit(10) {
+                        Some(empty) => vec!['.'; empty as usize],
+                        None => vec![tile],
+                    })
+                    .collect()
+            })
+            .collect();
+        let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+        let mut board = Board::from_rows(&rows, size)?;
+        if let Some(cells) = cells {
+            board.set_cells_from_str(cells)?;
+        }
+
+        // The reached checkpoints are marked, and come first
+        let reached = goals.iter().take_while(|goal| goal.ends_with('!')).count();
+        if goals[reached..].iter().any(|goal| goal.ends_with('!')) {
+            return Err(anyhow!(
+                "The checkpoints are reached in order, in \"{notation}\""
+            ));
+        }
+        let checkpoints: Vec<Piece> = goals
+            .iter()
+            .map(|goal| parse_goal(goal.trim_end_matches('!')))
+            .collect::<Result<_>>()?;
+        board.progress = GoalProgress::new(&checkpoints)?;
+        board.progress.reached = reached as u8;
+        board.progress.advance(&board.pieces);
+        Ok(board)
+    }
+
+    /// Build a board from the rows of its layout, top row first, see FromStr
+    fn from_rows(rows: &[&str], size: Size) -> Result<Self> {
+        if rows.len() != size.y as usize {
+            return Err(anyhow!("Expected {} rows, found {}", size.y, rows.len()));
+        }
+
+        let mut tiles: BTreeMap<char, Vec<Coordinates>> = BTreeMap::new();
+        for (row_index, row) in rows.iter().enumerate() {
+            if row.chars().count() != size.x as usize {
+                return Err(anyhow!(
+                    "Row {} is not {} tiles wide",
+                    row_index + 1,
+                    size.x
+                ));
+            }
+            let y = (size.y as usize - 1 - row_index) as i8;
+            for (x, tile) in row.chars().enumerate() {
+                if tile != '.' && tile != ' ' {
+                    let coordinates = Coordinates { x: x as i8, y };
+                    tiles.entry(tile).or_default().push(coordinates);
+                }
+            }
+        }
+
+        let pieces: Vec<Piece> = tiles
+            .iter()
+            .map(|(name, tiles)| {
+                let bounds = |axis: fn(&Coordinates) -> i8| {
+                    let bounds = tiles.iter().map(axis).minmax().into_option();
+                    bounds.expect("Pieces have at least one tile")
+                };
+                let (min_x, max_x) = bounds(|tile| tile.x);
+                let (min_y, max_y) = bounds(|tile| tile.y);
+                let size = Size {
+                    x: (max_x - min_x + 1) as u8,
+                    y: (max_y - min_y + 1) as u8,
+                };
+                // Every tile holds one letter, so a piece is a rectangle if it fills its bounds
+                (tiles.len() == (size.x * size.y) as usize)
+                    .then_some(Piece {
+                        position: Coordinates { x: min_x, y: min_y },
+                        size,
+                    })
+                    .with_context(|| format!("Piece {name} is not a rectangle"))
+            })
+            .collect::<Result<_>>()?;
+        let piece_count = pieces.len();
+        let mut board = Board {
+            size,
+            pieces: pieces
+                .try_into()
+                .map_err(|_| anyhow!("Expected 10 pieces, found {piece_count}"))?,
+            cells: Cells::default(),
+            progress: GoalProgress::default(),
+        };
+
+        // Sort the pieces to ensure correct ID calculation
+        board.pieces.sort();
+        Ok(board)
+    }
+}
+
+/// Well known positions, with their names. The classic layouts are given top row first, they use
+/// the pieces of the start position.
+pub fn get_annotated_positions() -> Vec<(&'static str, Board)> {
+    let classic = |layout| parse_position(layout).expect("Classic layouts are valid");
+    vec![
+        ("the start position (Heng Dao Li Ma)", get_start_board()),
+        ("Yi Lu Jin Jun", classic("ABBG/ABBH/CDFI/CDFJ/.EE.")),
+        ("Wei Er Bu Jian", classic("ABBG/ABBH/CEEI/CDFJ/.DF.")),
+    ]
+}
+
 impl Eq for Board {}
 
 impl PartialEq<Self> for Board {
     fn eq(&self, other: &Self) -> bool {
-        self.size.eq(&other.size) && self.pieces.eq(&other.pieces)
+        self.size.eq(&other.size)
+            && self.pieces.eq(&other.pieces)
+            && self.cells.eq(&other.cells)
+            && self.progress.eq(&other.progress)
     }
 }
 
@@ -135,6 +784,13 @@ impl Hash for Board {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.pieces.hash(state);
         self.size.hash(state);
+        // Boards without special cells or checkpoints keep the ids they had before these existed
+        if !self.cells.is_empty() {
+            self.cells.hash(state);
+        }
+        if !self.progress.is_empty() {
+            self.progress.hash(state);
+        }
     }
 }
 
@@ -201,6 +857,8 @@ pub fn get_start_board() -> Board {
     let mut new_board = Board {
         pieces: PIECES,
         size: SIZE,
+        cells: Cells::default(),
+        progress: GoalProgress::default(),
     };
 
     // After modifying the board, we need to sort it to ensure correct ID calculation.
@@ -272,6 +930,8 @@ pub fn get_solved_board() -> Board {
     let mut new_board = Board {
         pieces: PIECES,
         size: SIZE,
+        cells: Cells::default(),
+        progress: GoalProgress::default(),
     };
 
     // After modifying the board, we need to sort it to ensure correct ID calculation.
@@ -279,8 +939,111 @@ pub fn get_solved_board() -> Board {
     new_board
 }
 
-/// For each piece and cartesian directions, try to move piece in direction for as many steps as possible
-pub fn get_valid_moves(board: &Board) -> Vec<(SlideMove, Board)> {
+/// The directions in the order that moves are generated in
+const DIRECTIONS: [SlideDirection; 4] = [
+    SlideDirection::Up,
+    SlideDirection::Down,
+    SlideDirection::Left,
+    SlideDirection::Right,
+];
+
+/// The valid moves of a board and the boards they lead to, generated one at a time
+pub struct ValidMoves<'a> {
+    board: &'a Board,
+    piece: usize,
+    direction: usize,
+    distance: u8,
+}
+
+impl Iterator for ValidMoves<'_> {
+    type Item = (SlideMove, Board);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let max_distance = std::cmp::max(self.board.size.x, self.board.size.y);
+        while let Some(piece) = self.board.pieces.get(self.piece) {
+            self.distance += 1;
+            if self.distance < max_distance {
+                let slide_move = SlideMove {
+                    start: piece.position,
+                    direction: DIRECTIONS[self.direction],
+                    distance: self.distance,
+                };
+                let moved = Piece {
+                    position: slide_move.get_endpoint(),
+                    size: piece.size,
+                };
+                let direction = slide_move.direction;
+                let blocked = !self.can_step(&moved, direction)
+                    || (self.distance == 1 && !self.board.cells_allow(piece, direction));
+                if !blocked {
+                    // Pieces slide over ice until they are blocked
+                    let next = Piece {
+                        position: SlideMove {
+                            distance: self.distance + 1,
+                            ..slide_move
+                        }
+                        .get_endpoint(),
+                        size: piece.size,
+                    };
+                    if self.board.is_on_ice(&moved) && self.can_step(&next, direction) {
+                        continue;
+                    }
+                    let mut new_board = *self.board;
+                    new_board.pieces[self.piece] = moved;
+                    // After modifying the board, we need to sort it to ensure correct ID calculation.
+                    new_board.pieces.sort();
+                    new_board.progress.advance(&new_board.pieces);
+                    return Some((slide_move, new_board));
+                }
+            }
+
+            // The piece can't slide any further this way, try the next direction or piece
+            self.distance = 0;
+            self.direction += 1;
+            if self.direction == DIRECTIONS.len() {
+                self.direction = 0;
+                self.piece += 1;
+            }
+        }
+        None
+    }
+}
+
+impl ValidMoves<'_> {
+    /// Whether the piece can slide into a place, one step further than it already could
+    fn can_step(&self, moved: &Piece, direction: SlideDirection) -> bool {
+        // The rest of the board is valid already, so only the moved piece needs checking
+        is_on_board(moved, self.board)
+            && !self
+                .board
+                .pieces
+                .iter()
+                .enumerate()
+                .any(|(other, piece)| other != self.piece && collide(moved, piece))
+            && self.board.cells_allow(moved, direction)
+    }
+}
+
+/// For each piece and cartesian directions, try to move piece in direction for as many steps as possible.
+/// The moves are generated lazily, so callers can stop early without generating the rest.
+pub fn get_valid_moves(board: &Board) -> ValidMoves<'_> {
+    ValidMoves {
+        board,
+        piece: 0,
+        direction: 0,
+        distance: 0,
+    }
+}
+
+/// All valid moves of a board, see get_valid_moves
+pub fn collect_valid_moves(board: &Board) -> Vec<(SlideMove, Board)> {
+    get_valid_moves(board).collect()
+}
+
+/// Every valid move found by moving each piece one step at a time with make_move, this is the
+/// reference that the faster move generation is tested against
+#[cfg(test)]
+pub(crate) fn get_valid_moves_by_make_move(board: &Board) -> Vec<(SlideMove, Board)> {
     // This lambda will move one piece by a specified distance and direction
     let move_piece =
         |piece: &Piece, direction: SlideDirection, distance: u8| -> Option<(SlideMove, Board)> {
@@ -297,12 +1060,13 @@ pub fn get_valid_moves(board: &Board) -> Vec<(SlideMove, Board)> {
             }
         };
 
-    // This lambda moves one piece in one direction, for as far as possible.
-    let move_piece_until_it_cant_no_more =
+    // This lambda moves one piece in one direction, by every distance. A piece can't stop on ice,
+    // so a longer move can be valid where a shorter one isn't.
+    let move_piece_every_distance =
         |(piece, direction): (&Piece, SlideDirection)| -> Vec<(SlideMove, Board)> {
-            let distance_range: Range<u8> = 1..std::cmp::max(board.size.x, board.size.y) as u8;
+            let distance_range: std::ops::Range<u8> = 1..std::cmp::max(board.size.x, board.size.y);
             distance_range
-                .map_while(|distance| move_piece(piece, direction, distance))
+                .filter_map(|distance| move_piece(piece, direction, distance))
                 .collect()
         };
 
@@ -316,38 +1080,174 @@ pub fn get_valid_moves(board: &Board) -> Vec<(SlideMove, Board)> {
             SlideDirection::Left,
             SlideDirection::Right,
         ])
-        .flat_map(move_piece_until_it_cant_no_more)
+        .flat_map(move_piece_every_distance)
         .collect()
 }
 
+/// Why a move can't be made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveRejection {
+    /// There is no piece at the start of the move
+    NoPiece,
+    /// The move doesn't go anywhere
+    NoDistance,
+    /// The piece would slide off the board
+    OffBoard,
+    /// Another piece is in the way, this is the position of the first one that the piece runs into
+    Blocked(Coordinates),
+    /// A one-way cell doesn't let the piece slide this way
+    OneWay(Coordinates),
+    /// The piece can't stop on the ice, it would slide on
+    OnIce(Coordinates),
+}
+
+impl fmt::Display for MoveRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveRejection::NoPiece => write!(f, "there is no piece to move"),
+            MoveRejection::NoDistance => write!(f, "the piece doesn't go anywhere"),
+            MoveRejection::OffBoard => write!(f, "the piece would slide off the board"),
+            MoveRejection::Blocked(other) => write!(f, "the piece at {other} is in the way"),
+            MoveRejection::OneWay(cell) => write!(f, "the one-way cell {cell} points elsewhere"),
+            MoveRejection::OnIce(cell) => write!(f, "the piece can't stop on the ice at {cell}"),
+        }
+    }
+}
+
+/// Find out why a move can't be made, by sliding the piece one step at a time like the move does
+/// @returns the reason, or none if the move can be made
+pub fn explain_move(board: &Board, slide_move: &SlideMove) -> Option<MoveRejection> {
+    let Some(index) = board
+        .pieces
+        .iter()
+        .position(|piece| piece.position == slide_move.start)
+    else {
+        return Some(MoveRejection::NoPiece);
+    };
+    if slide_move.distance == 0 {
+        return Some(MoveRejection::NoDistance);
+    }
+    let piece = board.pieces[index];
+    let direction = slide_move.direction;
+    let step = |distance: u8| Piece {
+        position: SlideMove {
+            distance,
+            ..*slide_move
+        }
+        .get_endpoint(),
+        size: piece.size,
+    };
+    // @returns why the piece can't slide into a place, one step further than it already could
+    let step_rejection = |moved: &Piece| {
+        if !is_on_board(moved, board) {
+            return Some(MoveRejection::OffBoard);
+        }
+        let blocking = board
+            .pieces
+            .iter()
+            .enumerate()
+            .find(|(other, other_piece)| *other != index && collide(moved, other_piece));
+        if let Some((_other, other_piece)) = blocking {
+            return Some(MoveRejection::Blocked(other_piece.position));
+        }
+        board
+            .one_way_cell_against(moved, direction)
+            .map(MoveRejection::OneWay)
+    };
+
+    if let Some(cell) = board.one_way_cell_against(&piece, direction) {
+        return Some(MoveRejection::OneWay(cell));
+    }
+    for distance in 1..=slide_move.distance {
+        if let Some(rejection) = step_rejection(&step(distance)) {
+            return Some(rejection);
+        }
+    }
+    let end = step(slide_move.distance);
+    if board.is_on_ice(&end) && step_rejection(&step(slide_move.distance + 1)).is_none() {
+        let ice = board
+            .covered_cells(&end)
+            .find(|cell| board.cell(*cell) == Cell::Ice)
+            .expect("The piece covers ice");
+        return Some(MoveRejection::OnIce(ice));
+    }
+    None
+}
+
+/// Find all the pieces that stop a move, a wide piece can run into several pieces at once
+/// @returns the positions of the pieces in the way at the first step that runs into any,
+///          or nothing if the move isn't stopped by pieces
+pub fn blocking_pieces(board: &Board, slide_move: &SlideMove) -> Vec<Coordinates> {
+    let Some(MoveRejection::Blocked(_)) = explain_move(board, slide_move) else {
+        return Vec::new();
+    };
+    let size = board
+        .pieces
+        .iter()
+        .find(|piece| piece.position == slide_move.start)
+        .expect("The move was explained to be blocked")
+        .size;
+    (1..=slide_move.distance)
+        .map(|distance| Piece {
+            position: SlideMove {
+                distance,
+                ..*slide_move
+            }
+            .get_endpoint(),
+            size,
+        })
+        .map(|moved| {
+            board
+                .pieces
+                .iter()
+                .filter(|other| other.position != slide_move.start && collide(&moved, other))
+                .map(|other| other.position)
+                .collect::<Vec<Coordinates>>()
+        })
+        .find(|blocking| !blocking.is_empty())
+        .expect("The move was explained to be blocked")
+}
+
 pub fn make_move(board: &Board, slide_move: &SlideMove) -> Result<Board> {
-    // Copy the board into a new board
-    let mut new_board = *board;
+    if let Some(rejection) = explain_move(board, slide_move) {
+        return Err(anyhow!("Invalid move, {rejection}"));
+    }
 
-    // Find the piece at the move start coordinates
-    let piece_to_move: &mut Piece = new_board
+    // Copy the board into a new board, and move the piece by the specified distance
+    let mut new_board = *board;
+    new_board
         .pieces
         .iter_mut()
-        .find(|piece: &&mut Piece| piece.position == slide_move.start)
-        .context("No piece to move")?;
-
-    // move the piece by specified distance
-    piece_to_move.position = slide_move.get_endpoint();
+        .find(|piece| piece.position == slide_move.start)
+        .expect("The move was explained to be valid")
+        .position = slide_move.get_endpoint();
 
     // After modifying the board, we need to sort it to ensure correct ID calculation.
     new_board.pieces.sort();
-
-    if !is_valid(&new_board) {
-        return Err(anyhow!("Invalid move"));
-    }
+    new_board.progress.advance(&new_board.pieces);
+    debug_assert!(
+        is_valid(&new_board),
+        "The explanation misses why {slide_move} is invalid"
+    );
     Ok(new_board)
 }
 
-/// Find if this board is a valid solution
+/// Apply a sequence of moves, failing on the first invalid move
+/// @returns the resulting board, or an error naming which move of the sequence failed and why
+pub fn apply_moves(board: &Board, slide_moves: &[SlideMove]) -> Result<Board> {
+    slide_moves
+        .iter()
+        .enumerate()
+        .try_fold(*board, |board, (index, slide_move)| {
+            make_move(&board, slide_move).with_context(|| {
+                format!("Move {} of the sequence ({slide_move}) failed", index + 1)
+            })
+        })
+}
+
+/// Find if this board is a valid solution, the goal only counts after all checkpoints were reached
 pub fn is_solution(board: &Board) -> bool {
-    board.pieces.iter().any(|piece: &Piece| {
-        piece.size.x == 2 && piece.size.y == 2 && piece.position.x == 1 && piece.position.y == 0
-    })
+    board.progress.is_complete() && board.pieces.contains(&GOAL)
 }
 
 /// Check that board only contains validly placed pieces
@@ -361,10 +1261,11 @@ fn is_valid(board: &Board) -> bool {
 
 /// Check that piece is entirely contained within the bounds of the board
 fn is_on_board(piece: &Piece, board: &Board) -> bool {
+    let end = piece.end();
     0 <= piece.position.x
-        && (piece.position.x + piece.size.x) <= board.size.x
+        && end.x <= board.size.x as i8
         && 0 <= piece.position.y
-        && (piece.position.y + piece.size.y) <= board.size.y
+        && end.y <= board.size.y as i8
 }
 
 fn has_collision(board: &Board) -> bool {
@@ -379,8 +1280,9 @@ fn has_collision(board: &Board) -> bool {
 }
 
 fn collide(a: &Piece, b: &Piece) -> bool {
-    a.position.x + a.size.x > b.position.x &&     // A right edge past B left
-        a.position.x < b.position.x + b.size.x &&       // A left edge past B right
-        a.position.y + a.size.y > b.position.y &&       // A top edge past B bottom
-        a.position.y < b.position.y + b.size.y
+    let (a_end, b_end) = (a.end(), b.end());
+    a_end.x > b.position.x &&     // A right edge past B left
+        a.position.x < b_end.x &&       // A left edge past B right
+        a_end.y > b.position.y &&       // A top edge past B bottom
+        a.position.y < b_end.y
 }
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: MIT
 
 use crate::board::{
-    get_solved_board, get_start_board, get_valid_moves, is_solution, is_valid, Coordinates,
-    SlideDirection, SlideMove,
+    apply_moves, blocking_pieces, collect_valid_moves, explain_move, get_solved_board,
+    get_start_board, get_valid_moves, get_valid_moves_by_make_move, is_solution, is_valid,
+    make_move, to_canonical_id, to_id, Board, Cell, Coordinates, MoveRejection, SlideDirection,
+    SlideMove,
 };
+use proptest::prelude::*;
 use std::hash::Hash;
+use std::str::FromStr;
 
 fn init() {
     let _ = env_logger::builder().is_test(true).try_init();
@@ -50,7 +54,7 @@ fn test_board_hash() {
 fn test_get_valid_moves() {
     init();
 
-    let moves = get_valid_moves(&get_start_board());
+    let moves = collect_valid_moves(&get_start_board());
     assert_eq!(moves.len(), 6);
     assert_eq!(
         moves[0].0,
@@ -101,3 +105,513 @@ fn test_get_valid_moves() {
         }
     );
 }
+
+#[test]
+fn test_apply_moves() {
+    init();
+
+    // Swap the two small pieces in the middle around, which results in the start board once again
+    let moves = [
+        SlideMove {
+            start: Coordinates { x: 1, y: 1 },
+            direction: SlideDirection::Down,
+            distance: 1,
+        },
+        SlideMove {
+            start: Coordinates { x: 1, y: 0 },
+            direction: SlideDirection::Right,
+            distance: 1,
+        },
+        SlideMove {
+            start: Coordinates { x: 2, y: 1 },
+            direction: SlideDirection::Left,
+            distance: 1,
+        },
+        SlideMove {
+            start: Coordinates { x: 2, y: 0 },
+            direction: SlideDirection::Up,
+            distance: 1,
+        },
+    ];
+    let board = apply_moves(&get_start_board(), &moves).expect("Failed to apply moves");
+    assert_eq!(board, get_start_board());
+
+    // An empty sequence leaves the board untouched
+    let board = apply_moves(&get_start_board(), &[]).expect("Failed to apply no moves");
+    assert_eq!(board, get_start_board());
+
+    // The second move collides with a piece, the error should point that out
+    let invalid_moves = [moves[0], moves[0]];
+    let error = apply_moves(&get_start_board(), &invalid_moves).unwrap_err();
+    assert!(error.to_string().starts_with("Move 2 of the sequence"));
+}
+
+#[test]
+fn test_mirrored() {
+    init();
+
+    // The start board is symmetrical
+    assert_eq!(get_start_board().mirrored(), get_start_board());
+
+    // Moving the bottom left piece right mirrors moving the bottom right piece left
+    let left = make_move(
+        &get_start_board(),
+        &SlideMove {
+            start: Coordinates { x: 0, y: 0 },
+            direction: SlideDirection::Right,
+            distance: 1,
+        },
+    )
+    .expect("Failed to make move");
+    let right = make_move(
+        &get_start_board(),
+        &SlideMove {
+            start: Coordinates { x: 3, y: 0 },
+            direction: SlideDirection::Left,
+            distance: 1,
+        },
+    )
+    .expect("Failed to make move");
+    assert_ne!(left, right);
+    assert_eq!(left.mirrored(), right);
+    assert_eq!(left.mirrored().mirrored(), left);
+
+    // Both mirror images share a canonical ID, which is the ID of one of them
+    assert_eq!(to_canonical_id(&left), to_canonical_id(&right));
+    assert!([to_id(&left), to_id(&right)].contains(&to_canonical_id(&left)));
+}
+
+#[test]
+fn test_rotated() {
+    init();
+    let board = get_start_board().rotated();
+    assert_eq!(board.to_string(), "C..J/BFGI/BEEI/ADDH/ADDH");
+    assert!(is_valid(&board));
+    assert_eq!(board.rotated(), get_start_board());
+    // The 2 by 2 piece starts at the top center, upside down it is at the goal
+    assert!(is_solution(&board));
+
+    // The cells and checkpoints turn with the board
+    let board =
+        Board::from_notation("CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 1x1@B1,2x2@B1 ^.../..../..../..../.>*.")
+            .unwrap()
+            .rotated();
+    assert_eq!(
+        board.to_notation(),
+        "C2J/BFGI/BEEI/ADDH/ADDH 4x5 1x1@C5,2x2@B1 .*<./..../..../..../...v"
+    );
+}
+
+#[test]
+fn test_coordinates_notation() {
+    init();
+    assert_eq!(
+        "B2".parse::<Coordinates>().unwrap(),
+        Coordinates { x: 1, y: 1 }
+    );
+    // Every tile of the board round-trips
+    for x in 0..4 {
+        for y in 0..5 {
+            let coordinates = Coordinates { x, y };
+            assert_eq!(
+                Coordinates::from_str(&coordinates.to_string()).unwrap(),
+                coordinates
+            );
+        }
+    }
+
+    for invalid in ["", "B", "b2", "2B", "B0", "B-1", "B2x", "BB2", "Ä1", "B200"] {
+        assert!(
+            invalid.parse::<Coordinates>().is_err(),
+            "Parsed \"{invalid}\""
+        );
+    }
+}
+
+#[test]
+fn test_move_notation() {
+    init();
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Right,
+        distance: 2,
+    };
+    assert_eq!(slide_move.to_string(), "B2⮕D2");
+    assert_eq!(format!("{slide_move:#}"), "B2>D2");
+    assert_eq!("B2⮕D2".parse::<SlideMove>().unwrap(), slide_move);
+    assert_eq!(" B2 > D2 ".parse::<SlideMove>().unwrap(), slide_move);
+
+    // Every move round-trips through both forms
+    for (slide_move, _board) in get_valid_moves(&get_start_board()) {
+        assert_eq!(
+            SlideMove::from_str(&slide_move.to_string()).unwrap(),
+            slide_move
+        );
+        assert_eq!(
+            SlideMove::from_str(&format!("{slide_move:#}")).unwrap(),
+            slide_move
+        );
+    }
+
+    for invalid in [
+        "", "B2", "B2-D2", "B2>B2", "B2>C3", "b2>d2", "B0>B1", "B2>Bx", "B2>", ">D2", "B2>D2>F2",
+    ] {
+        assert!(
+            invalid.parse::<SlideMove>().is_err(),
+            "Parsed \"{invalid}\""
+        );
+    }
+}
+
+#[test]
+fn test_board_layout() {
+    init();
+    let start: Board = "ABBC/ABBC/DEEF/DGHF/I..J".parse().unwrap();
+    assert_eq!(start, get_start_board());
+    let start: Board = "ABBC\nABBC\nDEEF\nDGHF\nI  J\n".parse().unwrap();
+    assert_eq!(start, get_start_board());
+
+    for invalid in [
+        "",
+        "ABBC/ABBC/DEEF/DGHF",
+        "ABBC/ABBC/DEEF/DGHF/I..JK",
+        "ABBC/ABBC/DEEF/DGHF/IH.J",
+        "ABBC/ABBC/DEEF/DGHF/I...",
+        "ABBC/ABBC/DEEF/DGHF/IKLJ",
+    ] {
+        assert!(invalid.parse::<Board>().is_err(), "Parsed \"{invalid}\"");
+    }
+}
+
+#[test]
+fn test_board_layout_display() {
+    init();
+    // The letters follow the sorted order of the pieces, not the traditional naming
+    assert_eq!(get_start_board().to_string(), "CFFJ/CFFJ/BEEI/BDGI/A..H");
+}
+
+#[test]
+fn test_board_notation() {
+    init();
+    let notation = get_start_board().to_notation();
+    assert_eq!(notation, "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1");
+    assert_eq!(Board::from_notation(&notation).unwrap(), get_start_board());
+    // Empty tiles may be written out
+    assert_eq!(
+        Board::from_notation("ABBC/ABBC/DEEF/DGHF/I..J 4x5 2x2@B1").unwrap(),
+        get_start_board()
+    );
+    assert!(is_solution(
+        &Board::from_notation("ACDE/FGHI/1J2/1BB1/1BB1 4x5 2x2@B1").unwrap()
+    ));
+
+    for invalid in [
+        "",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x6 2x2@B1",
+        "CFFJ/CFFJ/BEEI/BDGI/A3H 4x5 2x2@B1",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B4",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4by5 2x2@B1",
+    ] {
+        assert!(
+            Board::from_notation(invalid).is_err(),
+            "Parsed \"{invalid}\""
+        );
+    }
+}
+
+#[test]
+fn test_cells_notation() {
+    init();
+    let notation = "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ^.../..../..../..../.>*.";
+    let board = Board::from_notation(notation).unwrap();
+    assert_eq!(board.to_notation(), notation);
+    assert_eq!(
+        board.cell(Coordinates { x: 0, y: 4 }),
+        Cell::OneWay(SlideDirection::Up)
+    );
+    assert_eq!(
+        board.cell(Coordinates { x: 1, y: 0 }),
+        Cell::OneWay(SlideDirection::Right)
+    );
+    assert_eq!(board.cell(Coordinates { x: 2, y: 0 }), Cell::Ice);
+    assert_eq!(board.cell(Coordinates { x: 3, y: 0 }), Cell::Normal);
+
+    // The cells are part of the position, but boards without them keep their ids
+    assert_ne!(board, get_start_board());
+    assert_ne!(to_id(&board), to_id(&get_start_board()));
+    let normal =
+        Board::from_notation("CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../....");
+    assert_eq!(to_id(&normal.unwrap()), to_id(&get_start_board()));
+
+    // Mirroring swaps the one-way cells' left and right
+    assert_eq!(
+        board.mirrored().cell(Coordinates { x: 2, y: 0 }),
+        Cell::OneWay(SlideDirection::Left)
+    );
+    assert_eq!(board.mirrored().mirrored(), board);
+
+    for invalid in [
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../....",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../...",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../..x.",
+    ] {
+        assert!(
+            Board::from_notation(invalid).is_err(),
+            "Parsed \"{invalid}\""
+        );
+    }
+}
+
+#[test]
+fn test_large_board_cells() {
+    init();
+    // Boards with more cells than the masks have bits can't have special cells, but they can be played
+    let notation = "AABBC/AABBC/DEFGH/IJ.../...../...../..... 5x7 2x2@B1";
+    let board = Board::from_notation(notation).unwrap();
+    assert_eq!(board.cell(Coordinates { x: 4, y: 6 }), Cell::Normal);
+    assert!(get_valid_moves(&board).count() > 0);
+    assert!(make_move(&board, &"A4>A1".parse().unwrap()).is_ok());
+    assert!(Board::from_notation(&format!(
+        "{notation} ...../...../...../...../...../...../*...."
+    ))
+    .is_err());
+}
+
+#[test]
+fn test_one_way_cells() {
+    init();
+    // B1 can only be crossed to the right
+    let board = Board::from_notation("CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../.>..")
+        .unwrap();
+    let moves: Vec<String> = get_valid_moves(&board)
+        .map(|(slide_move, _board)| format!("{slide_move:#}"))
+        .collect();
+    assert!(moves.contains(&"A1>B1".to_string()));
+    assert!(moves.contains(&"A1>C1".to_string()));
+    assert!(moves.contains(&"D1>C1".to_string()));
+    assert!(!moves.contains(&"D1>B1".to_string()));
+    assert!(!moves.contains(&"B2>B1".to_string()));
+    assert!(make_move(&board, &"B2>B1".parse().unwrap()).is_err());
+
+    // The piece can't slide back, so the state graph gets one-way edges
+    let moved = make_move(&board, &"A1>B1".parse().unwrap()).unwrap();
+    assert!(!get_valid_moves(&moved).any(|(_slide_move, back)| back == board));
+    assert!(make_move(&moved, &"B1>A1".parse().unwrap()).is_err());
+}
+
+#[test]
+fn test_ice_cells() {
+    init();
+    // Pieces can't stop on B1, unless something stops them there
+    let board = Board::from_notation("CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../.*..")
+        .unwrap();
+    let moves: Vec<String> = get_valid_moves(&board)
+        .map(|(slide_move, _board)| format!("{slide_move:#}"))
+        .collect();
+    assert!(!moves.contains(&"A1>B1".to_string()));
+    assert!(moves.contains(&"A1>C1".to_string()));
+    // The wall and the piece on A1 stop the pieces on the ice
+    assert!(moves.contains(&"B2>B1".to_string()));
+    assert!(moves.contains(&"D1>B1".to_string()));
+    assert!(make_move(&board, &"A1>B1".parse().unwrap()).is_err());
+    // The reference finds the moves across the ice too
+    assert_eq!(
+        collect_valid_moves(&board),
+        get_valid_moves_by_make_move(&board)
+    );
+}
+
+#[test]
+fn test_explain_move() {
+    init();
+    let board = get_start_board();
+    let explain = |board: &Board, notation: &str| explain_move(board, &notation.parse().unwrap());
+    assert_eq!(explain(&board, "A1>C1"), None);
+    assert_eq!(explain(&board, "B1>C1"), Some(MoveRejection::NoPiece));
+    let off_board = SlideMove {
+        start: Coordinates { x: 0, y: 0 },
+        direction: SlideDirection::Left,
+        distance: 1,
+    };
+    assert_eq!(
+        explain_move(&board, &off_board),
+        Some(MoveRejection::OffBoard)
+    );
+    // The first piece in the way is reported, not the ones behind it
+    assert_eq!(
+        explain(&board, "A1>A4"),
+        Some(MoveRejection::Blocked(Coordinates { x: 0, y: 1 }))
+    );
+    assert_eq!(
+        explain(&board, "A1>D1"),
+        Some(MoveRejection::Blocked(Coordinates { x: 3, y: 0 }))
+    );
+    assert_eq!(
+        make_move(&board, &"A1>D1".parse().unwrap())
+            .unwrap_err()
+            .to_string(),
+        "Invalid move, the piece at D1 is in the way"
+    );
+
+    assert_eq!(
+        blocking_pieces(&board, &"A1>D1".parse().unwrap()),
+        [Coordinates { x: 3, y: 0 }]
+    );
+    assert!(blocking_pieces(&board, &"A1>C1".parse().unwrap()).is_empty());
+    // The wide piece at B3 runs into both pieces under it
+    let mut blocking = blocking_pieces(&board, &"B3>B2".parse().unwrap());
+    blocking.sort();
+    assert_eq!(
+        blocking,
+        [Coordinates { x: 1, y: 1 }, Coordinates { x: 2, y: 1 }]
+    );
+
+    let b1 = Coordinates { x: 1, y: 0 };
+    let one_way =
+        Board::from_notation("CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../.>..")
+            .unwrap();
+    assert_eq!(explain(&one_way, "A1>B1"), None);
+    assert_eq!(explain(&one_way, "D1>B1"), Some(MoveRejection::OneWay(b1)));
+    assert_eq!(explain(&one_way, "B2>B1"), Some(MoveRejection::OneWay(b1)));
+
+    let ice = Board::from_notation("CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1 ..../..../..../..../.*..")
+        .unwrap();
+    assert_eq!(explain(&ice, "A1>B1"), Some(MoveRejection::OnIce(b1)));
+    assert_eq!(explain(&ice, "D1>B1"), None);
+    // The moves that the generator finds are never rejected
+    for board in [board, one_way, ice] {
+        let valid = collect_valid_moves(&board);
+        for (slide_move, _board) in &valid {
+            assert_eq!(explain_move(&board, slide_move), None, "{slide_move}");
+        }
+    }
+}
+
+#[test]
+fn test_checkpoints() {
+    init();
+    let notation = "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 1x1@B1,2x2@B1";
+    let board = Board::from_notation(notation).unwrap();
+    assert_eq!(board.to_notation(), notation);
+    assert_eq!(board.progress.reached(), 0);
+    assert_eq!(
+        board.progress.next().unwrap().position,
+        Coordinates { x: 1, y: 0 }
+    );
+    assert_ne!(to_id(&board), to_id(&get_start_board()));
+
+    // Reaching the checkpoint makes the same layout another state
+    let moved = make_move(&board, &"B2>B1".parse().unwrap()).unwrap();
+    assert_eq!(moved.progress.reached(), 1);
+    assert_eq!(moved.progress.next(), None);
+    assert!(moved.to_notation().ends_with(" 1x1@B1!,2x2@B1"));
+    let back = make_move(&moved, &"B1>B2".parse().unwrap()).unwrap();
+    assert_eq!(back.pieces, board.pieces);
+    assert_ne!(back, board);
+    assert_eq!(Board::from_notation(&back.to_notation()).unwrap(), back);
+
+    // The goal only counts once the checkpoints are reached
+    let solved = "ACDE/FGHI/1J2/1BB1/1BB1 4x5";
+    assert!(!is_solution(
+        &Board::from_notation(&format!("{solved} 1x2@A1,2x2@B1")).unwrap()
+    ));
+    assert!(is_solution(
+        &Board::from_notation(&format!("{solved} 1x1@A5,2x2@B1")).unwrap()
+    ));
+
+    for invalid in [
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1,1x1@B1",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 1x1@A1,1x1@B1!,2x2@B1",
+        "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 1x1@A1,1x1@B1,1x1@C1,1x1@D1,1x1@A2,2x2@B1",
+    ] {
+        assert!(
+            Board::from_notation(invalid).is_err(),
+            "Parsed \"{invalid}\""
+        );
+    }
+}
+
+/// Random boards, reached by walking random moves from the start
+fn random_board() -> impl Strategy<Value = Board> {
+    prop::collection::vec(any::<prop::sample::Index>(), 0..60).prop_map(|choices| {
+        choices.iter().fold(get_start_board(), |board, choice| {
+            // Every move can be undone, so every reachable board has at least one move
+            let moves = collect_valid_moves(&board);
+            moves[choice.index(moves.len())].1
+        })
+    })
+}
+
+fn inverse(slide_move: &SlideMove) -> SlideMove {
+    SlideMove {
+        start: slide_move.get_endpoint(),
+        direction: match slide_move.direction {
+            SlideDirection::Up => SlideDirection::Down,
+            SlideDirection::Down => SlideDirection::Up,
+            SlideDirection::Left => SlideDirection::Right,
+            SlideDirection::Right => SlideDirection::Left,
+        },
+        distance: slide_move.distance,
+    }
+}
+
+proptest! {
+    // Each case walks many moves, fewer cases keep the debug build's test run short
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_move_and_inverse(board in random_board()) {
+        for (slide_move, moved) in get_valid_moves(&board) {
+            prop_assert_eq!(make_move(&moved, &inverse(&slide_move)).unwrap(), board);
+        }
+    }
+
+    #[test]
+    fn prop_layout_round_trip(board in random_board()) {
+        let parsed: Board = board.to_string().parse().unwrap();
+        prop_assert_eq!(parsed.pieces, board.pieces);
+        prop_assert_eq!(to_id(&parsed), to_id(&board));
+    }
+
+    #[test]
+    fn prop_notation_round_trip(board in random_board()) {
+        prop_assert_eq!(Board::from_notation(&board.to_notation()).unwrap(), board);
+    }
+
+    #[test]
+    fn prop_pieces_stay_sorted(board in random_board()) {
+        prop_assert!(board.pieces.is_sorted());
+        let mut sorted = board;
+        sorted.pieces.sort();
+        prop_assert_eq!(to_id(&sorted), to_id(&board));
+    }
+
+    #[test]
+    fn prop_move_generation_matches_make_move(board in random_board()) {
+        prop_assert_eq!(collect_valid_moves(&board), get_valid_moves_by_make_move(&board));
+    }
+
+    #[test]
+    fn prop_transforms(board in random_board()) {
+        for transformed in [board.mirrored(), board.rotated()] {
+            prop_assert!(is_valid(&transformed));
+            prop_assert_eq!(
+                get_valid_moves(&transformed).count(),
+                get_valid_moves(&board).count()
+            );
+        }
+        prop_assert_eq!(board.mirrored().mirrored(), board);
+        prop_assert_eq!(board.rotated().rotated(), board);
+        prop_assert_eq!(board.rotated().mirrored(), board.mirrored().rotated());
+    }
+
+    #[test]
+    fn prop_no_collisions(board in random_board()) {
+        prop_assert!(is_valid(&board));
+        for (_slide_move, moved) in get_valid_moves(&board) {
+            prop_assert!(is_valid(&moved));
+        }
+    }
+}
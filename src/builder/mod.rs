@@ -0,0 +1,306 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Sets up a game from typed options, without any JavaScript types. The wasm constructors read their
+//! options into this builder, native code and tests can use it directly.
+
+#[cfg(test)]
+pub(crate) mod unittest;
+
+use crate::board::{self, Board, BoardId, SlideMove};
+use crate::graph::{Graph, SolveOutcome};
+use crate::pattern_db::PatternDb;
+use crate::solver::{Solver, SolverReport};
+use crate::views::history::History;
+use crate::views::theme::Theme;
+use anyhow::{bail, ensure, Context, Result};
+use std::time::Duration;
+
+/// A graph that was solved ahead of time, with the board that the puzzle starts from
+struct SolvedGraph {
+    graph: Graph,
+    start_board: Board,
+    outcome: SolveOutcome,
+}
+
+/// A solved puzzle, and how its views are customized
+pub struct Game {
+    pub graph: Graph,
+    /// The state that the game starts and restarts from
+    pub start_state: BoardId,
+    /// The solver's statistics, if the builder solved the puzzle
+    pub report: Option<SolverReport>,
+    pub theme: Option<Theme>,
+    /// The time between the moves of auto-solve
+    pub auto_solve_interval: Option<Duration>,
+    /// How the graph grows as the player goes, if the puzzle isn't solved upfront
+    pub expansion: Option<LazyExpansion>,
+}
+
+/// A game that is played without any views, e.g. by native code or tests. It follows the moves and
+/// the history like the web app does.
+pub struct HeadlessGame {
+    pub game: Game,
+    active_state: BoardId,
+    history: History,
+}
+
+impl HeadlessGame {
+    fn new(game: Game) -> Self {
+        Self {
+            active_state: game.start_state,
+            history: History::new(game.start_state),
+            game,
+        }
+    }
+
+    pub fn active_state(&self) -> BoardId {
+        self.active_state
+    }
+
+    /// Whether the active state solves the puzzle
+    pub fn is_solved(&self) -> bool {
+        self.game
+            .graph
+            .get(self.active_state)
+            .is_some_and(|node| board::is_solution(&node.board))
+    }
+
+    /// Make a move from the active state
+    /// @returns the state that the move leads to, or an error if the move can't be made
+    pub fn do_move(&mut self, slide_move: &SlideMove) -> Result<BoardId> {
+        let node = self
+            .game
+            .graph
+            .get(self.active_state)
+            .context("The active state isn't in the graph")?;
+        let state = board::to_id(&board::make_move(&node.board, slide_move)?);
+        self.visit(state)?;
+        self.history.visit(state);
+        Ok(state)
+    }
+
+    /// Step back to the previous state, returns None if there is none
+    pub fn undo(&mut self) -> Result<Option<BoardId>> {
+        self.history
+            .undo()
+            .map(|state| self.visit(state).map(|_| state))
+            .transpose()
+    }
+
+    /// Step forward to the state that was last undone, returns None if there is none
+    pub fn redo(&mut self) -> Result<Option<BoardId>> {
+        self.history
+            .redo()
+            .map(|state| self.visit(state).map(|_| state))
+            .transpose()
+    }
+
+    /// Make a state the active one, the graph grows around it if it's expanded lazily
+    fn visit(&mut self, state: BoardId) -> Result<()> {
+        ensure!(
+            self.game.graph.contains(state),
+            "The state isn't in the puzzle's graph"
+        );
+        if let Some(expansion) = &self.game.expansion {
+            expansion.expand(&mut self.game.graph, state)?;
+        }
+        self.active_state = state;
+        Ok(())
+    }
+}
+
+/// Grows a graph around the states that the player visits, instead of solving the whole puzzle. The
+/// distances to the solution come from a pattern database, which is far smaller than the graph. They
+/// are lower bounds, so the moves that seem to get closer don't always do.
+pub struct LazyExpansion {
+    start_board: Board,
+    depth: u32,
+    distances: PatternDb,
+}
+
+impl LazyExpansion {
+    fn new(start_board: Board, depth: u32) -> Result<Self> {
+        ensure!(
+            depth > 0,
+            "The graph must be expanded at least one move deep"
+        );
+        // The relaxed puzzle has no special cells, its distances would be off on a board with them
+        ensure!(
+            start_board.cells.is_empty(),
+            "Puzzles with special cells can't be expanded lazily"
+        );
+        Ok(Self {
+            start_board,
+            depth,
+            distances: PatternDb::new(&start_board),
+        })
+    }
+
+    /// Add the states within the depth of a state of the graph, and estimate their distances
+    /// @returns the number of states that were added
+    pub fn expand(&self, graph: &mut Graph, center: BoardId) -> Result<usize> {
+        let center = graph
+            .get(center)
+            .map(|node| node.board)
+            .context("The expanded state isn't in the graph")?;
+        let edge_count = graph.edge_count();
+        let added = graph.expand(&center, self.depth)?;
+        // Moves between states that were known already can shorten the distances from the start too
+        if added > 0 || graph.edge_count() != edge_count {
+            // The solved states are at distance 1, like in a graph with the "fake" solution
+            graph.annotate_partial(&self.start_board, |board| {
+                self.distances.heuristic(board).map(|distance| distance + 1)
+            })?;
+        }
+        Ok(added)
+    }
+}
+
+/// Collects the options of a game, the standard puzzle is solved if no other is given
+#[derive(Default)]
+pub struct WiggersGraafBuilder {
+    puzzle: Option<Board>,
+    solved_graph: Option<SolvedGraph>,
+    start_position: Option<Board>,
+    theme: Option<Theme>,
+    auto_solve_speed: Option<f64>,
+    lazy_depth: Option<u32>,
+}
+
+impl WiggersGraafBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Solve a custom puzzle instead of the standard one
+    pub fn puzzle(mut self, start_board: Board) -> Self {
+        self.puzzle = Some(start_board);
+        self
+    }
+
+    /// Use a graph that was solved ahead of time, instead of solving the puzzle
+    pub fn solved_graph(mut self, graph: Graph, start_board: Board, outcome: SolveOutcome) -> Self {
+        self.solved_graph = Some(SolvedGraph {
+            graph,
+            start_board,
+            outcome,
+        });
+        self
+    }
+
+    /// Start and restart from another position than the puzzle's start, it must be reachable
+    pub fn start_position(mut self, position: Board) -> Self {
+        self.start_position = Some(position);
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Let auto-solve make this many moves per second
+    pub fn auto_solve_speed(mut self, moves_per_second: f64) -> Self {
+        self.auto_solve_speed = Some(moves_per_second);
+        self
+    }
+
+    /// Don't solve the puzzle upfront, expand the graph to this many moves around the player's state
+    /// instead. This starts far quicker for large puzzles, but the distances are only estimates.
+    pub fn lazy(mut self, depth: u32) -> Self {
+        self.lazy_depth = Some(depth);
+        self
+    }
+
+    /// Build the game, to be played without any views
+    pub fn build_headless(self) -> Result<HeadlessGame> {
+        self.build().map(HeadlessGame::new)
+    }
+
+    /// Solve the puzzle unless its graph was given, and check that the options fit it.
+    /// Fails with a message for the player if an option is invalid or the puzzle can't be solved.
+    pub fn build(self) -> Result<Game> {
+        ensure!(
+            self.puzzle.is_none() || self.solved_graph.is_none(),
+            "A puzzle can't be given along with a graph that was solved ahead of time"
+        );
+        let auto_solve_interval = self
+            .auto_solve_speed
+            .map(|speed| {
+                ensure!(
+                    speed.is_finite() && speed > 0.0,
+                    "The auto-solve speed must be a positive number of moves per second"
+                );
+                Ok(Duration::from_secs_f64(1.0 / speed))
+            })
+            .transpose()?;
+
+        if let Some(depth) = self.lazy_depth {
+            ensure!(
+                self.solved_graph.is_none(),
+                "A graph that was solved ahead of time can't be expanded lazily"
+            );
+            // Without solving, the start position can't be checked, the graph grows from it instead
+            let start_board = self
+                .start_position
+                .or(self.puzzle)
+                .unwrap_or_else(board::get_start_board);
+            let expansion = LazyExpansion::new(start_board, depth)?;
+            ensure!(
+                expansion.distances.heuristic(&start_board).is_some(),
+                "This puzzle can't be solved, even with only its large pieces on the board"
+            );
+            let mut graph = Graph::new();
+            graph.add_node(start_board)?;
+            let start_state = board::to_id(&start_board);
+            expansion.expand(&mut graph, start_state)?;
+            return Ok(Game {
+                graph,
+                start_state,
+                report: None,
+                theme: self.theme,
+                auto_solve_interval,
+                expansion: Some(expansion),
+            });
+        }
+
+        let (solved_graph, report) = match self.solved_graph {
+            Some(solved_graph) => (solved_graph, None),
+            None => {
+                let solver =
+                    Solver::with_start(self.puzzle.unwrap_or_else(board::get_start_board))?;
+                let solved_graph = SolvedGraph {
+                    graph: solver.graph,
+                    start_board: solver.start_board,
+                    outcome: solver.outcome,
+                };
+                (solved_graph, Some(solver.report))
+            }
+        };
+        if let SolveOutcome::Unsolvable { reachable_states } = solved_graph.outcome {
+            bail!("This puzzle can't be solved, none of the {reachable_states} positions it can reach is a solution");
+        }
+
+        let start_state = match self.start_position {
+            Some(position) => {
+                let state = board::to_id(&position);
+                ensure!(
+                    solved_graph.graph.contains(state),
+                    "This start position can't be reached in this puzzle"
+                );
+                state
+            }
+            None => board::to_id(&solved_graph.start_board),
+        };
+
+        Ok(Game {
+            graph: solved_graph.graph,
+            start_state,
+            report,
+            theme: self.theme,
+            auto_solve_interval,
+            expansion: None,
+        })
+    }
+}
@@ -0,0 +1,211 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{get_start_board, make_move, parse_position, to_id, BoardId};
+use crate::builder::WiggersGraafBuilder;
+use crate::graph::{Graph, SolveOutcome};
+use crate::solver::Solver;
+use crate::views::theme::Theme;
+use std::time::Duration;
+
+/// A puzzle with little room to move, the 2 by 2 piece is one move from the goal
+pub(crate) const PUZZLE: &str = "AABB/CDDE/CFFE/GFFH/I..J";
+
+/// Solve the small puzzle, for the tests that need a graph
+/// @returns the graph, and the puzzle's start state
+pub(crate) fn solve() -> (Graph, BoardId) {
+    let puzzle = parse_position(PUZZLE).unwrap();
+    let game = WiggersGraafBuilder::new().puzzle(puzzle).build().unwrap();
+    (game.graph, to_id(&puzzle))
+}
+
+#[test]
+fn test_build() {
+    let puzzle = parse_position(PUZZLE).unwrap();
+    let game = WiggersGraafBuilder::new()
+        .puzzle(puzzle)
+        .theme(Theme::DARK)
+        .auto_solve_speed(4.0)
+        .build()
+        .unwrap();
+    assert_eq!(game.start_state, to_id(&puzzle));
+    assert!(game.graph.contains(game.start_state));
+    let report = game.report.unwrap();
+    assert_eq!(report.nodes, game.graph.node_count());
+    assert!(report.memory <= report.memory_before_compact);
+    assert_eq!(game.theme, Some(Theme::DARK));
+    assert_eq!(game.auto_solve_interval, Some(Duration::from_millis(250)));
+}
+
+#[test]
+fn test_start_position() {
+    let puzzle = parse_position(PUZZLE).unwrap();
+    let position = parse_position("AABB/CDDE/CFFE/GFFH/.I.J").unwrap();
+    let game = WiggersGraafBuilder::new()
+        .puzzle(puzzle)
+        .start_position(position)
+        .build()
+        .unwrap();
+    assert_eq!(game.start_state, to_id(&position));
+
+    // A graph that was solved ahead of time is used as it is
+    let rebuilt = WiggersGraafBuilder::new()
+        .solved_graph(
+            game.graph,
+            puzzle,
+            SolveOutcome::Solvable {
+                distance_to_solution: 1,
+            },
+        )
+        .start_position(position)
+        .build()
+        .unwrap();
+    assert!(rebuilt.report.is_none());
+
+    // The puzzle would be ignored in favor of the solved graph
+    let (graph, _start) = solve();
+    assert!(WiggersGraafBuilder::new()
+        .puzzle(puzzle)
+        .solved_graph(
+            graph,
+            puzzle,
+            SolveOutcome::Solvable {
+                distance_to_solution: 1,
+            },
+        )
+        .build()
+        .is_err());
+
+    // The standard puzzle's start isn't reachable from this one
+    assert!(WiggersGraafBuilder::new()
+        .puzzle(puzzle)
+        .start_position(get_start_board())
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_auto_solve_speed() {
+    let build = |speed| {
+        WiggersGraafBuilder::new()
+            .solved_graph(
+                Graph::new(),
+                get_start_board(),
+                SolveOutcome::Solvable {
+                    distance_to_solution: 81,
+                },
+            )
+            .auto_solve_speed(speed)
+            .build()
+    };
+    assert!(build(5.0).is_ok());
+    for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(build(speed).is_err(), "{speed}");
+    }
+}
+
+#[test]
+fn test_one_way_distances() {
+    // The piece on D1 can slide off it to the left, but not back
+    let game = WiggersGraafBuilder::new()
+        .puzzle(parse_position(&format!("{PUZZLE} 4x5 2x2@B1 ..../..../..../..../...<")).unwrap())
+        .build()
+        .unwrap();
+    let graph = &game.graph;
+    assert!(graph.nodes().len() > 1);
+    // Every state that can be solved has a move that gets one closer, distances follow the moves
+    for node in graph.nodes() {
+        let Some(distance) = node.distance_to_solution().filter(|distance| *distance > 0) else {
+            continue;
+        };
+        assert!(graph
+            .edges(node)
+            .iter()
+            .any(|edge| graph.node(edge.neighbor).distance_to_solution() == Some(distance - 1)));
+    }
+}
+
+#[test]
+fn test_checkpoints() {
+    let build = |notation| {
+        let game = WiggersGraafBuilder::new()
+            .puzzle(parse_position(notation).unwrap())
+            .build()
+            .unwrap();
+        let start = game.graph.get(game.start_state).unwrap();
+        start.distance_to_solution().unwrap()
+    };
+    // The piece on A1 slides to C1 and back out of the way, before the 2 by 2 piece can move down
+    assert_eq!(build("AABB/CDDE/CFFE/GFFH/I2J 4x5 2x2@B1"), 2);
+    assert_eq!(build("AABB/CDDE/CFFE/GFFH/I2J 4x5 1x1@C1,2x2@B1"), 4);
+}
+
+#[test]
+fn test_lazy() {
+    let game = WiggersGraafBuilder::new().lazy(2).build().unwrap();
+    assert_eq!(game.start_state, to_id(&get_start_board()));
+    assert!(game.report.is_none());
+    let mut graph = game.graph;
+    let solved = Solver::with_start(get_start_board()).unwrap().graph;
+    assert!(graph.node_count() < solved.node_count());
+
+    // The estimated distances never exceed the real ones
+    let expansion = game.expansion.unwrap();
+    let state = graph
+        .nodes()
+        .iter()
+        .find(|node| node.distance_to_start() == Some(2))
+        .unwrap()
+        .id;
+    assert!(expansion.expand(&mut graph, state).unwrap() > 0);
+    for node in graph.nodes() {
+        let real = solved.get(node.id).unwrap().distance_to_solution().unwrap();
+        assert!(node.distance_to_solution().unwrap() <= real);
+        assert!(node.distance_to_start().unwrap() <= 4);
+    }
+    assert_eq!(expansion.expand(&mut graph, state).unwrap(), 0);
+
+    // The graph is either solved ahead of time or expanded as the player goes
+    assert!(WiggersGraafBuilder::new()
+        .solved_graph(
+            solved,
+            get_start_board(),
+            SolveOutcome::Solvable {
+                distance_to_solution: 1
+            },
+        )
+        .lazy(2)
+        .build()
+        .is_err());
+    assert!(WiggersGraafBuilder::new().lazy(0).build().is_err());
+}
+
+#[test]
+fn test_headless() {
+    let puzzle = parse_position(PUZZLE).unwrap();
+    let mut headless = WiggersGraafBuilder::new()
+        .puzzle(puzzle)
+        .build_headless()
+        .unwrap();
+    assert_eq!(headless.active_state(), to_id(&puzzle));
+    assert!(!headless.is_solved());
+    assert!(headless.undo().unwrap().is_none());
+
+    // The 2 by 2 piece moves down onto the goal
+    let solved = to_id(&make_move(&puzzle, &"B2>B1".parse().unwrap()).unwrap());
+    assert_eq!(headless.do_move(&"B2>B1".parse().unwrap()).unwrap(), solved);
+    assert!(headless.is_solved());
+    assert!(headless.do_move(&"B1>A1".parse().unwrap()).is_err());
+    assert_eq!(headless.active_state(), solved);
+
+    assert_eq!(headless.undo().unwrap(), Some(to_id(&puzzle)));
+    assert_eq!(headless.active_state(), to_id(&puzzle));
+    assert_eq!(headless.redo().unwrap(), Some(solved));
+    assert!(headless.redo().unwrap().is_none());
+
+    // A lazily expanded graph grows along with the moves
+    let mut headless = WiggersGraafBuilder::new().lazy(1).build_headless().unwrap();
+    let node_count = headless.game.graph.node_count();
+    headless.do_move(&"B2>B1".parse().unwrap()).unwrap();
+    assert!(headless.game.graph.node_count() > node_count);
+}
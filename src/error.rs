@@ -0,0 +1,26 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! The errors of the graph and the solver, these are converted to JavaScript errors at the wasm boundary
+
+use crate::board::BoardId;
+use crate::graph::NodeIndex;
+use wasm_bindgen::JsValue;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    #[error("Unknown state {0:016x}")]
+    UnknownState(BoardId),
+    #[error("Hash collision, two different boards reduce to the same ID {0:016x}")]
+    HashCollision(BoardId),
+    #[error("An edge leads to state {0}, which isn't in the graph")]
+    DanglingEdge(NodeIndex),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<Error> for JsValue {
+    fn from(error: Error) -> Self {
+        js_sys::Error::new(&error.to_string()).into()
+    }
+}
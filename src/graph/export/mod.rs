@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Export of the analyzed graph as newline-delimited JSON, for tools like pandas or networkx.
+//! Each line is either a node or an edge, the nodes come first:
+//!
+//! ```text
+//! {"type":"node","id":"1a2b3c4d5e6f7a8b","position":"CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1","moves_to_start":0,"moves_to_solution":81}
+//! {"type":"edge","from":"1a2b3c4d5e6f7a8b","to":"0f1e2d3c4b5a6978","move":"B2>B1"}
+//! ```
+//!
+//! IDs are the hexadecimal board IDs, these are the same across runs. The "fake" solution state and
+//! its moves are left out, so solved states are at 0 moves from the solution. Unknown distances are null.
+//! Moves are reversible, each is listed once in either direction.
+
+#[cfg(test)]
+mod unittest;
+
+use crate::graph::{Graph, Node};
+use std::io::{Result, Write};
+
+/// Whether this is the "fake" solution state that all solved states lead to
+pub(crate) fn is_fake_solution(node: &Node) -> bool {
+    node.distance_to_solution() == Some(0)
+}
+
+fn to_json(distance: Option<u32>) -> String {
+    distance.map_or("null".to_string(), |distance| distance.to_string())
+}
+
+/// Write every state and move of the graph, one JSON object per line
+pub fn write_ndjson(graph: &Graph, writer: &mut impl Write) -> Result<()> {
+    let nodes = graph.nodes().iter().filter(|node| !is_fake_solution(node));
+    for node in nodes.clone() {
+        writeln!(
+            writer,
+            r#"{{"type":"node","id":"{:016x}","position":"{}","moves_to_start":{},"moves_to_solution":{}}}"#,
+            node.id,
+            node.board.to_notation(),
+            to_json(node.distance_to_start()),
+            to_json(node.distance_to_solution().map(|distance| distance - 1)),
+        )?;
+    }
+    for node in nodes {
+        let neighbors = graph
+            .neighbors(node)
+            .filter(|(_edge, neighbor)| !is_fake_solution(neighbor));
+        for (edge, neighbor) in neighbors {
+            writeln!(
+                writer,
+                r#"{{"type":"edge","from":"{:016x}","to":"{:016x}","move":"{:#}"}}"#,
+                node.id, neighbor.id, edge.slide_move
+            )?;
+        }
+    }
+    Ok(())
+}
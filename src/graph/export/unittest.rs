@@ -0,0 +1,36 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::graph::export::write_ndjson;
+use crate::graph::unittest::build_chain;
+
+#[test]
+fn test_write_ndjson() {
+    let (graph, ids) = build_chain();
+    let mut output = Vec::new();
+    write_ndjson(&graph, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    // The fake solution and the moves to it are left out
+    assert_eq!(lines.len(), 3 + 4);
+    assert!(!output.contains(&format!("{:016x}", ids[3])));
+    assert_eq!(
+        lines[0],
+        format!(
+            r#"{{"type":"node","id":"{:016x}","position":"CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1","moves_to_start":0,"moves_to_solution":2}}"#,
+            ids[0]
+        )
+    );
+    assert!(lines[2].ends_with(r#""moves_to_start":2,"moves_to_solution":0}"#));
+    assert_eq!(
+        lines[3],
+        format!(
+            r#"{{"type":"edge","from":"{:016x}","to":"{:016x}","move":"B2>B1"}}"#,
+            ids[0], ids[1]
+        )
+    );
+    assert!(lines[3..]
+        .iter()
+        .all(|line| line.starts_with(r#"{"type":"edge""#)));
+}
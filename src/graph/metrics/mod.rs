@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Whole-graph metrics, moves are reversible so the graph is treated as undirected
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::BoardId;
+use crate::graph::{Graph, NodeIndex};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metrics {
+    /// The largest eccentricity, i.e. the most moves that the best route between two states can take
+    pub diameter: u32,
+    /// The smallest eccentricity
+    pub radius: u32,
+    /// The most moves that the best route from each state to any state of its component takes
+    pub eccentricities: HashMap<BoardId, u32>,
+    /// The sizes of the connected components, largest first
+    pub component_sizes: Vec<usize>,
+}
+
+impl Metrics {
+    pub fn new(graph: &Graph) -> Self {
+        let components = find_components(graph);
+        let mut eccentricities = HashMap::with_capacity(graph.node_count());
+        for component in &components {
+            let component_eccentricities = find_eccentricities(graph, component);
+            eccentricities.extend(
+                component_eccentricities
+                    .into_iter()
+                    .map(|(index, eccentricity)| (graph.node(index).id, eccentricity)),
+            );
+        }
+        let mut component_sizes: Vec<usize> = components.iter().map(Vec::len).collect();
+        component_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+        Self {
+            diameter: eccentricities.values().copied().max().unwrap_or(0),
+            radius: eccentricities.values().copied().min().unwrap_or(0),
+            eccentricities,
+            component_sizes,
+        }
+    }
+}
+
+/// Count the moves from one state to every state it can reach
+/// @returns the states in the order that they are reached, with their distances
+pub(crate) fn distances_from(graph: &Graph, from: NodeIndex) -> Vec<(NodeIndex, u32)> {
+    let mut visited = vec![false; graph.node_count()];
+    visited[from as usize] = true;
+    let mut distances = vec![(from, 0)];
+    let mut next = 0;
+    while let Some(&(index, distance)) = distances.get(next) {
+        next += 1;
+        for edge in graph.edges(graph.node(index)) {
+            if !visited[edge.neighbor as usize] {
+                visited[edge.neighbor as usize] = true;
+                distances.push((edge.neighbor, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Group the states into the sets that are reachable from one another
+pub fn find_components(graph: &Graph) -> Vec<Vec<NodeIndex>> {
+    let mut assigned = vec![false; graph.node_count()];
+    let mut components = Vec::new();
+    for index in 0..graph.node_count() as NodeIndex {
+        if assigned[index as usize] {
+            continue;
+        }
+        let component: Vec<NodeIndex> = distances_from(graph, index)
+            .into_iter()
+            .map(|(index, _distance)| index)
+            .collect();
+        for index in &component {
+            assigned[*index as usize] = true;
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Find the exact eccentricity of every state of a component, without a search from every state.
+/// This is the BoundingEccentricities algorithm by Takes and Kosters: each search narrows the bounds
+/// of the eccentricities of all states, until every state's bounds meet.
+fn find_eccentricities(graph: &Graph, component: &[NodeIndex]) -> HashMap<NodeIndex, u32> {
+    let mut lower: HashMap<NodeIndex, u32> = component.iter().map(|index| (*index, 0)).collect();
+    let mut upper: HashMap<NodeIndex, u32> =
+        component.iter().map(|index| (*index, u32::MAX)).collect();
+    let mut candidates: HashSet<NodeIndex> = component.iter().copied().collect();
+    let mut pick_highest_upper = true;
+
+    while !candidates.is_empty() {
+        // Alternate between the state with the highest upper bound and the one with the lowest lower bound
+        let candidate = match pick_highest_upper {
+            true => candidates
+                .iter()
+                .max_by_key(|index| (upper[index], **index)),
+            false => candidates
+                .iter()
+                .min_by_key(|index| (lower[index], **index)),
+        };
+        let candidate = *candidate.expect("Candidates can't be empty");
+        pick_highest_upper = !pick_highest_upper;
+
+        let distances: HashMap<NodeIndex, u32> =
+            distances_from(graph, candidate).into_iter().collect();
+        let eccentricity = distances.values().copied().max().unwrap_or(0);
+        candidates.retain(|index| {
+            let distance = distances[index];
+            let lowest = lower[index].max(distance.max(eccentricity - distance));
+            let highest = upper[index].min(eccentricity + distance);
+            lower.insert(*index, lowest);
+            upper.insert(*index, highest);
+            lowest != highest
+        });
+    }
+    lower
+}
@@ -0,0 +1,51 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{
+    get_solved_board, get_start_board, make_move, to_id, Coordinates, SlideDirection, SlideMove,
+};
+use crate::graph::metrics::Metrics;
+use crate::graph::Graph;
+
+#[test]
+fn test_metrics() {
+    let mut graph = Graph::new();
+
+    // A chain of four states, and a state that is unreachable from the chain
+    let mut boards = vec![get_start_board()];
+    for (x, y, direction) in [
+        (1, 1, SlideDirection::Down),
+        (1, 0, SlideDirection::Right),
+        (2, 1, SlideDirection::Left),
+    ] {
+        let slide_move = SlideMove {
+            start: Coordinates { x, y },
+            direction,
+            distance: 1,
+        };
+        let board = make_move(boards.last().unwrap(), &slide_move).expect("Failed to make move");
+        graph.add_node(*boards.last().unwrap()).unwrap();
+        graph.add_node(board).unwrap();
+        graph
+            .add_edge(boards.last().unwrap(), &board, &slide_move)
+            .unwrap();
+        graph
+            .add_edge(&board, boards.last().unwrap(), &slide_move)
+            .unwrap();
+        boards.push(board);
+    }
+    graph.add_node(get_solved_board()).unwrap();
+
+    let metrics = Metrics::new(&graph);
+    let eccentricities: Vec<u32> = boards
+        .iter()
+        .map(|board| metrics.eccentricities[&to_id(board)])
+        .collect();
+    assert_eq!(eccentricities, [3, 2, 2, 3]);
+    assert_eq!(metrics.eccentricities[&to_id(&get_solved_board())], 0);
+    assert_eq!(metrics.diameter, 3);
+    assert_eq!(metrics.radius, 0);
+    assert_eq!(metrics.component_sizes, [4, 1]);
+
+    assert_eq!(Metrics::new(&Graph::new()).diameter, 0);
+}
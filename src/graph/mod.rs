@@ -1,29 +1,114 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
+pub mod export;
+pub mod metrics;
+pub mod stream;
 #[cfg(test)]
-mod unittest;
+pub(crate) mod unittest;
 
-use crate::board::{to_id, Board, BoardId, SlideMove};
-use std::collections::{HashMap, VecDeque};
+use crate::board::{get_valid_moves, to_id, Board, BoardId, SlideMove};
+use crate::error::{Error, Result};
+use itertools::Either;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
 
+/// The position of a node in the graph's arena
+pub type NodeIndex = u32;
+
+/// Marks a distance that is not known, because the node can't be reached
+const UNKNOWN_DISTANCE: u16 = u16::MAX;
+
+/// The nodes are kept small, the standard puzzle alone has tens of thousands of them
 #[derive(Clone)]
 pub struct Node {
+    pub id: BoardId,
     pub board: Board,
-    pub edges: Vec<Edge>,
-    pub distance_to_start: Option<u32>,
-    pub distance_to_solution: Option<u32>,
+    /// The node's edges are a slice of the graph's shared edge list
+    edges: EdgeRange,
+    distance_to_start: u16,
+    distance_to_solution: u16,
     pub _on_shortest_path: bool,
+    /// The number of possible moves, not counting the "fake" solution moves. It stops at 255.
+    pub branching_factor: u8,
+    /// If this state is in a dead-end pocket, the state through which the pocket is entered.
+    /// Every way to the solution leads back through this entry, for nested pockets it is the innermost entry.
+    pub dead_end_entry: Option<NodeIndex>,
+    /// Whether this state is a cut vertex, i.e. the entry of a dead-end pocket
+    pub bottleneck: bool,
+    /// The moves that a player who doesn't know the optimal solution needs on average, see `simulate::difficulty`
+    estimated_moves: u16,
+}
+
+#[derive(Clone, Copy, Default)]
+struct EdgeRange {
+    offset: u32,
+    len: u16,
+}
+
+impl EdgeRange {
+    fn range(&self) -> std::ops::Range<usize> {
+        self.offset as usize..self.offset as usize + self.len as usize
+    }
+}
+
+impl Node {
+    pub fn distance_to_start(&self) -> Option<u32> {
+        decode_distance(self.distance_to_start)
+    }
+
+    pub fn distance_to_solution(&self) -> Option<u32> {
+        decode_distance(self.distance_to_solution)
+    }
+
+    /// The estimated moves to the solution, if the difficulty was estimated
+    pub fn estimated_moves(&self) -> Option<u32> {
+        decode_distance(self.estimated_moves)
+    }
+}
+
+fn decode_distance(distance: u16) -> Option<u32> {
+    (distance != UNKNOWN_DISTANCE).then_some(distance as u32)
+}
+
+/// Longer distances saturate, a 4 by 5 board doesn't come close
+fn encode_distance(distance: u32) -> u16 {
+    distance.min(UNKNOWN_DISTANCE as u32 - 1) as u16
+}
+
+/// A node's board and edges, detached from the graph
+#[derive(Clone)]
+pub struct Neighborhood {
+    pub board: Board,
+    pub edges: Vec<Edge>,
 }
 
 #[derive(Clone)]
 pub struct Edge {
-    pub neighbor: BoardId,
+    pub neighbor: NodeIndex,
     pub slide_move: SlideMove,
+    /// Whether the neighbor has an edge back, a move of a constrained piece may only go one way
+    pub reversible: bool,
 }
 
+/// Whether the solution can be reached from the start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveOutcome {
+    Solvable {
+        distance_to_solution: u32,
+    },
+    /// The solution is out of reach, the start only leads to a number of other states
+    Unsolvable {
+        reachable_states: usize,
+    },
+}
+
+/// The nodes are kept in an arena, edges refer to their neighbors by index so traversals don't re-hash
+#[derive(Clone)]
 pub struct Graph {
-    pub map: HashMap<BoardId, Node>,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    indices: HashMap<BoardId, NodeIndex>,
     pub max_distance_to_start: u32,
     pub max_distance_to_solution: u32,
 }
@@ -37,173 +122,569 @@ impl Default for Graph {
 impl Graph {
     pub fn new() -> Graph {
         Graph {
-            map: HashMap::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            indices: HashMap::new(),
             max_distance_to_start: 0,
             max_distance_to_solution: 0,
         }
     }
 
-    pub fn add_node(&mut self, board: Board) {
+    /// Add a node for a board, unless there is one already
+    /// @returns the index of the board's node
+    pub fn add_node(&mut self, board: Board) -> Result<NodeIndex> {
         let hash = to_id(&board);
-        if let Some(entry) = self.map.get(&hash) {
+        if let Some(&index) = self.indices.get(&hash) {
+            let entry = &self.nodes[index as usize];
             if entry.board != board {
-                panic!("Hash collision! These boards are not the same, but they produce the same hash.\
+                log::error!("Hash collision! These boards are not the same, but they produce the same hash.\
                  New Board: {:?}, Existing Board: {:?}, both reduced to hash: {:?}", board, entry.board, hash);
+                return Err(Error::HashCollision(hash));
             }
 
             // We already found an identical entry, nothing to do
-            return;
+            return Ok(index);
         }
 
-        self.map.insert(
-            hash,
-            Node {
-                board,
-                edges: Vec::new(),
-                distance_to_start: None,
-                distance_to_solution: None,
-                _on_shortest_path: false,
-            },
-        );
+        let index = self.nodes.len() as NodeIndex;
+        self.indices.insert(hash, index);
+        self.nodes.push(Node {
+            id: hash,
+            board,
+            edges: EdgeRange::default(),
+            distance_to_start: UNKNOWN_DISTANCE,
+            distance_to_solution: UNKNOWN_DISTANCE,
+            _on_shortest_path: false,
+            branching_factor: 0,
+            dead_end_entry: None,
+            bottleneck: false,
+            estimated_moves: UNKNOWN_DISTANCE,
+        });
+        Ok(index)
     }
 
     pub fn contains_node(&self, board: &Board) -> bool {
-        let hash = to_id(board);
-        self.map.contains_key(&hash)
+        self.contains(to_id(board))
+    }
+
+    pub fn contains(&self, id: BoardId) -> bool {
+        self.indices.contains_key(&id)
     }
 
-    pub fn _node_count(&self) -> usize {
-        self.map.len()
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
     }
 
-    pub fn add_edge(&mut self, from: &Board, to: &Board, slide_move: &SlideMove) {
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn index_of(&self, id: BoardId) -> Option<NodeIndex> {
+        self.indices.get(&id).copied()
+    }
+
+    pub fn get(&self, id: BoardId) -> Option<&Node> {
+        Some(self.node(self.index_of(id)?))
+    }
+
+    /// Get the node at an index, which must come from this graph
+    pub fn node(&self, index: NodeIndex) -> &Node {
+        &self.nodes[index as usize]
+    }
+
+    pub fn edges(&self, node: &Node) -> &[Edge] {
+        &self.edges[node.edges.range()]
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.nodes.iter().map(|node| node.edges.len as usize).sum()
+    }
+
+    /// Get the nodes that a node's edges lead to, along with the edges
+    pub fn neighbors<'a>(&'a self, node: &'a Node) -> impl Iterator<Item = (&'a Edge, &'a Node)> {
+        self.edges(node)
+            .iter()
+            .map(|edge| (edge, self.node(edge.neighbor)))
+    }
+
+    pub fn neighborhood(&self, index: NodeIndex) -> Neighborhood {
+        let node = self.node(index);
+        Neighborhood {
+            board: node.board,
+            edges: self.edges(node).to_vec(),
+        }
+    }
+
+    /// The number of bytes that the graph has allocated
+    pub fn memory_footprint(&self) -> usize {
+        self.nodes.capacity() * size_of::<Node>()
+            + self.edges.capacity() * size_of::<Edge>()
+            + self.indices.capacity() * size_of::<(BoardId, NodeIndex)>()
+    }
+
+    /// Add an edge from a known node, the node that it leads to is added if it is unknown
+    pub fn add_edge(&mut self, from: &Board, to: &Board, slide_move: &SlideMove) -> Result<()> {
         let id_a = to_id(from);
-        let id_b = to_id(to);
-        self.map
-            .get_mut(&id_a)
-            .expect("Inserting edge from unknown node")
-            .edges
-            .push(Edge {
-                neighbor: id_b,
-                slide_move: *slide_move,
-            });
-    }
-
-    pub fn analyze(&mut self, start: &Board, solution: &Board) {
-        // Find distances from start board
-        self.max_distance_to_start = self.distance_from(to_id(start), |node, distance| {
-            if node.distance_to_start.is_some() {
+        let index_a = self.index_of(id_a).ok_or(Error::UnknownState(id_a))?;
+        let index_b = self.add_node(*to)?;
+
+        // Grow the node's slice of the edge list, move it to the end of the list first if it isn't there yet
+        let mut range = self.nodes[index_a as usize].edges;
+        if range.range().end != self.edges.len() {
+            let offset = self.edges.len() as u32;
+            self.edges.extend_from_within(range.range());
+            range.offset = offset;
+        }
+        self.edges.push(Edge {
+            neighbor: index_b,
+            slide_move: *slide_move,
+            reversible: false,
+        });
+        range.len += 1;
+        self.nodes[index_a as usize].edges = range;
+
+        // Edges are added one way at a time, the edge back makes both of them reversible
+        let range_b = self.nodes[index_b as usize].edges.range();
+        if let Some(back) = self.edges[range_b]
+            .iter_mut()
+            .find(|edge| edge.neighbor == index_a)
+        {
+            back.reversible = true;
+            self.edges
+                .last_mut()
+                .expect("The edge was just added")
+                .reversible = true;
+        }
+        Ok(())
+    }
+
+    /// Drop the edges left behind by moved slices, and the unused capacity
+    fn compact(&mut self) {
+        let mut edges = Vec::with_capacity(self.edge_count());
+        for node in self.nodes.iter_mut() {
+            let offset = edges.len() as u32;
+            edges.extend_from_slice(&self.edges[node.edges.range()]);
+            node.edges.offset = offset;
+        }
+        self.edges = edges;
+        self.nodes.shrink_to_fit();
+        self.indices.shrink_to_fit();
+    }
+
+    /// Add the states within a number of moves of a state, for graphs that grow as the player goes
+    /// instead of being generated all at once. States that were expanded before keep their edges, the
+    /// distances of the new states are unknown until the graph is analyzed, or annotated with
+    /// annotate_partial. The moves to the outermost states are known before their own moves are.
+    /// @returns the number of states that were added
+    pub fn expand(&mut self, center: &Board, depth: u32) -> Result<usize> {
+        let node_count = self.node_count();
+        self.add_node(*center)?;
+        let mut visited = HashSet::from([to_id(center)]);
+        let mut frontier = vec![*center];
+        for _ in 0..depth {
+            let mut next_frontier = Vec::new();
+            for board in frontier {
+                let index = self.add_node(board)?;
+                let expanded = !self.edges(self.node(index)).is_empty();
+                let neighbors: Vec<Board> = match expanded {
+                    true => self
+                        .neighbors(self.node(index))
+                        .map(|(_edge, neighbor)| neighbor.board)
+                        .collect(),
+                    false => {
+                        let mut neighbors = Vec::new();
+                        for (slide_move, neighbor) in get_valid_moves(&board) {
+                            self.add_edge(&board, &neighbor, &slide_move)?;
+                            // The outermost states aren't expanded, so the edge back may be missing
+                            // still. Look the move back up, or the move would show as one-way.
+                            if get_valid_moves(&neighbor)
+                                .any(|(_slide_move, back)| to_id(&back) == to_id(&board))
+                            {
+                                let edge = self.edges.last_mut().expect("The edge was just added");
+                                edge.reversible = true;
+                            }
+                            neighbors.push(neighbor);
+                        }
+                        neighbors
+                    }
+                };
+                next_frontier.extend(
+                    neighbors
+                        .into_iter()
+                        .filter(|neighbor| visited.insert(to_id(neighbor))),
+                );
+            }
+            frontier = next_frontier;
+        }
+        Ok(self.node_count() - node_count)
+    }
+
+    /// Annotate a graph that holds only part of the states, see expand. The distance from the start
+    /// goes through the states that were added so far, so it can be longer than in the whole graph. The
+    /// distance to the solution is estimated for the states that don't have one yet, as the solution
+    /// doesn't need to be part of the graph.
+    /// @param estimate the distance of a state to the solution, or None if it can't reach the solution
+    pub fn annotate_partial(
+        &mut self,
+        start: &Board,
+        estimate: impl Fn(&Board) -> Option<u32>,
+    ) -> Result<()> {
+        let id = to_id(start);
+        let start = self.index_of(id).ok_or(Error::UnknownState(id))?;
+
+        // New states can connect the known ones through shorter paths, so all distances are redone
+        for node in self.nodes.iter_mut() {
+            node.distance_to_start = UNKNOWN_DISTANCE;
+        }
+        self.max_distance_to_start = self.distance_from(start, None, |node, distance| {
+            if node.distance_to_start().is_some() {
                 // This node was already visited
                 return false;
             }
-            node.distance_to_start = Some(*distance);
+            node.distance_to_start = encode_distance(*distance);
             true
-        });
+        })?;
+
+        self.max_distance_to_solution = 0;
+        for node in self.nodes.iter_mut() {
+            if node.distance_to_solution().is_none() {
+                node.distance_to_solution =
+                    estimate(&node.board).map_or(UNKNOWN_DISTANCE, encode_distance);
+            }
+            if let Some(distance) = node.distance_to_solution() {
+                self.max_distance_to_solution = self.max_distance_to_solution.max(distance);
+            }
+        }
+        Ok(())
+    }
+
+    /// Annotate a node with the moves that a non-optimal player is estimated to need
+    pub fn set_estimated_moves(&mut self, index: NodeIndex, moves: Option<u32>) {
+        self.nodes[index as usize].estimated_moves =
+            moves.map_or(UNKNOWN_DISTANCE, encode_distance);
+    }
 
-        // Find distances from solution board
-        self.max_distance_to_solution = self.distance_from(to_id(solution), |node, distance| {
-            if node.distance_to_solution.is_some() {
+    /// Annotate the nodes with their distances and structure
+    /// @returns whether the solution can be reached from the start, the analysis is complete either way,
+    /// or an error if the start or the solution is unknown
+    pub fn analyze(&mut self, start: &Board, solution: &Board) -> Result<SolveOutcome> {
+        let [start, solution] = [to_id(start), to_id(solution)]
+            .map(|id| self.index_of(id).ok_or(Error::UnknownState(id)));
+        let (start, solution) = (start?, solution?);
+        self.compact();
+
+        // Find distances from start board
+        self.max_distance_to_start = self.distance_from(start, None, |node, distance| {
+            if node.distance_to_start().is_some() {
                 // This node was already visited
                 return false;
             }
-            node.distance_to_solution = Some(*distance);
+            node.distance_to_start = encode_distance(*distance);
             true
-        });
+        })?;
+
+        // Find distances to solution board, against the moves, as some moves can't be undone
+        let incoming = self.incoming_edges()?;
+        let incoming = incoming.as_deref();
+        self.max_distance_to_solution =
+            self.distance_from(solution, incoming, |node, distance| {
+                if node.distance_to_solution().is_some() {
+                    // This node was already visited
+                    return false;
+                }
+                node.distance_to_solution = encode_distance(*distance);
+                true
+            })?;
+
+        self.count_branching_factors()?;
+        self.find_dead_ends(solution)?;
 
         // TODO (Menno 10.12.2024) annotate boards that are part of fasted solution
         println!(
             "Minimum moves from start to solution is {:?}",
-            self.map
-                .get(&to_id(start))
-                .expect("Huh how did that happen")
-                .distance_to_solution
+            self.node(start).distance_to_solution()
         );
         println!(
             "Minimum moves from solution to start is {:?}",
-            self.map
-                .get(&to_id(solution))
-                .expect("Huh how did that happen")
-                .distance_to_start
+            self.node(solution).distance_to_start()
         );
+
+        Ok(match self.node(start).distance_to_solution() {
+            Some(distance_to_solution) => SolveOutcome::Solvable {
+                distance_to_solution,
+            },
+            None => SolveOutcome::Unsolvable {
+                reachable_states: self
+                    .nodes
+                    .iter()
+                    .filter(|node| node.distance_to_start().is_some())
+                    .count(),
+            },
+        })
+    }
+
+    fn count_branching_factors(&mut self) -> Result<()> {
+        for index in 0..self.nodes.len() {
+            let mut moves = 0usize;
+            for edge in self.edges(&self.nodes[index]) {
+                let neighbor = self
+                    .nodes
+                    .get(edge.neighbor as usize)
+                    .ok_or(Error::DanglingEdge(edge.neighbor))?;
+                if neighbor.distance_to_solution() != Some(0) {
+                    moves += 1;
+                }
+            }
+            self.nodes[index].branching_factor = u8::try_from(moves).unwrap_or(u8::MAX);
+        }
+        Ok(())
+    }
+
+    /// Find the dead-end pockets, i.e. the states that are cut off from the solution by a single entry state.
+    /// The entries are the bottlenecks, these are the cut vertices of the graph.
+    /// This is Tarjan's articulation point search, in a depth first traversal from the solution.
+    fn find_dead_ends(&mut self, solution: NodeIndex) -> Result<()> {
+        let mut discovery: Vec<Option<u32>> = vec![None; self.nodes.len()];
+        let mut low: Vec<u32> = vec![0; self.nodes.len()];
+        let mut parents: Vec<Option<NodeIndex>> = vec![None; self.nodes.len()];
+        discovery[solution as usize] = Some(0);
+        let mut preorder = vec![solution];
+
+        // Each entry is a state, and the index of its next edge to traverse
+        let mut stack: Vec<(NodeIndex, usize)> = vec![(solution, 0)];
+        while let Some(&(key, edge_index)) = stack.last() {
+            let Some(edge) = self.edges(self.node(key)).get(edge_index) else {
+                // All edges are traversed, pass the lowest reachable discovery time on to the parent
+                stack.pop();
+                if let Some(parent) = parents[key as usize] {
+                    low[parent as usize] = low[parent as usize].min(low[key as usize]);
+                }
+                continue;
+            };
+            stack.last_mut().expect("Stack can't be empty").1 += 1;
+
+            let neighbor = edge.neighbor as usize;
+            let neighbor_discovery = *discovery
+                .get(neighbor)
+                .ok_or(Error::DanglingEdge(edge.neighbor))?;
+            match neighbor_discovery {
+                None => {
+                    let time = preorder.len() as u32;
+                    discovery[neighbor] = Some(time);
+                    low[neighbor] = time;
+                    parents[neighbor] = Some(key);
+                    preorder.push(edge.neighbor);
+                    stack.push((edge.neighbor, 0));
+                }
+                Some(time) if parents[key as usize] != Some(edge.neighbor) => {
+                    low[key as usize] = low[key as usize].min(time);
+                }
+                Some(_) => {}
+            }
+        }
+
+        // A subtree that can't reach above its parent is a pocket, parents are visited before their children
+        for key in preorder.into_iter().skip(1) {
+            let parent = parents[key as usize].expect("Only the solution has no parent");
+            let entry =
+                match parent != solution && Some(low[key as usize]) >= discovery[parent as usize] {
+                    true => {
+                        self.nodes[parent as usize].bottleneck = true;
+                        Some(parent)
+                    }
+                    false => self.node(parent).dead_end_entry,
+                };
+            self.nodes[key as usize].dead_end_entry = entry;
+        }
+        Ok(())
+    }
+
+    /// The nodes that each node can be reached from, or none if every move can be undone,
+    /// in which case these are the nodes' neighbors
+    fn incoming_edges(&self) -> Result<Option<Vec<Vec<NodeIndex>>>> {
+        if self.edges.iter().all(|edge| edge.reversible) {
+            return Ok(None);
+        }
+        let mut incoming = vec![Vec::new(); self.nodes.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            for edge in self.edges(node) {
+                incoming
+                    .get_mut(edge.neighbor as usize)
+                    .ok_or(Error::DanglingEdge(edge.neighbor))?
+                    .push(index as NodeIndex);
+            }
+        }
+        Ok(Some(incoming))
     }
 
     /// Do a breadth first traversal, counting distance from a starting point. Returns max distance
-    fn distance_from<Pred>(&mut self, from: BoardId, pred: Pred) -> u32
+    /// @param incoming traverse the edges backwards, see incoming_edges, instead of along the moves
+    fn distance_from<Pred>(
+        &mut self,
+        from: NodeIndex,
+        incoming: Option<&[Vec<NodeIndex>]>,
+        pred: Pred,
+    ) -> Result<u32>
     where
         Pred: Fn(&mut Node, &u32) -> bool,
     {
-        struct QueueEntry {
-            key: BoardId,
-            distance_from: u32,
-        }
-        // Create a queue with board keys and their corresponding distance to the start staring point.
-        // We initialize the queue with the starting board, which has 0 distance to itself.
-        let mut inspection_queue: VecDeque<QueueEntry> = VecDeque::from([QueueEntry {
-            key: from,
-            distance_from: 0,
-        }]);
+        // Create a queue with node indices and their corresponding distance to the start staring point.
+        // We initialize the queue with the starting node, which has 0 distance to itself.
+        let mut inspection_queue: VecDeque<(NodeIndex, u32)> = VecDeque::from([(from, 0)]);
         let mut max_distance_from = 0;
 
-        while !inspection_queue.is_empty() {
-            let entry = inspection_queue
-                .pop_front()
-                .expect("Failed to pop from queue, is it empty?");
+        while let Some((index, distance_from)) = inspection_queue.pop_front() {
             let node = self
-                .map
-                .get_mut(&entry.key)
-                .expect("Graph does not contain this board.");
+                .nodes
+                .get_mut(index as usize)
+                .ok_or(Error::DanglingEdge(index))?;
+            let edges = &self.edges[node.edges.range()];
+            let neighbors = match incoming {
+                Some(incoming) => Either::Left(incoming[index as usize].iter().copied()),
+                None => Either::Right(edges.iter().map(|edge| edge.neighbor)),
+            };
 
             // Run predicate
-            if !pred(node, &entry.distance_from) {
+            if !pred(node, &distance_from) {
                 // Predicate claims this node was already visited, ignore it.
                 continue;
             }
 
-            if max_distance_from < entry.distance_from {
-                max_distance_from = entry.distance_from;
+            if max_distance_from < distance_from {
+                max_distance_from = distance_from;
+            }
+
+            let neighbors_distance_from = distance_from + 1;
+            for neighbor in neighbors {
+                inspection_queue.push_back((neighbor, neighbors_distance_from));
+            }
+        }
+        Ok(max_distance_from)
+    }
+
+    /// Find one of the shortest sequences of moves between two states, with a breadth first search
+    /// @param max_length the maximum number of moves to search for, longer paths are not found
+    /// @returns the edges to follow in order, or None if there is no path within the maximum length
+    pub fn find_path(&self, from: BoardId, to: BoardId, max_length: u32) -> Option<Vec<Edge>> {
+        let (from, to) = (self.index_of(from)?, self.index_of(to)?);
+        // Remember through which edge each state was first reached
+        let mut reached_by: HashMap<NodeIndex, Option<(NodeIndex, &Edge)>> =
+            HashMap::from([(from, None)]);
+        let mut inspection_queue: VecDeque<(NodeIndex, u32)> = VecDeque::from([(from, 0)]);
+
+        while let Some((key, distance)) = inspection_queue.pop_front() {
+            if key == to {
+                // Walk back to the starting point
+                let mut path = Vec::new();
+                let mut current = key;
+                while let Some(Some((previous, edge))) = reached_by.get(&current) {
+                    path.push((*edge).clone());
+                    current = *previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if distance == max_length {
+                continue;
             }
 
-            let neighbors_distance_from = entry.distance_from + 1;
-            for edge in node.edges.iter() {
-                inspection_queue.push_back(QueueEntry {
-                    key: edge.neighbor,
-                    distance_from: neighbors_distance_from,
-                });
+            for edge in self.edges(self.node(key)) {
+                if let Entry::Vacant(entry) = reached_by.entry(edge.neighbor) {
+                    entry.insert(Some((key, edge)));
+                    inspection_queue.push_back((edge.neighbor, distance + 1));
+                }
             }
         }
-        max_distance_from
+        None
+    }
+
+    /// Find the states that can be reached from a state in at most a number of moves, with the fewest
+    /// moves that reach each of them. The "fake" solution can't be reached, as it isn't a real state.
+    pub fn within(&self, from: BoardId, max_moves: u32) -> HashMap<BoardId, u32> {
+        let Some(from) = self.index_of(from) else {
+            return HashMap::new();
+        };
+        let mut reached: HashMap<NodeIndex, u32> = HashMap::from([(from, 0)]);
+        let mut inspection_queue: VecDeque<NodeIndex> = VecDeque::from([from]);
+        while let Some(key) = inspection_queue.pop_front() {
+            let moves = reached[&key];
+            if moves == max_moves {
+                continue;
+            }
+            for edge in self.edges(self.node(key)) {
+                if export::is_fake_solution(self.node(edge.neighbor)) {
+                    continue;
+                }
+                if let Entry::Vacant(entry) = reached.entry(edge.neighbor) {
+                    entry.insert(moves + 1);
+                    inspection_queue.push_back(edge.neighbor);
+                }
+            }
+        }
+        reached
+            .into_iter()
+            .map(|(index, moves)| (self.node(index).id, moves))
+            .collect()
+    }
+
+    /// Check whether a state lies on one of the shortest paths from the start to the solution,
+    /// i.e. whether its distances to both add up to the start's distance to the solution
+    /// @param start the state that the graph was analyzed from
+    pub fn is_on_shortest_path(&self, start: BoardId, state: BoardId) -> bool {
+        let Some(length) = self.get(start).and_then(Node::distance_to_solution) else {
+            return false;
+        };
+        self.get(state).is_some_and(|node| {
+            node.distance_to_start()
+                .zip(node.distance_to_solution())
+                .is_some_and(|(to_start, to_solution)| to_start + to_solution == length)
+        })
+    }
+
+    /// Find the bottlenecks that every way from a state to the solution passes through
+    /// @returns the bottlenecks in the order that they are passed
+    pub fn checkpoints(&self, from: BoardId) -> Vec<BoardId> {
+        let entry_of = |node: &&Node| Some(self.node(node.dead_end_entry?));
+        std::iter::successors(self.get(from).and_then(|node| entry_of(&node)), entry_of)
+            .map(|node| node.id)
+            .collect()
+    }
+
+    /// Follow one of the optimal paths from a state to the solution, using the analyzed distances
+    /// @returns the states along the path, starting with from, or nothing if from is not analyzed
+    pub fn solution_path(&self, from: BoardId) -> Vec<BoardId> {
+        let mut path = Vec::new();
+        let mut current = self.get(from);
+        while let Some((node, distance)) =
+            current.and_then(|node| Some((node, node.distance_to_solution()?)))
+        {
+            path.push(node.id);
+            // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+            if distance <= 1 {
+                break;
+            }
+            current = self
+                .neighbors(node)
+                .map(|(_edge, neighbor)| neighbor)
+                .find(|neighbor| neighbor.distance_to_solution() == Some(distance - 1));
+        }
+        path
     }
 
     /// Do a breadth first traversal on only the shortest paths between from and to
-    fn _shortest_path(&mut self, from: BoardId, _to: BoardId) {
-        struct QueueEntry {
-            key: BoardId,
-            distance_from: u32,
-        }
-        // Create a queue with board keys and their corresponding distance to the start staring point.
-        // We initialize the queue with the starting board, which has 0 distance to itself.
-        let mut inspection_queue: VecDeque<QueueEntry> = VecDeque::from([QueueEntry {
-            key: from,
-            distance_from: 0,
-        }]);
-
-        while !inspection_queue.is_empty() {
-            let entry = inspection_queue
-                .pop_front()
-                .expect("Failed to pop from queue, is it empty?");
-            let node = self
-                .map
-                .get_mut(&entry.key)
-                .expect("Graph does not contain this board.");
+    fn _shortest_path(&mut self, from: NodeIndex, _to: NodeIndex) {
+        // Create a queue with node indices and their corresponding distance to the start staring point.
+        // We initialize the queue with the starting node, which has 0 distance to itself.
+        let mut inspection_queue: VecDeque<(NodeIndex, u32)> = VecDeque::from([(from, 0)]);
+
+        while let Some((index, distance_from)) = inspection_queue.pop_front() {
+            let node = &self.nodes[index as usize];
 
             // TODO breadth first shortest paths
 
-            let neighbors_distance_from = entry.distance_from + 1;
-            for edge in node.edges.iter() {
-                inspection_queue.push_back(QueueEntry {
-                    key: edge.neighbor,
-                    distance_from: neighbors_distance_from,
-                });
+            let neighbors_distance_from = distance_from + 1;
+            for edge in self.edges(node) {
+                inspection_queue.push_back((edge.neighbor, neighbors_distance_from));
             }
         }
     }
@@ -0,0 +1,392 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! A compact binary format of a solved graph, that is written and read in frames. Neither side holds
+//! more than a frame besides the graph itself, so big custom puzzles don't need twice their memory.
+//!
+//! The stream starts with a magic and a version, followed by frames of a kind byte, a little-endian
+//! u32 payload length, and the payload:
+//! - Start: the start board and the solution board, followed by the puzzle's special cells and
+//!   its checkpoints
+//! - Nodes: boards and the number of checkpoints they reached, in the order of their node indices
+//! - Edges: the index of the node that an edge leaves from, the index it leads to, and its move
+//! - End: no payload, the graph is complete
+//!
+//! The distances are not stored, the reader analyzes the graph again, which is quick compared to
+//! generating it.
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{
+    Board, Cells, Coordinates, GoalProgress, Piece, Size, SlideDirection, SlideMove,
+};
+use crate::graph::{Graph, NodeIndex, SolveOutcome};
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Write};
+
+const MAGIC: &[u8; 4] = b"WGGR";
+const VERSION: u8 = 2;
+
+/// The most boards or edges in a single frame
+const FRAME_ENTRIES: usize = 4096;
+
+/// The size of the frame header, its kind and its payload length
+const FRAME_HEADER_SIZE: usize = 5;
+
+const BOARD_SIZE: usize = 2 + 10 * 4;
+/// A board and the number of checkpoints it reached
+const NODE_SIZE: usize = BOARD_SIZE + 1;
+const CELLS_SIZE: usize = 5 * 4;
+pub(crate) const PIECE_SIZE: usize = 4;
+const EDGE_SIZE: usize = 4 + 4 + 4;
+
+/// The kinds of frames, in the order that they appear
+const KIND_START: u8 = 0;
+const KIND_NODES: u8 = 1;
+const KIND_EDGES: u8 = 2;
+const KIND_END: u8 = 3;
+
+pub(crate) fn encode_piece(piece: &Piece, payload: &mut Vec<u8>) {
+    payload.extend_from_slice(&[
+        piece.position.x as u8,
+        piece.position.y as u8,
+        piece.size.x,
+        piece.size.y,
+    ]);
+}
+
+pub(crate) fn decode_piece(bytes: &[u8]) -> Piece {
+    Piece {
+        position: Coordinates {
+            x: bytes[0] as i8,
+            y: bytes[1] as i8,
+        },
+        size: Size {
+            x: bytes[2],
+            y: bytes[3],
+        },
+    }
+}
+
+fn encode_board(board: &Board, payload: &mut Vec<u8>) {
+    payload.extend_from_slice(&[board.size.x, board.size.y]);
+    for piece in &board.pieces {
+        encode_piece(piece, payload);
+    }
+}
+
+/// Decode the layout of a board, its cells and checkpoints are those of the puzzle
+fn decode_board(bytes: &[u8]) -> Board {
+    Board {
+        size: Size {
+            x: bytes[0],
+            y: bytes[1],
+        },
+        pieces: std::array::from_fn(|index| {
+            let offset = 2 + index * PIECE_SIZE;
+            decode_piece(&bytes[offset..offset + PIECE_SIZE])
+        }),
+        cells: Cells::default(),
+        progress: GoalProgress::default(),
+    }
+}
+
+fn encode_node(board: &Board, payload: &mut Vec<u8>) {
+    encode_board(board, payload);
+    payload.push(board.progress.reached() as u8);
+}
+
+/// Decode a state, with the puzzle's cells and checkpoints
+fn decode_node(bytes: &[u8], (cells, progress): (Cells, GoalProgress)) -> Result<Board> {
+    let mut board = decode_board(&bytes[..BOARD_SIZE]);
+    board.cells = cells;
+    board.progress = progress.with_reached(bytes[BOARD_SIZE] as usize)?;
+    Ok(board)
+}
+
+fn encode_checkpoints(progress: &GoalProgress, payload: &mut Vec<u8>) {
+    payload.push(progress.checkpoints().len() as u8);
+    for checkpoint in progress.checkpoints() {
+        encode_piece(checkpoint, payload);
+    }
+}
+
+fn decode_checkpoints(bytes: &[u8]) -> Result<GoalProgress> {
+    let checkpoints: Vec<Piece> = bytes[1..]
+        .chunks_exact(PIECE_SIZE)
+        .map(decode_piece)
+        .collect();
+    if checkpoints.len() != bytes[0] as usize {
+        bail!(
+            "Expected {} checkpoints, found {}",
+            bytes[0],
+            checkpoints.len()
+        );
+    }
+    GoalProgress::new(&checkpoints)
+}
+
+fn encode_cells(cells: &Cells, payload: &mut Vec<u8>) {
+    for mask in cells.one_way.iter().chain([&cells.ice]) {
+        payload.extend_from_slice(&mask.to_le_bytes());
+    }
+}
+
+fn decode_cells(bytes: &[u8]) -> Cells {
+    let mask = |index: usize| {
+        u32::from_le_bytes(
+            bytes[index * 4..index * 4 + 4]
+                .try_into()
+                .expect("Cells hold masks"),
+        )
+    };
+    Cells {
+        one_way: std::array::from_fn(mask),
+        ice: mask(4),
+    }
+}
+
+fn encode_move(slide_move: &SlideMove) -> [u8; 4] {
+    let direction = match slide_move.direction {
+        SlideDirection::Up => 0,
+        SlideDirection::Down => 1,
+        SlideDirection::Left => 2,
+        SlideDirection::Right => 3,
+    };
+    [
+        slide_move.start.x as u8,
+        slide_move.start.y as u8,
+        direction,
+        slide_move.distance,
+    ]
+}
+
+fn decode_move(bytes: &[u8]) -> Result<SlideMove> {
+    let direction = match bytes[2] {
+        0 => SlideDirection::Up,
+        1 => SlideDirection::Down,
+        2 => SlideDirection::Left,
+        3 => SlideDirection::Right,
+        other => bail!("Invalid move direction {other}"),
+    };
+    Ok(SlideMove {
+        start: Coordinates {
+            x: bytes[0] as i8,
+            y: bytes[1] as i8,
+        },
+        direction,
+        distance: bytes[3],
+    })
+}
+
+fn write_frame(writer: &mut impl Write, kind: u8, payload: &[u8]) -> Result<()> {
+    writer.write_all(&[kind])?;
+    writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+    writer.write_all(payload)?;
+    Ok(())
+}
+
+/// Write a graph frame by frame, along with the boards that it was analyzed from
+pub fn write_graph(
+    graph: &Graph,
+    start_board: &Board,
+    solution_board: &Board,
+    writer: &mut impl Write,
+) -> Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION])?;
+
+    let mut payload = Vec::with_capacity(FRAME_ENTRIES * NODE_SIZE);
+    encode_node(start_board, &mut payload);
+    encode_board(solution_board, &mut payload);
+    encode_cells(&start_board.cells, &mut payload);
+    encode_checkpoints(&start_board.progress, &mut payload);
+    write_frame(writer, KIND_START, &payload)?;
+
+    for nodes in graph.nodes().chunks(FRAME_ENTRIES) {
+        payload.clear();
+        for node in nodes {
+            encode_node(&node.board, &mut payload);
+        }
+        write_frame(writer, KIND_NODES, &payload)?;
+    }
+
+    payload.clear();
+    for (index, node) in graph.nodes().iter().enumerate() {
+        for edge in graph.edges(node) {
+            payload.extend_from_slice(&(index as NodeIndex).to_le_bytes());
+            payload.extend_from_slice(&edge.neighbor.to_le_bytes());
+            payload.extend_from_slice(&encode_move(&edge.slide_move));
+            if payload.len() == FRAME_ENTRIES * EDGE_SIZE {
+                write_frame(writer, KIND_EDGES, &payload)?;
+                payload.clear();
+            }
+        }
+    }
+    if !payload.is_empty() {
+        write_frame(writer, KIND_EDGES, &payload)?;
+    }
+    write_frame(writer, KIND_END, &[])
+}
+
+/// A graph that was read from a stream, analyzed like the solver does
+pub struct DecodedGraph {
+    pub graph: Graph,
+    pub start_board: Board,
+    pub outcome: SolveOutcome,
+}
+
+/// Decodes a stream as its bytes arrive, e.g. while it is being downloaded
+#[derive(Default)]
+pub struct GraphDecoder {
+    /// The bytes of an incomplete frame, or of the magic and version
+    pending: Vec<u8>,
+    started: bool,
+    /// The start board and the solution board
+    boards: Option<(Board, Board)>,
+    /// The special cells and the checkpoints of every state
+    rules: Option<(Cells, GoalProgress)>,
+    graph: Graph,
+    complete: bool,
+}
+
+impl GraphDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of states decoded so far
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// The board that the puzzle starts from, once the start has been decoded
+    pub fn start_board(&self) -> Option<Board> {
+        self.boards.map(|(start_board, _solution)| start_board)
+    }
+
+    /// Decode all complete frames, the bytes of an incomplete frame are kept until the rest arrives
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.extend_from_slice(bytes);
+        let consumed = self.decode_frames(&pending)?;
+        pending.drain(..consumed);
+        self.pending = pending;
+        Ok(())
+    }
+
+    /// @returns the number of bytes that were decoded
+    fn decode_frames(&mut self, bytes: &[u8]) -> Result<usize> {
+        let mut consumed = 0;
+        if !self.started {
+            if bytes.len() < MAGIC.len() + 1 {
+                return Ok(0);
+            }
+            if &bytes[..MAGIC.len()] != MAGIC {
+                bail!("This is not a graph");
+            }
+            if bytes[MAGIC.len()] != VERSION {
+                bail!("Unsupported graph version {}", bytes[MAGIC.len()]);
+            }
+            self.started = true;
+            consumed = MAGIC.len() + 1;
+        }
+
+        while let Some(header) = bytes.get(consumed..consumed + FRAME_HEADER_SIZE) {
+            let kind = header[0];
+            let length =
+                u32::from_le_bytes(header[1..].try_into().expect("The header has a length"));
+            let start = consumed + FRAME_HEADER_SIZE;
+            let Some(payload) = bytes.get(start..start + length as usize) else {
+                break;
+            };
+            self.decode_frame(kind, payload)?;
+            consumed = start + length as usize;
+        }
+        Ok(consumed)
+    }
+
+    fn decode_frame(&mut self, kind: u8, payload: &[u8]) -> Result<()> {
+        if self.complete {
+            bail!("Unexpected data after the end of the graph");
+        }
+        match kind {
+            KIND_START if payload.len() > NODE_SIZE + BOARD_SIZE + CELLS_SIZE => {
+                let (boards, rules) = payload.split_at(NODE_SIZE + BOARD_SIZE);
+                let (cells, checkpoints) = rules.split_at(CELLS_SIZE);
+                let rules = (decode_cells(cells), decode_checkpoints(checkpoints)?);
+                let start_board = decode_node(&boards[..NODE_SIZE], rules)?;
+                self.boards = Some((start_board, decode_board(&boards[NODE_SIZE..])));
+                self.rules = Some(rules);
+            }
+            KIND_NODES if payload.len().is_multiple_of(NODE_SIZE) => {
+                let (Some(rules), Some((_start, solution))) = (self.rules, self.boards) else {
+                    bail!("The states came before the start");
+                };
+                for node in payload.chunks_exact(NODE_SIZE) {
+                    // The "fake" solution isn't a state that the board can be in, it has no rules
+                    let board = match decode_board(&node[..BOARD_SIZE]) {
+                        board if board == solution => board,
+                        _ => decode_node(node, rules)?,
+                    };
+                    let expected = self.graph.node_count() as NodeIndex;
+                    if self.graph.add_node(board)? != expected {
+                        bail!("Duplicate state {expected}");
+                    }
+                }
+            }
+            KIND_EDGES if payload.len().is_multiple_of(EDGE_SIZE) => {
+                for edge in payload.chunks_exact(EDGE_SIZE) {
+                    let node = |offset: usize| {
+                        let index = NodeIndex::from_le_bytes(
+                            edge[offset..offset + 4]
+                                .try_into()
+                                .expect("Edges hold indices"),
+                        );
+                        self.graph
+                            .nodes()
+                            .get(index as usize)
+                            .map(|node| node.board)
+                            .with_context(|| format!("Unknown state {index}"))
+                    };
+                    let (from, to) = (node(0)?, node(4)?);
+                    self.graph.add_edge(&from, &to, &decode_move(&edge[8..])?)?;
+                }
+            }
+            KIND_END if payload.is_empty() => self.complete = true,
+            _ => bail!("Invalid frame of kind {kind} with {} bytes", payload.len()),
+        }
+        Ok(())
+    }
+
+    /// Analyze the decoded graph
+    /// @returns an error if the stream was incomplete
+    pub fn finish(self) -> Result<DecodedGraph> {
+        if !self.complete || !self.pending.is_empty() {
+            bail!("The graph is incomplete");
+        }
+        let (start_board, solution_board) = self.boards.context("The graph has no start")?;
+        let mut graph = self.graph;
+        let outcome = graph.analyze(&start_board, &solution_board)?;
+        Ok(DecodedGraph {
+            graph,
+            start_board,
+            outcome,
+        })
+    }
+}
+
+/// Read a whole stream
+pub fn read_graph(reader: &mut impl Read) -> Result<DecodedGraph> {
+    let mut decoder = GraphDecoder::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let count = reader.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        decoder.feed(&buffer[..count])?;
+    }
+    decoder.finish()
+}
@@ -0,0 +1,85 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{get_solved_board, Board};
+use crate::graph::stream::{read_graph, write_graph, GraphDecoder};
+use crate::graph::unittest::build_chain;
+use crate::graph::SolveOutcome;
+use crate::solver::Solver;
+
+#[test]
+fn test_stream_round_trip() {
+    let (graph, ids) = build_chain();
+    let start = graph.get(ids[0]).unwrap().board;
+    let solution = graph.get(ids[3]).unwrap().board;
+    let mut bytes = Vec::new();
+    write_graph(&graph, &start, &solution, &mut bytes).unwrap();
+
+    let decoded = read_graph(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded.start_board, start);
+    assert_eq!(
+        decoded.outcome,
+        SolveOutcome::Solvable {
+            distance_to_solution: 3
+        }
+    );
+    assert_eq!(decoded.graph.node_count(), graph.node_count());
+    assert_eq!(decoded.graph.edge_count(), graph.edge_count());
+    for node in graph.nodes() {
+        let decoded_node = decoded.graph.get(node.id).unwrap();
+        assert_eq!(
+            decoded_node.distance_to_solution(),
+            node.distance_to_solution()
+        );
+        assert_eq!(decoded_node.distance_to_start(), node.distance_to_start());
+        let moves = |graph: &crate::graph::Graph, node| {
+            graph
+                .edges(node)
+                .iter()
+                .map(|edge| edge.slide_move)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(moves(&decoded.graph, decoded_node), moves(&graph, node));
+    }
+
+    // The bytes may arrive in any portions
+    // The start is known before the states are, so the board can be shown while the rest arrives
+    let mut decoder = GraphDecoder::new();
+    assert_eq!(decoder.start_board(), None);
+    let mut chunks = bytes.chunks(7);
+    while decoder.start_board().is_none() {
+        decoder.feed(chunks.next().unwrap()).unwrap();
+    }
+    assert_eq!(decoder.start_board(), Some(start));
+    assert!(decoder.node_count() < graph.node_count());
+    for chunk in chunks {
+        decoder.feed(chunk).unwrap();
+    }
+    assert_eq!(decoder.node_count(), graph.node_count());
+    assert_eq!(
+        decoder.finish().unwrap().graph.edge_count(),
+        graph.edge_count()
+    );
+
+    // Truncated and foreign streams are rejected
+    assert!(read_graph(&mut &bytes[..bytes.len() - 1]).is_err());
+    assert!(read_graph(&mut &b"\x89PNG\r\n"[..]).is_err());
+}
+
+#[test]
+fn test_stream_rules() {
+    let start =
+        Board::from_notation("AABB/CDDE/CFFE/GFFH/I..J 4x5 1x1@C1,2x2@B1 ..../..../..../..../.>*.")
+            .unwrap();
+    let solver = Solver::with_start(start).unwrap();
+    let mut bytes = Vec::new();
+    write_graph(&solver.graph, &start, &get_solved_board(), &mut bytes).unwrap();
+
+    // The states keep their cells and checkpoints, and with them their ids
+    let decoded = read_graph(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded.start_board, start);
+    assert_eq!(decoded.outcome, solver.outcome);
+    for node in solver.graph.nodes() {
+        assert_eq!(decoded.graph.get(node.id).unwrap().board, node.board);
+    }
+}
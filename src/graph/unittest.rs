@@ -1,8 +1,13 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
-use crate::board::{get_start_board, make_move, Coordinates, SlideDirection, SlideMove};
-use crate::graph::{to_id, Graph};
+use crate::board::{
+    get_solved_board, get_start_board, get_valid_moves, make_move, Coordinates, SlideDirection,
+    SlideMove,
+};
+use crate::board::{Board, BoardId};
+use crate::error::Error;
+use crate::graph::{to_id, Graph, SolveOutcome};
 
 fn init() {
     let _ = env_logger::builder().is_test(true).try_init();
@@ -42,113 +47,431 @@ fn test_analyse() {
 
     assert_eq!(board_1, board_5);
 
-    graph.add_node(board_1);
-    graph.add_node(board_2);
-    graph.add_node(board_3);
-    graph.add_node(board_4);
+    graph.add_node(board_1).unwrap();
+    graph.add_node(board_2).unwrap();
+    graph.add_node(board_3).unwrap();
+    graph.add_node(board_4).unwrap();
     // This should not have effect, as board_5 equals board_1
-    graph.add_node(board_5);
+    graph.add_node(board_5).unwrap();
 
-    graph.add_edge(&board_1, &board_2, &move_1);
-    graph.add_edge(&board_2, &board_1, &move_1);
-    graph.add_edge(&board_2, &board_3, &move_2);
-    graph.add_edge(&board_3, &board_2, &move_2);
-    graph.add_edge(&board_3, &board_4, &move_3);
-    graph.add_edge(&board_4, &board_3, &move_3);
-    graph.add_edge(&board_4, &board_5, &move_4);
-    graph.add_edge(&board_5, &board_4, &move_4);
+    graph.add_edge(&board_1, &board_2, &move_1).unwrap();
+    graph.add_edge(&board_2, &board_1, &move_1).unwrap();
+    graph.add_edge(&board_2, &board_3, &move_2).unwrap();
+    graph.add_edge(&board_3, &board_2, &move_2).unwrap();
+    graph.add_edge(&board_3, &board_4, &move_3).unwrap();
+    graph.add_edge(&board_4, &board_3, &move_3).unwrap();
+    graph.add_edge(&board_4, &board_5, &move_4).unwrap();
+    graph.add_edge(&board_5, &board_4, &move_4).unwrap();
 
-    graph.analyze(&board_1, &board_4);
+    assert_eq!(
+        graph.analyze(&board_1, &board_4).unwrap(),
+        SolveOutcome::Solvable {
+            distance_to_solution: 1
+        }
+    );
 
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_1))
+            .get(to_id(&board_1))
             .unwrap()
-            .distance_to_start
+            .distance_to_start()
             .unwrap(),
         0
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_2))
+            .get(to_id(&board_2))
             .unwrap()
-            .distance_to_start
+            .distance_to_start()
             .unwrap(),
         1
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_3))
+            .get(to_id(&board_3))
             .unwrap()
-            .distance_to_start
+            .distance_to_start()
             .unwrap(),
         2
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_4))
+            .get(to_id(&board_4))
             .unwrap()
-            .distance_to_start
+            .distance_to_start()
             .unwrap(),
         1
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_5))
+            .get(to_id(&board_5))
             .unwrap()
-            .distance_to_start
+            .distance_to_start()
             .unwrap(),
         0
     );
 
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_1))
+            .get(to_id(&board_1))
             .unwrap()
-            .distance_to_solution
+            .distance_to_solution()
             .unwrap(),
         1
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_2))
+            .get(to_id(&board_2))
             .unwrap()
-            .distance_to_solution
+            .distance_to_solution()
             .unwrap(),
         2
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_3))
+            .get(to_id(&board_3))
             .unwrap()
-            .distance_to_solution
+            .distance_to_solution()
             .unwrap(),
         1
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_4))
+            .get(to_id(&board_4))
             .unwrap()
-            .distance_to_solution
+            .distance_to_solution()
             .unwrap(),
         0
     );
     assert_eq!(
         graph
-            .map
-            .get(&to_id(&board_5))
+            .get(to_id(&board_5))
             .unwrap()
-            .distance_to_solution
+            .distance_to_solution()
             .unwrap(),
         1
     );
+
+    // Every state of a cycle has two ways to the solution
+    assert!(graph
+        .nodes()
+        .iter()
+        .all(|node| node.dead_end_entry.is_none()));
+    assert!(graph.nodes().iter().all(|node| !node.bottleneck));
+}
+
+#[test]
+fn test_find_path() {
+    init();
+    let mut graph: Graph = Graph::new();
+
+    let board_1 = get_start_board();
+    let move_1 = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    let board_2 = make_move(&board_1, &move_1).expect("Failed to make move");
+    let move_2 = SlideMove {
+        start: Coordinates { x: 1, y: 0 },
+        direction: SlideDirection::Right,
+        distance: 1,
+    };
+    let board_3 = make_move(&board_2, &move_2).expect("Failed to make move");
+
+    graph.add_node(board_1).unwrap();
+    graph.add_node(board_2).unwrap();
+    graph.add_node(board_3).unwrap();
+    graph.add_edge(&board_1, &board_2, &move_1).unwrap();
+    graph.add_edge(&board_2, &board_3, &move_2).unwrap();
+
+    let (id_1, id_2, id_3) = (to_id(&board_1), to_id(&board_2), to_id(&board_3));
+
+    // A state is reached from itself without moves
+    assert!(graph.find_path(id_1, id_1, 0).unwrap().is_empty());
+
+    let path = graph.find_path(id_1, id_3, 5).expect("Path should exist");
+    assert_eq!(path.len(), 2);
+    assert_eq!(graph.node(path[0].neighbor).id, id_2);
+    assert_eq!(path[0].slide_move, move_1);
+    assert_eq!(graph.node(path[1].neighbor).id, id_3);
+    assert_eq!(path[1].slide_move, move_2);
+
+    // The path is longer than allowed
+    assert!(graph.find_path(id_1, id_3, 1).is_none());
+    // Edges are directed, there is no way back in this graph
+    assert!(graph.find_path(id_3, id_1, 5).is_none());
+}
+
+/// A chain of four states, the last acts as the "fake" solution
+pub(crate) fn build_chain() -> (Graph, Vec<BoardId>) {
+    let mut graph: Graph = Graph::new();
+    let mut boards = vec![get_start_board()];
+    let moves = [
+        SlideMove {
+            start: Coordinates { x: 1, y: 1 },
+            direction: SlideDirection::Down,
+            distance: 1,
+        },
+        SlideMove {
+            start: Coordinates { x: 1, y: 0 },
+            direction: SlideDirection::Right,
+            distance: 1,
+        },
+        SlideMove {
+            start: Coordinates { x: 2, y: 1 },
+            direction: SlideDirection::Left,
+            distance: 1,
+        },
+    ];
+    for slide_move in &moves {
+        let board = make_move(boards.last().unwrap(), slide_move).expect("Failed to make move");
+        boards.push(board);
+    }
+    boards.iter().for_each(|board| {
+        graph.add_node(*board).unwrap();
+    });
+    for (pair, slide_move) in boards.windows(2).zip(&moves) {
+        graph.add_edge(&pair[0], &pair[1], slide_move).unwrap();
+        graph.add_edge(&pair[1], &pair[0], slide_move).unwrap();
+    }
+    graph.analyze(&boards[0], &boards[3]).unwrap();
+    (graph, boards.iter().map(to_id).collect())
+}
+
+#[test]
+fn test_solution_path() {
+    init();
+    let (graph, ids) = build_chain();
+
+    // The path ends at the solved state, which leads to the fake solution
+    assert_eq!(graph.solution_path(ids[0]), ids[0..3]);
+    assert_eq!(graph.solution_path(ids[2]), ids[2..3]);
+    assert!(graph.solution_path(12345).is_empty());
+}
+
+#[test]
+fn test_edge_direction() {
+    init();
+    let mut graph: Graph = Graph::new();
+    let board_1 = get_start_board();
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    let board_2 = make_move(&board_1, &slide_move).expect("Failed to make move");
+    graph.add_node(board_1).unwrap();
+    let reversible = |graph: &Graph, board: &Board| {
+        graph
+            .edges(graph.get(to_id(board)).unwrap())
+            .iter()
+            .map(|edge| edge.reversible)
+            .collect::<Vec<_>>()
+    };
+
+    // An edge is one-way until the edge back is added
+    graph.add_edge(&board_1, &board_2, &slide_move).unwrap();
+    assert_eq!(reversible(&graph, &board_1), [false]);
+    graph.add_edge(&board_2, &board_1, &slide_move).unwrap();
+    assert_eq!(reversible(&graph, &board_1), [true]);
+    assert_eq!(reversible(&graph, &board_2), [true]);
+}
+
+#[test]
+fn test_within() {
+    init();
+    let (graph, ids) = build_chain();
+
+    let reached = graph.within(ids[1], 1);
+    assert_eq!(reached.len(), 3);
+    assert_eq!(reached[&ids[1]], 0);
+    assert_eq!(reached[&ids[0]], 1);
+    assert_eq!(reached[&ids[2]], 1);
+    // The fake solution is never reached
+    assert_eq!(graph.within(ids[0], 10).len(), 3);
+    assert_eq!(graph.within(ids[0], 0).len(), 1);
+    assert!(graph.within(12345, 10).is_empty());
+}
+
+#[test]
+fn test_is_on_shortest_path() {
+    init();
+    let (graph, ids) = build_chain();
+
+    // Every state of the chain is on the only path
+    assert!(ids.iter().all(|id| graph.is_on_shortest_path(ids[0], *id)));
+    assert!(!graph.is_on_shortest_path(ids[0], 12345));
+    assert!(!graph.is_on_shortest_path(12345, ids[0]));
+}
+
+#[test]
+fn test_branching_and_dead_ends() {
+    init();
+    let (graph, ids) = build_chain();
+    let node = |index: usize| graph.get(ids[index]).unwrap();
+
+    // Moves to the fake solution don't count
+    assert_eq!(node(0).branching_factor, 1);
+    assert_eq!(node(1).branching_factor, 2);
+    assert_eq!(node(2).branching_factor, 1);
+
+    // Each state of the chain is a pocket behind the next state, except the one next to the solution
+    assert_eq!(node(0).dead_end_entry, graph.index_of(ids[1]));
+    assert_eq!(node(1).dead_end_entry, graph.index_of(ids[2]));
+    assert_eq!(node(2).dead_end_entry, None);
+    assert_eq!(node(3).dead_end_entry, None);
+
+    // The states that lead into a pocket are bottlenecks, the solution's neighbor leads into the chain
+    let bottlenecks: Vec<bool> = (0..4).map(|index| node(index).bottleneck).collect();
+    assert_eq!(bottlenecks, [false, true, true, false]);
+    assert_eq!(graph.checkpoints(ids[0]), [ids[1], ids[2]]);
+    assert!(graph.checkpoints(ids[2]).is_empty());
+}
+
+#[test]
+fn test_analyse_unsolvable() {
+    init();
+    let mut graph: Graph = Graph::new();
+
+    let board_1 = get_start_board();
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    let board_2 = make_move(&board_1, &slide_move).expect("Failed to make move");
+    let solution = get_solved_board();
+    graph.add_node(board_1).unwrap();
+    graph.add_node(board_2).unwrap();
+    graph.add_node(solution).unwrap();
+    graph.add_edge(&board_1, &board_2, &slide_move).unwrap();
+    graph.add_edge(&board_2, &board_1, &slide_move).unwrap();
+
+    assert_eq!(
+        graph.analyze(&board_1, &solution).unwrap(),
+        SolveOutcome::Unsolvable {
+            reachable_states: 2
+        }
+    );
+}
+
+#[test]
+fn test_errors() {
+    init();
+    let mut graph: Graph = Graph::new();
+
+    let board_1 = get_start_board();
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    let board_2 = make_move(&board_1, &slide_move).expect("Failed to make move");
+    let (id_1, id_2) = (to_id(&board_1), to_id(&board_2));
+
+    assert_eq!(
+        graph.add_edge(&board_1, &board_2, &slide_move),
+        Err(Error::UnknownState(id_1))
+    );
+    graph.add_node(board_1).unwrap();
+    assert_eq!(
+        graph.analyze(&board_1, &board_2),
+        Err(Error::UnknownState(id_2))
+    );
+
+    // The node that an edge leads to is added along with it
+    graph.add_edge(&board_1, &board_2, &slide_move).unwrap();
+    assert!(graph.contains(id_2));
+    assert_eq!(graph.node_count(), 2);
+
+    // An edge into a state that isn't in the graph fails the analysis, instead of panicking
+    graph.edges[0].neighbor = 7;
+    assert_eq!(
+        graph.analyze(&board_1, &board_2),
+        Err(Error::DanglingEdge(7))
+    );
+    assert_eq!(graph.count_branching_factors(), Err(Error::DanglingEdge(7)));
+    assert_eq!(graph.find_dead_ends(0), Err(Error::DanglingEdge(7)));
+}
+
+#[test]
+fn test_edge_list() {
+    init();
+    let (mut graph, ids) = build_chain();
+    let neighbor_ids = |graph: &Graph, id| -> Vec<BoardId> {
+        let node = graph.get(id).unwrap();
+        graph
+            .neighbors(node)
+            .map(|(_edge, neighbor)| neighbor.id)
+            .collect()
+    };
+    assert_eq!(neighbor_ids(&graph, ids[1]), [ids[0], ids[2]]);
+
+    // Adding to the edges of a node that isn't last moves its edges to the end of the list
+    let board = graph.get(ids[1]).unwrap().board;
+    let slide_move = graph.edges(graph.get(ids[1]).unwrap())[0].slide_move;
+    graph.add_edge(&board, &board, &slide_move).unwrap();
+    assert_eq!(neighbor_ids(&graph, ids[1]), [ids[0], ids[2], ids[1]]);
+    assert_eq!(neighbor_ids(&graph, ids[2]), [ids[1], ids[3]]);
+
+    // Compacting drops the edges that were left behind
+    let edge_count = graph.edge_count();
+    graph.compact();
+    assert_eq!(graph.edges.len(), edge_count);
+    assert_eq!(neighbor_ids(&graph, ids[1]), [ids[0], ids[2], ids[1]]);
+}
+
+#[test]
+fn test_expand() {
+    init();
+    let mut graph = Graph::new();
+    let start = get_start_board();
+
+    // The states that the start's moves lead to aren't expanded yet
+    let moves = get_valid_moves(&start).count();
+    assert_eq!(graph.expand(&start, 1).unwrap(), 1 + moves);
+    assert_eq!(graph.edge_count(), moves);
+    let start_node = graph.get(to_id(&start)).unwrap();
+    assert_eq!(start_node.distance_to_solution(), None);
+
+    // Expanding again only adds what lies beyond the previous frontier
+    assert_eq!(graph.expand(&start, 1).unwrap(), 0);
+    assert_eq!(graph.edge_count(), moves);
+    let added = graph.expand(&start, 2).unwrap();
+    assert!(added > 0);
+    assert_eq!(graph.expand(&start, 2).unwrap(), 0);
+    assert_eq!(graph.node_count(), 1 + moves + added);
+}
+
+#[test]
+fn test_annotate_partial() {
+    init();
+    let mut graph = Graph::new();
+    let start = get_start_board();
+    graph.expand(&start, 2).unwrap();
+
+    // The solution isn't part of the graph, the distances to it are estimated instead
+    assert!(graph.analyze(&start, &get_solved_board()).is_err());
+    graph.annotate_partial(&start, |_board| Some(7)).unwrap();
+    assert_eq!(graph.max_distance_to_start, 2);
+    assert_eq!(graph.max_distance_to_solution, 7);
+    for node in graph.nodes() {
+        assert!(node.distance_to_start().unwrap() <= 2);
+        assert_eq!(node.distance_to_solution(), Some(7));
+    }
+
+    // The moves to the outermost states can be undone, even though the moves back aren't known yet
+    assert!(graph.edges.iter().all(|edge| edge.reversible));
+
+    // Known distances are kept, as are the distances from the start when the graph grows
+    graph.annotate_partial(&start, |_board| Some(3)).unwrap();
+    assert_eq!(graph.max_distance_to_solution, 7);
+    graph.expand(&start, 3).unwrap();
+    graph.annotate_partial(&start, |_board| Some(3)).unwrap();
+    assert_eq!(graph.max_distance_to_start, 3);
+    assert!(graph
+        .nodes()
+        .iter()
+        .any(|node| node.distance_to_solution() == Some(3)));
 }
@@ -1,17 +1,35 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
-mod board;
-mod graph;
-mod solver;
+mod allocation_counter;
+pub mod board;
+pub mod builder;
+mod error;
+pub mod graph;
+pub mod pattern_db;
+mod record;
+pub mod simulate;
+pub mod solver;
 mod views;
 
-use crate::solver::Solver;
-use crate::views::StatefulViews;
+use crate::board::{BoardId, SlideMove};
+use crate::builder::WiggersGraafBuilder;
+pub use crate::views::graph_handle::GraphHandle;
+use crate::views::graph_loader::{fetch_graph, Progress, LOADING_DEPTH};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::views::graph_view::raster::render_graph_png;
+use crate::views::options::builder_from_js;
+pub use crate::views::theme::Theme;
+use crate::views::{ElementIds, Elements, StatefulViews};
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
+/// The most steps per second of the board's fixed timestep, a frame takes up to 100 steps then.
+/// Faster rates would freeze the page, as every step of a frame is taken before it's drawn.
+const MAX_FIXED_TIMESTEP_RATE: f64 = 1000.0;
+
 #[wasm_bindgen]
 pub struct WiggersGraaf {
     stateful_views: Rc<RefCell<StatefulViews>>,
@@ -19,30 +37,544 @@ pub struct WiggersGraaf {
 
 #[wasm_bindgen]
 impl WiggersGraaf {
+    /// Create the game, optionally customized by an options object like
+    /// `{puzzle, start_position, theme, auto_solve_speed, lazy_depth}`:
+    /// - puzzle: a custom puzzle given as a layout like "ABBC/ABBC/DEEF/DGHF/I..J", or in notation
+    ///   like "ABBC/ABBC/DEEF/DGHF/I2J 4x5 2x2@B1". A string instead of an object is taken as the puzzle.
+    /// - start_position: the position to start and restart from, it must be reachable in the puzzle
+    /// - theme: the name of a theme, like set_theme takes
+    /// - auto_solve_speed: the number of moves that auto-solve makes per second, 5 by default
+    /// - lazy_depth: don't solve the puzzle upfront, only expand the graph this many moves around the
+    ///   player's state. The distances to the solution are estimates then.
+    ///
+    /// Fails with a message for the player if the options are invalid or the puzzle can't be solved.
     #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         meta_canvas_id: &str,
+        meta_legend_div_id: &str,
         board_canvas_id: &str,
         moves_div_id: &str,
         restart_div_id: &str,
         solve_div_id: &str,
+        temperature_div_id: &str,
+        stats_div_id: &str,
+        options: JsValue,
     ) -> Result<Self, JsValue> {
-        console_error_panic_hook::set_once();
-        env_logger::init();
+        let elements = Elements::find(&ElementIds {
+            meta_canvas: meta_canvas_id,
+            meta_legend_div: meta_legend_div_id,
+            board_canvas: board_canvas_id,
+            moves_div: moves_div_id,
+            restart_div: restart_div_id,
+            solve_div: solve_div_id,
+            temperature_div: temperature_div_id,
+            stats_div: stats_div_id,
+        })?;
+        Self::build(elements, builder_from_js(&options)?)
+    }
+
+    /// Create the game on elements instead of element IDs, given as an options object like
+    /// `{meta_canvas, meta_legend_div, board_canvas, moves_div, restart_div, solve_div, temperature_div,
+    /// stats_div}`. The elements are canvases and divs like the constructor's IDs refer to. The object
+    /// may hold the constructor's options too.
+    pub fn with_elements(options: &JsValue) -> Result<WiggersGraaf, JsValue> {
+        let elements = Elements::from_options(options)?;
+        Self::build(elements, builder_from_js(options)?)
+    }
 
-        let solver = Solver::new();
+    /// Create the game from a graph that was solved ahead of time, e.g. with `--export graph`, instead
+    /// of solving it in the page. The options are like those of `with_elements`, with a `graph_url` to
+    /// fetch the graph from instead of a puzzle. The start position, theme and auto-solve speed options
+    /// apply as well. The optional `on_progress` function is called with
+    /// `{states, bytes, total_bytes}` while the graph loads, the total is undefined if it's unknown.
+    ///
+    /// The board is shown and can be played as soon as the puzzle's start has loaded. Until the whole
+    /// graph has, it's expanded around the player's state like with the `lazy_depth` option.
+    pub async fn load(options: JsValue) -> Result<WiggersGraaf, JsValue> {
+        init_logging();
+        let elements = Elements::from_options(&options)?;
+        let builder = builder_from_js(&options)?;
+        let url = js_sys::Reflect::get(&options, &"graph_url".into())?
+            .as_string()
+            .ok_or(JsValue::from_str("Missing graph_url"))?;
+        let on_progress = js_sys::Reflect::get(&options, &"on_progress".into())?
+            .dyn_into::<js_sys::Function>()
+            .ok();
 
+        // The game that is shown while the graph loads, if it could be shown early
+        let mut early_game: Option<Option<WiggersGraaf>> = None;
+        let decoded = fetch_graph(&url, &mut |progress: Progress| {
+            if let (None, Some(start_board)) = (&early_game, progress.start_board) {
+                let early_builder = builder_from_js(&options)?
+                    .puzzle(start_board)
+                    .lazy(LOADING_DEPTH);
+                early_game = Some(
+                    match Self::build(Elements::from_options(&options)?, early_builder) {
+                        Ok(game) => Some(game),
+                        Err(error) => {
+                            // E.g. puzzles with special cells can't be expanded lazily
+                            log::info!("The board is shown once the graph has loaded: {error:?}");
+                            None
+                        }
+                    },
+                );
+            }
+
+            let Some(on_progress) = &on_progress else {
+                return Ok(());
+            };
+            let object = js_sys::Object::new();
+            let _ = js_sys::Reflect::set(&object, &"states".into(), &progress.states.into());
+            let _ = js_sys::Reflect::set(&object, &"bytes".into(), &progress.bytes.into());
+            let _ = js_sys::Reflect::set(
+                &object,
+                &"total_bytes".into(),
+                &progress
+                    .total_bytes
+                    .map_or(JsValue::UNDEFINED, JsValue::from),
+            );
+            if let Err(error) = on_progress.call1(&JsValue::NULL, &object) {
+                log::warn!("The progress callback failed: {error:?}");
+            }
+            Ok(())
+        })
+        .await;
+        let early_game = early_game.flatten();
+        let builder = decoded.map(|decoded| {
+            builder.solved_graph(decoded.graph, decoded.start_board, decoded.outcome)
+        });
+
+        let Some(game) = early_game else {
+            return Self::build(elements, builder?);
+        };
+        // The player goes on from where they are, on the whole graph
+        let completed = match builder.and_then(|builder| builder.build().map_err(to_js_error)) {
+            Ok(complete_game) => {
+                StatefulViews::complete_graph(&game.stateful_views, complete_game.graph).await
+            }
+            Err(error) => Err(error),
+        };
+        match completed {
+            Ok(()) => Ok(game),
+            Err(error) => {
+                // Don't leave a game behind that the page can't reach
+                if let Err(destroy_error) = game.destroy() {
+                    log::warn!("Failed to destroy the game: {destroy_error:?}");
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Solve the puzzle and show it on the elements
+    fn build(elements: Elements, builder: WiggersGraafBuilder) -> Result<Self, JsValue> {
+        init_logging();
+
+        let game = builder.build().map_err(to_js_error)?;
+        if let Some(report) = &game.report {
+            log::info!("{report}");
+        }
         let instance = Self {
             stateful_views: StatefulViews::new(
-                solver.graph,
-                meta_canvas_id,
-                board_canvas_id,
-                moves_div_id,
-                restart_div_id,
-                solve_div_id,
+                game.graph,
+                game.expansion,
+                game.start_state,
+                elements,
             )?,
         };
-        StatefulViews::restart(&instance.stateful_views);
+        instance
+            .stateful_views
+            .borrow()
+            .configure(game.theme, game.auto_solve_interval);
+        StatefulViews::start(&instance.stateful_views);
         Ok(instance)
     }
+
+    /// Show the path that the most recently moved piece travelled during this session
+    pub fn show_piece_trajectory(&self, enabled: bool) {
+        self.stateful_views.borrow().show_piece_trajectory(enabled);
+    }
+
+    /// Outline the cells of the board faintly, so the empty cells stand out between the pieces
+    pub fn show_board_grid(&self, enabled: bool) {
+        self.stateful_views.borrow().show_board_grid(enabled);
+    }
+
+    /// Advance the board's animations in fixed steps, at a rate of steps per second, and draw the
+    /// pieces between the steps. Leave it undefined to advance them by the time between frames.
+    /// The rate can be at most 1000 steps per second.
+    pub fn set_fixed_timestep(&self, rate: Option<f64>) -> Result<(), JsValue> {
+        let step = rate
+            .map(|rate| {
+                Some(rate)
+                    .filter(|rate| *rate <= MAX_FIXED_TIMESTEP_RATE)
+                    .and_then(|rate| Duration::try_from_secs_f64(1.0 / rate).ok())
+                    .ok_or_else(|| {
+                        JsValue::from_str(&format!(
+                            "The rate must be a positive number of at most {MAX_FIXED_TIMESTEP_RATE} steps per second"
+                        ))
+                    })
+            })
+            .transpose()?;
+        self.stateful_views.borrow().set_fixed_timestep(step);
+        Ok(())
+    }
+
+    /// Write labels on the pieces, the letters match the layout notation of the CLI
+    /// @param labels either "none", "letters" or "numbers"
+    pub fn set_piece_labels(&self, labels: &str) -> Result<(), JsValue> {
+        let labels = labels.parse().map_err(to_js_error)?;
+        self.stateful_views.borrow().set_piece_labels(labels);
+        Ok(())
+    }
+
+    /// Surround the states in the graph that can be reached within a number of moves with a halo,
+    /// it follows the current position. Leaving out the moves, or zero moves, hides it.
+    pub fn show_horizon(&self, moves: Option<u32>) {
+        self.stateful_views.borrow().show_horizon(moves);
+    }
+
+    /// Overlay the optimal path from the current position to the solution onto the graph
+    pub fn show_solution_path(&self, enabled: bool) {
+        self.stateful_views.borrow().show_solution_path(enabled);
+    }
+
+    /// Overlay a ghost onto the graph, where your best solve of this puzzle was after as many moves.
+    /// The best solve is remembered, solves with jumps, hints or auto-solve don't count.
+    pub fn show_ghost(&self, enabled: bool) {
+        self.stateful_views.borrow().show_ghost(enabled);
+    }
+
+    /// Color the graph's nodes by a metric: "distance-to-solution", "distance-to-start",
+    /// "branching-factor", "visit-count", "bottlenecks", "difficulty" or "uniform"
+    pub fn set_graph_color_mode(&self, color_mode: &str) -> Result<(), JsValue> {
+        let color_mode = color_mode.parse().map_err(to_js_error)?;
+        self.stateful_views
+            .borrow_mut()
+            .set_graph_color_mode(color_mode);
+        Ok(())
+    }
+
+    /// Place the graph's nodes by a layout: "binned" in columns by their distance to the solution,
+    /// "force-directed" so that the shape of the state space shows, "radial" in rings around the
+    /// current position, or "sunburst" in rings around the solution. The nodes morph into their new places.
+    pub fn set_graph_layout(&self, layout: &str) -> Result<(), JsValue> {
+        let layout = layout.parse().map_err(to_js_error)?;
+        self.stateful_views.borrow().set_graph_layout(layout);
+        Ok(())
+    }
+
+    /// Switch the colors of all views: "light", "dark", "high-contrast" or "colorblind-safe"
+    pub fn set_theme(&self, theme: &str) -> Result<(), JsValue> {
+        let theme = theme.parse().map_err(to_js_error)?;
+        self.stateful_views.borrow().set_theme(theme);
+        Ok(())
+    }
+
+    /// Mark how effective moves are with shapes, in addition to colors, this setting is remembered
+    pub fn set_shape_coded_moves(&self, enabled: bool) {
+        self.stateful_views.borrow().set_shape_coded_moves(enabled);
+    }
+
+    /// List the moves by the piece that they move instead of by how they change the distance,
+    /// hovering a piece on the board then reveals its moves. This setting is remembered.
+    pub fn set_group_moves_by_piece(&self, enabled: bool) {
+        self.stateful_views
+            .borrow()
+            .set_group_moves_by_piece(enabled);
+    }
+
+    /// List each position that the moves end in once, by the shortest of the moves that lead there.
+    /// This setting is remembered.
+    pub fn set_collapse_move_chains(&self, enabled: bool) {
+        self.stateful_views
+            .borrow()
+            .set_collapse_move_chains(enabled);
+    }
+
+    /// Let auto-solve continue through moves that keep the distance to the solution, when no move
+    /// gets closer. It doesn't return to states that it passed. This setting is remembered.
+    pub fn set_auto_solve_through_neutral(&self, enabled: bool) {
+        self.stateful_views
+            .borrow()
+            .set_auto_solve_through_neutral(enabled);
+    }
+
+    /// Make a move from the current position, given in notation like "B2⮕B1" or "B2>B1"
+    pub fn do_move_notation(&self, notation: &str) -> Result<(), JsValue> {
+        StatefulViews::do_move_notation(&self.stateful_views, notation).map_err(to_js_error)
+    }
+
+    /// Explain why a move, given in notation, can't be made from the current position, e.g. "the piece
+    /// at C1 is in the way". Returns nothing if the move can be made.
+    pub fn explain_move(&self, notation: &str) -> Result<Option<String>, JsValue> {
+        let slide_move: SlideMove = notation.parse().map_err(to_js_error)?;
+        Ok(self
+            .stateful_views
+            .borrow()
+            .explain_move(&slide_move)
+            .map(|rejection| rejection.to_string()))
+    }
+
+    /// Step back to the previous position, returns false if there is none or a move is ongoing
+    pub fn undo(&self) -> bool {
+        StatefulViews::undo(&self.stateful_views)
+    }
+
+    /// Step forward to the position that was last undone, returns false if there is none
+    pub fn redo(&self) -> bool {
+        StatefulViews::redo(&self.stateful_views)
+    }
+
+    pub fn restart(&self) {
+        StatefulViews::restart(&self.stateful_views);
+    }
+
+    /// Go to a position, given as a BoardId in hex like the events and permalinks use
+    pub fn set_state(&self, board_id: &str) -> Result<(), JsValue> {
+        let state = BoardId::from_str_radix(board_id, 16)
+            .map_err(|error| JsValue::from_str(&format!("Invalid BoardId: {error}")))?;
+        StatefulViews::go_to(&self.stateful_views, state).map_err(to_js_error)
+    }
+
+    /// The current position in notation, like "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1".
+    /// The constructor's puzzle accepts this notation too.
+    pub fn get_position(&self) -> String {
+        self.stateful_views.borrow().active_board().to_notation()
+    }
+
+    /// Remove the game from the page: all event listeners and observers are unregistered, and the
+    /// scheduled frames are cancelled. The game can't be used afterwards, the elements can be reused.
+    pub fn destroy(self) -> Result<(), JsValue> {
+        self.stateful_views.borrow().destroy()
+    }
+
+    /// A read-only view of the solved graph, for drawing it with other libraries, e.g. D3
+    pub fn graph(&self) -> GraphHandle {
+        GraphHandle::new(self.stateful_views.clone())
+    }
+
+    /// Draw the graph as SVG, including the traversed edges and highlighted states. Unlike a screenshot
+    /// of the canvas, this stays sharp at any size.
+    /// @returns undefined until the graph has been arranged
+    pub fn export_graph_svg(&self) -> Option<String> {
+        self.stateful_views.borrow().export_graph_svg()
+    }
+
+    /// Draw a picture of the current board, so it can be shared
+    /// @param format either "png" or "svg"
+    /// @param width, height the size of the picture in pixels
+    /// @returns the picture as a data URL
+    pub fn export_board_image(
+        &self,
+        format: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<String, JsValue> {
+        let format = format.parse().map_err(to_js_error)?;
+        self.stateful_views
+            .borrow()
+            .export_board_image(format, width, height)
+    }
+
+    /// Write the current position to the clipboard in notation, the promise resolves once it is written
+    pub fn copy_position(&self) -> Result<js_sys::Promise, JsValue> {
+        self.stateful_views.borrow().copy_position()
+    }
+
+    /// Load a position from the clipboard, in notation or as a layout. If the clipboard doesn't hold a
+    /// position of this puzzle, the promise rejects and a "paste_rejected" event tells why.
+    pub fn paste_position(&self) -> js_sys::Promise {
+        let stateful_views = self.stateful_views.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            StatefulViews::paste_position(&stateful_views)
+                .await
+                .map(|_| JsValue::UNDEFINED)
+                .map_err(to_js_error)
+        })
+    }
+
+    /// Switch to the mirror image of the current position, the pieces slide across into their places.
+    /// The promise rejects if the mirror image can't be reached in this puzzle.
+    pub fn flip_board(&self) -> js_sys::Promise {
+        let stateful_views = self.stateful_views.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            StatefulViews::flip_board(&stateful_views)
+                .await
+                .map(|_| JsValue::UNDEFINED)
+                .map_err(to_js_error)
+        })
+    }
+
+    /// The current position, as a BoardId in hex
+    pub fn get_state(&self) -> String {
+        format!("{:016x}", self.stateful_views.borrow().active_state())
+    }
+
+    /// The moves of this game as text, to share or archive it
+    pub fn export_record(&self) -> String {
+        self.stateful_views.borrow().export_record()
+    }
+
+    /// Continue from the end of an exported game, which becomes the history to undo through
+    pub fn import_record(&self, record: &str) -> Result<(), JsValue> {
+        self.stateful_views
+            .borrow()
+            .import_record(record)
+            .map_err(to_js_error)
+    }
+
+    /// Play back an exported game from its start, this emits "replay_progress" events
+    pub fn start_replay(&self, record: &str) -> Result<(), JsValue> {
+        StatefulViews::start_replay(&self.stateful_views, record).map_err(to_js_error)
+    }
+
+    /// Step back through the visited states to the latest one that lies on a shortest path from the start
+    /// to the solution, returns false if there is none or a move is ongoing
+    pub fn back_to_best_known(&self) -> bool {
+        StatefulViews::back_to_best_known(&self.stateful_views)
+    }
+
+    /// Scramble the board with quick random moves, landing in a position of the chosen difficulty
+    /// @param moves the number of random moves
+    /// @param min_moves, max_moves the range of the fewest moves that solve the final position
+    pub fn shuffle(&self, moves: u32, min_moves: u32, max_moves: u32) -> Result<(), JsValue> {
+        StatefulViews::shuffle(&self.stateful_views, moves as usize, min_moves..=max_moves)
+            .map_err(to_js_error)
+    }
+
+    /// Play the remaining optimal path from the current position to the solution, then stop.
+    /// This plays like a replay, it emits "replay_progress" events and a "solved" event at the end.
+    pub fn solve_from_here(&self) -> Result<(), JsValue> {
+        StatefulViews::solve_from_here(&self.stateful_views).map_err(to_js_error)
+    }
+
+    pub fn replay_play(&self) {
+        StatefulViews::set_replay_playing(&self.stateful_views, true);
+    }
+
+    pub fn replay_pause(&self) {
+        StatefulViews::set_replay_playing(&self.stateful_views, false);
+    }
+
+    pub fn replay_step_forward(&self) -> bool {
+        StatefulViews::replay_step(&self.stateful_views, true)
+    }
+
+    pub fn replay_step_back(&self) -> bool {
+        StatefulViews::replay_step(&self.stateful_views, false)
+    }
+
+    /// Show the state after the given number of recorded steps
+    pub fn replay_seek(&self, position: usize) -> bool {
+        StatefulViews::replay_seek(&self.stateful_views, position)
+    }
+
+    /// Close the replay, the game continues from the state it shows
+    pub fn stop_replay(&self) {
+        self.stateful_views.borrow().stop_replay();
+    }
+
+    /// Measure the state graph, this takes a while. Returns an object with the graph's diameter and radius,
+    /// the number of connected components and the size of the largest, and the current position's eccentricity.
+    pub fn get_metrics(&self) -> Result<JsValue, JsValue> {
+        let stateful_views = self.stateful_views.borrow();
+        let metrics = stateful_views.metrics();
+        let eccentricity = metrics
+            .eccentricities
+            .get(&stateful_views.active_state())
+            .copied();
+        let largest_component = metrics.component_sizes.first().copied().unwrap_or(0);
+
+        let object = js_sys::Object::new();
+        let set = |key: &str, value: JsValue| js_sys::Reflect::set(&object, &key.into(), &value);
+        set("diameter", metrics.diameter.into())?;
+        set("radius", metrics.radius.into())?;
+        set("components", (metrics.component_sizes.len() as u32).into())?;
+        set("largest_component", (largest_component as u32).into())?;
+        set("eccentricity", eccentricity.into())?;
+        Ok(object.into())
+    }
+
+    /// Subscribe to game events, the callback receives objects like
+    /// `{type: "move_made", state: "00c0ffee00c0ffee", move: "B2⮕B1"}`.
+    /// The types are "move_made", "state_changed", "checkpoint_reached" with the number of reached
+    /// checkpoints and their count, "solved", "restart", "auto_solve_started",
+    /// "auto_solve_stopped", "replay_progress" with a position, length and playing flag,
+    /// "replay_ended", and "speedrun_finished" with the time in milliseconds and whether it is the
+    /// best time. States are BoardIds as hex strings.
+    pub fn on_event(&self, callback: js_sys::Function) {
+        self.stateful_views.borrow().subscribe(callback);
+    }
+
+    /// Silence the sound effects, this setting is remembered
+    pub fn set_muted(&self, muted: bool) {
+        self.stateful_views.borrow().set_muted(muted);
+    }
+
+    /// Make pieces and graph nodes jump to their new places instead of sliding, this setting is
+    /// remembered. Leave it undefined to follow the system's prefers-reduced-motion preference.
+    pub fn set_reduced_motion(&self, enabled: Option<bool>) {
+        self.stateful_views.borrow().set_reduced_motion(enabled);
+    }
+
+    /// Let a piece that is let go of without making a move spring back to its place, swinging past
+    /// it before it settles, instead of easing back. This setting is remembered.
+    pub fn set_spring_back(&self, enabled: bool) {
+        self.stateful_views.borrow().set_spring_back(enabled);
+    }
+
+    /// Time the game from the first move to the solution, with splits at every 10 moves to go.
+    /// Restarting starts a new run, hints, auto-solve and jumps make the run no longer count.
+    pub fn set_speedrun(&self, enabled: bool) {
+        self.stateful_views.borrow().set_speedrun(enabled);
+    }
+
+    /// The time of the speedrun in milliseconds, this keeps running until the solution is reached.
+    /// Undefined if speedrun mode is off or the run no longer counts.
+    pub fn get_speedrun_time(&self) -> Option<f64> {
+        let time = self.stateful_views.borrow().speedrun_time()?;
+        Some(time.as_secs_f64() * 1000.0)
+    }
+
+    /// The speedrun as an object with its state, "ready", "running", "finished" or "invalidated",
+    /// the best time of this puzzle in milliseconds if there is one, and the splits as an array of
+    /// objects with the moves to go and the time. Null if speedrun mode is off.
+    pub fn get_speedrun(&self) -> Result<JsValue, JsValue> {
+        let Some((speedrun, best_time)) = self.stateful_views.borrow().speedrun() else {
+            return Ok(JsValue::NULL);
+        };
+        let to_millis = |time: Duration| JsValue::from(time.as_secs_f64() * 1000.0);
+        let splits = js_sys::Array::new();
+        for split in speedrun.splits() {
+            let object = js_sys::Object::new();
+            js_sys::Reflect::set(&object, &"moves".into(), &split.moves.into())?;
+            js_sys::Reflect::set(&object, &"time".into(), &to_millis(split.time))?;
+            splits.push(&object);
+        }
+
+        let object = js_sys::Object::new();
+        let set = |key: &str, value: JsValue| js_sys::Reflect::set(&object, &key.into(), &value);
+        set("state", speedrun.state().name().into())?;
+        set("best_time", best_time.map_or(JsValue::UNDEFINED, to_millis))?;
+        set("splits", splits.into())?;
+        Ok(object.into())
+    }
+
+    /// Treat mirrored positions as equivalent, e.g. for permalinks and recognizing known positions
+    pub fn set_symmetry_folding(&self, enabled: bool) {
+        self.stateful_views.borrow().set_symmetry_folding(enabled);
+    }
+}
+
+fn init_logging() {
+    console_error_panic_hook::set_once();
+    // Ignore that the logger is already set, when a page builds more than one game
+    let _ = env_logger::try_init();
+}
+
+/// Convert an error into a JavaScript error message, including the errors that caused it
+fn to_js_error(error: anyhow::Error) -> JsValue {
+    JsValue::from_str(&format!("{error:#}"))
 }
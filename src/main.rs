@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Draw or export the state space of a puzzle without a browser, e.g. for documentation images or
+//! analysis in other tools:
+//!
+//! ```text
+//! cargo run --release -- [--export png|json|graph|pattern-db] <output> [puzzle]
+//! cargo run --release -- --simulate optimal|toward-exit|random [puzzle]
+//! ```
+//!
+//! The PNG is drawn like the graph view, the JSON is newline-delimited with a line per state and move.
+//! The graph is the solved graph in the compact binary format of `graph::stream`, to be loaded again
+//! without solving. The pattern database holds the relaxed distances of `pattern_db`.
+//! The puzzle is a position in notation or a layout, like the web app's puzzle option. The standard
+//! puzzle is used when it is omitted.
+//!
+//! Simulating plays games from states spread over the state space, with a policy that picks every
+//! move, and prints how it compares to the optimal solution.
+
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use wiggers_graaf::board;
+use wiggers_graaf::builder::WiggersGraafBuilder;
+use wiggers_graaf::graph::export::write_ndjson;
+use wiggers_graaf::graph::stream::write_graph;
+use wiggers_graaf::pattern_db::PatternDb;
+#[cfg(not(target_arch = "wasm32"))]
+use wiggers_graaf::render_graph_png;
+use wiggers_graaf::simulate::policies::BuiltinPolicy;
+use wiggers_graaf::simulate::{play, sample_starts, Summary};
+use wiggers_graaf::solver::Solver;
+
+const USAGE: &str = "Usage: wiggers-graaf [--export png|json|graph|pattern-db] <output> [puzzle]
+       wiggers-graaf --simulate optimal|toward-exit|random [puzzle]";
+
+/// The number of games that a simulation plays
+const SIMULATED_GAMES: usize = 100;
+
+/// Games that take this many moves are stopped, the policy is unlikely to solve them
+const SIMULATED_MAX_MOVES: u32 = 1000;
+
+enum Format {
+    Png,
+    Json,
+    Graph,
+    PatternDb,
+}
+
+/// Build the graph of a puzzle
+fn solve(start: board::Board) -> Result<Solver> {
+    let solver = Solver::with_start(start)?;
+    eprintln!("{}", solver.report);
+    Ok(solver)
+}
+
+/// The puzzle of the arguments, or the standard puzzle when it is omitted
+fn parse_puzzle(puzzle: Option<String>) -> Result<board::Board> {
+    match puzzle {
+        Some(puzzle) => board::parse_position(&puzzle).context("This puzzle is not valid"),
+        None => Ok(board::get_start_board()),
+    }
+}
+
+/// Play games with a policy, and print statistics about them
+fn simulate(policy: BuiltinPolicy, start: board::Board) -> Result<()> {
+    let game = WiggersGraafBuilder::new().puzzle(start).build()?;
+    if let Some(report) = &game.report {
+        eprintln!("{report}");
+    }
+    // Every game gets a fresh policy, as policies may remember the states that they passed
+    let games = sample_starts(&game.graph, SIMULATED_GAMES)
+        .into_iter()
+        .map(|start| {
+            let mut policy_fn = policy.create(&game.graph);
+            play(&game.graph, start, policy_fn.as_mut(), SIMULATED_MAX_MOVES)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    println!("{policy:?}: {}", Summary::new(&games));
+    Ok(())
+}
+
+/// Create a file and write it in one go
+fn write_file(output: &str, write: impl FnOnce(&mut BufWriter<File>) -> Result<()>) -> Result<()> {
+    let file = File::create(output).with_context(|| format!("Failed to create {output}"))?;
+    let mut writer = BufWriter::new(file);
+    write(&mut writer)
+        .and_then(|_| Ok(writer.flush()?))
+        .with_context(|| format!("Failed to write {output}"))
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.next_if_eq("--simulate").is_some() {
+        let policy = args.next().context(USAGE)?.parse()?;
+        let start = parse_puzzle(args.next())?;
+        return simulate(policy, start);
+    }
+    let format = match args.next_if_eq("--export") {
+        Some(_flag) => match args.next().as_deref() {
+            Some("png") => Format::Png,
+            Some("json") => Format::Json,
+            Some("graph") => Format::Graph,
+            Some("pattern-db") => Format::PatternDb,
+            _ => bail!(USAGE),
+        },
+        None => Format::Png,
+    };
+    let output = args.next().context(USAGE)?;
+    let start = parse_puzzle(args.next())?;
+
+    match format {
+        #[cfg(target_arch = "wasm32")]
+        Format::Png => bail!("PNG export is only available in native builds"),
+        #[cfg(not(target_arch = "wasm32"))]
+        Format::Png => {
+            let game = WiggersGraafBuilder::new().puzzle(start).build()?;
+            if let Some(report) = &game.report {
+                eprintln!("{report}");
+            }
+            let png = render_graph_png(&game.graph, game.start_state)?;
+            std::fs::write(&output, png).with_context(|| format!("Failed to write {output}"))
+        }
+        Format::Json => {
+            let solver = solve(start)?;
+            write_file(&output, |writer| Ok(write_ndjson(&solver.graph, writer)?))
+        }
+        Format::Graph => {
+            let solver = solve(start)?;
+            write_file(&output, |writer| {
+                write_graph(
+                    &solver.graph,
+                    &solver.start_board,
+                    &board::get_solved_board(),
+                    writer,
+                )
+            })
+        }
+        // The database is precomputed without solving the puzzle
+        Format::PatternDb => write_file(&output, |writer| PatternDb::new(&start).write(writer)),
+    }
+}
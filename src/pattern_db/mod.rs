@@ -0,0 +1,265 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! A pattern database holds the distances of a relaxed puzzle, in which only the large pieces are on
+//! the board. Small pieces can only get in the way, so a relaxed distance never exceeds the real
+//! distance. That makes it an admissible heuristic for an A* search, which solves a position without
+//! generating the full graph.
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{
+    get_valid_moves, is_solution, to_id, Board, BoardId, Piece, Size, SlideDirection, SlideMove,
+    GOAL,
+};
+use crate::graph::stream::{decode_piece, encode_piece, PIECE_SIZE};
+use anyhow::{bail, Context, Result};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::io::{Read, Write};
+
+const MAGIC: &[u8; 4] = b"WGPD";
+const VERSION: u8 = 2;
+
+/// Marks relaxed states from which the goal can't be reached, nor can any real state that relaxes to it
+const UNSOLVABLE: u8 = u8::MAX;
+
+/// The large pieces of a board, sorted. The database is keyed by the pieces themselves, rather than by
+/// a hash of them, as a collision would give a board the distance of another.
+type Pattern = Vec<Piece>;
+
+fn to_pattern(board: &Board) -> Pattern {
+    let mut pattern: Pattern = board
+        .pieces
+        .iter()
+        .filter(|piece| piece.size.x as u32 * piece.size.y as u32 > 1)
+        .copied()
+        .collect();
+    pattern.sort();
+    pattern
+}
+
+fn fits(piece: &Piece, size: Size) -> bool {
+    let end = piece.end();
+    0 <= piece.position.x && end.x <= size.x as i8 && 0 <= piece.position.y && end.y <= size.y as i8
+}
+
+fn overlap(a: &Piece, b: &Piece) -> bool {
+    let (a_end, b_end) = (a.end(), b.end());
+    a_end.x > b.position.x
+        && a.position.x < b_end.x
+        && a_end.y > b.position.y
+        && a.position.y < b_end.y
+}
+
+/// Slide each piece in each direction for as far as it can go, like the real moves
+fn relaxed_moves(pattern: &Pattern, size: Size) -> Vec<Pattern> {
+    let mut patterns = Vec::new();
+    for (index, piece) in pattern.iter().enumerate() {
+        for direction in [
+            SlideDirection::Up,
+            SlideDirection::Down,
+            SlideDirection::Left,
+            SlideDirection::Right,
+        ] {
+            for distance in 1..size.x.max(size.y) {
+                let slide_move = SlideMove {
+                    start: piece.position,
+                    direction,
+                    distance,
+                };
+                let moved = Piece {
+                    position: slide_move.get_endpoint(),
+                    size: piece.size,
+                };
+                let blocked = !fits(&moved, size)
+                    || pattern
+                        .iter()
+                        .enumerate()
+                        .any(|(other, piece)| other != index && overlap(&moved, piece));
+                if blocked {
+                    break;
+                }
+                let mut next = pattern.clone();
+                next[index] = moved;
+                next.sort();
+                patterns.push(next);
+            }
+        }
+    }
+    patterns
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternDb {
+    /// The moves to the goal from each relaxed state
+    distances: HashMap<Pattern, u8>,
+}
+
+impl PatternDb {
+    /// Precompute the distances of all relaxed states that a start board can reach
+    pub fn new(start: &Board) -> Self {
+        // Find the reachable relaxed states and their moves
+        let mut patterns = vec![to_pattern(start)];
+        let mut indices = HashMap::from([(patterns[0].clone(), 0)]);
+        let mut neighbors: Vec<Vec<usize>> = Vec::new();
+        let mut next = 0;
+        while let Some(pattern) = patterns.get(next) {
+            let mut pattern_neighbors = Vec::new();
+            for neighbor in relaxed_moves(pattern, start.size) {
+                let index = *indices.entry(neighbor.clone()).or_insert_with(|| {
+                    patterns.push(neighbor);
+                    patterns.len() - 1
+                });
+                pattern_neighbors.push(index);
+            }
+            neighbors.push(pattern_neighbors);
+            next += 1;
+        }
+
+        // Moves are reversible, so search backwards from all goal states at once
+        let mut distances = vec![UNSOLVABLE; patterns.len()];
+        let mut queue: VecDeque<usize> = (0..patterns.len())
+            .filter(|index| patterns[*index].contains(&GOAL))
+            .collect();
+        for index in &queue {
+            distances[*index] = 0;
+        }
+        while let Some(index) = queue.pop_front() {
+            for neighbor in &neighbors[index] {
+                if distances[*neighbor] == UNSOLVABLE {
+                    distances[*neighbor] = distances[index].saturating_add(1).min(UNSOLVABLE - 1);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+
+        Self {
+            distances: indices
+                .into_iter()
+                .map(|(id, index)| (id, distances[index]))
+                .collect(),
+        }
+    }
+
+    /// The number of relaxed states
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// A lower bound of the moves from a board to the solution
+    /// @returns None if the board can't be solved, or 0 if this database doesn't know the board
+    pub fn heuristic(&self, board: &Board) -> Option<u32> {
+        match self.distances.get(&to_pattern(board)) {
+            Some(&UNSOLVABLE) => None,
+            Some(distance) => Some(*distance as u32),
+            None => Some(0),
+        }
+    }
+
+    /// Find one of the shortest solutions of a board with an A* search
+    /// @returns the moves, or None if the board can't be solved
+    pub fn solve(&self, start: &Board) -> Option<Vec<SlideMove>> {
+        let mut boards: HashMap<BoardId, Board> = HashMap::from([(to_id(start), *start)]);
+        let mut costs: HashMap<BoardId, u32> = HashMap::from([(to_id(start), 0)]);
+        let mut came_from: HashMap<BoardId, (BoardId, SlideMove)> = HashMap::new();
+        let mut open = BinaryHeap::from([Reverse((self.heuristic(start)?, 0, to_id(start)))]);
+
+        while let Some(Reverse((_estimate, cost, id))) = open.pop() {
+            if costs.get(&id).is_some_and(|best| *best < cost) {
+                // A shorter way to this board was found after it was queued
+                continue;
+            }
+            let board = boards[&id];
+            if is_solution(&board) {
+                let mut moves = Vec::new();
+                let mut current = id;
+                while let Some((previous, slide_move)) = came_from.get(&current) {
+                    moves.push(*slide_move);
+                    current = *previous;
+                }
+                moves.reverse();
+                return Some(moves);
+            }
+            for (slide_move, neighbor) in get_valid_moves(&board) {
+                let neighbor_id = to_id(&neighbor);
+                let neighbor_cost = cost + 1;
+                if costs
+                    .get(&neighbor_id)
+                    .is_some_and(|best| *best <= neighbor_cost)
+                {
+                    continue;
+                }
+                let Some(heuristic) = self.heuristic(&neighbor) else {
+                    continue;
+                };
+                costs.insert(neighbor_id, neighbor_cost);
+                boards.insert(neighbor_id, neighbor);
+                came_from.insert(neighbor_id, (id, slide_move));
+                open.push(Reverse((
+                    neighbor_cost + heuristic,
+                    neighbor_cost,
+                    neighbor_id,
+                )));
+            }
+        }
+        None
+    }
+
+    /// Write the database, so it can be loaded instead of precomputed
+    pub fn write(&self, writer: &mut impl Write) -> Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.distances.len() as u64).to_le_bytes())?;
+        // Sort the entries, so that the same database is always written the same way
+        let mut entries: Vec<_> = self.distances.iter().collect();
+        entries.sort_unstable();
+        for (pattern, distance) in entries {
+            let mut entry = vec![pattern.len() as u8];
+            pattern
+                .iter()
+                .for_each(|piece| encode_piece(piece, &mut entry));
+            entry.push(*distance);
+            writer.write_all(&entry)?;
+        }
+        Ok(())
+    }
+
+    pub fn read(reader: &mut impl Read) -> Result<Self> {
+        let mut header = [0; 5];
+        reader
+            .read_exact(&mut header)
+            .context("This is not a pattern database")?;
+        if &header[..4] != MAGIC {
+            bail!("This is not a pattern database");
+        }
+        if header[4] != VERSION {
+            bail!("Unsupported pattern database version {}", header[4]);
+        }
+        let mut count = [0; 8];
+        reader.read_exact(&mut count)?;
+        let count = u64::from_le_bytes(count);
+
+        let mut distances = HashMap::new();
+        for _ in 0..count {
+            // Each entry is the number of pieces, the pieces, and the distance
+            let mut piece_count = [0; 1];
+            reader
+                .read_exact(&mut piece_count)
+                .context("The pattern database is incomplete")?;
+            let mut entry = vec![0; piece_count[0] as usize * PIECE_SIZE + 1];
+            reader
+                .read_exact(&mut entry)
+                .context("The pattern database is incomplete")?;
+            let (pieces, distance) = entry.split_at(entry.len() - 1);
+            let pattern = pieces.chunks_exact(PIECE_SIZE).map(decode_piece).collect();
+            distances.insert(pattern, distance[0]);
+        }
+        Ok(Self { distances })
+    }
+}
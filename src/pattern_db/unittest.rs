@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{apply_moves, get_start_board, is_solution, parse_position};
+use crate::pattern_db::PatternDb;
+
+#[test]
+fn test_solve() {
+    // The 2 by 2 piece is the only large piece, it is two moves from the goal
+    let start = parse_position("ACDE/FGHI/.BB./.BB./.J..").unwrap();
+    let db = PatternDb::new(&start);
+    assert!(db.heuristic(&start).unwrap() <= 2);
+
+    let moves = db.solve(&start).unwrap();
+    assert_eq!(moves.len(), 2);
+    assert!(is_solution(&apply_moves(&start, &moves).unwrap()));
+}
+
+#[test]
+fn test_heuristic() {
+    let start = get_start_board();
+    let db = PatternDb::new(&start);
+    // The relaxed puzzle takes fewer moves than the real one, which takes 90
+    let heuristic = db.heuristic(&start).unwrap();
+    assert!(heuristic > 0 && heuristic <= 90, "{heuristic}");
+
+    let mut bytes = Vec::new();
+    db.write(&mut bytes).unwrap();
+    assert_eq!(PatternDb::read(&mut bytes.as_slice()).unwrap(), db);
+    assert!(PatternDb::read(&mut &bytes[..bytes.len() - 1]).is_err());
+    assert!(PatternDb::read(&mut &b"WGGR\x01"[..]).is_err());
+    // The first version was keyed by hashes, which depend on the Rust version
+    bytes[4] = 1;
+    assert!(PatternDb::read(&mut bytes.as_slice()).is_err());
+}
@@ -0,0 +1,153 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Game records list every step of a game, in a PGN-like text format:
+//!
+//! ```text
+//! [Start "1a2b3c4d5e6f7a8b"]
+//! 1. B2>B1 {0.000} 2. @0f1e2d3c4b5a6978 {2.500}
+//! ```
+//!
+//! The header names the starting state. Each numbered step is either a move in the ASCII notation of
+//! SlideMove, or a jump to a state prefixed with '@'. The braces hold seconds since the game started.
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{BoardId, SlideMove};
+use crate::graph::Graph;
+use anyhow::{anyhow, Context, Error, Result};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Move(SlideMove),
+    /// A jump to a state that isn't one move away, e.g. by selecting it in the graph
+    Jump(BoardId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// Time since the start of the game
+    pub time: Duration,
+    pub step: Step,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub start: BoardId,
+    pub entries: Vec<Entry>,
+}
+
+impl GameRecord {
+    pub fn new(start: BoardId) -> Self {
+        Self {
+            start,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, time: Duration, step: Step) {
+        self.entries.push(Entry { time, step });
+    }
+
+    /// The time of the last step, or zero if nothing happened yet
+    pub fn duration(&self) -> Duration {
+        self.entries
+            .last()
+            .map_or(Duration::ZERO, |entry| entry.time)
+    }
+
+    /// Play the record through the graph
+    /// @returns the start state followed by the state after each step, or an error naming the first invalid step
+    pub fn replay(&self, graph: &Graph) -> Result<Vec<BoardId>> {
+        if !graph.contains(self.start) {
+            return Err(anyhow!("Unknown start position {:016x}", self.start));
+        }
+        let mut states = vec![self.start];
+        for (index, entry) in self.entries.iter().enumerate() {
+            let current = *states.last().expect("States can't be empty");
+            let next = match entry.step {
+                Step::Move(slide_move) => graph
+                    .get(current)
+                    .and_then(|node| {
+                        graph
+                            .neighbors(node)
+                            .find(|(edge, _neighbor)| edge.slide_move == slide_move)
+                    })
+                    .map(|(_edge, neighbor)| neighbor.id)
+                    .with_context(|| {
+                        format!("Step {} ({slide_move}) is not possible", index + 1)
+                    })?,
+                Step::Jump(state) => graph
+                    .contains(state)
+                    .then_some(state)
+                    .with_context(|| format!("Step {} jumps to an unknown position", index + 1))?,
+            };
+            states.push(next);
+        }
+        Ok(states)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[Start \"{:016x}\"]", self.start)?;
+        let steps: Vec<String> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let step = match entry.step {
+                    Step::Move(slide_move) => format!("{slide_move:#}"),
+                    Step::Jump(state) => format!("@{state:016x}"),
+                };
+                format!("{}. {step} {{{:.3}}}", index + 1, entry.time.as_secs_f64())
+            })
+            .collect();
+        writeln!(f, "{}", steps.join(" "))
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut start = None;
+        let mut tokens = Vec::new();
+        for line in s.lines().map(str::trim) {
+            if let Some(tag) = line.strip_prefix('[').and_then(|tag| tag.strip_suffix(']')) {
+                let (key, value) = tag.split_once(' ').context("Invalid header")?;
+                if key == "Start" {
+                    let value = value.trim().trim_matches('"');
+                    start = Some(BoardId::from_str_radix(value, 16).context("Invalid start")?);
+                }
+                // Other headers carry no information that we use
+            } else {
+                tokens.extend(line.split_whitespace());
+            }
+        }
+        let mut record = GameRecord::new(start.context("Missing start header")?);
+
+        // Skip the step numbers, the steps are in order anyway
+        let mut tokens = tokens.into_iter().filter(|token| !token.ends_with('.'));
+        while let Some(step) = tokens.next() {
+            let step = match step.strip_prefix('@') {
+                Some(state) => Step::Jump(
+                    BoardId::from_str_radix(state, 16)
+                        .with_context(|| format!("Invalid position \"{state}\""))?,
+                ),
+                None => Step::Move(step.parse()?),
+            };
+            let time = tokens
+                .next()
+                .and_then(|time| time.strip_prefix('{')?.strip_suffix('}')?.parse().ok())
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .context("Missing or invalid time")?;
+            record.push(time, step);
+        }
+        Ok(record)
+    }
+}
@@ -0,0 +1,70 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{get_start_board, make_move, to_id, Coordinates, SlideDirection, SlideMove};
+use crate::graph::Graph;
+use crate::record::{GameRecord, Step};
+use std::time::Duration;
+
+fn init() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn test_round_trip() {
+    init();
+    let mut record = GameRecord::new(0x1a2b3c4d5e6f7a8b);
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    record.push(Duration::ZERO, Step::Move(slide_move));
+    record.push(Duration::from_millis(2500), Step::Jump(0xf1e2d3c4b5a6978));
+
+    let text = record.to_string();
+    assert_eq!(
+        text,
+        "[Start \"1a2b3c4d5e6f7a8b\"]\n1. B2>B1 {0.000} 2. @0f1e2d3c4b5a6978 {2.500}\n"
+    );
+    assert_eq!(text.parse::<GameRecord>().unwrap(), record);
+
+    // Unknown headers and line breaks don't matter
+    let reformatted = "[Event \"Casual\"]\n[Start \"1a2b3c4d5e6f7a8b\"]\n1. B2>B1\n{0.000}\n2. @0f1e2d3c4b5a6978 {2.500}";
+    assert_eq!(reformatted.parse::<GameRecord>().unwrap(), record);
+
+    assert!("1. B2>B1 {0.000}".parse::<GameRecord>().is_err());
+    assert!("[Start \"1a\"]\n1. B2>B1".parse::<GameRecord>().is_err());
+    assert!("[Start \"1a\"]\n1. B2>B1 {-1}"
+        .parse::<GameRecord>()
+        .is_err());
+}
+
+#[test]
+fn test_replay() {
+    init();
+    let board_1 = get_start_board();
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    let board_2 = make_move(&board_1, &slide_move).expect("Failed to make move");
+    let mut graph = Graph::new();
+    graph.add_node(board_1).unwrap();
+    graph.add_node(board_2).unwrap();
+    graph.add_edge(&board_1, &board_2, &slide_move).unwrap();
+    let (id_1, id_2) = (to_id(&board_1), to_id(&board_2));
+
+    let mut record = GameRecord::new(id_1);
+    record.push(Duration::ZERO, Step::Move(slide_move));
+    record.push(Duration::from_secs(1), Step::Jump(id_1));
+    assert_eq!(record.replay(&graph).unwrap(), vec![id_1, id_2, id_1]);
+
+    // The move isn't possible a second time in a row
+    record.push(Duration::from_secs(2), Step::Move(slide_move));
+    record.push(Duration::from_secs(3), Step::Move(slide_move));
+    assert!(record.replay(&graph).is_err());
+
+    assert!(GameRecord::new(12345).replay(&graph).is_err());
+}
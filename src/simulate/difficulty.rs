@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Estimate how many moves a player who doesn't know the optimal solution needs from each state.
+//! The simulated player usually makes the best move, but now and then a random one. From most states
+//! a mistake costs a couple of moves, but from traps it leads into pockets that take long to leave.
+
+use crate::graph::{Graph, NodeIndex};
+use crate::simulate::XorShift;
+
+/// The walks that are simulated from each state
+pub const WALKS_PER_STATE: u32 = 4;
+
+/// The seed of the walks, so that every game estimates the same difficulty
+pub const SEED: u64 = 0xd1ff;
+
+/// The chance that the player makes a random move, in percent
+const MISTAKE_PERCENTAGE: usize = 25;
+
+/// Walks are stopped after this many times the optimal moves, they count as taking that many moves
+const MAX_MOVES_FACTOR: u32 = 10;
+
+/// Walk from a state until it is solved, the state must be able to reach the solution
+/// @returns the number of moves that the walk took, or the most it may take if it got stuck
+fn walk(graph: &Graph, from: NodeIndex, rng: &mut XorShift) -> u32 {
+    let distance = |index: NodeIndex| graph.node(index).distance_to_solution().unwrap_or(u32::MAX);
+    // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+    let max_moves = (distance(from) - 1) * MAX_MOVES_FACTOR;
+    let mut index = from;
+    let mut moves = 0;
+    while distance(index) > 1 && moves < max_moves {
+        let neighbors: Vec<NodeIndex> = graph
+            .edges(graph.node(index))
+            .iter()
+            .map(|edge| edge.neighbor)
+            .filter(|neighbor| distance(*neighbor) != 0)
+            .collect();
+        if neighbors.is_empty() {
+            // A mistake led past a one-way cell into a dead end, the player is stuck there
+            return max_moves;
+        }
+        index = match rng.below(100) < MISTAKE_PERCENTAGE {
+            true => neighbors[rng.below(neighbors.len())],
+            false => *neighbors
+                .iter()
+                .min_by_key(|neighbor| distance(**neighbor))
+                .expect("A state that can reach the solution has neighbors"),
+        };
+        moves += 1;
+    }
+    moves
+}
+
+/// Annotate every state that can reach the solution with its estimated moves, the average of a
+/// number of walks
+pub fn estimate_difficulty(graph: &mut Graph, walks: u32, seed: u64) {
+    let mut rng = XorShift::new(seed);
+    let estimates: Vec<Option<u32>> = (0..graph.node_count() as NodeIndex)
+        .map(|index| {
+            // The "fake" solution isn't a state that the player can be in
+            let distance = graph.node(index).distance_to_solution()?;
+            (distance > 0).then(|| {
+                let total: u32 = (0..walks).map(|_| walk(graph, index, &mut rng)).sum();
+                total.div_ceil(walks.max(1))
+            })
+        })
+        .collect();
+    for (index, estimate) in estimates.into_iter().enumerate() {
+        graph.set_estimated_moves(index as NodeIndex, estimate);
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Play games without any views, with a policy that picks every move. This compares heuristics, like
+//! always moving the large piece toward the exit, with the optimal solution.
+
+pub mod difficulty;
+pub mod policies;
+pub mod shuffle;
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{is_solution, make_move, to_id, BoardId, SlideMove};
+use crate::error::{Error, Result};
+use crate::graph::export::is_fake_solution;
+use crate::graph::{Graph, Node};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A small xorshift generator, the simulations are repeatable with the same seed
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        // The generator is stuck at zero
+        Self(seed.max(1))
+    }
+
+    /// Pick a number below the bound, which must not be zero
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+/// Picks the move to make from a state, or gives up by picking none
+pub type Policy<'a> = dyn FnMut(&Node) -> Option<SlideMove> + 'a;
+
+/// How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    Solved,
+    /// The policy didn't pick a move
+    GaveUp,
+    /// The policy picked a move that isn't possible
+    InvalidMove(SlideMove),
+    /// The game took too many moves
+    MoveLimit,
+}
+
+/// What happened in a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStats {
+    pub start: BoardId,
+    pub ending: Ending,
+    pub moves: u32,
+    /// The number of different states that the game passed, including its start
+    pub states_visited: usize,
+    /// The fewest moves that solve the game, if it can be solved
+    pub optimal_moves: Option<u32>,
+}
+
+/// Play a game from a state, until it is solved or the policy stops making progress
+pub fn play(
+    graph: &Graph,
+    start: BoardId,
+    policy: &mut Policy,
+    max_moves: u32,
+) -> Result<GameStats> {
+    let mut node = graph.get(start).ok_or(Error::UnknownState(start))?;
+    let mut visited = HashSet::from([start]);
+    let mut moves = 0;
+    let ending = loop {
+        if is_solution(&node.board) {
+            break Ending::Solved;
+        }
+        if moves == max_moves {
+            break Ending::MoveLimit;
+        }
+        let Some(slide_move) = policy(node) else {
+            break Ending::GaveUp;
+        };
+        let Ok(board) = make_move(&node.board, &slide_move) else {
+            break Ending::InvalidMove(slide_move);
+        };
+        let state = to_id(&board);
+        node = graph.get(state).ok_or(Error::UnknownState(state))?;
+        visited.insert(state);
+        moves += 1;
+    };
+
+    // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+    let optimal_moves = graph
+        .get(start)
+        .and_then(Node::distance_to_solution)
+        .map(|distance| distance - 1);
+    Ok(GameStats {
+        start,
+        ending,
+        moves,
+        states_visited: visited.len(),
+        optimal_moves,
+    })
+}
+
+/// Pick up to count states to start games from, spread evenly over the states that can be solved
+pub fn sample_starts(graph: &Graph, count: usize) -> Vec<BoardId> {
+    let candidates: Vec<BoardId> = graph
+        .nodes()
+        .iter()
+        .filter(|node| {
+            !is_fake_solution(node)
+                && node.distance_to_solution().is_some()
+                && !is_solution(&node.board)
+        })
+        .map(|node| node.id)
+        .collect();
+    let step = candidates.len().div_ceil(count.max(1)).max(1);
+    candidates.into_iter().step_by(step).take(count).collect()
+}
+
+/// Statistics over a number of games
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub games: usize,
+    pub solved: usize,
+    /// The average number of moves that the solved games took
+    pub mean_moves: f64,
+    /// The average number of moves that the solved games would have taken when played optimally
+    pub mean_optimal_moves: f64,
+    /// The average number of different states that the games passed
+    pub mean_states_visited: f64,
+}
+
+impl Summary {
+    pub fn new(games: &[GameStats]) -> Self {
+        let solved: Vec<&GameStats> = games
+            .iter()
+            .filter(|game| game.ending == Ending::Solved)
+            .collect();
+        let mean = |values: &mut dyn Iterator<Item = f64>, count: usize| {
+            values.fold(0.0, |sum, value| sum + value) / count.max(1) as f64
+        };
+        Self {
+            games: games.len(),
+            solved: solved.len(),
+            mean_moves: mean(
+                &mut solved.iter().map(|game| game.moves as f64),
+                solved.len(),
+            ),
+            mean_optimal_moves: mean(
+                &mut solved
+                    .iter()
+                    .map(|game| game.optimal_moves.unwrap_or_default() as f64),
+                solved.len(),
+            ),
+            mean_states_visited: mean(
+                &mut games.iter().map(|game| game.states_visited as f64),
+                games.len(),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Solved {} of {} games in {:.1} moves on average, where {:.1} moves are optimal, visiting {:.1} states on average",
+            self.solved,
+            self.games,
+            self.mean_moves,
+            self.mean_optimal_moves,
+            self.mean_states_visited,
+        )
+    }
+}
@@ -0,0 +1,97 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{collect_valid_moves, to_id, Board, BoardId, SlideMove, GOAL};
+use crate::graph::export::is_fake_solution;
+use crate::graph::{Graph, Node};
+use crate::simulate::{Policy, XorShift};
+use anyhow::{anyhow, Error};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// The seed of the random policy, so that its games can be played again
+const RANDOM_SEED: u64 = 0x5eed;
+
+/// The policies that come with the simulator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinPolicy {
+    Optimal,
+    TowardExit,
+    Random,
+}
+
+impl BuiltinPolicy {
+    pub fn create<'a>(&self, graph: &'a Graph) -> Box<Policy<'a>> {
+        match self {
+            BuiltinPolicy::Optimal => Box::new(optimal(graph)),
+            BuiltinPolicy::TowardExit => Box::new(toward_exit()),
+            BuiltinPolicy::Random => Box::new(random(RANDOM_SEED)),
+        }
+    }
+}
+
+impl FromStr for BuiltinPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "optimal" => Ok(BuiltinPolicy::Optimal),
+            "toward-exit" => Ok(BuiltinPolicy::TowardExit),
+            "random" => Ok(BuiltinPolicy::Random),
+            _ => Err(anyhow!("Unknown policy \"{s}\"")),
+        }
+    }
+}
+
+/// Follow the solver's distances, this solves every game in the fewest moves
+pub fn optimal(graph: &Graph) -> impl FnMut(&Node) -> Option<SlideMove> + '_ {
+    move |node| {
+        graph
+            .neighbors(node)
+            .filter(|(_edge, neighbor)| !is_fake_solution(neighbor))
+            .min_by_key(|(_edge, neighbor)| neighbor.distance_to_solution().unwrap_or(u32::MAX))
+            .map(|(edge, _neighbor)| edge.slide_move)
+    }
+}
+
+/// How many tiles the large piece is away from the exit
+fn distance_to_exit(board: &Board) -> u32 {
+    board
+        .pieces
+        .iter()
+        .filter(|piece| piece.size == GOAL.size)
+        .map(|piece| {
+            piece.position.x.abs_diff(GOAL.position.x) as u32
+                + piece.position.y.abs_diff(GOAL.position.y) as u32
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Greedily move the large piece toward the exit. Moves to the states that were visited the least
+/// are preferred, otherwise this would go back and forth between two states.
+pub fn toward_exit() -> impl FnMut(&Node) -> Option<SlideMove> {
+    let mut visits: HashMap<BoardId, u32> = HashMap::new();
+    move |node| {
+        *visits.entry(node.id).or_default() += 1;
+        collect_valid_moves(&node.board)
+            .into_iter()
+            .min_by_key(|(_slide_move, board)| {
+                (
+                    visits.get(&to_id(board)).copied().unwrap_or_default(),
+                    distance_to_exit(board),
+                )
+            })
+            .map(|(slide_move, _board)| slide_move)
+    }
+}
+
+/// Make any valid move, picked by a xorshift generator with the given seed
+pub fn random(seed: u64) -> impl FnMut(&Node) -> Option<SlideMove> {
+    let mut rng = XorShift::new(seed);
+    move |node| {
+        let moves = collect_valid_moves(&node.board);
+        let index = rng.below(moves.len().max(1));
+        moves.get(index).map(|(slide_move, _board)| *slide_move)
+    }
+}
@@ -0,0 +1,70 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Scramble the board with random moves, until it lands in a position of the chosen difficulty
+
+use crate::board::BoardId;
+use crate::graph::{Edge, Graph, NodeIndex};
+use crate::simulate::XorShift;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// The random walks that are tried before giving up on the difficulty
+const MAX_ATTEMPTS: usize = 200;
+
+/// Walk a number of random moves, the walk avoids the states it already passed when it can
+fn walk(graph: &Graph, from: NodeIndex, moves: usize, rng: &mut XorShift) -> Vec<Edge> {
+    let mut visited = HashSet::from([from]);
+    let mut index = from;
+    let mut path = Vec::with_capacity(moves);
+    for _ in 0..moves {
+        // The "fake" solution isn't a state that the board can be in
+        let edges: Vec<&Edge> = graph
+            .edges(graph.node(index))
+            .iter()
+            .filter(|edge| graph.node(edge.neighbor).distance_to_solution() != Some(0))
+            .collect();
+        let unvisited: Vec<&Edge> = edges
+            .iter()
+            .copied()
+            .filter(|edge| !visited.contains(&edge.neighbor))
+            .collect();
+        let options = match unvisited.is_empty() {
+            true => edges,
+            false => unvisited,
+        };
+        if options.is_empty() {
+            break;
+        }
+        let edge = options[rng.below(options.len())];
+        visited.insert(edge.neighbor);
+        index = edge.neighbor;
+        path.push(edge.clone());
+    }
+    path
+}
+
+/// Find random moves from a state, that end in a state that takes a number of moves to solve
+/// @param solve_moves the range of the fewest moves that solve the final state
+/// @returns the path of moves, or none if no walk ended in the range
+pub fn shuffle(
+    graph: &Graph,
+    start: BoardId,
+    moves: usize,
+    solve_moves: RangeInclusive<u32>,
+    seed: u64,
+) -> Option<Vec<Edge>> {
+    let from = graph.index_of(start)?;
+    let mut rng = XorShift::new(seed);
+    (0..MAX_ATTEMPTS)
+        .map(|_| walk(graph, from, moves, &mut rng))
+        .find(|path| {
+            let end = path.last().map_or(from, |edge| edge.neighbor);
+            // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+            graph
+                .node(end)
+                .distance_to_solution()
+                .and_then(|distance| distance.checked_sub(1))
+                .is_some_and(|optimal_moves| solve_moves.contains(&optimal_moves))
+        })
+}
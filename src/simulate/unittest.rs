@@ -0,0 +1,136 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{make_move, to_id};
+use crate::builder::unittest::solve;
+use crate::graph::unittest::build_chain;
+use crate::graph::Graph;
+use crate::simulate::difficulty::{estimate_difficulty, SEED, WALKS_PER_STATE};
+use crate::simulate::policies::{optimal, random, toward_exit, BuiltinPolicy};
+use crate::simulate::shuffle::shuffle;
+use crate::simulate::{play, sample_starts, Ending, Summary};
+
+#[test]
+fn test_optimal_policy() {
+    let (graph, start) = solve();
+    let starts = sample_starts(&graph, 10);
+    assert!(starts.contains(&start) || starts.len() == 10);
+    for start in starts {
+        let stats = play(&graph, start, &mut optimal(&graph), 100).unwrap();
+        assert_eq!(stats.ending, Ending::Solved);
+        assert_eq!(Some(stats.moves), stats.optimal_moves);
+        assert_eq!(stats.states_visited, stats.moves as usize + 1);
+    }
+}
+
+#[test]
+fn test_heuristic_policies() {
+    let (graph, start) = solve();
+    let stats = play(&graph, start, &mut toward_exit(), 100).unwrap();
+    assert_eq!(stats.ending, Ending::Solved);
+    assert_eq!(stats.moves, 1);
+
+    // The random policy may wander off, but not beyond the move limit
+    let stats = play(&graph, start, &mut random(1), 5).unwrap();
+    assert!(stats.moves <= 5);
+    assert!(stats.states_visited <= 6);
+
+    // A policy that gives up ends the game right away
+    let stats = play(&graph, start, &mut |_node: &_| None, 5).unwrap();
+    assert_eq!(stats.ending, Ending::GaveUp);
+    assert_eq!(stats.moves, 0);
+    assert!(play(&graph, 0, &mut |_node: &_| None, 5).is_err());
+}
+
+#[test]
+fn test_summary() {
+    let (graph, _start) = solve();
+    let games: Vec<_> = sample_starts(&graph, 20)
+        .into_iter()
+        .map(|start| {
+            play(
+                &graph,
+                start,
+                BuiltinPolicy::Optimal.create(&graph).as_mut(),
+                100,
+            )
+            .unwrap()
+        })
+        .collect();
+    let summary = Summary::new(&games);
+    assert_eq!(summary.solved, summary.games);
+    assert_eq!(summary.mean_moves, summary.mean_optimal_moves);
+    assert!("toward-exit".parse::<BuiltinPolicy>().is_ok());
+    assert!("genius".parse::<BuiltinPolicy>().is_err());
+}
+
+#[test]
+fn test_estimate_difficulty() {
+    let (mut graph, _start) = solve();
+    // The builder leaves the estimate until the difficulty is shown
+    let estimated = |graph: &Graph| -> Vec<Option<u32>> {
+        graph
+            .nodes()
+            .iter()
+            .map(|node| node.estimated_moves())
+            .collect()
+    };
+    assert!(estimated(&graph).iter().all(Option::is_none));
+    estimate_difficulty(&mut graph, WALKS_PER_STATE, SEED);
+    let first = estimated(&graph);
+    estimate_difficulty(&mut graph, WALKS_PER_STATE, SEED);
+    assert_eq!(estimated(&graph), first);
+
+    for node in graph.nodes() {
+        match node.distance_to_solution() {
+            // The "fake" solution isn't estimated
+            Some(0) | None => assert_eq!(node.estimated_moves(), None),
+            Some(distance) => {
+                let estimate = node.estimated_moves().unwrap();
+                assert!(estimate >= distance - 1);
+                assert!(estimate <= (distance - 1) * 10);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_estimate_difficulty_with_dead_ends() {
+    let (mut graph, ids) = build_chain();
+    // A move from the start that can't be undone, into a state without moves
+    let start = graph.get(ids[0]).unwrap().board;
+    let solution = graph.get(ids[3]).unwrap().board;
+    let slide_move = "A1>B1".parse().unwrap();
+    let dead_end = make_move(&start, &slide_move).unwrap();
+    graph.add_edge(&start, &dead_end, &slide_move).unwrap();
+    graph.analyze(&start, &solution).unwrap();
+
+    // The walks that get stuck count as taking the most moves
+    estimate_difficulty(&mut graph, 50, SEED);
+    let estimate = graph.get(ids[0]).unwrap().estimated_moves().unwrap();
+    assert!(estimate > 2 && estimate <= 20, "{estimate}");
+    assert_eq!(graph.get(to_id(&dead_end)).unwrap().estimated_moves(), None);
+}
+
+#[test]
+fn test_shuffle() {
+    let (graph, start) = solve();
+    let path = shuffle(&graph, start, 8, 3..=100, 1).unwrap();
+    assert_eq!(path.len(), 8);
+
+    // The path is a chain of moves, which ends at the chosen difficulty
+    let mut index = graph.index_of(start).unwrap();
+    for edge in &path {
+        assert!(graph
+            .edges(graph.node(index))
+            .iter()
+            .any(|other| other.neighbor == edge.neighbor && other.slide_move == edge.slide_move));
+        index = edge.neighbor;
+    }
+    // The distances count the "fake" solution too, so it takes at least 3 moves
+    assert!(graph.node(index).distance_to_solution().unwrap() > 3);
+
+    // A shuffle of a single move can't get far from the solution
+    assert!(shuffle(&graph, start, 1, 50..=100, 1).is_none());
+    assert!(shuffle(&graph, 0, 8, 0..=100, 1).is_none());
+}
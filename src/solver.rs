@@ -1,66 +1,163 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
+use crate::allocation_counter::allocation_count;
 use crate::board::{
     get_solved_board, get_start_board, get_valid_moves, is_solution, Board, Coordinates,
     SlideDirection, SlideMove,
 };
-use crate::graph::Graph;
+use crate::error::Result;
+use crate::graph::{Graph, SolveOutcome};
+use std::fmt;
+use std::time::Duration;
+
+/// Statistics about the work done by the solver, to compare optimizations
+#[derive(Debug, Clone, Default)]
+pub struct SolverReport {
+    /// The number of boards produced by valid moves, including duplicates
+    pub boards_generated: u64,
+    /// The number of generated boards that were already known
+    pub duplicate_hits: u64,
+    /// The number of unique nodes in the graph
+    pub nodes: usize,
+    /// The number of edges in the graph
+    pub edges: usize,
+    /// The number of bytes that the graph has allocated
+    pub memory: usize,
+    /// The number of bytes that the graph had allocated before it was compacted, at the end of generating
+    pub memory_before_compact: usize,
+    /// How long building and analyzing the graph took
+    pub duration: Duration,
+    /// The number of heap allocations, only available with the count-allocations feature
+    pub allocations: Option<u64>,
+}
+
+impl SolverReport {
+    pub fn nodes_per_second(&self) -> f64 {
+        self.nodes as f64 / self.duration.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl fmt::Display for SolverReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Solved {} nodes and {} edges in {:.3}s ({:.0} nodes/s) taking {} KiB ({} KiB before compacting), {} boards generated of which {} duplicates",
+            self.nodes,
+            self.edges,
+            self.duration.as_secs_f64(),
+            self.nodes_per_second(),
+            self.memory / 1024,
+            self.memory_before_compact / 1024,
+            self.boards_generated,
+            self.duplicate_hits,
+        )?;
+        if let Some(allocations) = self.allocations {
+            write!(f, ", {allocations} allocations")?;
+        }
+        Ok(())
+    }
+}
+
+/// Get a monotonic timestamp. std::time::Instant is not available in the browser, there the
+/// performance timer is used like the views do, which is zero if there is no window to get it from.
+fn now() -> Duration {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| window.performance())
+            .map_or(Duration::ZERO, |performance| {
+                Duration::from_secs_f64(performance.now() / 1000.0)
+            })
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::sync::OnceLock;
+        use std::time::Instant;
+        static EPOCH: OnceLock<Instant> = OnceLock::new();
+        EPOCH.get_or_init(Instant::now).elapsed()
+    }
+}
 
 pub struct Solver {
     // We only want the graph to be publicly accessible from Rust code, disable wasm binding
     pub graph: Graph,
-    start_board: Board,
+    pub report: SolverReport,
+    pub outcome: SolveOutcome,
+    pub start_board: Board,
     solution_node: Board,
 }
 
-impl Default for Solver {
-    fn default() -> Self {
-        Self::new()
+impl Solver {
+    /// Creates a new solver instance and builds the graph of the standard puzzle from scratch
+    pub fn new() -> Result<Solver> {
+        Self::with_start(get_start_board())
     }
-}
 
-impl Solver {
-    /// Creates a new solver instance and builds the graph from scratch
-    pub fn new() -> Solver {
+    /// Creates a new solver instance and builds the graph of a custom puzzle.
+    /// Not every puzzle can be solved, the outcome tells whether this one can.
+    pub fn with_start(start_board: Board) -> Result<Solver> {
+        let start_time = now();
+        let start_allocations = allocation_count();
+
         // Create graph
         let mut solver: Solver = Solver {
             graph: Graph::new(),
-            start_board: get_start_board(),
+            report: SolverReport::default(),
+            outcome: SolveOutcome::Unsolvable {
+                reachable_states: 0,
+            },
+            start_board,
             solution_node: get_solved_board(),
         };
 
         // Add solution to graph
-        solver.graph.add_node(solver.solution_node);
+        solver.graph.add_node(solver.solution_node)?;
 
         // Find all possible moves from start
-        generate_moves(&mut solver);
+        generate_moves(&mut solver)?;
+        solver.report.memory_before_compact = solver.graph.memory_footprint();
 
         // Analyze the moves to find the distances from each node to start and solution
-        solver
+        solver.outcome = solver
             .graph
-            .analyze(&solver.start_board, &solver.solution_node);
+            .analyze(&solver.start_board, &solver.solution_node)?;
+
+        // Finish the report
+        let report = &mut solver.report;
+        report.nodes = solver.graph.node_count();
+        report.edges = solver.graph.edge_count();
+        report.memory = solver.graph.memory_footprint();
+        report.duration = now().saturating_sub(start_time);
+        report.allocations = allocation_count()
+            .zip(start_allocations)
+            .map(|(end, start)| end - start);
 
-        solver
+        Ok(solver)
     }
 }
 
-fn generate_moves(solver: &mut Solver) {
+fn generate_moves(solver: &mut Solver) -> Result<()> {
     // Create process queue and initialize it with the start board
     let mut inspection_queue: Vec<Board> = vec![solver.start_board];
 
     while let Some(board) = inspection_queue.pop() {
-        solver.graph.add_node(board);
-
-        get_valid_moves(&board)
-            .iter()
-            .for_each(|(slide_move, new_board)| {
-                // Queue this board for analysis, if it hasn't been analyzed previously
-                if !solver.graph.contains_node(new_board) && !inspection_queue.contains(new_board) {
-                    inspection_queue.push(*new_board)
-                }
-                solver.graph.add_edge(&board, new_board, slide_move);
-            });
+        solver.graph.add_node(board)?;
+
+        for (slide_move, new_board) in get_valid_moves(&board) {
+            solver.report.boards_generated += 1;
+
+            // Queue this board for analysis, if it hasn't been found previously.
+            // Adding the edge adds the board to the graph, so queued boards are known too.
+            if !solver.graph.contains_node(&new_board) {
+                inspection_queue.push(new_board)
+            } else {
+                solver.report.duplicate_hits += 1;
+            }
+            // The edge back is added when the new board's moves are generated, so a move that can't
+            // be undone stays a single-direction edge
+            solver.graph.add_edge(&board, &new_board, &slide_move)?;
+        }
 
         if is_solution(&board) {
             // TODO(Menno 13.11.2024) Add some fake SlideMove edge value and undo the pub on SlideMove and components
@@ -72,7 +169,7 @@ fn generate_moves(solver: &mut Solver) {
                     direction: SlideDirection::Down,
                     distance: 1,
                 },
-            );
+            )?;
             solver.graph.add_edge(
                 &solver.solution_node,
                 &board,
@@ -81,7 +178,8 @@ fn generate_moves(solver: &mut Solver) {
                     direction: SlideDirection::Down,
                     distance: 1,
                 },
-            );
+            )?;
         }
     }
+    Ok(())
 }
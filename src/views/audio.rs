@@ -0,0 +1,129 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use wasm_bindgen::JsValue;
+use web_sys::{AudioContext, OscillatorType};
+
+/// The sound effects, these are synthesized so that no audio files have to be downloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sound {
+    /// A move was committed
+    Slide,
+    /// A state was selected
+    Click,
+    /// The puzzle was solved
+    Fanfare,
+}
+
+/// A single note of a sound effect, that glides from one frequency to another
+struct Tone {
+    waveform: OscillatorType,
+    start_frequency: f32,
+    end_frequency: f32,
+    /// Seconds after the start of the sound effect
+    start: f64,
+    /// In seconds
+    duration: f64,
+    volume: f32,
+}
+
+impl Sound {
+    fn tones(self) -> Vec<Tone> {
+        match self {
+            Sound::Slide => vec![Tone {
+                waveform: OscillatorType::Triangle,
+                start_frequency: 220.0,
+                end_frequency: 110.0,
+                start: 0.0,
+                duration: 0.12,
+                volume: 0.3,
+            }],
+            Sound::Click => vec![Tone {
+                waveform: OscillatorType::Square,
+                start_frequency: 1200.0,
+                end_frequency: 1000.0,
+                start: 0.0,
+                duration: 0.03,
+                volume: 0.08,
+            }],
+            // A rising C major arpeggio
+            Sound::Fanfare => [523.25, 659.25, 783.99, 1046.5]
+                .into_iter()
+                .enumerate()
+                .map(|(index, frequency)| Tone {
+                    waveform: OscillatorType::Triangle,
+                    start_frequency: frequency,
+                    end_frequency: frequency,
+                    start: index as f64 * 0.12,
+                    duration: if index == 3 { 0.6 } else { 0.15 },
+                    volume: 0.25,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Plays the sound effects, unless muted
+#[derive(Default)]
+pub struct Audio {
+    /// Browsers only allow audio after a user gesture, so the context is created on first use
+    context: Option<AudioContext>,
+    muted: bool,
+}
+
+impl Audio {
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    /// Release the audio context, a later sound creates a new one
+    pub fn close(&mut self) {
+        if let Some(context) = self.context.take() {
+            if let Err(error) = context.close() {
+                log::warn!("Failed to close the audio context: {error:?}");
+            }
+        }
+    }
+
+    pub fn play(&mut self, sound: Sound) {
+        if self.muted {
+            return;
+        }
+        if let Err(error) = self.try_play(sound) {
+            log::warn!("Failed to play {sound:?} sound: {error:?}");
+        }
+    }
+
+    fn try_play(&mut self, sound: Sound) -> Result<(), JsValue> {
+        let context = match self.context.take() {
+            Some(context) => context,
+            None => AudioContext::new()?,
+        };
+        let context = self.context.insert(context);
+        // The context starts suspended when it was created without a user gesture
+        let _ = context.resume()?;
+
+        let now = context.current_time();
+        for tone in sound.tones() {
+            let start = now + tone.start;
+            let end = start + tone.duration;
+
+            let oscillator = context.create_oscillator()?;
+            oscillator.set_type(tone.waveform);
+            let frequency = oscillator.frequency();
+            frequency.set_value_at_time(tone.start_frequency, start)?;
+            frequency.exponential_ramp_to_value_at_time(tone.end_frequency, end)?;
+
+            // Fade out, to prevent clicks at the end of the tone
+            let gain = context.create_gain()?;
+            gain.gain().set_value_at_time(tone.volume, start)?;
+            gain.gain().exponential_ramp_to_value_at_time(0.001, end)?;
+
+            oscillator.connect_with_audio_node(&gain)?;
+            gain.connect_with_audio_node(&context.destination())?;
+            oscillator.start_with_when(start)?;
+            oscillator.stop_with_when(end)?;
+        }
+        Ok(())
+    }
+}
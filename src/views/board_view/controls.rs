@@ -2,16 +2,32 @@
 // SPDX-License-Identifier: MIT
 
 use crate::views::pointer_handler::{MouseHandler, PointerEvent};
-use crate::views::utils::Coordinates;
+use crate::views::utils::{Coordinates, Delta};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::{Rc, Weak};
 use wasm_bindgen::JsValue;
 use web_sys::HtmlElement;
 
 pub struct Controls {
     on_event_cb: Box<OnPointerEventCb>,
-    drag_pointer_index: Option<i32>,
-    _pointer_handler: Rc<RefCell<MouseHandler>>,
+    /// The coordinates of every pointer that is currently down
+    pointers: HashMap<i32, Coordinates>,
+    gesture: Gesture,
+    /// Whether the last event of the ongoing gesture was handled, to block the browser's touch scrolling
+    gesture_handled: bool,
+    pointer_handler: Rc<RefCell<MouseHandler>>,
+}
+
+/// The gesture that the pointers are currently performing
+enum Gesture {
+    None,
+    /// A single pointer drags a piece
+    Drag(i32),
+    /// A single pointer pans the view
+    Pan(i32),
+    /// Two pointers zoom and pan the view, tracked by their previous center and distance
+    Pinch(Coordinates, f64),
 }
 
 /// The callback type for the handler to call on a mouse event
@@ -21,6 +37,20 @@ pub enum ControlEvent {
     Down(Coordinates),
     Move(Coordinates),
     Up(),
+    /// A pointer moves over the canvas without being down
+    Hover(Coordinates),
+    /// The pointer left the canvas
+    Leave(),
+    /// Abandon the ongoing drag, e.g. because a second finger started a pinch
+    Cancel(),
+    /// Pan the view by a delta in canvas pixels
+    Pan(Delta),
+    /// Zoom around the given coordinates by a factor, and pan the view by a delta in canvas pixels
+    Pinch(Coordinates, f64, Delta),
+    /// Zoom around the given coordinates, by the scrolled distance in canvas pixels
+    Zoom(Coordinates, f64),
+    /// Open a menu at the given coordinates
+    ContextMenu(Coordinates),
 }
 
 impl Controls {
@@ -32,8 +62,10 @@ impl Controls {
             let self_ref = self_ref.clone();
             RefCell::new(Self {
                 on_event_cb,
-                drag_pointer_index: None,
-                _pointer_handler: MouseHandler::new(
+                pointers: HashMap::new(),
+                gesture: Gesture::None,
+                gesture_handled: false,
+                pointer_handler: MouseHandler::new(
                     target,
                     Box::new(move |event| -> bool {
                         self_ref.upgrade().unwrap().borrow_mut().handle_event(event)
@@ -44,35 +76,118 @@ impl Controls {
         }))
     }
 
+    /// Stop listening to the target's pointer events
+    pub fn detach(&self) {
+        self.pointer_handler.borrow_mut().detach();
+    }
+
     fn handle_event(&mut self, event: PointerEvent) -> bool {
         let mut handled = false;
         match event {
             PointerEvent::Down((index, _timestamp, coordinates)) => {
-                // If we were not dragging yet, then try to initiate a drag
-                if self.drag_pointer_index.is_none()
-                    && (self.on_event_cb)(ControlEvent::Down(coordinates))
-                {
-                    self.drag_pointer_index = Some(index);
-                    handled = true;
+                self.pointers.insert(index, coordinates);
+                match self.gesture {
+                    Gesture::None => {
+                        // Try to initiate a drag, otherwise this pointer pans the view
+                        if (self.on_event_cb)(ControlEvent::Down(coordinates)) {
+                            self.gesture = Gesture::Drag(index);
+                            handled = true;
+                        } else {
+                            self.gesture = Gesture::Pan(index);
+                        }
+                    }
+                    Gesture::Drag(_) | Gesture::Pan(_) | Gesture::Pinch(..) => {
+                        // A second pointer turns the gesture into a pinch
+                        if let Gesture::Drag(_) = self.gesture {
+                            (self.on_event_cb)(ControlEvent::Cancel());
+                        }
+                        if let Some((center, distance)) = self.pinch_geometry() {
+                            self.gesture = Gesture::Pinch(center, distance);
+                        }
+                        handled = true;
+                    }
                 }
             }
             PointerEvent::Up((index, _timestamp, _coordinates)) => {
-                if self.drag_pointer_index == Some(index) {
-                    self.drag_pointer_index = None;
-                    handled = (self.on_event_cb)(ControlEvent::Up());
+                let previous = self.pointers.remove(&index);
+                match self.gesture {
+                    Gesture::Drag(drag_index) if drag_index == index => {
+                        self.gesture = Gesture::None;
+                        handled = (self.on_event_cb)(ControlEvent::Up());
+                    }
+                    Gesture::Pan(pan_index) if pan_index == index => {
+                        self.gesture = Gesture::None;
+                    }
+                    // Lifting a finger ends the pinch, the remaining finger does nothing until it is lifted too
+                    Gesture::Pinch(..) if previous.is_some() => {
+                        handled = true;
+                        if self.pointers.is_empty() {
+                            self.gesture = Gesture::None;
+                        }
+                    }
+                    _ => {}
                 }
             }
             PointerEvent::Move((index, _timestamp, coordinates)) => {
-                if self.drag_pointer_index == Some(index) {
-                    handled = (self.on_event_cb)(ControlEvent::Move(coordinates));
+                let Some(previous) = self.pointers.insert(index, coordinates) else {
+                    // Not one of our pointers, it merely hovers over the canvas
+                    self.pointers.remove(&index);
+                    return (self.on_event_cb)(ControlEvent::Hover(coordinates));
+                };
+                match self.gesture {
+                    Gesture::Drag(drag_index) if drag_index == index => {
+                        handled = (self.on_event_cb)(ControlEvent::Move(coordinates));
+                    }
+                    Gesture::Pan(pan_index) if pan_index == index => {
+                        handled = (self.on_event_cb)(ControlEvent::Pan(coordinates - previous));
+                    }
+                    Gesture::Pinch(previous_center, previous_distance) => {
+                        if let Some((center, distance)) = self.pinch_geometry() {
+                            self.gesture = Gesture::Pinch(center, distance);
+                            handled = (self.on_event_cb)(ControlEvent::Pinch(
+                                center,
+                                distance / previous_distance,
+                                center - previous_center,
+                            ));
+                        }
+                    }
+                    _ => {}
                 }
             }
+            PointerEvent::Leave(_index) => {
+                return (self.on_event_cb)(ControlEvent::Leave());
+            }
             PointerEvent::TouchMove() => {
-                // Prevent default behavior of touchmove if pointer is down
-                handled = self.drag_pointer_index.is_some()
+                // Prevent default behavior of touchmove if a gesture is ongoing
+                return !matches!(self.gesture, Gesture::None) && self.gesture_handled;
+            }
+            PointerEvent::ContextMenu(coordinates) => {
+                // A long press also opens the menu, which ends the gesture that the press started
+                if let Gesture::Drag(_) = self.gesture {
+                    (self.on_event_cb)(ControlEvent::Cancel());
+                }
+                self.gesture = Gesture::None;
+                self.pointers.clear();
+                return (self.on_event_cb)(ControlEvent::ContextMenu(coordinates));
+            }
+            PointerEvent::Wheel(wheel) => {
+                if wheel.zoom {
+                    handled =
+                        (self.on_event_cb)(ControlEvent::Zoom(wheel.coordinates, wheel.delta.y));
+                }
+                return handled;
             }
-            PointerEvent::Wheel(_) => {}
         }
+        self.gesture_handled = handled;
         handled
     }
+
+    /// The center and the distance between the first two pointers, if they are apart
+    fn pinch_geometry(&self) -> Option<(Coordinates, f64)> {
+        let mut pointers = self.pointers.values();
+        let first = *pointers.next()?;
+        let second = *pointers.next()?;
+        let distance = (second - first).length();
+        (distance > 0.0).then(|| (first.lerp(second, 0.5), distance))
+    }
 }
@@ -2,14 +2,21 @@
 // SPDX-License-Identifier: MIT
 
 use crate::board::Axis;
-use crate::views::board_view::visual_board::{VisualCoordinates, VisualPiece, VisualSize};
-use crate::views::utils::{Coordinates, Size};
+use crate::views::board_view::visual_board::{
+    VisualCoordinates, VisualPiece, VisualRect, VisualSize,
+};
+use crate::views::i18n::Direction;
+use crate::views::utils::{Coordinates, Delta, Size};
 
 const AXIS_PADDING: f64 = 4.0;
 const AXIS_GIRTH: f64 = 16.0;
 const PIECE_PADDING: f64 = 1.0;
 
+/// The maximum zoom level, the minimum of 1.0 fits the whole board into the canvas
+const ZOOM_MAXIMUM: f64 = 4.0;
+
 /// Layout of a board in canvas space, note we flip the Y-axis for this layout, 0 is at the bottom.
+/// On top of the fitted layout, the user can zoom and pan the view, this is applied as a separate transform.
 #[derive(PartialEq, Copy, Clone)]
 pub struct Layout {
     scale: f64,
@@ -19,20 +26,30 @@ pub struct Layout {
     axis_girth: f64,
     horizontal_axis_offset: Coordinates,
     vertical_axis_offset: Coordinates,
+    /// The zoom of the view, relative to the fitted layout
+    zoom: f64,
+    /// The translation of the view in canvas pixels, applied after zooming
+    pan: Delta,
 }
 
 impl Layout {
     /// Create an empty layout
     pub fn zero() -> Self {
-        Self::new(VisualSize::zero(), Size::zero(), 0.0)
+        Self::new(VisualSize::zero(), Size::zero(), 0.0, Direction::default())
     }
 
     /// Calculate a draw layout for a given board
     /// @param board_size the number of tiles in either axis
     /// @param canvas_size the number of device pixels in either axis
     /// @param axis_size
+    /// @param direction the locale direction, right-to-left places the vertical axis on the right of the board
     /// @returns object containing a scale of pixels per game unit, and offset in pixels to center the board inside the canvas
-    pub fn new(board: VisualSize, canvas: Size, device_pixel_ratio: f64) -> Self {
+    pub fn new(
+        board: VisualSize,
+        canvas: Size,
+        device_pixel_ratio: f64,
+        direction: Direction,
+    ) -> Self {
         // Find space required by the axes
         let axis_girth = (AXIS_GIRTH * device_pixel_ratio).round();
         let axis_padding = (AXIS_PADDING * device_pixel_ratio).round();
@@ -60,7 +77,12 @@ impl Layout {
         let horizontal_axis_offset_x = content_offset_x - piece_gap;
         let horizontal_axis_offset_y = content_offset_y - axis_padding;
 
-        let vertical_axis_offset_x = content_offset_x - axis_size;
+        // The vertical axis flanks the board on the left, or on the right for mirrored layouts
+        let vertical_axis_offset_x = if direction.is_mirrored() {
+            content_offset_x + gaps_x + (rendering_scale * board.width) + axis_padding
+        } else {
+            content_offset_x - axis_size
+        };
         let vertical_axis_offset_y = content_offset_y;
 
         Self {
@@ -74,9 +96,64 @@ impl Layout {
                 horizontal_axis_offset_y,
             ),
             vertical_axis_offset: Coordinates::new(vertical_axis_offset_x, vertical_axis_offset_y),
+            zoom: 1.0,
+            pan: Delta::zero(),
         }
     }
 
+    /// Take over the zoom and pan of another layout, e.g. after the canvas was resized
+    pub fn with_view_of(mut self, other: &Layout) -> Self {
+        self.zoom = other.zoom;
+        // Keep the pan relative to the canvas size
+        self.pan = if other.canvas.is_empty() {
+            Delta::zero()
+        } else {
+            Delta::new(
+                other.pan.x * self.canvas.width / other.canvas.width,
+                other.pan.y * self.canvas.height / other.canvas.height,
+            )
+        };
+        self.clamp_pan();
+        self
+    }
+
+    /// Whether the two layouts are equal, apart from their zoom and pan
+    pub fn has_same_fit(&self, other: &Layout) -> bool {
+        self.with_view_of(&Layout::zero()) == other.with_view_of(&Layout::zero())
+    }
+
+    pub fn is_zoomed(&self) -> bool {
+        self.zoom > 1.0
+    }
+
+    /// The view transform as canvas 2D transform parameters, i.e. [a, b, c, d, e, f]
+    pub fn view_transform(&self) -> [f64; 6] {
+        [self.zoom, 0.0, 0.0, self.zoom, self.pan.x, self.pan.y]
+    }
+
+    /// Zoom the view by a factor, while keeping the given canvas coordinates in place
+    pub fn zoom_at(&mut self, factor: f64, center: Coordinates) {
+        let zoom = (self.zoom * factor).clamp(1.0, ZOOM_MAXIMUM);
+        self.pan = center.to_vector() + (self.pan - center.to_vector()) * (zoom / self.zoom);
+        self.zoom = zoom;
+        self.clamp_pan();
+    }
+
+    /// Move the view by a delta in canvas pixels
+    pub fn pan_by(&mut self, delta: Delta) {
+        self.pan += delta;
+        self.clamp_pan();
+    }
+
+    /// Keep the zoomed canvas covering the whole canvas, so the board can't be dragged out of view
+    fn clamp_pan(&mut self) {
+        self.pan.x = self.pan.x.clamp(self.canvas.width * (1.0 - self.zoom), 0.0);
+        self.pan.y = self
+            .pan
+            .y
+            .clamp(self.canvas.height * (1.0 - self.zoom), 0.0);
+    }
+
     pub fn is_zero(&self) -> bool {
         self.canvas.is_empty() || self.scale == 0.0
     }
@@ -86,8 +163,13 @@ impl Layout {
     }
 
     pub fn apply_to_piece(&self, piece: &VisualPiece) -> (Coordinates, Size, f64) {
-        let pos = piece.rect.origin + piece.visual_offset;
-        let size = piece.rect.size;
+        self.apply_to_rect(&piece.rect.translate(piece.visual_offset))
+    }
+
+    /// Apply the layout to an area of the board, such as the place a piece could move to
+    pub fn apply_to_rect(&self, rect: &VisualRect) -> (Coordinates, Size, f64) {
+        let pos = rect.origin;
+        let size = rect.size;
 
         // Start rendering from xy offset, then each piece gets an additional pixel offset to create a gap between each other.
         let x: f64 = self.board_offset.x + pos.x * (self.scale + self.piece_gap);
@@ -104,6 +186,20 @@ impl Layout {
         )
     }
 
+    /// Apply the layout to a point on the board
+    pub fn apply_to_coordinates(&self, coordinates: VisualCoordinates) -> Coordinates {
+        Coordinates::new(
+            self.board_offset.x + coordinates.x * (self.scale + self.piece_gap),
+            self.canvas.height
+                - (self.board_offset.y + coordinates.y * (self.scale + self.piece_gap)),
+        )
+    }
+
+    /// The width of lines drawn over the board
+    pub fn line_width(&self) -> f64 {
+        (0.1 * self.scale).max(1.0)
+    }
+
     /// Apply the layout to find where to draw the label
     /// @returns the center of the text label
     pub fn apply_to_axis_label(&self, index: u32, axis: &Axis) -> Coordinates {
@@ -152,6 +248,8 @@ impl Layout {
     }
 
     pub fn apply_inverse_to_mouse(&self, coordinates: Coordinates) -> VisualCoordinates {
+        // Undo the view transform first
+        let coordinates = (coordinates - self.pan) / self.zoom;
         let x: f64 = (coordinates.x - self.board_offset.x) / (self.scale + self.piece_gap);
         let y: f64 = ((self.canvas.height - coordinates.y) - self.board_offset.y)
             / (self.scale + self.piece_gap);
@@ -161,4 +259,9 @@ impl Layout {
     pub fn axis_label_font_size_px(&self) -> u8 {
         self.axis_girth as u8
     }
+
+    /// The labels on the pieces scale with the tiles
+    pub fn piece_label_font_size_px(&self) -> f64 {
+        (0.4 * self.scale).floor()
+    }
 }
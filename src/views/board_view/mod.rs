@@ -3,19 +3,33 @@
 mod controls;
 mod layout;
 mod renderer;
+mod trajectory;
 pub mod visual_board;
 
+#[cfg(test)]
+mod unittest;
+
+use crate::board;
 use crate::board::SlideMove;
 use crate::graph;
 use crate::views::board_view::controls::{ControlEvent, Controls};
 use crate::views::board_view::layout::Layout;
 use crate::views::board_view::renderer::Renderer;
+pub use crate::views::board_view::renderer::{ImageFormat, PieceLabels};
+use crate::views::board_view::trajectory::PieceTracker;
 use crate::views::board_view::visual_board::{
-    AnimatableOffset, Animation, AnimationRepeatBehavior, DragEndResult, DragMove, VisualBoard,
+    AnimatableOffset, Animation, AnimationRepeatBehavior, DragEndResult, DragMove, Spring,
+    VisualBoard,
 };
-use crate::views::frame_scheduler::FrameScheduler;
+use crate::views::clock::Clock;
+use crate::views::context_menu::{ContextMenu, MenuAction};
+use crate::views::frame_scheduler::{Frame, FrameScheduler};
+use crate::views::i18n;
+use crate::views::i18n::Direction;
+use crate::views::motion::Motion;
 use crate::views::resize_observer::ResizeObserver;
-use crate::views::utils::{get_element_of_type, Size};
+use crate::views::theme::Theme;
+use crate::views::utils::Size;
 use futures::channel::oneshot;
 use keyframe::{keyframes, AnimationSequence};
 use std::cell::RefCell;
@@ -24,38 +38,97 @@ use std::time::Duration;
 use wasm_bindgen::JsValue;
 use web_sys::HtmlCanvasElement;
 
-pub type OnDragMoveCb = dyn FnMut(DragMove) -> graph::Node;
+/// How much the zoom changes per scrolled canvas pixel
+const ZOOM_SPEED: f64 = 0.002;
+
+/// How long it takes to fade from one board into another, when they aren't one move apart, or when
+/// motion is reduced
+const CROSS_FADE_DURATION: Duration = Duration::from_millis(250);
+
+/// How long it takes a piece to slide into its new place
+pub const MOVE_DURATION: Duration = Duration::from_millis(150);
+
+/// How long it takes the pieces to slide into their mirrored places, when the board is flipped
+const FLIP_DURATION: Duration = Duration::from_millis(400);
+
+/// A fade from the previous board into the current one
+struct CrossFade {
+    previous: VisualBoard,
+    start_time: Option<Duration>,
+    /// How far the fade has come, in range [0.0, 1.0)
+    progress: f64,
+}
+
+/// What happened on the board, that the other views may follow
+pub enum BoardEvent {
+    /// A piece was dragged into a new position, the state follows with transition_to
+    DragMove(DragMove),
+    /// The pointer moved onto a draggable piece, or off of it
+    Hovered(Option<board::Coordinates>),
+    /// An action was picked from the board's menu
+    MenuAction(MenuAction),
+}
+
+pub type OnBoardEventCb = dyn FnMut(BoardEvent);
 
 pub struct BoardView {
-    on_drag_move_cb: Box<OnDragMoveCb>,
+    self_ref: Weak<RefCell<Self>>,
+    on_board_event_cb: Box<OnBoardEventCb>,
+    /// The draggable piece under the pointer
+    hovered_piece: Option<board::Coordinates>,
+    /// The piece whose move is previewed
+    previewed_piece: Option<board::Coordinates>,
     frame_scheduler: FrameScheduler,
-    _resize_observer: ResizeObserver,
-    _pointer_controls: Rc<RefCell<Controls>>,
+    resize_observer: ResizeObserver,
+    pointer_controls: Rc<RefCell<Controls>>,
+    context_menu: ContextMenu,
     visual_board: VisualBoard,
+    /// The board of the displayed state, if one is displayed
+    board: Option<board::Board>,
+    /// Released pieces spring back to their place with this spring, instead of easing back
+    spring: Option<Spring>,
+    /// The step that the animations advance by, if they advance by fixed steps
+    fixed_timestep: Option<Duration>,
     layout: Layout,
+    direction: Direction,
+    piece_tracker: PieceTracker,
+    show_trajectory: bool,
     renderer: Renderer,
+    cross_fade: Option<CrossFade>,
+    motion: Motion,
+    /// Fade into the next state instead of sliding the moved piece, as motion is reduced
+    fade_into_next_state: bool,
+    canvas: HtmlCanvasElement,
 }
 impl BoardView {
     pub fn new(
-        canvas_id: &str,
-        on_drag_move_cb: Box<OnDragMoveCb>,
+        canvas: HtmlCanvasElement,
+        clock: Rc<dyn Clock>,
+        motion: Motion,
+        on_board_event_cb: Box<OnBoardEventCb>,
     ) -> Result<Rc<RefCell<Self>>, JsValue> {
-        let canvas: HtmlCanvasElement = get_element_of_type(canvas_id)?;
         Ok(Rc::new_cyclic(|self_ref: &Weak<RefCell<BoardView>>| {
             let self_ref_for_on_frame_cb = self_ref.clone();
             let self_ref_for_resize_observer_cb = self_ref.clone();
             let self_ref_for_mouse_event_cb = self_ref.clone();
+            let self_ref_for_menu_cb = self_ref.clone();
 
             RefCell::new(Self {
-                on_drag_move_cb,
-                frame_scheduler: FrameScheduler::new(Box::new(move |timestamp: Duration| {
-                    self_ref_for_on_frame_cb
-                        .upgrade()
-                        .unwrap()
-                        .borrow_mut()
-                        .draw(timestamp);
-                })),
-                _resize_observer: ResizeObserver::new(
+                self_ref: self_ref.clone(),
+                on_board_event_cb,
+                hovered_piece: None,
+                previewed_piece: None,
+                frame_scheduler: FrameScheduler::new(
+                    clock,
+                    Box::new(move |_frame: Frame| {
+                        self_ref_for_on_frame_cb
+                            .upgrade()
+                            .unwrap()
+                            .borrow_mut()
+                            .draw();
+                    }),
+                ),
+                resize_observer: ResizeObserver::new(
                     &canvas,
                     Box::new(move |width, height| {
                         self_ref_for_resize_observer_cb
@@ -65,7 +138,7 @@ impl BoardView {
                             .resize(width, height);
                     }),
                 ),
-                _pointer_controls: Controls::new(
+                pointer_controls: Controls::new(
                     &canvas,
                     Box::new(move |event: ControlEvent| {
                         self_ref_for_mouse_event_cb
@@ -76,90 +149,272 @@ impl BoardView {
                     }),
                 )
                 .expect("Could not create board MouseHandler"),
+                context_menu: ContextMenu::new(Box::new(move |action| {
+                    let self_ref = self_ref_for_menu_cb.upgrade().unwrap();
+                    (self_ref.borrow_mut().on_board_event_cb)(BoardEvent::MenuAction(action));
+                }))
+                .expect("Could not create board menu"),
                 visual_board: VisualBoard::empty(),
+                board: None,
+                spring: None,
+                fixed_timestep: None,
                 layout: Layout::zero(),
-                renderer: Renderer::new(canvas).expect("Could not initialize board renderer"),
+                direction: i18n::get_direction(),
+                piece_tracker: PieceTracker::default(),
+                show_trajectory: false,
+                renderer: Renderer::new(canvas.clone())
+                    .expect("Could not initialize board renderer"),
+                cross_fade: None,
+                motion,
+                fade_into_next_state: false,
+                canvas,
             })
         }))
     }
 
     pub fn preview_move(&mut self, target_move: Option<SlideMove>) {
-        let animation_done = match target_move {
-            None => {
-                self.visual_board.highlight(&None);
-                self.visual_board.animate(None)
-            }
-            Some(slide_move) => {
-                self.visual_board.highlight(&Some(slide_move.start));
-
-                let from = AnimatableOffset::zero();
-                let to = AnimatableOffset::from_distance_and_direction(
-                    slide_move.distance as f64,
-                    slide_move.direction,
-                );
-                self.visual_board.animate(Some(Animation {
-                    sequence: keyframes![
-                        (from, 0.0),
-                        (from, 1.0, keyframe::functions::EaseInOutCubic),
-                        (to, 1.15),
-                        (to, 2.15, keyframe::functions::EaseInOutCubic),
-                        (from, 2.3)
-                    ],
-                    target: slide_move.start,
-                    repeat: AnimationRepeatBehavior::Loop,
-                }))
+        // Only one move is previewed at a time
+        self.stop_preview();
+        self.visual_board.highlight(&None);
+
+        if let Some(slide_move) = target_move {
+            self.visual_board.highlight(&Some(slide_move.start));
+            if self.motion.is_reduced() {
+                // The highlight alone shows which piece moves
+                self.frame_scheduler
+                    .schedule()
+                    .expect("Couldn't schedule frame");
+                return;
             }
-        };
-        // Ignore the future, this animation never finishes anyway.
-        drop(animation_done);
 
-        self.frame_scheduler
-            .schedule()
-            .expect("Couldn't schedule frame");
+            let from = AnimatableOffset::zero();
+            let to = AnimatableOffset::from_distance_and_direction(
+                slide_move.distance as f64,
+                slide_move.direction,
+            );
+            let animation_done = self.visual_board.animate(Animation {
+                sequence: keyframes![
+                    (from, 0.0),
+                    (from, 1.0, keyframe::functions::EaseInOutCubic),
+                    (to, 1.15),
+                    (to, 2.15, keyframe::functions::EaseInOutCubic),
+                    (from, 2.3)
+                ],
+                target: slide_move.start,
+                repeat: AnimationRepeatBehavior::Loop,
+            });
+            // Ignore the future, this animation never finishes anyway.
+            drop(animation_done);
+            self.previewed_piece = Some(slide_move.start);
+        }
+
+        self.animate_pieces();
+    }
+
+    fn stop_preview(&mut self) {
+        if let Some(piece) = self.previewed_piece.take() {
+            self.visual_board.stop_animation(&piece);
+        }
+    }
+
+    /// Stop listening to the canvas and the page, and stop drawing
+    pub fn destroy(&mut self) -> Result<(), JsValue> {
+        self.pointer_controls.borrow().detach();
+        self.resize_observer.disconnect();
+        self.context_menu.remove();
+        self.visual_board.stop_animations();
+        self.cross_fade = None;
+        self.frame_scheduler.cancel()
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.renderer.set_theme(theme);
+        self.frame_scheduler.schedule().unwrap();
+    }
+
+    /// Write letters or numbers on the pieces, so positions can be discussed in the layout notation
+    pub fn set_piece_labels(&mut self, labels: PieceLabels) {
+        self.renderer.set_labels(labels);
+        self.frame_scheduler.schedule().unwrap();
+    }
+
+    /// Let released pieces spring back to their place, swinging past it before they settle
+    pub fn set_spring_back(&mut self, enabled: bool) {
+        self.spring = enabled.then(Spring::default);
+        self.visual_board.spring = self.spring;
+    }
+
+    /// Advance the animations in fixed steps, which keeps them smooth on devices that stutter
+    pub fn set_fixed_timestep(&mut self, step: Option<Duration>) {
+        self.fixed_timestep = step;
+        self.visual_board.set_fixed_timestep(step);
+    }
+
+    /// Outline the cells of the board, which makes it easier to judge the gaps on large boards
+    pub fn show_grid(&mut self, enabled: bool) {
+        self.renderer.set_show_grid(enabled);
+        self.frame_scheduler.schedule().unwrap();
+    }
+
+    /// Draw a picture of the current board, in the current theme
+    /// @returns the picture as a data URL
+    pub fn export_image(
+        &self,
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+    ) -> Result<String, JsValue> {
+        renderer::export_image(
+            &self.visual_board,
+            self.renderer.theme(),
+            self.renderer.labels(),
+            Size::new(width as f64, height as f64),
+            self.direction,
+            format,
+        )
+    }
+
+    /// Show or hide the path that the selected piece travelled during this session
+    pub fn show_trajectory(&mut self, enabled: bool) {
+        self.show_trajectory = enabled;
+        self.frame_scheduler.schedule().unwrap();
     }
 
     pub fn do_move(&mut self, slide_move: &SlideMove) -> oneshot::Receiver<()> {
+        self.do_move_for(slide_move, MOVE_DURATION)
+    }
+
+    /// Animate a move that takes a given time, chained moves can be sped up this way
+    pub fn do_move_for(
+        &mut self,
+        slide_move: &SlideMove,
+        duration: Duration,
+    ) -> oneshot::Receiver<()> {
+        self.piece_tracker.apply(slide_move);
+        // The other pieces keep animating, e.g. while moves are chained, only the preview stops
+        self.stop_preview();
+
+        if self.motion.is_reduced() {
+            self.visual_board.stop_animation(&slide_move.start);
+            self.fade_into_next_state = true;
+            let (sender, receiver) = oneshot::channel();
+            sender.send(()).expect("The receiver is still alive");
+            return receiver;
+        }
+
         let from = AnimatableOffset::zero();
         let to = AnimatableOffset::from_distance_and_direction(
             slide_move.distance as f64,
             slide_move.direction,
         );
 
-        let animation_done = self.visual_board.animate(Some(Animation {
-            sequence: keyframes![(from, 0.0, keyframe::functions::EaseInOutCubic), (to, 0.15)],
+        // This replaces the piece's own animation, if it has one
+        let animation_done = self.visual_board.animate(Animation {
+            sequence: keyframes![
+                (from, 0.0, keyframe::functions::EaseInOutCubic),
+                (to, duration.as_secs_f64())
+            ],
             target: slide_move.start,
             repeat: AnimationRepeatBehavior::None,
-        }));
+        });
 
-        self.frame_scheduler.schedule().unwrap();
+        self.animate_pieces();
 
         animation_done
     }
 
-    pub fn transition_to(&mut self, state: &graph::Node) {
-        // TODO(Menno 30.06.2025) Animate this transition
+    /// Animate the pieces sliding across into their places in the board's mirror image,
+    /// the mirrored state follows with transition_to
+    /// @returns a receiver for each piece, they are sent to when the piece arrives
+    pub fn flip(&mut self, mirrored: &board::Board) -> Vec<oneshot::Receiver<()>> {
+        // The pieces are followed into their mirrored places, their trajectories start anew
+        self.piece_tracker.reset(mirrored);
+        self.visual_board.stop_animations();
+        self.previewed_piece = None;
+
+        if self.motion.is_reduced() {
+            self.fade_into_next_state = true;
+            return Vec::new();
+        }
+
+        let width = self.visual_board.size.width;
+        let animations: Vec<Animation> = self
+            .visual_board
+            .pieces
+            .iter()
+            .map(|(position, piece)| {
+                let x = piece.rect.origin.x;
+                let to = AnimatableOffset::new(width - piece.rect.size.width - 2.0 * x, 0.0);
+                Animation {
+                    sequence: keyframes![
+                        (
+                            AnimatableOffset::zero(),
+                            0.0,
+                            keyframe::functions::EaseInOutCubic
+                        ),
+                        (to, FLIP_DURATION.as_secs_f64())
+                    ],
+                    target: *position,
+                    repeat: AnimationRepeatBehavior::None,
+                }
+            })
+            .collect();
+        let animations_done = animations
+            .into_iter()
+            .map(|animation| self.visual_board.animate(animation))
+            .collect();
+        self.animate_pieces();
+        animations_done
+    }
+
+    pub fn transition_to(&mut self, state: &graph::Neighborhood) {
+        // Pieces can only be followed through moves, a jump to an unrelated state starts tracking anew
+        let jumped = !self.piece_tracker.matches(&state.board);
+        if jumped {
+            self.piece_tracker.reset(&state.board);
+        }
+
+        // A state that isn't reached through a sliding piece fades in from the current board
+        let fade = jumped | std::mem::take(&mut self.fade_into_next_state);
+        if fade && !self.visual_board.pieces.is_empty() {
+            let previous = std::mem::replace(&mut self.visual_board, VisualBoard::empty());
+            self.cross_fade = Some(CrossFade {
+                previous,
+                start_time: None,
+                progress: 0.0,
+            });
+            self.animate("cross_fade", Self::fade_out_previous);
+        }
         self.set_state(state);
     }
 
     fn handle_pointer_event(&mut self, event: ControlEvent) -> bool {
         let mut handled = false;
-        // TODO(Menno 06.08.2025) Highlight pieces if we hover over them
         match event {
             ControlEvent::Down(coordinates) => {
                 let coordinates = self.layout.apply_inverse_to_mouse(coordinates);
                 handled = self.visual_board.start_drag(coordinates);
+                if let Some(piece) = self.visual_board.dragged_piece() {
+                    self.piece_tracker.select(piece);
+                    self.set_cursor(Some("grabbing"));
+                }
             }
             ControlEvent::Up() => {
+                self.set_cursor(None);
                 match self.visual_board.stop_drag() {
                     DragEndResult::Some(visual_move) => {
                         // TODO(Menno 16.08.2025) Animate this and the other views
-                        let new_state = (self.on_drag_move_cb)(visual_move);
-                        self.set_state(&new_state);
+                        self.piece_tracker.apply(&visual_move.slide_move);
+                        (self.on_board_event_cb)(BoardEvent::DragMove(visual_move));
                         handled = true;
                     }
                     DragEndResult::None => {
                         handled = true;
                     }
+                    DragEndResult::Pushed(slide_move) => {
+                        self.explain_push(&slide_move);
+                        handled = true;
+                    }
                     DragEndResult::Invalid => {
                         // Ignore such an event
                     }
@@ -169,20 +424,113 @@ impl BoardView {
                 let coordinates = self.layout.apply_inverse_to_mouse(coordinates);
                 handled = self.visual_board.drag(coordinates);
             }
+            ControlEvent::Hover(coordinates) => {
+                let coordinates = self.layout.apply_inverse_to_mouse(coordinates);
+                let piece = self.visual_board.hover(coordinates);
+                self.set_cursor(piece.is_some().then_some("grab"));
+                self.set_hovered_piece(piece);
+                // Redraw to show the highlight, but leave the event to the browser
+                self.frame_scheduler.schedule().unwrap();
+            }
+            ControlEvent::Leave() => {
+                if self.visual_board.dragged_piece().is_none() {
+                    self.visual_board.highlight(&None);
+                    self.set_cursor(None);
+                    self.set_hovered_piece(None);
+                    self.frame_scheduler.schedule().unwrap();
+                }
+            }
+            ControlEvent::Cancel() => {
+                self.visual_board.cancel_drag();
+                handled = true;
+            }
+            ControlEvent::ContextMenu(coordinates) => {
+                // The menu floats over the page, convert the canvas pixels into CSS pixels of the viewport
+                let rect = self.canvas.get_bounding_client_rect();
+                let device_pixel_ratio =
+                    web_sys::window().map_or(1.0, |window| window.device_pixel_ratio());
+                self.context_menu
+                    .open(
+                        rect.left() + coordinates.x / device_pixel_ratio,
+                        rect.top() + coordinates.y / device_pixel_ratio,
+                    )
+                    .expect("Failed to open board menu");
+                handled = true;
+            }
+            ControlEvent::Pan(delta) => {
+                // Without zoom there is nothing to pan, let the browser scroll the page instead
+                if self.layout.is_zoomed() {
+                    self.layout.pan_by(delta);
+                    handled = true;
+                }
+            }
+            ControlEvent::Pinch(center, factor, delta) => {
+                self.layout.pan_by(delta);
+                self.layout.zoom_at(factor, center);
+                handled = true;
+            }
+            ControlEvent::Zoom(coordinates, movement) => {
+                // Scrolling down zooms out
+                self.layout
+                    .zoom_at((-movement * ZOOM_SPEED).exp(), coordinates);
+                handled = true;
+            }
         }
         if handled {
-            self.frame_scheduler.schedule().unwrap();
+            // Released pieces snap or spring back into place
+            self.animate_pieces();
         }
         handled
     }
 
-    fn set_state(&mut self, state: &graph::Node) {
+    /// Tell the other views which piece is hovered, when that changes
+    fn set_hovered_piece(&mut self, piece: Option<board::Coordinates>) {
+        if self.hovered_piece != piece {
+            self.hovered_piece = piece;
+            (self.on_board_event_cb)(BoardEvent::Hovered(piece));
+        }
+    }
+
+    /// Point out a piece, e.g. while its moves are hovered in another view
+    pub fn highlight_piece(&mut self, piece: Option<board::Coordinates>) {
+        self.visual_board.highlight_only(piece);
+        self.frame_scheduler.schedule().unwrap();
+    }
+
+    /// Show whether pieces can be grabbed, or are being dragged
+    fn set_cursor(&self, cursor: Option<&str>) {
+        let style = self.canvas.style();
+        match cursor {
+            Some(cursor) => style.set_property("cursor", cursor).unwrap(),
+            None => style.remove_property("cursor").map(drop).unwrap(),
+        }
+    }
+
+    /// Flash the pieces that stopped a pushed piece, instead of only letting it snap back
+    fn explain_push(&mut self, slide_move: &SlideMove) {
+        let Some(board) = self.board else {
+            return;
+        };
+        let blocking = board::blocking_pieces(&board, slide_move);
+        if !blocking.is_empty() {
+            self.visual_board.flash(blocking);
+            self.animate_pieces();
+        }
+    }
+
+    fn set_state(&mut self, state: &graph::Neighborhood) {
         self.visual_board = VisualBoard::new(state);
+        self.previewed_piece = None;
+        self.visual_board.spring = self.spring;
+        self.visual_board.set_fixed_timestep(self.fixed_timestep);
+        self.board = Some(state.board);
         self.layout = Layout::new(
             self.visual_board.size,
             self.layout.get_canvas_size(),
             web_sys::window().unwrap().device_pixel_ratio(),
-        );
+            self.direction,
+        )
+        .with_view_of(&self.layout);
         self.frame_scheduler.schedule().unwrap();
     }
 
@@ -193,18 +541,62 @@ impl BoardView {
             self.visual_board.size,
             Size::new(width, height),
             window.device_pixel_ratio(),
-        );
+            self.direction,
+        )
+        .with_view_of(&self.layout);
         self.frame_scheduler.schedule().unwrap();
     }
 
-    fn draw(&mut self, timestamp: Duration) {
-        // Update board and draw it
-        let request_new_frame = self.visual_board.update_to(timestamp).is_ok();
-        self.renderer.draw(&self.visual_board, &self.layout);
+    /// Request frames until the ticker stops the animation, this replaces a running animation of the same name
+    fn animate(
+        &mut self,
+        name: &'static str,
+        mut tick: impl FnMut(&mut Self, Duration) -> bool + 'static,
+    ) {
+        let self_ref = self.self_ref.clone();
+        self.frame_scheduler
+            .animate(
+                name,
+                Box::new(move |frame: Frame| {
+                    self_ref
+                        .upgrade()
+                        .is_some_and(|view| tick(&mut view.borrow_mut(), frame.timestamp))
+                }),
+            )
+            .unwrap();
+    }
+
+    /// Advance the pieces' animations and their flash, until they have all finished
+    fn animate_pieces(&mut self) {
+        self.animate("pieces", |view, timestamp| {
+            view.visual_board.update_to(timestamp).is_ok()
+        });
+    }
 
-        // Schedule next frame if board is still animating
-        if request_new_frame {
-            self.frame_scheduler.schedule().unwrap();
+    /// @returns whether the previous board is still fading out
+    fn fade_out_previous(&mut self, timestamp: Duration) -> bool {
+        let Some(fade) = &mut self.cross_fade else {
+            return false;
+        };
+        let elapsed = timestamp.saturating_sub(*fade.start_time.get_or_insert(timestamp));
+        fade.progress = elapsed.as_secs_f64() / CROSS_FADE_DURATION.as_secs_f64();
+        if fade.progress >= 1.0 {
+            self.cross_fade = None;
         }
+        self.cross_fade.is_some()
+    }
+
+    /// Draw the board, the animations have been advanced to the frame by their tickers
+    fn draw(&mut self) {
+        let trajectory = self
+            .piece_tracker
+            .selected_trajectory()
+            .filter(|_| self.show_trajectory);
+        let previous = self
+            .cross_fade
+            .as_ref()
+            .map(|fade| (&fade.previous, fade.progress));
+        self.renderer
+            .draw(&self.visual_board, &self.layout, trajectory, previous);
     }
 }
@@ -4,14 +4,180 @@
 use crate::board;
 use crate::board::Axis;
 use crate::views::board_view::layout::Layout;
-use crate::views::board_view::visual_board::{VisualBoard, VisualSize};
-use crate::views::utils::Coordinates;
+use crate::views::board_view::trajectory::Trajectory;
+use crate::views::board_view::visual_board::{
+    VisualBoard, VisualCoordinates, VisualRect, VisualSize,
+};
+use crate::views::i18n::Direction;
+use crate::views::theme::Theme;
+use crate::views::utils::{get_document, Coordinates, Size};
+use anyhow::anyhow;
+use std::str::FromStr;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
     CanvasRenderingContext2d, HtmlCanvasElement, OffscreenCanvas, OffscreenCanvasRenderingContext2d,
 };
 
-const AXIS_COLOR: &str = "rgb(179, 179, 179)";
+const TRAJECTORY_OPACITY: f64 = 0.8;
+const AFFORDANCE_OPACITY: f64 = 0.5;
+/// The grid is drawn in the color of the axes, but faint enough to stay behind the pieces
+const GRID_OPACITY: f64 = 0.12;
+/// Ice is drawn like the grid, but stands out more
+const ICE_OPACITY: f64 = 0.3;
+const ONE_WAY_OPACITY: f64 = 0.5;
+
+/// The formats that pictures of the board are exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "png" => Ok(ImageFormat::Png),
+            "svg" => Ok(ImageFormat::Svg),
+            _ => Err(anyhow!(
+                "Unknown image format \"{s}\", expected \"png\" or \"svg\""
+            )),
+        }
+    }
+}
+
+/// What is written on the pieces, the letters match the layout notation, e.g. "AABB/CDDE/..."
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PieceLabels {
+    #[default]
+    None,
+    Letters,
+    Numbers,
+}
+
+impl PieceLabels {
+    /// The label of a piece, by its place among the board's sorted pieces
+    pub fn label(&self, order: usize) -> Option<String> {
+        match self {
+            PieceLabels::None => None,
+            PieceLabels::Letters => Some(char::from(b'A' + order as u8).to_string()),
+            PieceLabels::Numbers => Some((order + 1).to_string()),
+        }
+    }
+}
+
+impl FromStr for PieceLabels {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "none" => Ok(PieceLabels::None),
+            "letters" => Ok(PieceLabels::Letters),
+            "numbers" => Ok(PieceLabels::Numbers),
+            _ => Err(anyhow!(
+                "Unknown piece labels \"{s}\", expected \"none\", \"letters\" or \"numbers\""
+            )),
+        }
+    }
+}
+
+/// Draw a still picture of the board, without highlights or zoom
+/// @param size the size of the picture in pixels
+/// @returns the picture as a data URL
+pub fn export_image(
+    board: &VisualBoard,
+    theme: Theme,
+    labels: PieceLabels,
+    size: Size,
+    direction: Direction,
+    format: ImageFormat,
+) -> Result<String, JsValue> {
+    let layout = Layout::new(board.size, size, 1.0, direction);
+    match format {
+        ImageFormat::Png => {
+            // The canvas is never attached to the page
+            let canvas: HtmlCanvasElement = get_document()?.create_element("canvas")?.dyn_into()?;
+            let mut renderer = Renderer::new(canvas.clone())?;
+            renderer.set_theme(theme);
+            renderer.show_highlights = false;
+            renderer.labels = labels;
+            renderer.draw(board, &layout, None, None);
+            canvas.to_data_url_with_type("image/png")
+        }
+        ImageFormat::Svg => Ok(format!(
+            "data:image/svg+xml;charset=utf-8,{}",
+            js_sys::encode_uri_component(&to_svg(board, &layout, &theme, labels))
+        )),
+    }
+}
+
+/// A rectangle in SVG, the canvas allows negative sizes but SVG doesn't
+fn svg_rect(position: Coordinates, size: Size, corner_radius: f64, fill: &str) -> String {
+    let x = position.x.min(position.x + size.width);
+    let y = position.y.min(position.y + size.height);
+    format!(
+        r#"<rect x="{x}" y="{y}" width="{}" height="{}" rx="{corner_radius}" fill="{fill}"/>"#,
+        size.width.abs(),
+        size.height.abs()
+    )
+}
+
+/// Draw the board as SVG, with the same shapes that the renderer draws on its canvas
+pub(super) fn to_svg(
+    board: &VisualBoard,
+    layout: &Layout,
+    theme: &Theme,
+    labels: PieceLabels,
+) -> String {
+    let canvas_size = layout.get_canvas_size();
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        canvas_size.width, canvas_size.height
+    );
+
+    let axis_color = theme.axis.to_css(1.0);
+    let axes = [
+        (Axis::Horizontal, board.size.width as u32),
+        (Axis::Vertical, board.size.height as u32),
+    ];
+    for (axis, count) in &axes {
+        for index in 0..=*count {
+            let (position, size) = layout.apply_to_axis_tick(index, axis);
+            svg.push_str(&svg_rect(position, size, 0.0, &axis_color));
+        }
+        for index in 0..*count {
+            let position = layout.apply_to_axis_label(index, axis);
+            let label = board::Coordinates::axis_to_string(*axis, index as u8);
+            svg.push_str(&format!(
+                r#"<text x="{}" y="{}" fill="{axis_color}" font-family="Roboto Mono" font-size="{}" text-anchor="middle" dominant-baseline="middle">{label}</text>"#,
+                position.x,
+                position.y,
+                layout.axis_label_font_size_px()
+            ));
+        }
+    }
+
+    // Sort the pieces, so that the same board always gives the same picture
+    let mut pieces: Vec<_> = board.pieces.iter().collect();
+    pieces.sort_by_key(|(coordinates, _piece)| **coordinates);
+    for (_coordinates, piece) in pieces {
+        let (position, size, corner_radius) = layout.apply_to_piece(piece);
+        let fill = theme.piece_color(piece.kind).to_css(theme.piece_opacity);
+        svg.push_str(&svg_rect(position, size, corner_radius, &fill));
+        if let Some(label) = labels.label(piece.order) {
+            svg.push_str(&format!(
+                r#"<text x="{}" y="{}" fill="{}" font-family="Roboto Mono" font-size="{}" text-anchor="middle" dominant-baseline="middle">{label}</text>"#,
+                position.x + size.width / 2.0,
+                position.y + size.height / 2.0,
+                theme.piece_label.to_css(1.0),
+                layout.piece_label_font_size_px()
+            ));
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
 
 fn create_context_2d(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d, JsValue> {
     Ok(canvas
@@ -31,10 +197,16 @@ fn create_offscreen_context_2d(
 
 pub struct Renderer {
     layout: Layout,
+    theme: Theme,
     canvas: HtmlCanvasElement,
     ctx: CanvasRenderingContext2d,
     axes_canvas: OffscreenCanvas,
     axes_ctx: OffscreenCanvasRenderingContext2d,
+    /// Whether highlighted pieces stand out and show where they can move to
+    show_highlights: bool,
+    labels: PieceLabels,
+    /// Whether every cell of the board is outlined, so the gaps between pieces are easy to judge
+    show_grid: bool,
 }
 
 impl Renderer {
@@ -45,14 +217,50 @@ impl Renderer {
 
         Ok(Self {
             layout: Layout::zero(),
+            theme: Theme::default(),
             canvas,
             ctx,
             axes_canvas,
             axes_ctx,
+            show_highlights: true,
+            labels: PieceLabels::None,
+            show_grid: false,
         })
     }
 
-    pub fn draw(&mut self, board: &VisualBoard, layout: &Layout) {
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        // Invalidate the cache, so the axes are redrawn in the new colors
+        self.layout = Layout::zero();
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    pub fn set_labels(&mut self, labels: PieceLabels) {
+        self.labels = labels;
+    }
+
+    pub fn labels(&self) -> PieceLabels {
+        self.labels
+    }
+
+    pub fn set_show_grid(&mut self, enabled: bool) {
+        self.show_grid = enabled;
+        // Invalidate the cache, the grid is drawn along with the axes
+        self.layout = Layout::zero();
+    }
+
+    /// Draw the board, optionally cross-fading from a previous board
+    /// @param previous the board to fade out, and the progress of the fade in [0.0, 1.0]
+    pub fn draw(
+        &mut self,
+        board: &VisualBoard,
+        layout: &Layout,
+        trajectory: Option<&Trajectory>,
+        previous: Option<(&VisualBoard, f64)>,
+    ) {
         // Don't draw if our layout isn't valid
         if layout.is_zero() {
             return;
@@ -61,11 +269,17 @@ impl Renderer {
         // Rebuild the cache if needed
         if self.cache_layout(layout) {
             // cache was cleared, draw the axes for this layout into cache
-            self.draw_axes(&board.size);
+            self.draw_axes(board);
         }
 
         let ctx = &self.ctx;
 
+        // Apply the user's zoom and pan, the cached axes are drawn in the fitted layout
+        self.layout = *layout;
+        let [a, b, c, d, e, f] = layout.view_transform();
+        ctx.set_transform(a, b, c, d, e, f)
+            .expect("Could not set view transform");
+
         // Set the blend mode to ignore the destination buffer,
         // this means that we can use draw_image_with_offscreen_canvas to effectively clear the destination canvas.
         ctx.set_global_composite_operation("copy")
@@ -76,22 +290,186 @@ impl Renderer {
         ctx.set_global_composite_operation("source-over")
             .expect("Could not reset compositing");
 
-        // Draw the game pieces
-        board.pieces.iter().for_each(|(_, piece)| {
+        // Draw the game pieces, fading the new board in over the previous one
+        match previous {
+            Some((previous, progress)) => {
+                self.draw_pieces(previous, 1.0 - progress);
+                self.draw_pieces(board, progress);
+            }
+            None => self.draw_pieces(board, 1.0),
+        }
+
+        self.draw_checkpoint(board);
+        self.draw_move_affordances(board);
+
+        if let Some(trajectory) = trajectory {
+            self.draw_trajectory(trajectory);
+        }
+
+        ctx.reset_transform().expect("Could not reset transform");
+    }
+
+    fn draw_pieces(&self, board: &VisualBoard, fade: f64) {
+        let ctx = &self.ctx;
+        ctx.set_font(&format!(
+            "{}px Roboto Mono",
+            self.layout.piece_label_font_size_px()
+        ));
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        board.pieces.iter().for_each(|(position, piece)| {
             ctx.begin_path();
 
-            let opacity: f64 = if piece.highlighted { 1.0 } else { 0.8 };
-            ctx.set_fill_style_str(format!("rgba({},{opacity})", piece.color).as_str());
+            let opacity: f64 = fade
+                * match piece.highlighted && self.show_highlights {
+                    true => self.theme.piece_highlight_opacity,
+                    false => self.theme.piece_opacity,
+                };
+            ctx.set_fill_style_str(&self.theme.piece_color(piece.kind).to_css(opacity));
 
             let (pos, size, corner_radius) = self.layout.apply_to_piece(piece);
             ctx.round_rect_with_f64(pos.x, pos.y, size.width, size.height, corner_radius)
                 .expect("Failed to draw piece");
             ctx.fill();
+
+            // A piece that stopped another flashes in the color of bad moves
+            let brightness = board.flash_brightness(position);
+            if brightness > 0.0 {
+                ctx.set_fill_style_str(&self.theme.move_negative.to_css(fade * brightness));
+                ctx.fill();
+            }
+
+            // The label is placed by the same offset rectangle, so it moves along with the piece
+            if let Some(label) = self.labels.label(piece.order) {
+                ctx.set_fill_style_str(&self.theme.piece_label.to_css(fade));
+                ctx.fill_text(&label, pos.x + size.width / 2.0, pos.y + size.height / 2.0)
+                    .expect("Failed to draw piece label");
+            }
         });
     }
 
-    fn draw_axes(&self, board_size: &VisualSize) {
+    /// Outline the checkpoint to reach next, in the color of the pieces that can reach it
+    fn draw_checkpoint(&self, board: &VisualBoard) {
+        let Some((rect, kind)) = &board.checkpoint else {
+            return;
+        };
+        let ctx = &self.ctx;
+        let line_width = self.layout.line_width();
+        ctx.set_line_width(line_width);
+        ctx.set_stroke_style_str(&self.theme.piece_color(*kind).to_css(1.0));
+        let (pos, size, corner_radius) = self.layout.apply_to_rect(rect);
+        // Inset the outline like the move affordances, so it stays within the checkpoint's cells
+        let inset = line_width * 0.5;
+        ctx.begin_path();
+        ctx.round_rect_with_f64(
+            pos.x + inset,
+            pos.y - inset,
+            size.width - line_width,
+            size.height + line_width,
+            corner_radius,
+        )
+        .expect("Failed to draw checkpoint");
+        ctx.stroke();
+    }
+
+    /// Outline where the highlighted pieces can move to, so players can see their options on the board itself
+    fn draw_move_affordances(&self, board: &VisualBoard) {
+        let ctx = &self.ctx;
+        let line_width = self.layout.line_width();
+        ctx.set_line_width(line_width);
+        ctx.set_line_dash(&js_sys::Array::of2(
+            &JsValue::from_f64(line_width * 2.0),
+            &JsValue::from_f64(line_width * 2.0),
+        ))
+        .expect("Could not set line dash");
+
+        let highlighted = board
+            .pieces
+            .values()
+            .filter(|piece| piece.highlighted && self.show_highlights);
+        for piece in highlighted {
+            ctx.set_stroke_style_str(
+                &self
+                    .theme
+                    .piece_color(piece.kind)
+                    .to_css(AFFORDANCE_OPACITY),
+            );
+            for drag_move in &piece.drag_moves {
+                // The target area is centered on the offset that the move results in
+                let target = piece
+                    .rect
+                    .translate(drag_move.target_area.center().to_vector());
+                let (pos, size, corner_radius) = self.layout.apply_to_rect(&target);
+
+                // Inset the outline, so it doesn't overlap the neighboring pieces
+                let inset = line_width * 0.5;
+                ctx.begin_path();
+                ctx.round_rect_with_f64(
+                    pos.x + inset,
+                    pos.y - inset,
+                    size.width - line_width,
+                    size.height + line_width,
+                    corner_radius,
+                )
+                .expect("Failed to draw move affordance");
+                ctx.stroke();
+            }
+        }
+
+        ctx.set_line_dash(&js_sys::Array::new())
+            .expect("Could not reset line dash");
+    }
+
+    /// Draw the path of a piece as a polyline through the centers of the cells it occupied
+    fn draw_trajectory(&self, trajectory: &Trajectory) {
+        let ctx = &self.ctx;
+        let line_width = self.layout.line_width();
+        let color = self.theme.trajectory.to_css(TRAJECTORY_OPACITY);
+        ctx.set_stroke_style_str(&color);
+        ctx.set_fill_style_str(&color);
+        ctx.set_line_width(line_width);
+        ctx.set_line_join("round");
+
+        ctx.begin_path();
+        for center in &trajectory.centers {
+            let point = self.layout.apply_to_coordinates(*center);
+            ctx.line_to(point.x, point.y);
+        }
+        ctx.stroke();
+
+        // Mark each visited cell with a dot
+        for center in &trajectory.centers {
+            let point = self.layout.apply_to_coordinates(*center);
+            ctx.begin_path();
+            ctx.arc(point.x, point.y, line_width, 0.0, std::f64::consts::TAU)
+                .expect("Failed to draw trajectory point");
+            ctx.fill();
+        }
+    }
+
+    fn draw_axes(&self, board: &VisualBoard) {
         let ctx = &self.axes_ctx;
+        let board_size = &board.size;
+
+        // Draw the cells first, the pieces cover them so only the empty cells stand out
+        if self.show_grid {
+            ctx.set_fill_style_str(&self.theme.axis.to_css(GRID_OPACITY));
+            for y in 0..board_size.height as u32 {
+                for x in 0..board_size.width as u32 {
+                    let cell = VisualRect::new(
+                        VisualCoordinates::new(x as f64, y as f64),
+                        VisualSize::new(1.0, 1.0),
+                    );
+                    let (pos, size, corner_radius) = self.layout.apply_to_rect(&cell);
+                    ctx.begin_path();
+                    ctx.round_rect_with_f64(pos.x, pos.y, size.width, size.height, corner_radius)
+                        .expect("Failed to draw grid cell");
+                    ctx.fill();
+                }
+            }
+            ctx.set_fill_style_str(&self.theme.axis.to_css(1.0));
+        }
+        self.draw_cells(board);
 
         // Draw all ticks
         let draw_ticks = |axis: Axis, count: u32| {
@@ -122,11 +500,45 @@ impl Renderer {
         }
     }
 
+    /// Draw the cells that aren't normal, ice is filled and one-way cells show their direction
+    fn draw_cells(&self, board: &VisualBoard) {
+        let ctx = &self.axes_ctx;
+        for (coordinates, cell) in &board.cells {
+            let rect = VisualRect::new(
+                VisualCoordinates::new(coordinates.x as f64, coordinates.y as f64),
+                VisualSize::new(1.0, 1.0),
+            );
+            let (pos, size, corner_radius) = self.layout.apply_to_rect(&rect);
+            match cell {
+                board::Cell::Normal => {}
+                board::Cell::Ice => {
+                    ctx.set_fill_style_str(&self.theme.axis.to_css(ICE_OPACITY));
+                    ctx.begin_path();
+                    ctx.round_rect_with_f64(pos.x, pos.y, size.width, size.height, corner_radius)
+                        .expect("Failed to draw ice");
+                    ctx.fill();
+                }
+                board::Cell::OneWay(direction) => {
+                    let arrow = match direction {
+                        board::SlideDirection::Up => "↑",
+                        board::SlideDirection::Down => "↓",
+                        board::SlideDirection::Left => "←",
+                        board::SlideDirection::Right => "→",
+                    };
+                    ctx.set_fill_style_str(&self.theme.axis.to_css(ONE_WAY_OPACITY));
+                    ctx.fill_text(arrow, pos.x + size.width / 2.0, pos.y + size.height / 2.0)
+                        .expect("Failed to draw one-way cell");
+                }
+            }
+        }
+        ctx.set_fill_style_str(&self.theme.axis.to_css(1.0));
+    }
+
     /// Rebuild render cache if layout changed
     /// @param layout the layout to apply
     /// @return true if the cache was cleared
     fn cache_layout(&mut self, layout: &Layout) -> bool {
-        if *layout == self.layout {
+        if layout.has_same_fit(&self.layout) {
             return false;
         }
         self.layout = *layout;
@@ -145,7 +557,8 @@ impl Renderer {
             .set_font(format!("{}px Roboto Mono", self.layout.axis_label_font_size_px()).as_str());
         self.axes_ctx.set_text_align("center");
         self.axes_ctx.set_text_baseline("middle");
-        self.axes_ctx.set_fill_style_str(AXIS_COLOR);
+        self.axes_ctx
+            .set_fill_style_str(&self.theme.axis.to_css(1.0));
 
         true
     }
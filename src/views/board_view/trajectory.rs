@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board;
+use crate::board::{Board, SlideMove};
+use crate::views::board_view::visual_board::{VisualCoordinates, VisualSize};
+use std::collections::HashMap;
+
+/// The identity of a piece, which survives moves, unlike its position
+pub type PieceId = usize;
+
+/// The path that a single piece travelled during the session
+pub struct Trajectory {
+    pub size: VisualSize,
+    /// The centers of the cells that the piece occupied, in visiting order
+    pub centers: Vec<VisualCoordinates>,
+}
+
+/// Tracks the identity of each piece across moves.
+/// Boards keep their pieces sorted by position, so the identity of a piece is lost when it moves.
+#[derive(Default)]
+pub struct PieceTracker {
+    identities: HashMap<board::Coordinates, PieceId>,
+    trajectories: Vec<Trajectory>,
+    selected: Option<PieceId>,
+}
+
+fn center_of(position: board::Coordinates, size: VisualSize) -> VisualCoordinates {
+    VisualCoordinates::new(
+        position.x as f64 + size.width * 0.5,
+        position.y as f64 + size.height * 0.5,
+    )
+}
+
+impl PieceTracker {
+    /// Start tracking a new set of pieces, forgetting all previous trajectories
+    pub fn reset(&mut self, board: &Board) {
+        self.identities.clear();
+        self.trajectories.clear();
+        self.selected = None;
+        for (id, piece) in board.pieces.iter().enumerate() {
+            let size = VisualSize::new(piece.size.x as f64, piece.size.y as f64);
+            self.identities.insert(piece.position, id);
+            self.trajectories.push(Trajectory {
+                size,
+                centers: vec![center_of(piece.position, size)],
+            });
+        }
+    }
+
+    /// Check if the tracked pieces are at the same positions as the pieces of this board
+    pub fn matches(&self, board: &Board) -> bool {
+        self.identities.len() == board.pieces.len()
+            && board
+                .pieces
+                .iter()
+                .all(|piece| self.identities.contains_key(&piece.position))
+    }
+
+    /// Follow a piece through a move, and select it
+    pub fn apply(&mut self, slide_move: &SlideMove) {
+        let Some(id) = self.identities.remove(&slide_move.start) else {
+            return;
+        };
+        let end = slide_move.get_endpoint();
+        self.identities.insert(end, id);
+        let trajectory = &mut self.trajectories[id];
+        trajectory.centers.push(center_of(end, trajectory.size));
+        self.selected = Some(id);
+    }
+
+    /// Select the piece at the given position, if any
+    pub fn select(&mut self, position: board::Coordinates) {
+        self.selected = self.identities.get(&position).copied();
+    }
+
+    pub fn selected_trajectory(&self) -> Option<&Trajectory> {
+        self.trajectories.get(self.selected?)
+    }
+}
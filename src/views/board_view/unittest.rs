@@ -0,0 +1,368 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{get_start_board, Coordinates};
+use crate::graph;
+use crate::views::board_view::layout::Layout;
+use crate::views::board_view::renderer::{to_svg, PieceLabels};
+use crate::views::board_view::visual_board::{
+    AnimatableOffset, Animation, AnimationRepeatBehavior, Spring, VisualBoard, VisualSize,
+};
+use crate::views::clock::{Clock, ManualClock};
+use crate::views::i18n::Direction;
+use crate::views::theme::Theme;
+use crate::views::utils;
+use crate::views::utils::Size;
+use keyframe::functions::Linear;
+use keyframe::{keyframes, AnimationSequence, EasingFunction};
+use std::time::Duration;
+
+fn init() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+/// Run the board through frames at 50 per second, for a duration that is a multiple of 20ms
+/// @returns the result of the last frame
+fn run_frames(visual_board: &mut VisualBoard, clock: &ManualClock, duration: Duration) -> bool {
+    let frame = Duration::from_millis(20);
+    (0..duration.as_millis() / frame.as_millis())
+        .map(|_| {
+            clock.advance(frame);
+            visual_board.update_to(clock.now()).is_ok()
+        })
+        .last()
+        .expect("The duration spans frames")
+}
+
+#[test]
+fn test_animation_with_manual_clock() {
+    init();
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let mut visual_board = VisualBoard::new(&node);
+    let target = Coordinates { x: 1, y: 3 };
+    let from = AnimatableOffset::zero();
+    let to = AnimatableOffset::new(0.0, -1.0);
+    let mut done = visual_board.animate(Animation {
+        sequence: keyframes![(from, 0.0), (to, 1.0)],
+        target,
+        repeat: AnimationRepeatBehavior::None,
+    });
+
+    // The first frame marks the start of the animation, regardless of the clock's time
+    let clock = ManualClock::default();
+    clock.advance(Duration::from_secs(10));
+    assert!(visual_board.update_to(clock.now()).is_ok());
+    assert_eq!(visual_board.pieces[&target].visual_offset.y, 0.0);
+
+    // Halfway through, the piece is halfway
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(500)
+    ));
+    assert!((visual_board.pieces[&target].visual_offset.y + 0.5).abs() < 1e-9);
+    assert_eq!(done.try_recv(), Ok(None));
+
+    // Past the end, the animation is finished and reported as done
+    assert!(!run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(600)
+    ));
+    assert_eq!(done.try_recv(), Ok(Some(())));
+}
+
+#[test]
+fn test_concurrent_animations() {
+    init();
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let mut visual_board = VisualBoard::new(&node);
+    let short_target = Coordinates { x: 1, y: 3 };
+    let long_target = Coordinates { x: 0, y: 0 };
+    let from = AnimatableOffset::zero();
+    let to = AnimatableOffset::new(0.0, -1.0);
+    let mut short_done = visual_board.animate(Animation {
+        sequence: keyframes![(from, 0.0), (to, 1.0)],
+        target: short_target,
+        repeat: AnimationRepeatBehavior::None,
+    });
+    let mut long_done = visual_board.animate(Animation {
+        sequence: keyframes![(from, 0.0), (to, 2.0)],
+        target: long_target,
+        repeat: AnimationRepeatBehavior::None,
+    });
+
+    // Both pieces move at the same time
+    let clock = ManualClock::default();
+    assert!(visual_board.update_to(clock.now()).is_ok());
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(500)
+    ));
+    let short_offset = visual_board.pieces[&short_target].visual_offset.y;
+    let long_offset = visual_board.pieces[&long_target].visual_offset.y;
+    assert!((short_offset + 0.5).abs() < 1e-9);
+    assert!(long_offset < 0.0 && long_offset > short_offset);
+
+    // The short animation finishes, while the long one keeps running
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(600)
+    ));
+    assert_eq!(short_done.try_recv(), Ok(Some(())));
+    assert_eq!(long_done.try_recv(), Ok(None));
+
+    // Stopping one piece leaves the others running
+    let mut stopped_done = visual_board.animate(Animation {
+        sequence: keyframes![(from, 0.0), (to, 1.0)],
+        target: short_target,
+        repeat: AnimationRepeatBehavior::Loop,
+    });
+    visual_board.stop_animation(&short_target);
+    assert_eq!(stopped_done.try_recv(), Ok(Some(())));
+    assert_eq!(visual_board.pieces[&short_target].visual_offset.y, 0.0);
+    assert_eq!(long_done.try_recv(), Ok(None));
+
+    assert!(!run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_secs(1)
+    ));
+    assert_eq!(long_done.try_recv(), Ok(Some(())));
+}
+
+#[test]
+fn test_frame_gaps_are_clamped() {
+    init();
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let mut visual_board = VisualBoard::new(&node);
+    let target = Coordinates { x: 1, y: 3 };
+    let from = AnimatableOffset::zero();
+    let to = AnimatableOffset::new(0.0, -1.0);
+    let _done = visual_board.animate(Animation {
+        sequence: keyframes![(from, 0.0, Linear), (to, 1.0)],
+        target,
+        repeat: AnimationRepeatBehavior::None,
+    });
+    let clock = ManualClock::default();
+    assert!(visual_board.update_to(clock.now()).is_ok());
+
+    // A frame after a long gap, like from a tab in the background, continues where it left off
+    clock.advance(Duration::from_secs(30));
+    assert!(visual_board.update_to(clock.now()).is_ok());
+    assert!((visual_board.pieces[&target].visual_offset.y + 0.1).abs() < 1e-9);
+}
+
+#[test]
+fn test_fixed_timestep() {
+    init();
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let mut visual_board = VisualBoard::new(&node);
+    visual_board.set_fixed_timestep(Some(Duration::from_millis(100)));
+    let target = Coordinates { x: 1, y: 3 };
+    let from = AnimatableOffset::zero();
+    let to = AnimatableOffset::new(0.0, -1.0);
+    let mut done = visual_board.animate(Animation {
+        sequence: keyframes![(from, 0.0, Linear), (to, 1.0)],
+        target,
+        repeat: AnimationRepeatBehavior::None,
+    });
+    let clock = ManualClock::default();
+    assert!(visual_board.update_to(clock.now()).is_ok());
+
+    // The animation starts at the first step, the piece is drawn between the steps
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(100)
+    ));
+    assert_eq!(visual_board.pieces[&target].visual_offset.y, 0.0);
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(100)
+    ));
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(40)
+    ));
+    assert!((visual_board.pieces[&target].visual_offset.y + 0.04).abs() < 1e-9);
+
+    // The frames don't line up with the steps, yet the animation ends on its last step
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(860)
+    ));
+    assert_eq!(done.try_recv(), Ok(None));
+    assert!(!run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(100)
+    ));
+    assert_eq!(visual_board.pieces[&target].visual_offset.y, -1.0);
+    assert_eq!(done.try_recv(), Ok(Some(())));
+}
+
+#[test]
+fn test_spring() {
+    let spring = Spring::default();
+    assert!(spring.y(0.0).abs() < 1e-9);
+    assert!((spring.y(1.0) - 1.0).abs() < 1e-9);
+
+    // It swings past the end, but not by much, and settles close to it before the end
+    let samples: Vec<f64> = (0..=100).map(|x| spring.y(x as f64 / 100.0)).collect();
+    let overshoot = samples.iter().copied().fold(0.0, f64::max) - 1.0;
+    assert!(overshoot > 0.05 && overshoot < 0.25, "{overshoot}");
+    assert!(samples[80..].iter().all(|y| (y - 1.0).abs() < 0.01));
+}
+
+#[test]
+fn test_flash() {
+    init();
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let mut visual_board = VisualBoard::new(&node);
+    let flashed = Coordinates { x: 1, y: 1 };
+    let other = Coordinates { x: 2, y: 1 };
+    visual_board.flash(vec![flashed]);
+
+    // The flash starts bright and fades out, only on the flashed piece
+    let clock = ManualClock::default();
+    assert!(visual_board.update_to(clock.now()).is_ok());
+    assert_eq!(visual_board.flash_brightness(&flashed), 1.0);
+    assert_eq!(visual_board.flash_brightness(&other), 0.0);
+    assert!(run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(200)
+    ));
+    assert!((visual_board.flash_brightness(&flashed) - 0.5).abs() < 1e-9);
+
+    assert!(!run_frames(
+        &mut visual_board,
+        &clock,
+        Duration::from_millis(300)
+    ));
+    assert_eq!(visual_board.flash_brightness(&flashed), 0.0);
+}
+
+#[test]
+fn test_layout_zoom_keeps_center_in_place() {
+    init();
+    let mut layout = Layout::new(
+        VisualSize::new(4.0, 5.0),
+        Size::new(400.0, 500.0),
+        1.0,
+        Direction::default(),
+    );
+    let center = utils::Coordinates::new(300.0, 100.0);
+    let before = layout.apply_inverse_to_mouse(center);
+
+    layout.zoom_at(2.0, center);
+    assert!(layout.is_zoomed());
+    let after = layout.apply_inverse_to_mouse(center);
+    assert!((after - before).length() < 1e-9);
+
+    // The view can't be panned beyond the edge of the board's canvas
+    layout.pan_by(utils::Delta::new(1000.0, 1000.0));
+    assert_eq!(layout.view_transform(), [2.0, 0.0, 0.0, 2.0, 0.0, 0.0]);
+
+    // Zooming out all the way returns to the fitted layout
+    layout.zoom_at(0.1, center);
+    assert!(!layout.is_zoomed());
+    assert_eq!(layout.view_transform(), [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_svg_export() {
+    init();
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let visual_board = VisualBoard::new(&node);
+    let layout = Layout::new(
+        visual_board.size,
+        Size::new(400.0, 500.0),
+        1.0,
+        Direction::LeftToRight,
+    );
+    let svg = to_svg(&visual_board, &layout, &Theme::default(), PieceLabels::None);
+
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.ends_with("</svg>"));
+    assert!(svg.contains(r#"width="400" height="500""#));
+    // The ticks of both axes, followed by the ten pieces
+    assert_eq!(svg.matches("<rect ").count(), 5 + 6 + 10);
+    assert_eq!(svg.matches("<text ").count(), 4 + 5);
+    // The canvas may have negative sizes, SVG may not
+    assert!(!svg.contains("=\"-"));
+    assert_eq!(
+        svg,
+        to_svg(&visual_board, &layout, &Theme::default(), PieceLabels::None)
+    );
+}
+
+#[test]
+fn test_piece_labels() {
+    init();
+    assert_eq!(PieceLabels::None.label(0), None);
+    assert_eq!(PieceLabels::Letters.label(2), Some("C".to_string()));
+    assert_eq!(PieceLabels::Numbers.label(9), Some("10".to_string()));
+    assert_eq!(
+        "letters".parse::<PieceLabels>().unwrap(),
+        PieceLabels::Letters
+    );
+    assert!("roman".parse::<PieceLabels>().is_err());
+
+    let node = graph::Neighborhood {
+        board: get_start_board(),
+        edges: Vec::new(),
+    };
+    let visual_board = VisualBoard::new(&node);
+    let layout = Layout::new(
+        visual_board.size,
+        Size::new(400.0, 500.0),
+        1.0,
+        Direction::LeftToRight,
+    );
+    // The pieces are lettered like the layout notation, whose rows start at the top
+    let layout_notation = get_start_board().to_string();
+    let rows: Vec<&str> = layout_notation.split('/').collect();
+    for (coordinates, piece) in &visual_board.pieces {
+        let row = rows[rows.len() - 1 - coordinates.y as usize];
+        let letter = row.chars().nth(coordinates.x as usize).unwrap();
+        assert_eq!(
+            PieceLabels::Letters.label(piece.order),
+            Some(letter.to_string())
+        );
+    }
+
+    let svg = to_svg(
+        &visual_board,
+        &layout,
+        &Theme::default(),
+        PieceLabels::Letters,
+    );
+    assert_eq!(svg.matches("<text ").count(), 4 + 5 + 10);
+    assert!(svg.contains(">A</text>"));
+    assert!(svg.contains(">J</text>"));
+}
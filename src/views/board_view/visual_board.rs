@@ -1,36 +1,14 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
-use crate::board::{BoardId, SlideDirection, SlideMove};
+use crate::board::{SlideDirection, SlideMove};
+use crate::graph::NodeIndex;
+use crate::views::theme::PieceKind;
 use crate::{board, graph};
 use futures::channel::oneshot;
-use keyframe::{keyframes, AnimationSequence, CanTween};
+use keyframe::{keyframes, AnimationSequence, CanTween, EasingFunction};
 use std::collections::HashMap;
 use std::time::Duration;
-use wasm_bindgen::JsValue;
-use web_sys::console::error_1;
-
-/**
- * The lookup a piece size in the color scheme (palette from https://mycolor.space/?hex=%23754BFF&sub=1)
- * @param size the size of the piece
- * @param highlight whether the piece is currently highlighted
- * @returns the color of the piece
- */
-fn get_color(size: &board::Size) -> String {
-    if size.x == 1 && size.y == 1 {
-        return "75,123,255".into();
-    } else if size.x == 1 && size.y == 2 {
-        return "117,75,255".into();
-    } else if size.x == 2 && size.y == 1 {
-        return "75,213,255".into();
-    } else if size.x == 2 && size.y == 2 {
-        return "255,207,75".into();
-    }
-    error_1(&JsValue::from_str(
-        format!("Unknown Piece size: (x: {}, y: {})", size.x, size.y).as_str(),
-    ));
-    "255,0,255".into()
-}
 
 /// Send if this option contains a sender
 fn resolve_if_sender(sender: &mut Option<oneshot::Sender<()>>) {
@@ -40,14 +18,44 @@ fn resolve_if_sender(sender: &mut Option<oneshot::Sender<()>>) {
     }
 }
 
-/// Collect the visual pieces for a given graph Node
-fn collect_pieces(state: &graph::Node) -> HashMap<board::Coordinates, VisualPiece> {
+/// Find the move one step past where a piece can go, if it is pulled at least OVERSHOOT_DISTANCE past
+/// there along the axis that it is pulled furthest along
+fn find_overshoot(
+    start: board::Coordinates,
+    pulled: VisualOffset,
+    range: &VisualRange2D,
+) -> Option<SlideMove> {
+    let (direction, pulled, limit) = match pulled.x.abs() > pulled.y.abs() {
+        true if pulled.x > 0.0 => (SlideDirection::Right, pulled.x, range.right),
+        true => (SlideDirection::Left, -pulled.x, -range.left),
+        false if pulled.y > 0.0 => (SlideDirection::Up, pulled.y, range.top),
+        false => (SlideDirection::Down, -pulled.y, -range.bottom),
+    };
+    (pulled - limit >= OVERSHOOT_DISTANCE).then(|| SlideMove {
+        start,
+        direction,
+        distance: limit.round() as u8 + 1,
+    })
+}
+
+/// Collect the cells of a board that aren't normal
+fn collect_cells(board: &board::Board) -> Vec<(board::Coordinates, board::Cell)> {
+    (0..board.size.y as i8)
+        .flat_map(|y| (0..board.size.x as i8).map(move |x| board::Coordinates { x, y }))
+        .map(|coordinates| (coordinates, board.cell(coordinates)))
+        .filter(|(_coordinates, cell)| *cell != board::Cell::Normal)
+        .collect()
+}
+
+/// Collect the visual pieces for a given graph Neighborhood
+fn collect_pieces(state: &graph::Neighborhood) -> HashMap<board::Coordinates, VisualPiece> {
     // Map all the board pieces
     let mut pieces: HashMap<board::Coordinates, VisualPiece> = state
         .board
         .pieces
         .iter()
-        .map(|piece| {
+        .enumerate()
+        .map(|(order, piece)| {
             (
                 piece.position,
                 VisualPiece {
@@ -59,7 +67,8 @@ fn collect_pieces(state: &graph::Node) -> HashMap<board::Coordinates, VisualPiec
                     offset_range: VisualRange2D::zero(),
                     drag_moves: Vec::new(),
                     highlighted: false,
-                    color: get_color(&piece.size),
+                    kind: PieceKind::of(&piece.size),
+                    order,
                 },
             )
         })
@@ -107,8 +116,8 @@ fn collect_pieces(state: &graph::Node) -> HashMap<board::Coordinates, VisualPiec
             }
         }
         drag_moves.push(DragMove {
-            _slide_move: *slide_move,
-            resulting_id: edge.neighbor,
+            slide_move: *slide_move,
+            resulting_index: edge.neighbor,
             target_area,
         })
     }
@@ -124,6 +133,41 @@ pub type VisualRange2D = euclid::SideOffsets2D<f64, VisualBoard>;
 pub type VisualBox2D = euclid::Box2D<f64, VisualBoard>;
 pub type VisualRect = euclid::Rect<f64, VisualBoard>;
 
+/// How long a released piece takes to return to its place, easing back
+const SNAP_BACK_DURATION: f64 = 0.15;
+
+/// How long a released piece takes to return to its place, springing back
+const SPRING_BACK_DURATION: f64 = 0.45;
+
+/// An easing that settles like a damped spring, it swings past the end before it comes to rest
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// How many times it swings past the end and back, over the whole animation
+    pub oscillations: f64,
+    /// How fast the swinging dies out, higher values overshoot less
+    pub damping: f64,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            oscillations: 1.5,
+            damping: 6.0,
+        }
+    }
+}
+
+impl EasingFunction for Spring {
+    fn y(&self, x: f64) -> f64 {
+        let oscillation = |x: f64| {
+            (-self.damping * x).exp() * (std::f64::consts::TAU * self.oscillations * x).cos()
+        };
+        // The swinging that would remain at the end is taken out gradually, so that the spring
+        // comes to rest exactly at the end instead of jumping there
+        1.0 - oscillation(x) + x * oscillation(1.0)
+    }
+}
+
 /// Coordinates that implement CanTween
 #[derive(Clone, Copy, Default)]
 pub struct AnimatableOffset(pub VisualOffset);
@@ -144,14 +188,37 @@ pub struct Animation {
 /// The metadata needed to run an Animation
 struct AnimationExecution {
     pub animation: Animation,
+    /// The start, on the animation clock of the board
     pub start_time: Option<Duration>,
     pub done_sender: Option<oneshot::Sender<()>>,
+    /// The offsets at the previous and the latest step, with a fixed timestep
+    pub stepped_offsets: [VisualOffset; 2],
 }
 
 struct Drag {
     pub target: board::Coordinates,
     /// Where this dragging started, stored as size so that it can be used for arithmetic
     pub start_coordinates: Option<VisualCoordinates>,
+    /// The move one step past where the piece can go, if the pointer pulls the piece that far
+    pub overshoot: Option<SlideMove>,
+}
+
+/// How far past where a piece can go the pointer must pull it, to push against what stops it
+const OVERSHOOT_DISTANCE: f64 = 0.5;
+
+/// The most that the animations advance by from one frame to the next, a frame that comes later
+/// after a stutter or from a tab in the background continues them from where they were
+const MAX_FRAME_DELTA: Duration = Duration::from_millis(100);
+
+/// How long the pieces flash, after they stopped another piece that was pushed against them
+const FLASH_DURATION: Duration = Duration::from_millis(400);
+
+/// Pieces that flash, as they stopped another piece
+struct Flash {
+    pieces: Vec<board::Coordinates>,
+    start_time: Option<Duration>,
+    /// How bright the flash still is, it fades from 1 to 0
+    brightness: f64,
 }
 
 pub enum DragEndResult {
@@ -159,24 +226,20 @@ pub enum DragEndResult {
     Invalid,
     /// In case that a drag did not result in a move
     None,
+    /// In case that a drag pushed the piece against what stops it, with the move one step too far
+    Pushed(SlideMove),
     /// In case that a drag resulted in move
     Some(DragMove),
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct DragMove {
-    pub _slide_move: SlideMove,
-    pub resulting_id: BoardId,
+    pub slide_move: SlideMove,
+    /// The node of the resulting state, in the graph of the displayed node
+    pub resulting_index: NodeIndex,
     pub target_area: VisualBox2D,
 }
 
-/// An optional dynamic element to a game board
-enum DynamicElement {
-    None,
-    Animation(AnimationExecution),
-    Drag(Drag),
-}
-
 /// A visual representation of a game piece
 pub struct VisualPiece {
     pub rect: VisualRect,
@@ -184,14 +247,35 @@ pub struct VisualPiece {
     pub offset_range: VisualRange2D,
     pub drag_moves: Vec<DragMove>,
     pub highlighted: bool,
-    pub color: String,
+    /// The kind of the piece by its size, which determines its color. It's resolved once when the
+    /// pieces are collected, not for every frame.
+    pub kind: PieceKind,
+    /// The place of the piece among the board's sorted pieces, the layout notation letters them in this order
+    pub order: usize,
 }
 
 /// A visual representation of a gameboard
 pub struct VisualBoard {
     pub size: VisualSize,
     pub pieces: HashMap<board::Coordinates, VisualPiece>,
-    dynamic_element: DynamicElement,
+    /// The cells that aren't normal
+    pub cells: Vec<(board::Coordinates, board::Cell)>,
+    /// The place of the checkpoint to reach next, and the kind of piece that must reach it
+    pub checkpoint: Option<(VisualRect, PieceKind)>,
+    /// Released pieces spring back to their place with this spring, instead of easing back
+    pub spring: Option<Spring>,
+    flash: Option<Flash>,
+    /// The running animations, at most one per piece
+    animations: HashMap<board::Coordinates, AnimationExecution>,
+    /// The time of the animation clock, it only runs while there are animations
+    time: Duration,
+    /// The timestamp of the previous frame, if animations ran in it
+    previous_frame: Option<Duration>,
+    /// The step that the animations advance by, if they advance by fixed steps
+    fixed_timestep: Option<Duration>,
+    /// The time of the animation clock that the animations were last stepped to, with a fixed step
+    stepped_time: Duration,
+    drag: Option<Drag>,
 }
 
 impl AnimatableOffset {
@@ -223,11 +307,31 @@ impl CanTween for AnimatableOffset {
 }
 
 impl VisualBoard {
-    pub fn new(state: &graph::Node) -> Self {
+    pub fn new(state: &graph::Neighborhood) -> Self {
         Self {
             size: VisualSize::new(state.board.size.x as f64, state.board.size.y as f64),
             pieces: collect_pieces(state),
-            dynamic_element: DynamicElement::None,
+            cells: collect_cells(&state.board),
+            checkpoint: state.board.progress.next().map(|checkpoint| {
+                (
+                    VisualRect::new(
+                        VisualCoordinates::new(
+                            checkpoint.position.x as f64,
+                            checkpoint.position.y as f64,
+                        ),
+                        VisualSize::new(checkpoint.size.x as f64, checkpoint.size.y as f64),
+                    ),
+                    PieceKind::of(&checkpoint.size),
+                )
+            }),
+            flash: None,
+            spring: None,
+            animations: HashMap::new(),
+            time: Duration::ZERO,
+            previous_frame: None,
+            fixed_timestep: None,
+            stepped_time: Duration::ZERO,
+            drag: None,
         }
     }
 
@@ -235,7 +339,16 @@ impl VisualBoard {
         Self {
             size: VisualSize::zero(),
             pieces: Default::default(),
-            dynamic_element: DynamicElement::None,
+            cells: Vec::new(),
+            checkpoint: None,
+            flash: None,
+            spring: None,
+            animations: HashMap::new(),
+            time: Duration::ZERO,
+            previous_frame: None,
+            fixed_timestep: None,
+            stepped_time: Duration::ZERO,
+            drag: None,
         }
     }
 
@@ -252,6 +365,33 @@ impl VisualBoard {
         }
     }
 
+    /// Highlight the draggable piece under the pointer, unless a drag or animation is ongoing
+    /// @returns the draggable piece under the pointer, if there is one
+    pub fn hover(&mut self, target: VisualCoordinates) -> Option<board::Coordinates> {
+        let piece: Option<board::Coordinates> = self
+            .pieces
+            .iter()
+            .find(|(_, piece)| piece.rect.contains(target) && !piece.drag_moves.is_empty())
+            .map(|(base_coordinates, _)| *base_coordinates);
+
+        if self.drag.is_none() && self.animations.is_empty() {
+            self.highlight(&None);
+            self.highlight(&piece);
+        }
+        piece
+    }
+
+    /// Highlight only the given piece, unless a drag is ongoing
+    pub fn highlight_only(&mut self, target: Option<board::Coordinates>) {
+        if self.drag.is_some() {
+            return;
+        }
+        self.highlight(&None);
+        if target.is_some_and(|target| self.pieces.contains_key(&target)) {
+            self.highlight(&target);
+        }
+    }
+
     /// Start dragging the targeted piece, returns true if this piece can be dragged
     pub fn start_drag(&mut self, target: VisualCoordinates) -> bool {
         // Find if the cursor is targeting a piece, and if that piece can be dragged
@@ -266,26 +406,40 @@ impl VisualBoard {
                 self.highlight(&None);
             }
             Some(base_coordinates) => {
-                // If we are currently running an animation, then clear it
-                self.clear_animation();
+                // Stop animating the grabbed piece, and any previews that would distract from the drag
+                self.stop_animation(&base_coordinates);
+                let previews: Vec<board::Coordinates> = self
+                    .animations
+                    .iter()
+                    .filter(|(_, execution)| {
+                        matches!(execution.animation.repeat, AnimationRepeatBehavior::Loop)
+                    })
+                    .map(|(target, _)| *target)
+                    .collect();
+                previews
+                    .iter()
+                    .for_each(|target| self.stop_animation(target));
                 self.highlight(&Some(base_coordinates));
 
                 // Start dragging
-                self.dynamic_element = DynamicElement::Drag(Drag {
+                self.drag = Some(Drag {
                     target: base_coordinates,
                     start_coordinates: None,
+                    overshoot: None,
                 });
             }
         }
         piece.is_some()
     }
 
+    /// The base coordinates of the piece that is being dragged, if any
+    pub fn dragged_piece(&self) -> Option<board::Coordinates> {
+        self.drag.as_ref().map(|drag| drag.target)
+    }
+
     pub fn stop_drag(&mut self) -> DragEndResult {
-        let target = {
-            let DynamicElement::Drag(drag) = &self.dynamic_element else {
-                return DragEndResult::Invalid;
-            };
-            drag.target
+        let Some(target) = self.dragged_piece() else {
+            return DragEndResult::Invalid;
         };
         self.highlight(&None);
 
@@ -299,23 +453,29 @@ impl VisualBoard {
                 .target_area
                 .contains(piece.visual_offset.to_point())
             {
+                self.drag = None;
                 return DragEndResult::Some(*possible_move);
             }
         }
 
         // Move was not made, reset the tile to its home
+        let overshoot = self.drag.as_ref().and_then(|drag| drag.overshoot);
+        self.start_post_drag_animation();
+        match overshoot {
+            Some(slide_move) => DragEndResult::Pushed(slide_move),
+            None => DragEndResult::None,
+        }
+    }
+
+    /// Abandon the current drag, which returns the piece to its home
+    pub fn cancel_drag(&mut self) {
         self.start_post_drag_animation();
-        DragEndResult::None
     }
 
     fn start_post_drag_animation(&mut self) {
-        // Find the piece that needs returning
-        let target = {
-            // If we are not dragging, then early exit
-            let DynamicElement::Drag(drag) = &mut self.dynamic_element else {
-                return;
-            };
-            drag.target
+        // Find the piece that needs returning, if we are not dragging, then early exit
+        let Some(Drag { target, .. }) = self.drag.take() else {
+            return;
         };
 
         self.highlight(&None);
@@ -328,18 +488,25 @@ impl VisualBoard {
         );
         let to = AnimatableOffset::zero();
 
-        let animation_done = self.animate(Some(Animation {
-            sequence: keyframes![(from, 0.0, keyframe::functions::EaseInOutCubic), (to, 0.15)],
+        let sequence = match self.spring {
+            Some(spring) => keyframes![(from, 0.0, spring), (to, SPRING_BACK_DURATION)],
+            None => keyframes![
+                (from, 0.0, keyframe::functions::EaseInOutCubic),
+                (to, SNAP_BACK_DURATION)
+            ],
+        };
+        let animation_done = self.animate(Animation {
+            sequence,
             target,
             repeat: AnimationRepeatBehavior::None,
-        }));
+        });
         drop(animation_done);
     }
 
     /// Apply drag offset to piece, returns true if dragging this piece has any potential effect
     pub fn drag(&mut self, coordinates: VisualCoordinates) -> bool {
         // If we are not dragging, then early exit
-        let DynamicElement::Drag(drag) = &mut self.dynamic_element else {
+        let Some(drag) = &mut self.drag else {
             return false;
         };
 
@@ -355,6 +522,7 @@ impl VisualBoard {
         let start = drag.start_coordinates.get_or_insert(coordinates);
         let mut offset = coordinates - *start;
         let range = &piece.offset_range;
+        drag.overshoot = find_overshoot(drag.target, offset, range);
 
         // Limit movement to free spaces, range determined by possible moves
         offset.x = offset.x.clamp(range.left, range.right);
@@ -371,70 +539,152 @@ impl VisualBoard {
         true
     }
 
-    pub fn animate(&mut self, animation: Option<Animation>) -> oneshot::Receiver<()> {
-        // Clear animation, if there is currently one
-        self.clear_animation();
+    /// Animate a piece, this replaces any animation that was already running for the same piece
+    pub fn animate(&mut self, animation: Animation) -> oneshot::Receiver<()> {
+        let target = animation.target;
+        self.stop_animation(&target);
+
+        // An animated piece can't be dragged at the same time
+        if self.dragged_piece() == Some(target) {
+            self.drag = None;
+        }
 
-        // (Re)set the animation
         let (sender, receiver) = oneshot::channel();
-        if let Some(animation) = animation {
-            self.dynamic_element = DynamicElement::Animation(AnimationExecution {
+        self.animations.insert(
+            target,
+            AnimationExecution {
+                stepped_offsets: [animation.sequence.now().0; 2],
                 animation,
                 start_time: None,
                 done_sender: Some(sender),
-            });
-        } else {
-            self.dynamic_element = DynamicElement::None;
-        }
+            },
+        );
         receiver
     }
 
-    fn clear_animation(&mut self) {
-        if let DynamicElement::Animation(animation) = &mut self.dynamic_element {
-            // Reset the visual board
-            self.pieces
-                .iter_mut()
-                .for_each(|(_, piece)| piece.visual_offset = VisualOffset::zero());
+    /// Flash pieces in the color of bad moves, this replaces a flash that was still running
+    pub fn flash(&mut self, pieces: Vec<board::Coordinates>) {
+        self.flash = (!pieces.is_empty()).then_some(Flash {
+            pieces,
+            start_time: None,
+            brightness: 1.0,
+        });
+    }
 
-            resolve_if_sender(&mut animation.done_sender)
-        };
+    /// How bright a piece flashes, zero if it doesn't
+    pub fn flash_brightness(&self, piece: &board::Coordinates) -> f64 {
+        self.flash
+            .as_ref()
+            .filter(|flash| flash.pieces.contains(piece))
+            .map_or(0.0, |flash| flash.brightness)
     }
 
-    pub fn update_to(&mut self, timestamp: Duration) -> Result<(), ()> {
-        // Decompose the current AnimationExecution, if any
-        let DynamicElement::Animation(AnimationExecution {
-            animation,
-            start_time,
-            done_sender,
-        }) = &mut self.dynamic_element
-        else {
-            // We are not running an animation
-            return Err(());
+    /// Stop all animations, and return their pieces to rest
+    pub fn stop_animations(&mut self) {
+        let targets: Vec<board::Coordinates> = self.animations.keys().copied().collect();
+        targets
+            .iter()
+            .for_each(|target| self.stop_animation(target));
+    }
+
+    /// Stop the animation of a piece, and return it to rest
+    pub fn stop_animation(&mut self, target: &board::Coordinates) {
+        if let Some(mut execution) = self.animations.remove(target) {
+            // Reset the visual piece
+            if let Some(piece) = self.pieces.get_mut(target) {
+                piece.visual_offset = VisualOffset::zero();
+            }
+            resolve_if_sender(&mut execution.done_sender)
         };
+    }
 
-        // Update the animated value
-        let diff = timestamp - *start_time.get_or_insert(timestamp);
-        let excess_time = animation.sequence.advance_to(diff.as_secs_f64());
-        if let Some(piece) = self.pieces.get_mut(&animation.target) {
-            piece.visual_offset = animation.sequence.now().0;
-        }
+    /// Advance the animations in fixed steps instead of by the time between frames, the pieces are
+    /// drawn between the last two steps. Without a step, they advance by the time between frames.
+    pub fn set_fixed_timestep(&mut self, step: Option<Duration>) {
+        self.fixed_timestep = step.filter(|step| !step.is_zero());
+        self.stepped_time = self.time;
+    }
 
-        // If the animation is finished, handle the looping behavior
-        if excess_time > 0.0 {
-            match animation.repeat {
-                AnimationRepeatBehavior::Loop => {
-                    // TODO(Menno 08.07.2025) For now we discard the excess time, which might result
-                    //  in a stutter if the animation is not at rest at the loop end/start.
-                    *start_time = None;
+    /// Advance all animations to the given frame timestamp. Frames that come long after the
+    /// previous one only advance them by MAX_FRAME_DELTA, so that they don't jump ahead.
+    /// @returns Ok if any animation is still running
+    pub fn update_to(&mut self, timestamp: Duration) -> Result<(), ()> {
+        let delta = self.previous_frame.map_or(Duration::ZERO, |previous| {
+            timestamp.saturating_sub(previous).min(MAX_FRAME_DELTA)
+        });
+        self.previous_frame = Some(timestamp);
+        self.time += delta;
+
+        match self.fixed_timestep {
+            None => self.step_animations(self.time),
+            Some(step) => {
+                while self.stepped_time + step <= self.time {
+                    self.stepped_time += step;
+                    self.step_animations(self.stepped_time);
                 }
-                AnimationRepeatBehavior::None => {
-                    resolve_if_sender(done_sender);
-
-                    self.dynamic_element = DynamicElement::None;
-                    return Err(());
+                // Draw the pieces between their last two steps, by how far the time is into the next step
+                let progress = (self.time - self.stepped_time).as_secs_f64() / step.as_secs_f64();
+                for (target, execution) in &self.animations {
+                    if let Some(piece) = self.pieces.get_mut(target) {
+                        let [previous, latest] = execution.stepped_offsets;
+                        piece.visual_offset = previous.lerp(latest, progress);
+                    }
                 }
             }
         }
+
+        // The flash fades out
+        if let Some(flash) = &mut self.flash {
+            let elapsed = self.time - *flash.start_time.get_or_insert(self.time);
+            flash.brightness = 1.0 - elapsed.as_secs_f64() / FLASH_DURATION.as_secs_f64();
+            if flash.brightness <= 0.0 {
+                self.flash = None;
+            }
+        }
+
+        if self.animations.is_empty() && self.flash.is_none() {
+            // The next frame starts a new run of animations, it shouldn't count the idle time
+            self.previous_frame = None;
+            return Err(());
+        }
         Ok(())
     }
+
+    /// Advance the animations to a time of the animation clock, and finish those that end by then
+    fn step_animations(&mut self, time: Duration) {
+        let pieces = &mut self.pieces;
+        self.animations.retain(|target, execution| {
+            let AnimationExecution {
+                animation,
+                start_time,
+                done_sender,
+                stepped_offsets,
+            } = execution;
+
+            // Update the animated value
+            let diff = time - *start_time.get_or_insert(time);
+            let excess_time = animation.sequence.advance_to(diff.as_secs_f64());
+            let offset = animation.sequence.now().0;
+            *stepped_offsets = [stepped_offsets[1], offset];
+            if let Some(piece) = pieces.get_mut(target) {
+                piece.visual_offset = offset;
+            }
+
+            // If the animation is finished, handle the looping behavior
+            if excess_time > 0.0 {
+                match animation.repeat {
+                    AnimationRepeatBehavior::Loop => {
+                        // TODO(Menno 08.07.2025) For now we discard the excess time, which might result
+                        //  in a stutter if the animation is not at rest at the loop end/start.
+                        *start_time = None;
+                    }
+                    AnimationRepeatBehavior::None => {
+                        resolve_if_sender(done_sender);
+                        return false;
+                    }
+                }
+            }
+            true
+        });
+    }
 }
@@ -0,0 +1,42 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::utils::get_timestamp;
+#[cfg(test)]
+use std::cell::Cell;
+use std::time::Duration;
+
+/// A source of timestamps that drives the animations of the views
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+/// The browser's high resolution clock, this is the same clock as the animation frame timestamps
+pub struct PerformanceClock;
+
+impl Clock for PerformanceClock {
+    fn now(&self) -> Duration {
+        get_timestamp().expect("Unable to get current time")
+    }
+}
+
+/// A clock that only advances when told to, so that animations can be stepped through deterministically
+#[cfg(test)]
+#[derive(Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn advance(&self, step: Duration) {
+        self.now.set(self.now.get() + step);
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
@@ -0,0 +1,138 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{BoardId, Coordinates};
+use crate::views::board_view::visual_board::DragMove;
+use crate::views::clock::Clock;
+use crate::views::frame_scheduler::FrameScheduler;
+use crate::views::moves_view::MoveInfo;
+use std::cell::RefCell;
+use std::mem::discriminant;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+/// A change that one view requests from the other views
+pub enum Command {
+    /// Jump straight to a state
+    JumpTo(BoardId),
+    /// Preview a move on the board and in the graph, or stop previewing
+    PreviewMove(Option<MoveInfo>),
+    /// Point out the move that leads to a state
+    LinkState(Option<BoardId>),
+    /// Highlight a piece on the board, or stop highlighting
+    HighlightPiece(Option<Coordinates>),
+    /// Show the moves of a piece that is hovered on the board
+    RevealPiece(Option<Coordinates>),
+    /// Animate a move and make it
+    DoMove(MoveInfo),
+    /// A piece was dragged into a new position, the board already shows it
+    DragMove(DragMove),
+    Restart,
+    /// Step back to the previous state
+    Undo,
+    /// Copy the active state to the clipboard, in notation
+    CopyPosition,
+    /// Point out the best move from the active state
+    ShowHint,
+    /// Switch to the mirror image of the active state
+    FlipBoard,
+    /// Auto-solve started making moves for the player
+    AutoSolveStarted,
+    /// Show a state of the history, while the player scrubs through it
+    ScrubHistory(usize),
+    /// Continue playing from a state of the history, the next move branches off there
+    ResumeFromHistory(usize),
+    /// Switch to another line of the history, and step to its end
+    SwitchBranch(usize),
+}
+
+impl Command {
+    /// Whether only the latest command of this kind matters, e.g. for hovering
+    fn is_superseded_by_latest(&self) -> bool {
+        matches!(
+            self,
+            Command::PreviewMove(_)
+                | Command::LinkState(_)
+                | Command::HighlightPiece(_)
+                | Command::RevealPiece(_)
+                | Command::ScrubHistory(_)
+        )
+    }
+}
+
+/// Collects the commands of a single frame, so that they are applied at once
+#[derive(Default)]
+pub struct CommandBatch {
+    commands: Vec<Command>,
+}
+
+impl CommandBatch {
+    /// Add a command, a hover-like command replaces the previous command of the same kind
+    pub fn push(&mut self, command: Command) {
+        if command.is_superseded_by_latest() {
+            // The command takes the place of the one it replaces, relative to the other kinds
+            let kind = discriminant(&command);
+            if let Some(queued) = self
+                .commands
+                .iter_mut()
+                .find(|queued| discriminant(*queued) == kind)
+            {
+                *queued = command;
+                return;
+            }
+        }
+        self.commands.push(command);
+    }
+
+    /// Take all commands in the order that they were pushed, leaving the batch empty
+    pub fn take(&mut self) -> Vec<Command> {
+        std::mem::take(&mut self.commands)
+    }
+}
+
+/// The callback type that receives the commands of a frame
+pub type OnCommandsCb = dyn FnMut(Vec<Command>);
+
+/// Carries the commands of the views to the one who applies them, at the next animation frame.
+/// Views publish commands while handling their own events, without borrowing any of the other views.
+/// Clones share the same batch.
+#[derive(Clone)]
+pub struct CommandBus {
+    batch: Rc<RefCell<CommandBatch>>,
+    scheduler: Rc<RefCell<FrameScheduler>>,
+}
+
+impl CommandBus {
+    pub fn new(clock: Rc<dyn Clock>, mut on_commands_cb: Box<OnCommandsCb>) -> Self {
+        let batch = Rc::new(RefCell::new(CommandBatch::default()));
+        let batch_clone = batch.clone();
+        let scheduler = FrameScheduler::new(
+            clock,
+            Box::new(move |_frame| {
+                let commands = batch_clone.borrow_mut().take();
+                on_commands_cb(commands);
+            }),
+        );
+        Self {
+            batch,
+            scheduler: Rc::new(RefCell::new(scheduler)),
+        }
+    }
+
+    pub fn publish(&self, command: Command) {
+        self.batch.borrow_mut().push(command);
+        self.scheduler
+            .borrow_mut()
+            .schedule()
+            .expect("Failed to schedule commands");
+    }
+
+    /// Drop the pending commands, without applying them
+    pub fn cancel(&self) -> Result<(), JsValue> {
+        self.batch.borrow_mut().take();
+        self.scheduler.borrow_mut().cancel()
+    }
+}
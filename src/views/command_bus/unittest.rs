@@ -0,0 +1,27 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::command_bus::{Command, CommandBatch};
+
+#[test]
+fn test_batch() {
+    let mut batch = CommandBatch::default();
+    batch.push(Command::LinkState(Some(1)));
+    batch.push(Command::JumpTo(2));
+    batch.push(Command::Restart);
+    batch.push(Command::LinkState(None));
+    batch.push(Command::JumpTo(3));
+
+    // Only the latest hover counts, in the place of the first one, and every jump is kept in order
+    let commands = batch.take();
+    assert!(matches!(
+        commands[..],
+        [
+            Command::LinkState(None),
+            Command::JumpTo(2),
+            Command::Restart,
+            Command::JumpTo(3)
+        ]
+    ));
+    assert!(batch.take().is_empty());
+}
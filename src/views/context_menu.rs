@@ -0,0 +1,123 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::event_listener::EventListener;
+use crate::views::utils::get_document;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlDivElement, HtmlElement, KeyboardEvent, Node};
+
+/// An action that the player picked from the menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    Restart,
+    Undo,
+    /// Copy the position in notation, e.g. "CFFJ/CFFJ/BEEI/BDGI/A2H 4x5 2x2@B1"
+    CopyPosition,
+    /// Point out the best move
+    ShowHint,
+    /// Switch to the board's mirror image
+    Flip,
+}
+
+/// The items of the menu, in the order that they are listed
+const ITEMS: [(MenuAction, &str); 5] = [
+    (MenuAction::Restart, "Restart"),
+    (MenuAction::Undo, "Undo"),
+    (MenuAction::CopyPosition, "Copy position"),
+    (MenuAction::ShowHint, "Show hint"),
+    (MenuAction::Flip, "Flip board"),
+];
+
+pub type OnMenuActionCb = dyn FnMut(MenuAction);
+
+/// A small menu that floats over the page, it closes when an item is picked or when the player
+/// presses anywhere else
+pub struct ContextMenu {
+    menu_div: HtmlDivElement,
+    /// The listeners on the document, that close the menu
+    document_listeners: Vec<EventListener>,
+}
+
+impl ContextMenu {
+    pub fn new(on_action_cb: Box<OnMenuActionCb>) -> Result<Self, JsValue> {
+        let document = get_document()?;
+        let menu_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
+        menu_div.class_list().add_2("context-menu", "glassy")?;
+        menu_div.set_attribute("role", "menu")?;
+        menu_div.set_hidden(true);
+
+        let on_action_cb = Rc::new(RefCell::new(on_action_cb));
+        for (action, label) in ITEMS {
+            let item: HtmlElement = document.create_element("button")?.dyn_into()?;
+            item.class_list().add_1("context-menu-item")?;
+            item.set_attribute("role", "menuitem")?;
+            item.set_text_content(Some(label));
+            let menu_div_clone = menu_div.clone();
+            let on_action_cb = on_action_cb.clone();
+            item.set_onclick(Some(
+                Closure::<dyn FnMut()>::new(move || {
+                    menu_div_clone.set_hidden(true);
+                    (on_action_cb.borrow_mut())(action);
+                })
+                .into_js_value()
+                .unchecked_ref(),
+            ));
+            menu_div.append_child(&item)?;
+        }
+
+        let menu_div_clone = menu_div.clone();
+        let on_pointerdown = EventListener::new(
+            &document,
+            "pointerdown",
+            move |event: web_sys::PointerEvent| {
+                let target = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<Node>().ok());
+                if !menu_div_clone.contains(target.as_ref()) {
+                    menu_div_clone.set_hidden(true);
+                }
+            },
+        )?;
+        let menu_div_clone = menu_div.clone();
+        let on_keydown = EventListener::new(&document, "keydown", move |event: KeyboardEvent| {
+            if event.key() == "Escape" {
+                menu_div_clone.set_hidden(true);
+            }
+        })?;
+
+        document
+            .body()
+            .ok_or(JsValue::from_str("Document has no body"))?
+            .append_child(&menu_div)?;
+        Ok(Self {
+            menu_div,
+            document_listeners: vec![on_pointerdown, on_keydown],
+        })
+    }
+
+    /// Take the menu off the page, and stop listening to the document
+    pub fn remove(&mut self) {
+        self.menu_div.remove();
+        self.document_listeners.clear();
+    }
+
+    /// Open the menu with its corner at a point of the viewport, in CSS pixels
+    pub fn open(&self, x: f64, y: f64) -> Result<(), JsValue> {
+        let style = self.menu_div.style();
+        style.set_property("left", &format!("{x}px"))?;
+        style.set_property("top", &format!("{y}px"))?;
+        self.menu_div.set_hidden(false);
+        // Let keyboard users pick an item right away
+        if let Some(first_item) = self
+            .menu_div
+            .first_element_child()
+            .and_then(|item| item.dyn_into::<HtmlElement>().ok())
+        {
+            first_item.focus()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{AddEventListenerOptions, EventTarget};
+
+/// An event listener that stays registered for as long as it lives, it is removed from its target
+/// when dropped
+pub struct EventListener {
+    target: EventTarget,
+    event_type: &'static str,
+    closure: Closure<dyn FnMut(JsValue)>,
+}
+
+impl EventListener {
+    pub fn new<TEvent: JsCast + 'static>(
+        target: &EventTarget,
+        event_type: &'static str,
+        handler: impl FnMut(TEvent) + 'static,
+    ) -> Result<Self, JsValue> {
+        Self::with_options(target, event_type, &AddEventListenerOptions::new(), handler)
+    }
+
+    pub fn with_options<TEvent: JsCast + 'static>(
+        target: &EventTarget,
+        event_type: &'static str,
+        options: &AddEventListenerOptions,
+        mut handler: impl FnMut(TEvent) + 'static,
+    ) -> Result<Self, JsValue> {
+        let closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            handler(event.unchecked_into());
+        });
+        target.add_event_listener_with_callback_and_add_event_listener_options(
+            event_type,
+            closure.as_ref().unchecked_ref(),
+            options,
+        )?;
+        Ok(Self {
+            target: target.clone(),
+            event_type,
+            closure,
+        })
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        if let Err(error) = self.target.remove_event_listener_with_callback(
+            self.event_type,
+            self.closure.as_ref().unchecked_ref(),
+        ) {
+            log::warn!("Failed to remove {} listener: {error:?}", self.event_type);
+        }
+    }
+}
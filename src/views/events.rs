@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{BoardId, SlideMove};
+use js_sys::{Function, Object, Reflect};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
+
+/// Something that happened in the game, which the hosting page may react to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    /// A move was made, leading to the given state
+    MoveMade(SlideMove, BoardId),
+    /// The active state changed, by a move or by a jump
+    StateChanged(BoardId),
+    /// A move reached the next checkpoint, the given number of checkpoints of all of them are reached
+    CheckpointReached {
+        state: BoardId,
+        reached: usize,
+        count: usize,
+    },
+    /// A move reached the solution
+    Solved(BoardId),
+    /// The game went back to the start, this isn't emitted when an ongoing move refuses the restart
+    Restart,
+    AutoSolveStarted,
+    AutoSolveStopped,
+    /// A replay moved to the state at a position in its recording, or started or stopped playing
+    ReplayProgress {
+        position: usize,
+        length: usize,
+        playing: bool,
+    },
+    /// The replay was closed, the game continues from the shown state
+    ReplayEnded,
+    /// The clipboard didn't hold a position that could be loaded
+    PasteRejected(PasteError),
+    /// A speedrun reached the solution, in a time that may be the best so far
+    SpeedrunFinished {
+        time: Duration,
+        best: bool,
+    },
+}
+
+/// Why a pasted position was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteError {
+    /// The clipboard couldn't be read, e.g. because the player didn't allow it
+    Unreadable,
+    /// The text isn't a position in notation or a layout
+    Invalid,
+    /// The position can't be reached in this puzzle
+    Unknown,
+    /// A move is ongoing, the game can't jump away from it
+    MoveOngoing,
+}
+
+impl fmt::Display for PasteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            PasteError::Unreadable => "Unable to read the clipboard",
+            PasteError::Invalid => "The clipboard doesn't hold a position",
+            PasteError::Unknown => "This position can't be reached in this puzzle",
+            PasteError::MoveOngoing => "Can't jump to a position while a move is ongoing",
+        };
+        write!(f, "{description}")
+    }
+}
+
+impl PasteError {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PasteError::Unreadable => "unreadable",
+            PasteError::Invalid => "invalid",
+            PasteError::Unknown => "unknown",
+            PasteError::MoveOngoing => "move_ongoing",
+        }
+    }
+}
+
+impl GameEvent {
+    /// The name of the event, as seen by JavaScript
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameEvent::MoveMade(..) => "move_made",
+            GameEvent::StateChanged(_) => "state_changed",
+            GameEvent::CheckpointReached { .. } => "checkpoint_reached",
+            GameEvent::Solved(_) => "solved",
+            GameEvent::Restart => "restart",
+            GameEvent::AutoSolveStarted => "auto_solve_started",
+            GameEvent::AutoSolveStopped => "auto_solve_stopped",
+            GameEvent::ReplayProgress { .. } => "replay_progress",
+            GameEvent::ReplayEnded => "replay_ended",
+            GameEvent::PasteRejected(_) => "paste_rejected",
+            GameEvent::SpeedrunFinished { .. } => "speedrun_finished",
+        }
+    }
+
+    /// Convert to a plain object like `{type: "move_made", state: "00c0ffee00c0ffee", move: "B2⮕B1"}`,
+    /// states are hex strings because JavaScript numbers can't hold every 64 bit BoardId
+    fn to_js(self) -> Result<JsValue, JsValue> {
+        let object = Object::new();
+        Reflect::set(&object, &"type".into(), &self.name().into())?;
+        let state = match self {
+            GameEvent::MoveMade(slide_move, state) => {
+                Reflect::set(&object, &"move".into(), &slide_move.to_string().into())?;
+                Some(state)
+            }
+            GameEvent::StateChanged(state) | GameEvent::Solved(state) => Some(state),
+            GameEvent::CheckpointReached {
+                state,
+                reached,
+                count,
+            } => {
+                Reflect::set(&object, &"reached".into(), &(reached as u32).into())?;
+                Reflect::set(&object, &"count".into(), &(count as u32).into())?;
+                Some(state)
+            }
+            GameEvent::ReplayProgress {
+                position,
+                length,
+                playing,
+            } => {
+                Reflect::set(&object, &"position".into(), &(position as u32).into())?;
+                Reflect::set(&object, &"length".into(), &(length as u32).into())?;
+                Reflect::set(&object, &"playing".into(), &playing.into())?;
+                None
+            }
+            GameEvent::PasteRejected(error) => {
+                Reflect::set(&object, &"reason".into(), &error.name().into())?;
+                None
+            }
+            GameEvent::SpeedrunFinished { time, best } => {
+                Reflect::set(
+                    &object,
+                    &"time".into(),
+                    &(time.as_secs_f64() * 1000.0).into(),
+                )?;
+                Reflect::set(&object, &"best".into(), &best.into())?;
+                None
+            }
+            _ => None,
+        };
+        if let Some(state) = state {
+            Reflect::set(&object, &"state".into(), &format!("{state:016x}").into())?;
+        }
+        Ok(object.into())
+    }
+}
+
+/// Passes game events to the callbacks that the hosting page subscribed,
+/// clones share the same subscriptions
+#[derive(Clone, Default)]
+pub struct Events {
+    listeners: Rc<RefCell<Vec<Function>>>,
+}
+
+impl Events {
+    pub fn subscribe(&self, callback: Function) {
+        self.listeners.borrow_mut().push(callback);
+    }
+
+    /// Forget all subscriptions
+    pub fn clear(&self) {
+        self.listeners.borrow_mut().clear();
+    }
+
+    /// Call the listeners asynchronously, so that they may call back into the game
+    pub fn emit(&self, event: GameEvent) {
+        if self.listeners.borrow().is_empty() {
+            return;
+        }
+        let listeners = self.listeners.clone();
+        spawn_local(async move {
+            let payload = match event.to_js() {
+                Ok(payload) => payload,
+                Err(error) => {
+                    log::warn!("Failed to convert {event:?} event: {error:?}");
+                    return;
+                }
+            };
+            // A listener may subscribe others, those only hear the next event
+            let listeners = listeners.borrow().clone();
+            for listener in listeners {
+                if let Err(error) = listener.call1(&JsValue::NULL, &payload) {
+                    log::warn!("Event listener failed on {}: {error:?}", event.name());
+                }
+            }
+        });
+    }
+}
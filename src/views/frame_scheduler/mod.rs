@@ -0,0 +1,169 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::views::clock::Clock;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::Window;
+
+/// The timing of an animation frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub timestamp: Duration,
+    /// The time since the previous frame, zero for the first frame after the scheduler was idle
+    pub delta: Duration,
+}
+
+pub type OnFrameCb = dyn FnMut(Frame);
+type OnFrameCbInternal = dyn FnMut(f64);
+
+/// Advances an animation by a frame
+/// @returns whether the animation continues, it is dropped otherwise
+pub type Ticker = dyn FnMut(Frame) -> bool;
+
+/// The animations that are running, by name
+#[derive(Default)]
+pub struct Animations {
+    tickers: Vec<(&'static str, Box<Ticker>)>,
+    previous_frame: Option<Duration>,
+}
+
+impl Animations {
+    /// Run an animation until its ticker stops it, this replaces a running animation of the same name
+    pub fn add(&mut self, name: &'static str, ticker: Box<Ticker>) {
+        self.tickers.retain(|(other, _ticker)| *other != name);
+        self.tickers.push((name, ticker));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tickers.is_empty()
+    }
+
+    /// Drive all animations through a frame. The tickers run without the animations borrowed, so that
+    /// they can start new animations, which replace those of the same name.
+    pub fn tick(animations: &RefCell<Animations>, timestamp: Duration) -> Frame {
+        let (frame, mut tickers) = {
+            let mut animations = animations.borrow_mut();
+            let delta = animations
+                .previous_frame
+                .map_or(Duration::ZERO, |previous| {
+                    timestamp.saturating_sub(previous)
+                });
+            animations.previous_frame = Some(timestamp);
+            (
+                Frame { timestamp, delta },
+                std::mem::take(&mut animations.tickers),
+            )
+        };
+        tickers.retain_mut(|(_name, ticker)| ticker(frame));
+
+        let mut animations = animations.borrow_mut();
+        let started = std::mem::replace(&mut animations.tickers, tickers);
+        for (name, ticker) in started {
+            animations.add(name, ticker);
+        }
+        if animations.is_empty() {
+            // The next frame starts a new run of animations, it shouldn't count the idle time
+            animations.previous_frame = None;
+        }
+        frame
+    }
+}
+
+struct Shared {
+    window: Window,
+    clock: Rc<dyn Clock>,
+    animations: RefCell<Animations>,
+    on_frame_closure: RefCell<Option<Closure<OnFrameCbInternal>>>,
+    frame_request_id: Cell<i32>,
+    frame_requested: Cell<bool>,
+}
+
+impl Shared {
+    fn request_frame(&self) -> Result<(), JsValue> {
+        if self.frame_requested.get() {
+            // A frame request is already pending.
+            return Ok(());
+        }
+        let on_frame_closure = self.on_frame_closure.borrow();
+        let on_frame_closure = on_frame_closure
+            .as_ref()
+            .expect("The frame closure is set on creation");
+        self.frame_request_id.set(
+            self.window
+                .request_animation_frame(on_frame_closure.as_ref().unchecked_ref())?,
+        );
+        self.frame_requested.set(true);
+        Ok(())
+    }
+}
+
+/// Requests animation frames, and keeps requesting them for as long as any animation runs
+pub struct FrameScheduler {
+    shared: Rc<Shared>,
+}
+
+impl FrameScheduler {
+    /// The frames are timestamped by the given clock, instead of by the browser's frame timestamp.
+    /// The callback is called after the animations have been driven through the frame.
+    pub fn new(clock: Rc<dyn Clock>, mut on_frame_cb: Box<OnFrameCb>) -> Self {
+        let shared = Rc::new(Shared {
+            window: web_sys::window().expect("Unable to access the window"),
+            clock,
+            animations: RefCell::new(Animations::default()),
+            on_frame_closure: RefCell::new(None),
+            frame_request_id: Cell::new(0),
+            frame_requested: Cell::new(false),
+        });
+
+        // The closure refers to the scheduler weakly, as the scheduler owns it
+        let shared_ref: Weak<Shared> = Rc::downgrade(&shared);
+        let on_frame_closure = Closure::new(move |_timestamp: f64| {
+            let Some(shared) = shared_ref.upgrade() else {
+                return;
+            };
+            shared.frame_requested.set(false);
+            let frame = Animations::tick(&shared.animations, shared.clock.now());
+            on_frame_cb(frame);
+            if !shared.animations.borrow().is_empty() {
+                shared.request_frame().expect("Couldn't schedule frame");
+            }
+        });
+        *shared.on_frame_closure.borrow_mut() = Some(on_frame_closure);
+
+        Self { shared }
+    }
+
+    /// The clock that timestamps the frames
+    pub fn clock(&self) -> &dyn Clock {
+        self.shared.clock.as_ref()
+    }
+
+    /// Request a single frame
+    pub fn schedule(&mut self) -> Result<(), JsValue> {
+        self.shared.request_frame()
+    }
+
+    /// Request frames until the ticker stops the animation, this replaces a running animation of the same name
+    pub fn animate(&mut self, name: &'static str, ticker: Box<Ticker>) -> Result<(), JsValue> {
+        self.shared.animations.borrow_mut().add(name, ticker);
+        self.shared.request_frame()
+    }
+
+    /// Cancel the requested frame and stop all animations
+    pub fn cancel(&mut self) -> Result<(), JsValue> {
+        *self.shared.animations.borrow_mut() = Animations::default();
+        if self.shared.frame_requested.replace(false) {
+            self.shared
+                .window
+                .cancel_animation_frame(self.shared.frame_request_id.get())?;
+        }
+        Ok(())
+    }
+}
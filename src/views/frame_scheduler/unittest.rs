@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::frame_scheduler::{Animations, Frame};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+fn ms(milliseconds: u64) -> Duration {
+    Duration::from_millis(milliseconds)
+}
+
+#[test]
+fn test_tick() {
+    let animations = Rc::new(RefCell::new(Animations::default()));
+    let frames = Rc::new(RefCell::new(Vec::new()));
+
+    // This animation runs for three frames
+    let frames_clone = frames.clone();
+    animations.borrow_mut().add(
+        "countdown",
+        Box::new(move |frame: Frame| {
+            frames_clone.borrow_mut().push(frame);
+            frames_clone.borrow().len() < 3
+        }),
+    );
+
+    assert_eq!(Animations::tick(&animations, ms(100)).delta, Duration::ZERO);
+    assert_eq!(Animations::tick(&animations, ms(116)).delta, ms(16));
+    assert!(!animations.borrow().is_empty());
+    Animations::tick(&animations, ms(150));
+    assert!(animations.borrow().is_empty());
+    assert_eq!(
+        frames
+            .borrow()
+            .iter()
+            .map(|frame| frame.delta)
+            .collect::<Vec<_>>(),
+        [Duration::ZERO, ms(16), ms(34)]
+    );
+
+    // The time that the scheduler was idle doesn't count
+    assert_eq!(
+        Animations::tick(&animations, ms(1000)).delta,
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_tickers_can_start_animations() {
+    let animations = Rc::new(RefCell::new(Animations::default()));
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+
+    let animations_clone = animations.clone();
+    let ticks_clone = ticks.clone();
+    animations.borrow_mut().add(
+        "first",
+        Box::new(move |_frame| {
+            ticks_clone.borrow_mut().push("first");
+            let ticks_clone = ticks_clone.clone();
+            animations_clone.borrow_mut().add(
+                "second",
+                Box::new(move |_frame| {
+                    ticks_clone.borrow_mut().push("second");
+                    false
+                }),
+            );
+            false
+        }),
+    );
+    // Adding an animation of the same name replaces it
+    animations
+        .borrow_mut()
+        .add("second", Box::new(|_frame| true));
+
+    Animations::tick(&animations, ms(0));
+    Animations::tick(&animations, ms(16));
+    assert_eq!(*ticks.borrow(), ["first", "second"]);
+    assert!(animations.borrow().is_empty());
+}
@@ -0,0 +1,171 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! The ghost of the best solve: while playing, the graph shows where the best solve of the same
+//! puzzle was after as many moves as the player made
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{BoardId, SlideMove};
+use crate::graph::Graph;
+use crate::record::{GameRecord, Step};
+use crate::views::utils::get_window;
+use std::time::Duration;
+use web_sys::Storage;
+
+/// The local storage key of a puzzle's best solve, followed by its start state in hex
+const STORAGE_KEY_PREFIX: &str = "wiggers-graaf-best-solve-";
+
+fn get_storage() -> Option<Storage> {
+    get_window().ok()?.local_storage().ok()?
+}
+
+/// Whether a solve beats the best one, by taking fewer steps or the same steps in less time
+fn is_better(solve: &GameRecord, best: Option<&GameRecord>) -> bool {
+    best.is_none_or(|best| {
+        (solve.entries.len(), solve.duration()) < (best.entries.len(), best.duration())
+    })
+}
+
+/// The player's attempt at solving the puzzle, from the start or from the last restart
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Attempt {
+    /// The attempt starts at the player's first move
+    Ready,
+    Playing {
+        record: GameRecord,
+        epoch: Duration,
+    },
+    /// The player jumped or got help, the attempt can't become the best solve
+    Invalidated,
+}
+
+/// The best solve of a puzzle, played through the graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BestSolve {
+    record: GameRecord,
+    /// The start state, followed by the state after each move
+    states: Vec<BoardId>,
+}
+
+impl BestSolve {
+    fn new(record: GameRecord, graph: &Graph) -> Option<Self> {
+        let states = record.replay(graph).ok()?;
+        Some(Self { record, states })
+    }
+
+    /// Load the best solve of the puzzle that starts at a state, if there is one and it fits the graph
+    fn load(start: BoardId, graph: &Graph) -> Option<Self> {
+        let stored = get_storage()?
+            .get_item(&format!("{STORAGE_KEY_PREFIX}{start:x}"))
+            .ok()??;
+        Self::new(stored.parse().ok()?, graph)
+    }
+
+    fn save(&self) {
+        let Some(storage) = get_storage() else {
+            return;
+        };
+        let key = format!("{STORAGE_KEY_PREFIX}{:x}", self.record.start);
+        if let Err(error) = storage.set_item(&key, &self.record.to_string()) {
+            log::warn!("Failed to store best solve: {error:?}");
+        }
+    }
+
+    /// Where this solve was after a number of moves, it stays at the solution once it got there
+    fn state_after(&self, moves: usize) -> BoardId {
+        self.states[moves.min(self.states.len() - 1)]
+    }
+}
+
+/// Follows the player's attempt, and keeps the best solve to compare it with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost {
+    attempt: Attempt,
+    /// The start state of the puzzle that the best solve was loaded for
+    puzzle: BoardId,
+    best: Option<BestSolve>,
+}
+
+impl Ghost {
+    /// Get ready for an attempt at the puzzle that starts at a state
+    pub fn new(start: BoardId, graph: &Graph) -> Self {
+        Self {
+            attempt: Attempt::Ready,
+            puzzle: start,
+            best: BestSolve::load(start, graph),
+        }
+    }
+
+    /// Count a move of the attempt, only the player's own moves start it
+    pub fn record_move(
+        &mut self,
+        graph: &Graph,
+        now: Duration,
+        previous_state: BoardId,
+        slide_move: SlideMove,
+        may_start: bool,
+    ) {
+        if self.attempt == Attempt::Ready && may_start {
+            if self.puzzle != previous_state {
+                // The game didn't start where the puzzle does, e.g. when it started from a permalink
+                self.puzzle = previous_state;
+                self.best = BestSolve::load(previous_state, graph);
+            }
+            self.attempt = Attempt::Playing {
+                record: GameRecord::new(previous_state),
+                epoch: now,
+            };
+        }
+        if let Attempt::Playing { record, epoch } = &mut self.attempt {
+            record.push(now.saturating_sub(*epoch), Step::Move(slide_move));
+        }
+    }
+
+    /// End the attempt at the solution, it becomes the best solve if it beats the previous one.
+    /// Its ghost shows from the next attempt on.
+    /// @returns whether the attempt is the new best solve, which should be saved
+    pub fn finish(&mut self, graph: &Graph) -> bool {
+        let Attempt::Playing { record, .. } =
+            std::mem::replace(&mut self.attempt, Attempt::Invalidated)
+        else {
+            return false;
+        };
+        if !is_better(&record, self.best.as_ref().map(|best| &best.record)) {
+            return false;
+        }
+        let Some(best) = BestSolve::new(record, graph) else {
+            return false;
+        };
+        self.best = Some(best);
+        true
+    }
+
+    /// Persist the best solve, for the attempts of later sessions
+    pub fn save(&self) {
+        if let Some(best) = &self.best {
+            best.save();
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.attempt = Attempt::Invalidated;
+    }
+
+    /// Start over, the next move of the player starts a new attempt
+    pub fn restart(&mut self) {
+        self.attempt = Attempt::Ready;
+    }
+
+    /// Where the best solve was after as many moves as the attempt has made so far
+    /// @returns None if there is no best solve, or the attempt no longer counts
+    pub fn state(&self) -> Option<BoardId> {
+        let moves = match &self.attempt {
+            Attempt::Ready => 0,
+            Attempt::Playing { record, .. } => record.entries.len(),
+            Attempt::Invalidated => return None,
+        };
+        Some(self.best.as_ref()?.state_after(moves))
+    }
+}
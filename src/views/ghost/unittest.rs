@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{collect_valid_moves, to_id, SlideMove};
+use crate::builder::unittest::solve;
+use crate::graph::Graph;
+use crate::views::ghost::{Attempt, Ghost};
+use std::time::Duration;
+
+/// Play moves from a state, without storing anything
+fn play(ghost: &mut Ghost, graph: &Graph, start: u64, moves: &[SlideMove]) -> u64 {
+    let mut state = start;
+    for (index, slide_move) in moves.iter().enumerate() {
+        let board = graph.get(state).unwrap().board;
+        let (_slide_move, next) = collect_valid_moves(&board)
+            .into_iter()
+            .find(|(valid_move, _next)| valid_move == slide_move)
+            .unwrap();
+        ghost.record_move(
+            graph,
+            Duration::from_secs(index as u64),
+            state,
+            *slide_move,
+            true,
+        );
+        state = to_id(&next);
+    }
+    state
+}
+
+/// The moves of the shortest solve, and a detour that ends at the start again
+fn moves(graph: &Graph, start: u64) -> (Vec<SlideMove>, [SlideMove; 2]) {
+    let node = graph.get(start).unwrap();
+    let (solving, _board) = collect_valid_moves(&node.board)
+        .into_iter()
+        .find(|(_slide_move, board)| crate::board::is_solution(board))
+        .unwrap();
+    let (away, board) = collect_valid_moves(&node.board)
+        .into_iter()
+        .find(|(_slide_move, board)| !crate::board::is_solution(board))
+        .unwrap();
+    let (back, _board) = collect_valid_moves(&board)
+        .into_iter()
+        .find(|(_slide_move, board)| to_id(board) == start)
+        .unwrap();
+    (vec![solving], [away, back])
+}
+
+#[test]
+fn test_best_solve() {
+    let (graph, start) = solve();
+    let (solving, detour) = moves(&graph, start);
+    let mut ghost = Ghost {
+        attempt: Attempt::Ready,
+        puzzle: start,
+        best: None,
+    };
+    assert_eq!(ghost.state(), None);
+
+    // A slow solve is the first best solve
+    let slow = [detour.as_slice(), &solving].concat();
+    let solved = play(&mut ghost, &graph, start, &slow);
+    assert!(ghost.finish(&graph));
+    assert_eq!(ghost.state(), None);
+
+    // The ghost follows the best solve, and waits at the solution
+    ghost.restart();
+    assert_eq!(ghost.state(), Some(start));
+    let away = play(&mut ghost, &graph, start, &detour[..1]);
+    assert_eq!(ghost.state(), Some(away));
+    play(&mut ghost, &graph, away, &detour[1..]);
+    play(&mut ghost, &graph, start, &detour);
+    assert_eq!(ghost.state(), Some(solved));
+
+    // A faster solve replaces it, a slower one doesn't
+    ghost.restart();
+    play(&mut ghost, &graph, start, &solving);
+    assert!(ghost.finish(&graph));
+    ghost.restart();
+    play(&mut ghost, &graph, start, &slow);
+    assert!(!ghost.finish(&graph));
+    ghost.restart();
+    assert_eq!(ghost.best.as_ref().unwrap().states, [start, solved]);
+
+    // An attempt with help doesn't count
+    play(&mut ghost, &graph, start, &detour[..1]);
+    ghost.invalidate();
+    assert_eq!(ghost.state(), None);
+    assert!(!ghost.finish(&graph));
+}
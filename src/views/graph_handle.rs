@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::BoardId;
+use crate::graph::export::is_fake_solution;
+use crate::graph::Node;
+use crate::views::StatefulViews;
+use js_sys::{Array, Object, Reflect};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+/// A read-only view of the solved graph, for visualizations in JS. States are BoardIds as hex
+/// strings, like the events use. The "fake" solution state that all solved states lead to is left
+/// out, so solved states are at 0 moves from the solution.
+#[wasm_bindgen]
+pub struct GraphHandle {
+    stateful_views: Rc<RefCell<StatefulViews>>,
+}
+
+impl GraphHandle {
+    pub fn new(stateful_views: Rc<RefCell<StatefulViews>>) -> Self {
+        Self { stateful_views }
+    }
+}
+
+fn parse_id(id: &str) -> Result<BoardId, JsValue> {
+    BoardId::from_str_radix(id, 16)
+        .map_err(|error| JsValue::from_str(&format!("Invalid BoardId: {error}")))
+}
+
+/// Describe a state as `{id, position, moves_to_start, moves_to_solution}`, unknown distances are
+/// undefined
+fn to_object(node: &Node) -> Result<JsValue, JsValue> {
+    let object = Object::new();
+    let set = |key: &str, value: JsValue| Reflect::set(&object, &key.into(), &value);
+    set("id", format!("{:016x}", node.id).into())?;
+    set("position", node.board.to_notation().into())?;
+    set("moves_to_start", node.distance_to_start().into())?;
+    set(
+        "moves_to_solution",
+        node.distance_to_solution()
+            .map(|distance| distance - 1)
+            .into(),
+    )?;
+    Ok(object.into())
+}
+
+#[wasm_bindgen]
+impl GraphHandle {
+    /// The number of states
+    pub fn node_count(&self) -> usize {
+        let stateful_views = self.stateful_views.borrow();
+        let graph = stateful_views.graph();
+        graph
+            .nodes()
+            .iter()
+            .filter(|node| !is_fake_solution(node))
+            .count()
+    }
+
+    /// Look up a state, returns undefined if it isn't in the graph
+    pub fn node(&self, id: &str) -> Result<JsValue, JsValue> {
+        let stateful_views = self.stateful_views.borrow();
+        let graph = stateful_views.graph();
+        match graph.get(parse_id(id)?) {
+            Some(node) if !is_fake_solution(node) => to_object(node),
+            _ => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// The moves from a state, as objects like `{move: "B2⮕B1", node: {id, ...}}`
+    pub fn neighbors(&self, id: &str) -> Result<Array, JsValue> {
+        let stateful_views = self.stateful_views.borrow();
+        let graph = stateful_views.graph();
+        let node = graph
+            .get(parse_id(id)?)
+            .ok_or(JsValue::from_str("Unknown state"))?;
+        let neighbors = Array::new();
+        for (edge, neighbor) in graph.neighbors(node) {
+            if is_fake_solution(neighbor) {
+                continue;
+            }
+            let object = Object::new();
+            Reflect::set(&object, &"move".into(), &edge.slide_move.to_string().into())?;
+            Reflect::set(&object, &"node".into(), &to_object(neighbor)?)?;
+            neighbors.push(&object);
+        }
+        Ok(neighbors)
+    }
+
+    /// The states that are a number of moves from the solution, e.g. 0 for the solved states
+    pub fn nodes_at_distance(&self, distance: u32) -> Result<Array, JsValue> {
+        let stateful_views = self.stateful_views.borrow();
+        let graph = stateful_views.graph();
+        let nodes = Array::new();
+        let at_distance = graph
+            .nodes()
+            .iter()
+            .filter(|node| node.distance_to_solution() == Some(distance + 1));
+        for node in at_distance {
+            nodes.push(&to_object(node)?);
+        }
+        Ok(nodes)
+    }
+}
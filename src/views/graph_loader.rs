@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::Board;
+use crate::graph::stream::{DecodedGraph, GraphDecoder};
+use crate::to_js_error;
+use crate::views::utils::get_window;
+use js_sys::{Promise, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ReadableStreamDefaultReader, Response};
+
+/// How far loading the graph has come
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    /// The number of states decoded so far
+    pub states: usize,
+    pub bytes: usize,
+    /// The size of the whole graph, if the server told
+    pub total_bytes: Option<usize>,
+    /// The board that the puzzle starts from, once it has been decoded
+    pub start_board: Option<Board>,
+}
+
+/// How many moves around the player's state the graph is expanded, while the whole graph loads
+pub const LOADING_DEPTH: u32 = 2;
+
+/// Let the page handle its events and paint, before continuing
+pub async fn yield_to_event_loop() -> Result<(), JsValue> {
+    let mut result = Ok(0);
+    let promise = Promise::new(&mut |resolve, _reject| {
+        result = get_window().and_then(|window| {
+            window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0)
+        });
+    });
+    result?;
+    JsFuture::from(promise).await?;
+    Ok(())
+}
+
+/// Fetch a graph in the format of graph::stream, and decode it while it downloads. The page keeps
+/// responding in the meantime, as the decoder yields to the event loop after every chunk.
+/// @param on_progress is called after every chunk, an error stops the loading
+pub async fn fetch_graph(
+    url: &str,
+    on_progress: &mut dyn FnMut(Progress) -> Result<(), JsValue>,
+) -> Result<DecodedGraph, JsValue> {
+    let response: Response = JsFuture::from(get_window()?.fetch_with_str(url))
+        .await?
+        .dyn_into()?;
+    if !response.ok() {
+        return Err(JsValue::from_str(&format!(
+            "Failed to load the graph: {} {}",
+            response.status(),
+            response.status_text()
+        )));
+    }
+    let total_bytes = response
+        .headers()
+        .get("content-length")?
+        .and_then(|length| length.parse().ok());
+    let reader: ReadableStreamDefaultReader = response
+        .body()
+        .ok_or(JsValue::from_str(
+            "Failed to load the graph: the response is empty",
+        ))?
+        .get_reader()
+        .dyn_into()?;
+
+    let mut decoder = GraphDecoder::new();
+    let mut bytes = 0;
+    loop {
+        let chunk = JsFuture::from(reader.read()).await?;
+        if Reflect::get(&chunk, &"done".into())?.is_truthy() {
+            break;
+        }
+        let chunk: Uint8Array = Reflect::get(&chunk, &"value".into())?.dyn_into()?;
+        let chunk = chunk.to_vec();
+        decoder.feed(&chunk).map_err(to_js_error)?;
+        bytes += chunk.len();
+        on_progress(Progress {
+            states: decoder.node_count(),
+            bytes,
+            total_bytes,
+            start_board: decoder.start_board(),
+        })?;
+        yield_to_event_loop().await?;
+    }
+    decoder.finish().map_err(to_js_error)
+}
@@ -0,0 +1,296 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::BoardId;
+use crate::graph::metrics::{distances_from, find_components};
+use crate::graph::{Graph, NodeIndex};
+use crate::views::graph_view::arrangement::{ArrangedGraph, Arrangement};
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+/// The ID and coordinates of each node
+type Positions = Vec<(BoardId, [f32; 2])>;
+
+/// The positions of a layout that doesn't depend on the active state, so that the nodes keep their
+/// places between moves. They are kept for as long as the graph keeps its size.
+#[derive(Default)]
+struct PositionCache(Option<((usize, usize), Positions)>);
+
+impl PositionCache {
+    fn get_or_place(
+        &mut self,
+        graph: &Graph,
+        place: impl FnOnce(&Graph) -> Positions,
+    ) -> Positions {
+        let size = (graph.node_count(), graph.edge_count());
+        match &self.0 {
+            Some((cached_size, positions)) if *cached_size == size => positions.clone(),
+            _ => {
+                let positions = place(graph);
+                self.0 = Some((size, positions.clone()));
+                positions
+            }
+        }
+    }
+}
+
+/// Groups the nodes into columns by their distance to the solution, within each column they are
+/// sorted by their distance from the start, and then by their ID. States that can't reach the
+/// solution, like those behind a one-way cell, form the last column.
+#[derive(Default)]
+pub struct BinnedLayout {
+    cache: PositionCache,
+}
+
+impl BinnedLayout {
+    fn place(graph: &Graph) -> Positions {
+        let trap_bin = graph.max_distance_to_solution as usize + 1;
+        let mut bins: Vec<Vec<(u32, BoardId)>> = vec![Vec::new(); trap_bin + 1];
+        for node in graph.nodes() {
+            let bin_index = node
+                .distance_to_solution()
+                .map_or(trap_bin, |distance| distance as usize);
+            let distance_to_start = node.distance_to_start().unwrap_or(u32::MAX);
+            bins[bin_index].push((distance_to_start, node.id));
+        }
+
+        let mut positions = Vec::with_capacity(graph.node_count());
+        for (bin_index, bin) in bins.iter_mut().enumerate() {
+            bin.sort_unstable();
+            for (node_index, (_distance_from_start, id)) in bin.iter().enumerate() {
+                positions.push((*id, [bin_index as f32, node_index as f32]));
+            }
+        }
+        positions
+    }
+}
+
+impl Arrangement for BinnedLayout {
+    fn arrange(&mut self, graph: &Graph, _active_state: BoardId) -> ArrangedGraph {
+        ArrangedGraph::fit(self.cache.get_or_place(graph, Self::place))
+    }
+}
+
+/// The number of steps that the forces take to settle
+const FORCE_ITERATIONS: usize = 50;
+
+/// The distance at which states stop pushing each other apart, the ideal length of an edge is one unit
+const REPULSION_RANGE: f32 = 2.0;
+
+/// Lets connected states pull each other together, while all states push each other apart, so
+/// that the shape of the state space shows. Only nearby states push each other, found through a grid
+/// of cells the size of the repulsion range, as the graph has too many states to consider all pairs.
+#[derive(Default)]
+pub struct ForceDirectedLayout {
+    cache: PositionCache,
+}
+
+impl ForceDirectedLayout {
+    fn simulate(graph: &Graph) -> Positions {
+        // Start from a spiral in the order of a search through each component, so that connected
+        // states start out close together
+        let order = find_components(graph).concat();
+        let mut positions = vec![[0.0f32; 2]; graph.node_count()];
+        for (rank, index) in order.iter().enumerate() {
+            let radius = (rank as f32 + 0.5).sqrt();
+            let angle = rank as f32 * 2.399_963;
+            positions[*index as usize] = [radius * angle.cos(), radius * angle.sin()];
+        }
+
+        let cell_of = |[x, y]: [f32; 2]| {
+            (
+                (x / REPULSION_RANGE).floor() as i32,
+                (y / REPULSION_RANGE).floor() as i32,
+            )
+        };
+        let initial_temperature = (graph.node_count() as f32).sqrt() * 0.1;
+        for iteration in 0..FORCE_ITERATIONS {
+            let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+            for (index, position) in positions.iter().enumerate() {
+                cells.entry(cell_of(*position)).or_default().push(index);
+            }
+
+            let mut displacements = vec![[0.0f32; 2]; positions.len()];
+            for (index, displacement) in displacements.iter_mut().enumerate() {
+                let [x, y] = positions[index];
+                let (cell_x, cell_y) = cell_of([x, y]);
+
+                // Push away from the nearby states, inversely to their distance
+                for cell in
+                    (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (cell_x + dx, cell_y + dy)))
+                {
+                    for other in cells.get(&cell).into_iter().flatten() {
+                        if *other == index {
+                            continue;
+                        }
+                        let [other_x, other_y] = positions[*other];
+                        let (dx, dy) = (x - other_x, y - other_y);
+                        let distance_squared = (dx * dx + dy * dy).max(0.0001);
+                        if distance_squared < REPULSION_RANGE * REPULSION_RANGE {
+                            displacement[0] += dx / distance_squared;
+                            displacement[1] += dy / distance_squared;
+                        }
+                    }
+                }
+
+                // Pull towards the neighbors, by the square of their distance
+                for edge in graph.edges(graph.node(index as NodeIndex)) {
+                    let [other_x, other_y] = positions[edge.neighbor as usize];
+                    let (dx, dy) = (other_x - x, other_y - y);
+                    let distance = (dx * dx + dy * dy).sqrt();
+                    displacement[0] += dx * distance;
+                    displacement[1] += dy * distance;
+                }
+            }
+
+            // Cool down, so that the states settle
+            let temperature =
+                initial_temperature * (1.0 - iteration as f32 / FORCE_ITERATIONS as f32);
+            for (position, [dx, dy]) in positions.iter_mut().zip(displacements) {
+                let length = (dx * dx + dy * dy).sqrt();
+                if length > 0.0 {
+                    let step = length.min(temperature) / length;
+                    position[0] += dx * step;
+                    position[1] += dy * step;
+                }
+            }
+        }
+
+        graph
+            .nodes()
+            .iter()
+            .zip(positions)
+            .map(|(node, position)| (node.id, position))
+            .collect()
+    }
+}
+
+impl Arrangement for ForceDirectedLayout {
+    fn arrange(&mut self, graph: &Graph, _active_state: BoardId) -> ArrangedGraph {
+        ArrangedGraph::fit(self.cache.get_or_place(graph, Self::simulate))
+    }
+}
+
+/// Places the active state in the center, with the other states in rings around it by the number of
+/// moves that they are away. States that the active state can't reach form the outer ring.
+pub struct RadialLayout;
+
+impl Arrangement for RadialLayout {
+    fn arrange(&mut self, graph: &Graph, active_state: BoardId) -> ArrangedGraph {
+        let mut rings: Vec<Vec<NodeIndex>> = Vec::new();
+        let mut reached = vec![false; graph.node_count()];
+        if let Some(active_index) = graph.index_of(active_state) {
+            // The search lists the states of each ring in the order of the states they are reached
+            // from, so that connected states end up at similar angles
+            for (index, distance) in distances_from(graph, active_index) {
+                if rings.len() <= distance as usize {
+                    rings.push(Vec::new());
+                }
+                rings[distance as usize].push(index);
+                reached[index as usize] = true;
+            }
+        }
+        let unreached: Vec<NodeIndex> = (0..graph.node_count() as NodeIndex)
+            .filter(|index| !reached[*index as usize])
+            .collect();
+        if !unreached.is_empty() {
+            rings.push(unreached);
+        }
+
+        ArrangedGraph::fit(place_rings(graph, &rings))
+    }
+}
+
+/// Places the solved states in the center, with the other states in rings around them by their
+/// distance to the solution, so that solving the puzzle falls inward. The states of each ring are
+/// ordered by the mean angle of their neighbors on the inner ring, which keeps edges between the
+/// rings short and avoids most crossings. States that can't reach the solution form the outer ring.
+#[derive(Default)]
+pub struct SunburstLayout {
+    cache: PositionCache,
+}
+
+impl SunburstLayout {
+    fn place(graph: &Graph) -> Positions {
+        let mut rings: Vec<Vec<NodeIndex>> =
+            vec![Vec::new(); graph.max_distance_to_solution as usize + 1];
+        let mut traps: Vec<NodeIndex> = Vec::new();
+        for (index, node) in graph.nodes().iter().enumerate() {
+            match node.distance_to_solution() {
+                Some(distance) => rings[distance as usize].push(index as NodeIndex),
+                None => traps.push(index as NodeIndex),
+            }
+        }
+
+        // Every state outside the center has a neighbor on the inner ring, that is one move closer
+        let mut angles = vec![0.0f32; graph.node_count()];
+        for (distance, ring) in rings.iter_mut().enumerate().skip(1) {
+            let mean_angle = |index: &NodeIndex| {
+                let (sin, cos) = graph
+                    .edges(graph.node(*index))
+                    .iter()
+                    .filter(|edge| {
+                        graph.node(edge.neighbor).distance_to_solution()
+                            == Some(distance as u32 - 1)
+                    })
+                    .map(|edge| angles[edge.neighbor as usize])
+                    .fold((0.0, 0.0), |(sin, cos), angle| {
+                        (sin + angle.sin(), cos + angle.cos())
+                    });
+                f32::atan2(sin, cos).rem_euclid(TAU)
+            };
+            let mut sorted: Vec<(f32, NodeIndex)> = ring
+                .iter()
+                .map(|index| (mean_angle(index), *index))
+                .collect();
+            sorted.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            *ring = sorted.into_iter().map(|(_angle, index)| index).collect();
+            for (rank, index) in ring.iter().enumerate() {
+                angles[*index as usize] = ring_angle(rank, ring.len());
+            }
+        }
+        if !traps.is_empty() {
+            rings.push(traps);
+        }
+        place_rings(graph, &rings)
+    }
+}
+
+impl Arrangement for SunburstLayout {
+    fn arrange(&mut self, graph: &Graph, _active_state: BoardId) -> ArrangedGraph {
+        ArrangedGraph::fit(self.cache.get_or_place(graph, Self::place))
+    }
+}
+
+/// The angle of a state on a ring, the states are spread evenly
+fn ring_angle(rank: usize, count: usize) -> f32 {
+    TAU * rank as f32 / count as f32
+}
+
+fn polar_to_cartesian(radius: f32, angle: f32) -> [f32; 2] {
+    [radius * angle.cos(), radius * angle.sin()]
+}
+
+/// Place rings of states around the center, in order. The rings are spaced so that the states of the
+/// most crowded ring are a unit apart.
+fn place_rings(graph: &Graph, rings: &[Vec<NodeIndex>]) -> Positions {
+    let spacing = rings
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(radius, ring)| ring.len() as f32 / (TAU * radius as f32))
+        .fold(1.0, f32::max);
+
+    let mut positions = Vec::with_capacity(graph.node_count());
+    for (radius, ring) in rings.iter().enumerate() {
+        let radius = radius as f32 * spacing;
+        for (rank, index) in ring.iter().enumerate() {
+            positions.push((
+                graph.node(*index).id,
+                polar_to_cartesian(radius, ring_angle(rank, ring.len())),
+            ));
+        }
+    }
+    positions
+}
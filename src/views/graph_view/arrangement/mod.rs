@@ -0,0 +1,431 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+mod layouts;
+#[cfg(test)]
+mod unittest;
+
+use crate::board::BoardId;
+use crate::graph::{Graph, Node};
+use crate::views::graph_view::palette;
+use crate::views::graph_view::traffic::Traffic;
+use crate::views::graph_view::visits::Visits;
+use crate::views::theme::{Rgb, Theme};
+use anyhow::{anyhow, Error};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub use layouts::{BinnedLayout, ForceDirectedLayout, RadialLayout, SunburstLayout};
+
+/// The number of floats that make up a single point
+pub const POINT_STRIDE: usize = 11;
+
+/// The number of leading floats of a point that can be interpolated: coordinates, size, color and outline color
+pub const POINT_TWEENABLE: usize = 9;
+
+/// The number of floats that make up a single edge vertex: coordinates, weight and color
+pub const EDGE_VERTEX_STRIDE: usize = 6;
+
+/// The visited_at value of nodes that are not part of the trail
+const NOT_VISITED: f32 = -1.0;
+
+/// The shapes of the points, these match the fragment shader's shape values
+pub const SHAPE_CIRCLE: f32 = 0.0;
+pub const SHAPE_DIAMOND: f32 = 1.0;
+
+/// The size of regular nodes, and of the active node
+const NODE_SIZE: f32 = 3.0;
+const ACTIVE_SIZE: f32 = 6.0;
+
+/// The length of the arrowheads on one-way edges in content space, they shrink on short edges
+const ARROW_LENGTH: f32 = 0.25;
+
+/// The angle between an arrowhead's wings and its edge
+const ARROW_ANGLE: f32 = 0.45;
+
+/// The metric that determines the color of each node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    Uniform,
+    #[default]
+    DistanceToSolution,
+    DistanceToStart,
+    BranchingFactor,
+    VisitCount,
+    /// The bottlenecks stand out, these are the states that cut dead-end pockets off from the rest
+    Bottlenecks,
+    /// The traps stand out, these are the states from which a player who now and then makes a
+    /// mistake needs many more moves than the optimal solution
+    Difficulty,
+}
+
+impl ColorMode {
+    fn metric(&self, node: &Node, visits: &Visits) -> Option<u32> {
+        match self {
+            ColorMode::Uniform => None,
+            ColorMode::DistanceToSolution => node.distance_to_solution(),
+            ColorMode::DistanceToStart => node.distance_to_start(),
+            ColorMode::BranchingFactor => Some(node.branching_factor as u32),
+            ColorMode::VisitCount => Some(visits.count(&node.id)),
+            ColorMode::Bottlenecks => Some(node.bottleneck as u32),
+            ColorMode::Difficulty => {
+                // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+                let optimal_moves = node.distance_to_solution()?.saturating_sub(1);
+                Some(node.estimated_moves()?.saturating_sub(optimal_moves))
+            }
+        }
+    }
+
+    /// Whether the metric also determines the size of the nodes
+    fn scales_size(&self) -> bool {
+        matches!(self, ColorMode::VisitCount | ColorMode::Bottlenecks)
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            ColorMode::Uniform => "",
+            ColorMode::DistanceToSolution => "Moves to solution",
+            ColorMode::DistanceToStart => "Moves from start",
+            ColorMode::BranchingFactor => "Possible moves",
+            ColorMode::VisitCount => "Visits",
+            ColorMode::Bottlenecks => "Bottleneck",
+            ColorMode::Difficulty => "Extra moves, estimated",
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => Ok(ColorMode::Uniform),
+            "distance-to-solution" => Ok(ColorMode::DistanceToSolution),
+            "distance-to-start" => Ok(ColorMode::DistanceToStart),
+            "branching-factor" => Ok(ColorMode::BranchingFactor),
+            "visit-count" => Ok(ColorMode::VisitCount),
+            "bottlenecks" => Ok(ColorMode::Bottlenecks),
+            "difficulty" => Ok(ColorMode::Difficulty),
+            _ => Err(anyhow!("Unknown color mode \"{s}\"")),
+        }
+    }
+}
+
+/// The range of the metric that the nodes are colored by
+pub struct ColorRange {
+    pub title: &'static str,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl ColorRange {
+    fn new(graph: &Graph, visits: &Visits, color_mode: ColorMode) -> Option<ColorRange> {
+        let (min, max) = graph
+            .nodes()
+            .iter()
+            .filter_map(|node| color_mode.metric(node, visits))
+            .fold(None, |range, value| match range {
+                None => Some((value, value)),
+                Some((min, max)) => Some((value.min(min), value.max(max))),
+            })?;
+        Some(ColorRange {
+            title: color_mode.title(),
+            min,
+            max,
+        })
+    }
+
+    /// Get the relative position of a value within this range, in range [0.0, 1.0]
+    fn normalize(&self, value: u32) -> f32 {
+        let span = (self.max - self.min).max(1) as f32;
+        value.saturating_sub(self.min) as f32 / span
+    }
+}
+
+/// The content space positions that an arrangement gives the nodes
+pub struct ArrangedGraph {
+    pub width: f32,
+    pub height: f32,
+    /// The ID and coordinates of each node, the points are listed in this order
+    pub positions: Vec<(BoardId, [f32; 2])>,
+}
+
+impl ArrangedGraph {
+    /// Move the positions to start at the origin, and fit the content size around them.
+    /// Like the bins of the binned layout, each node takes up a unit of space.
+    fn fit(mut positions: Vec<(BoardId, [f32; 2])>) -> Self {
+        let min = positions
+            .iter()
+            .fold([f32::INFINITY; 2], |[min_x, min_y], (_id, [x, y])| {
+                [min_x.min(*x), min_y.min(*y)]
+            });
+        let (mut width, mut height) = (0.0f32, 0.0f32);
+        for (_id, [x, y]) in &mut positions {
+            *x -= min[0];
+            *y -= min[1];
+            width = width.max(*x + 1.0);
+            height = height.max(*y + 1.0);
+        }
+        Self {
+            width,
+            height,
+            positions,
+        }
+    }
+}
+
+/// A strategy that places the nodes of the graph in content space
+pub trait Arrangement {
+    fn arrange(&mut self, graph: &Graph, active_state: BoardId) -> ArrangedGraph;
+}
+
+/// The arrangements that the graph view can switch between
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// Columns by the distance to the solution
+    #[default]
+    Binned,
+    /// Connected states pull together, while all states push each other apart
+    ForceDirected,
+    /// Rings around the active state, by the number of moves away from it
+    Radial,
+    /// Rings around the solution, by the distance to the solution
+    Sunburst,
+}
+
+impl Layout {
+    pub fn create(&self) -> Box<dyn Arrangement> {
+        match self {
+            Layout::Binned => Box::new(BinnedLayout::default()),
+            Layout::ForceDirected => Box::new(ForceDirectedLayout::default()),
+            Layout::Radial => Box::new(RadialLayout),
+            Layout::Sunburst => Box::new(SunburstLayout::default()),
+        }
+    }
+}
+
+impl FromStr for Layout {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "binned" => Ok(Layout::Binned),
+            "force-directed" => Ok(Layout::ForceDirected),
+            "radial" => Ok(Layout::Radial),
+            "sunburst" => Ok(Layout::Sunburst),
+            _ => Err(anyhow!("Unknown layout \"{s}\"")),
+        }
+    }
+}
+
+/// The points of an arranged graph, styled for drawing
+pub struct Scene {
+    pub width: f32,
+    pub height: f32,
+    pub points: Vec<f32>,
+    /// The ID of the node that each point represents, in the same order as the points
+    pub ids: Vec<BoardId>,
+    /// The point index of each node
+    pub indices: HashMap<BoardId, usize>,
+    /// The distance to the solution of each node, in the same order as the points. It's missing for
+    /// the states that can't reach the solution.
+    pub distances_to_solution: Vec<Option<u32>>,
+    /// The range of the metric that colors the nodes, if they are colored by a metric
+    pub color_range: Option<ColorRange>,
+    /// The edges that only go one way, from the first node to the second
+    pub one_way: HashSet<(BoardId, BoardId)>,
+}
+
+impl Scene {
+    /// Style the points of an arranged graph, the trail maps the previously visited states to the time of their visit
+    pub fn new(
+        graph: &Graph,
+        arranged: &ArrangedGraph,
+        active_state: BoardId,
+        trail: &HashMap<BoardId, Duration>,
+        visits: &Visits,
+        color_mode: ColorMode,
+        theme: &Theme,
+    ) -> Scene {
+        let mut scene = Scene {
+            width: arranged.width,
+            height: arranged.height,
+            points: Vec::with_capacity(arranged.positions.len() * POINT_STRIDE),
+            ids: Vec::with_capacity(arranged.positions.len()),
+            indices: HashMap::with_capacity(arranged.positions.len()),
+            distances_to_solution: Vec::with_capacity(arranged.positions.len()),
+            color_range: ColorRange::new(graph, visits, color_mode),
+            one_way: HashSet::new(),
+        };
+
+        // The nodes on a shortest path from the start to the solution have the lowest total distance
+        let shortest_path_length = graph
+            .nodes()
+            .iter()
+            .filter_map(|node| Some(node.distance_to_start()? + node.distance_to_solution()?))
+            .min();
+
+        for (id, [x, y]) in &arranged.positions {
+            let node = graph.get(*id).expect("Arranged nodes are in the graph");
+            let distance_to_solution = node.distance_to_solution();
+            let on_shortest_path = distance_to_solution
+                .zip(node.distance_to_start())
+                .is_some_and(|(to_solution, to_start)| {
+                    shortest_path_length == Some(to_solution + to_start)
+                });
+
+            scene.one_way.extend(
+                graph
+                    .neighbors(node)
+                    .filter(|(edge, _neighbor)| !edge.reversible)
+                    .map(|(_edge, neighbor)| (*id, neighbor.id)),
+            );
+            scene.indices.insert(*id, scene.ids.len());
+            scene.ids.push(*id);
+            scene.distances_to_solution.push(distance_to_solution);
+
+            // Add point's coordinates
+            scene.points.push(*x);
+            scene.points.push(*y);
+
+            // Add point's size, and its color and outline color, only highlighted nodes have a distinct outline
+            if *id == active_state {
+                scene.points.push(ACTIVE_SIZE);
+                scene
+                    .points
+                    .extend_from_slice(&theme.graph_active.to_normalized());
+                scene
+                    .points
+                    .extend_from_slice(&theme.graph_highlight_outline.to_normalized());
+            } else {
+                let (size, color) = match (&scene.color_range, color_mode.metric(node, visits)) {
+                    (Some(range), Some(value)) => {
+                        let t = range.normalize(value);
+                        let size = match color_mode.scales_size() {
+                            true => NODE_SIZE + (ACTIVE_SIZE - NODE_SIZE) * t,
+                            false => NODE_SIZE,
+                        };
+                        (size, palette::sample(t))
+                    }
+                    // The states that can't reach the solution stand out, unless the nodes are uniform
+                    (Some(_range), None) if distance_to_solution.is_none() => {
+                        (NODE_SIZE, theme.graph_trap.to_normalized())
+                    }
+                    _ => (NODE_SIZE, theme.graph_uniform.to_normalized()),
+                };
+                scene.points.push(size);
+                scene.points.extend_from_slice(&color);
+                scene.points.extend_from_slice(&color);
+            }
+
+            // Add point's visit time, the active node is never drawn as part of the trail
+            let visited_at = match trail.get(id) {
+                Some(timestamp) if *id != active_state => timestamp.as_secs_f32(),
+                _ => NOT_VISITED,
+            };
+            scene.points.push(visited_at);
+
+            // Add point's shape, the shortest path stands out as diamonds
+            scene.points.push(match on_shortest_path {
+                true => SHAPE_DIAMOND,
+                false => SHAPE_CIRCLE,
+            });
+        }
+        scene
+    }
+
+    /// Get the content space coordinates of a node's point, among points in this arrangement's order,
+    /// e.g. those of a transition
+    fn position_in(&self, points: &[f32], id: &BoardId) -> Option<(f32, f32)> {
+        let offset = self.indices.get(id)? * POINT_STRIDE;
+        Some((points[offset], points[offset + 1]))
+    }
+
+    /// Create line vertices for each edge that the player has traversed, weighted by its relative traffic
+    pub fn edges(&self, traffic: &Traffic, theme: &Theme) -> Vec<f32> {
+        self.edges_at(&self.points, traffic, theme)
+    }
+
+    /// Like edges, between the given points instead of the arrangement's own, e.g. during a transition
+    pub fn edges_at(&self, points: &[f32], traffic: &Traffic, theme: &Theme) -> Vec<f32> {
+        let max_count = traffic.max_count().max(1) as f32;
+        let mut vertices = Vec::new();
+        for ((a, b), count) in traffic.iter() {
+            self.push_edge(
+                &mut vertices,
+                points,
+                a,
+                b,
+                count as f32 / max_count,
+                theme.graph_edge,
+            );
+        }
+        vertices
+    }
+
+    /// Create line vertices along a path of states, at full weight
+    pub fn path_edges(&self, path: &[BoardId], color: Rgb) -> Vec<f32> {
+        self.path_edges_at(&self.points, path, color)
+    }
+
+    /// Like path_edges, between the given points instead of the arrangement's own
+    pub fn path_edges_at(&self, points: &[f32], path: &[BoardId], color: Rgb) -> Vec<f32> {
+        let mut vertices = Vec::new();
+        for pair in path.windows(2) {
+            self.push_edge(&mut vertices, points, &pair[0], &pair[1], 1.0, color);
+        }
+        vertices
+    }
+
+    fn push_edge(
+        &self,
+        vertices: &mut Vec<f32>,
+        points: &[f32],
+        a: &BoardId,
+        b: &BoardId,
+        weight: f32,
+        color: Rgb,
+    ) {
+        let (Some(a_position), Some(b_position)) =
+            (self.position_in(points, a), self.position_in(points, b))
+        else {
+            return;
+        };
+        let [red, green, blue] = color.to_normalized();
+        let mut push_line = |start: (f32, f32), end: (f32, f32)| {
+            vertices.extend_from_slice(&[start.0, start.1, weight, red, green, blue]);
+            vertices.extend_from_slice(&[end.0, end.1, weight, red, green, blue]);
+        };
+        push_line(a_position, b_position);
+
+        // A one-way edge gets an arrowhead halfway, pointing the way that it goes
+        let (from, to) = match (
+            self.one_way.contains(&(*a, *b)),
+            self.one_way.contains(&(*b, *a)),
+        ) {
+            (true, false) => (a_position, b_position),
+            (false, true) => (b_position, a_position),
+            _ => return,
+        };
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return;
+        }
+        let arrow_length = ARROW_LENGTH.min(length * 0.3);
+        let (dx, dy) = (dx / length, dy / length);
+        let tip = (
+            (from.0 + to.0 + dx * arrow_length) / 2.0,
+            (from.1 + to.1 + dy * arrow_length) / 2.0,
+        );
+        for angle in [ARROW_ANGLE, -ARROW_ANGLE] {
+            let (sin, cos) = angle.sin_cos();
+            let wing = (
+                tip.0 - (dx * cos - dy * sin) * arrow_length,
+                tip.1 - (dx * sin + dy * cos) * arrow_length,
+            );
+            push_line(tip, wing);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{Board, BoardId};
+use crate::builder::WiggersGraafBuilder;
+use crate::graph::unittest::build_chain;
+use crate::graph::Graph;
+use crate::views::graph_view::arrangement::{
+    ArrangedGraph, ColorMode, Layout, Scene, POINT_STRIDE, POINT_TWEENABLE,
+};
+use crate::views::graph_view::visits::Visits;
+use crate::views::theme::Theme;
+use std::collections::HashMap;
+
+fn positions(arranged: &ArrangedGraph) -> HashMap<u64, [f32; 2]> {
+    arranged.positions.iter().copied().collect()
+}
+
+#[test]
+fn test_layouts_place_every_node() {
+    let (graph, ids) = build_chain();
+    for layout in [
+        Layout::Binned,
+        Layout::ForceDirected,
+        Layout::Radial,
+        Layout::Sunburst,
+    ] {
+        let arranged = layout.create().arrange(&graph, ids[1]);
+        assert_eq!(arranged.positions.len(), graph.node_count(), "{layout:?}");
+        assert_eq!(positions(&arranged).len(), graph.node_count(), "{layout:?}");
+        for (_id, [x, y]) in &arranged.positions {
+            assert!((0.0..arranged.width).contains(x), "{layout:?}");
+            assert!((0.0..arranged.height).contains(y), "{layout:?}");
+        }
+    }
+}
+
+#[test]
+fn test_binned_layout() {
+    let (graph, ids) = build_chain();
+    let arranged = Layout::Binned.create().arrange(&graph, ids[0]);
+    for (id, [x, _y]) in &arranged.positions {
+        let node = graph.get(*id).unwrap();
+        assert_eq!(*x, node.distance_to_solution().unwrap() as f32);
+    }
+    assert_eq!(arranged.width, graph.max_distance_to_solution as f32 + 1.0);
+}
+
+#[test]
+fn test_nodes_keep_their_places() {
+    let (graph, ids) = build_chain();
+    for layout in [Layout::Binned, Layout::ForceDirected, Layout::Sunburst] {
+        let mut arrangement = layout.create();
+        let before = arrangement.arrange(&graph, ids[0]).positions;
+        let after = arrangement.arrange(&graph, ids[1]).positions;
+        assert_eq!(before, after, "{layout:?}");
+        // A fresh arrangement places them the same way
+        assert_eq!(layout.create().arrange(&graph, ids[2]).positions, before);
+    }
+}
+
+#[test]
+fn test_radial_layout() {
+    let (graph, ids) = build_chain();
+    let arranged = Layout::Radial.create().arrange(&graph, ids[1]);
+    let positions = positions(&arranged);
+    let center = positions[&ids[1]];
+    let radius = |id| {
+        let [x, y] = positions[id];
+        ((x - center[0]).powi(2) + (y - center[1]).powi(2)).sqrt()
+    };
+
+    // The neighbors of the active state share the first ring, the next state is further out
+    assert!(radius(&ids[0]) > 0.0);
+    assert!((radius(&ids[0]) - radius(&ids[2])).abs() < 0.001);
+    assert!(radius(&ids[3]) > radius(&ids[2]));
+}
+
+#[test]
+fn test_sunburst_layout() {
+    let (graph, ids) = build_chain();
+    let arranged = Layout::Sunburst.create().arrange(&graph, ids[0]);
+    let positions = positions(&arranged);
+    let center = positions[&ids[3]];
+    let radius = |id| {
+        let [x, y] = positions[id];
+        ((x - center[0]).powi(2) + (y - center[1]).powi(2)).sqrt()
+    };
+
+    // The rings are evenly spaced by the distance to the solution, which is in the center
+    let spacing = radius(&ids[2]);
+    assert!(spacing >= 1.0);
+    assert!((radius(&ids[1]) - 2.0 * spacing).abs() < 0.001);
+    assert!((radius(&ids[0]) - 3.0 * spacing).abs() < 0.001);
+}
+
+/// Solve a puzzle with a one-way cell, some of its states can't reach the solution
+/// @returns the graph, and the puzzle's start state
+fn build_one_way() -> (Graph, BoardId) {
+    let puzzle =
+        Board::from_notation("AABB/CDDE/CFFE/GFFH/I..J 4x5 2x2@B1 ..../..../..../..../>...")
+            .unwrap();
+    let game = WiggersGraafBuilder::new().puzzle(puzzle).build().unwrap();
+    (game.graph, game.start_state)
+}
+
+#[test]
+fn test_states_without_solution() {
+    let (graph, start) = build_one_way();
+    let is_trap = |id: &BoardId| graph.get(*id).unwrap().distance_to_solution().is_none();
+    assert!(graph.nodes().iter().any(|node| is_trap(&node.id)));
+    for layout in [Layout::ForceDirected, Layout::Radial] {
+        let arranged = layout.create().arrange(&graph, start);
+        assert_eq!(arranged.positions.len(), graph.node_count(), "{layout:?}");
+    }
+
+    // The traps form the last column of the binned layout
+    let arranged = Layout::Binned.create().arrange(&graph, start);
+    for (id, [x, _y]) in &arranged.positions {
+        let column = graph.max_distance_to_solution as f32 + 1.0;
+        assert_eq!(*x == column, is_trap(id));
+    }
+
+    // The traps form the outer ring of the sunburst layout
+    let arranged = Layout::Sunburst.create().arrange(&graph, start);
+    let positions = positions(&arranged);
+    let solution = graph
+        .nodes()
+        .iter()
+        .find(|node| node.distance_to_solution() == Some(0))
+        .unwrap();
+    let center = positions[&solution.id];
+    let radius = |[x, y]: [f32; 2]| ((x - center[0]).powi(2) + (y - center[1]).powi(2)).sqrt();
+    let (traps, others): (Vec<_>, Vec<_>) = arranged
+        .positions
+        .iter()
+        .partition(|(id, _position)| is_trap(id));
+    let closest_trap = traps.iter().map(|(_id, position)| radius(*position));
+    let furthest_other = others.iter().map(|(_id, position)| radius(*position));
+    assert!(closest_trap.fold(f32::INFINITY, f32::min) > furthest_other.fold(0.0, f32::max));
+
+    // The traps have their own color, when the nodes are colored by the distance to the solution
+    let scene = Scene::new(
+        &graph,
+        &arranged,
+        start,
+        &HashMap::new(),
+        &Visits::default(),
+        ColorMode::DistanceToSolution,
+        &Theme::LIGHT,
+    );
+    for (index, id) in scene.ids.iter().enumerate() {
+        let point = &scene.points[index * POINT_STRIDE..][..POINT_TWEENABLE];
+        let is_trap_color = point[3..6] == Theme::LIGHT.graph_trap.to_normalized();
+        assert_eq!(is_trap_color, is_trap(id) && *id != start);
+        assert_eq!(scene.distances_to_solution[index].is_none(), is_trap(id));
+    }
+}
+
+#[test]
+fn test_parse_layout() {
+    assert_eq!("radial".parse::<Layout>().unwrap(), Layout::Radial);
+    assert_eq!(
+        "force-directed".parse::<Layout>().unwrap(),
+        Layout::ForceDirected
+    );
+    assert!("circular".parse::<Layout>().is_err());
+}
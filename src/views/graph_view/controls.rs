@@ -5,6 +5,7 @@ use crate::views::pointer_handler::{MouseHandler, PointerEvent};
 use crate::views::utils::{Coordinates, Delta};
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
+use std::time::Duration;
 use wasm_bindgen::JsValue;
 use web_sys::HtmlElement;
 
@@ -13,16 +14,31 @@ pub struct Controls {
     // TODO(Menno 04.09.2025) Track multiple pointers for gestures
     drag_pointer_index: Option<i32>,
     previous_drag_coordinates: Coordinates,
-    _pointer_handler: Rc<RefCell<MouseHandler>>,
+    /// How far the pointer travelled since it went down, to tell clicks apart from drags
+    drag_distance: f64,
+    /// When and where the previous click happened, to detect double clicks
+    previous_click: Option<(Duration, Coordinates)>,
+    pointer_handler: Rc<RefCell<MouseHandler>>,
 }
 
 /// The callback type for the handler to call on a mouse event
 pub type OnPointerEventCb = dyn FnMut(ControlEvent);
 
+/// Pointers that travel less than this many pixels between down and up are considered a click
+const CLICK_TOLERANCE: f64 = 5.0;
+
+/// Two clicks within this interval, at roughly the same spot, are considered a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 pub enum ControlEvent {
     Down(Coordinates),
     Move(Delta),
     Up(),
+    Click(Coordinates),
+    DoubleClick(Coordinates),
+    Hover(Coordinates),
+    /// Zoom around the given coordinates, by the scrolled distance in canvas pixels
+    Zoom(Coordinates, f64),
 }
 
 impl Controls {
@@ -36,7 +52,9 @@ impl Controls {
                 on_event_cb,
                 drag_pointer_index: None,
                 previous_drag_coordinates: Coordinates::zero(),
-                _pointer_handler: MouseHandler::new(
+                drag_distance: 0.0,
+                previous_click: None,
+                pointer_handler: MouseHandler::new(
                     target,
                     Box::new(move |event| -> bool {
                         self_ref.upgrade().unwrap().borrow_mut().handle_event(event)
@@ -47,6 +65,11 @@ impl Controls {
         }))
     }
 
+    /// Stop listening to the target's pointer events
+    pub fn detach(&self) {
+        self.pointer_handler.borrow_mut().detach();
+    }
+
     fn handle_event(&mut self, event: PointerEvent) -> bool {
         let mut handled = false;
         match event {
@@ -54,30 +77,63 @@ impl Controls {
                 if self.drag_pointer_index.is_none() {
                     self.drag_pointer_index = Some(index);
                     self.previous_drag_coordinates = coordinates;
+                    self.drag_distance = 0.0;
                     (self.on_event_cb)(ControlEvent::Down(coordinates));
                     handled = true;
                 }
             }
-            PointerEvent::Up((index, _timestamp, _coordinates)) => {
+            PointerEvent::Up((index, timestamp, coordinates)) => {
                 if self.drag_pointer_index == Some(index) {
                     self.drag_pointer_index = None;
                     (self.on_event_cb)(ControlEvent::Up());
+                    if self.drag_distance < CLICK_TOLERANCE {
+                        (self.on_event_cb)(ControlEvent::Click(coordinates));
+                        self.detect_double_click(timestamp, coordinates);
+                    }
                     handled = true;
                 }
             }
             PointerEvent::Move((index, _timestamp, coordinates)) => {
                 if self.drag_pointer_index == Some(index) {
                     let delta = coordinates - self.previous_drag_coordinates;
+                    self.drag_distance += delta.length();
                     (self.on_event_cb)(ControlEvent::Move(delta));
                     self.previous_drag_coordinates = coordinates;
                     handled = true;
+                } else if self.drag_pointer_index.is_none() {
+                    (self.on_event_cb)(ControlEvent::Hover(coordinates));
                 }
             }
+            PointerEvent::Leave(_index) => {}
             PointerEvent::TouchMove() => {
                 handled = true;
             }
-            PointerEvent::Wheel(_) => {}
+            // The graph has no menu of its own, the browser's menu is shown
+            PointerEvent::ContextMenu(_coordinates) => {}
+            PointerEvent::Wheel(wheel) => {
+                // Plain scrolling is left to the page, so that the graph doesn't trap it
+                if wheel.zoom {
+                    (self.on_event_cb)(ControlEvent::Zoom(wheel.coordinates, wheel.delta.y));
+                    handled = true;
+                }
+            }
         }
         handled
     }
+
+    fn detect_double_click(&mut self, timestamp: Duration, coordinates: Coordinates) {
+        let is_double_click =
+            self.previous_click
+                .is_some_and(|(previous_time, previous_coordinates)| {
+                    timestamp.saturating_sub(previous_time) < DOUBLE_CLICK_INTERVAL
+                        && (coordinates - previous_coordinates).length() < CLICK_TOLERANCE
+                });
+        if is_double_click {
+            // A third click starts a new double click
+            self.previous_click = None;
+            (self.on_event_cb)(ControlEvent::DoubleClick(coordinates));
+        } else {
+            self.previous_click = Some((timestamp, coordinates));
+        }
+    }
 }
@@ -0,0 +1,103 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use euclid::Vector2D;
+use std::time::Duration;
+
+/// The time it takes for a fling to lose about 63% of its velocity
+const DECAY_TIME_CONSTANT: Duration = Duration::from_millis(325);
+
+/// Flings slower than this many units per second come to a halt
+const STOP_SPEED: f32 = 10.0;
+
+/// How much each new sample contributes to the tracked velocity
+const VELOCITY_SMOOTHING: f32 = 0.8;
+
+/// A pointer that rests longer than this before being released does not fling
+const RELEASE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The momentum of a drag, which keeps the content moving after the pointer is released.
+/// The velocity decays exponentially over time, so the motion does not depend on the frame rate.
+pub struct Fling<U> {
+    /// The velocity in units per second
+    velocity: Vector2D<f32, U>,
+    /// The time of the previous drag sample or animation frame
+    previous_time: Option<Duration>,
+    flinging: bool,
+}
+
+impl<U> Default for Fling<U> {
+    fn default() -> Self {
+        Self {
+            velocity: Vector2D::zero(),
+            previous_time: None,
+            flinging: false,
+        }
+    }
+}
+
+impl<U> Fling<U> {
+    /// Feed a drag movement into the velocity estimate, this stops any running fling
+    pub fn track(&mut self, delta: Vector2D<f32, U>, timestamp: Duration) {
+        if self.flinging {
+            self.stop();
+        }
+        if let Some(previous_time) = self.previous_time {
+            let elapsed = timestamp.saturating_sub(previous_time).as_secs_f32();
+            if elapsed > 0.0 {
+                let sample = delta / elapsed;
+                self.velocity = self.velocity.lerp(sample, VELOCITY_SMOOTHING);
+            }
+        }
+        self.previous_time = Some(timestamp);
+    }
+
+    /// Let go of the content, which starts a fling if it was moving fast enough
+    /// @returns whether the content is flinging
+    pub fn release(&mut self, timestamp: Duration) -> bool {
+        let resting = self
+            .previous_time
+            .is_none_or(|previous_time| timestamp.saturating_sub(previous_time) > RELEASE_TIMEOUT);
+        if resting || self.velocity.length() < STOP_SPEED {
+            self.stop();
+            return false;
+        }
+        self.previous_time = Some(timestamp);
+        self.flinging = true;
+        true
+    }
+
+    /// Bring the content to an immediate halt
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_flinging(&self) -> bool {
+        self.flinging
+    }
+
+    /// Advance the fling to the given frame timestamp
+    /// @returns how far the content moved since the previous frame, or None if the fling is over
+    pub fn step(&mut self, timestamp: Duration) -> Option<Vector2D<f32, U>> {
+        if !self.flinging {
+            return None;
+        }
+        let previous_time = self.previous_time.unwrap_or(timestamp);
+        let elapsed = timestamp.saturating_sub(previous_time).as_secs_f32();
+        self.previous_time = Some(timestamp);
+
+        // Integrate v(t) = v0 * e^(-t / tau) over the elapsed time
+        let time_constant = DECAY_TIME_CONSTANT.as_secs_f32();
+        let decay = (-elapsed / time_constant).exp();
+        let displacement = self.velocity * (time_constant * (1.0 - decay));
+        self.velocity *= decay;
+
+        if self.velocity.length() < STOP_SPEED {
+            self.stop();
+        }
+        Some(displacement)
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::fling::{Fling, DECAY_TIME_CONSTANT, STOP_SPEED};
+use euclid::default::Vector2D;
+use std::time::Duration;
+
+/// Drag at a constant velocity of 1000 units per second along x, and release
+fn start_fling() -> Fling<euclid::UnknownUnit> {
+    let mut fling = Fling::default();
+    for frame in 0..10 {
+        fling.track(Vector2D::new(16.0, 0.0), Duration::from_millis(frame * 16));
+    }
+    assert!(fling.release(Duration::from_millis(150)));
+    fling
+}
+
+/// Step the fling at a fixed frame interval until it stops
+/// @returns the total distance travelled, and how long the fling lasted
+fn run_fling(mut fling: Fling<euclid::UnknownUnit>, frame: Duration) -> (Vector2D<f32>, Duration) {
+    let mut distance = Vector2D::zero();
+    let mut timestamp = Duration::from_millis(150);
+    while fling.is_flinging() {
+        timestamp += frame;
+        distance += fling.step(timestamp).unwrap();
+    }
+    (distance, timestamp - Duration::from_millis(150))
+}
+
+#[test]
+fn test_fling_decays_and_stops() {
+    let (distance, duration) = run_fling(start_fling(), Duration::from_millis(16));
+
+    // The fling travels v0 * tau, minus the tail that gets cut off by the stop threshold
+    let expected = 1000.0 * DECAY_TIME_CONSTANT.as_secs_f32();
+    let cut_off = STOP_SPEED * DECAY_TIME_CONSTANT.as_secs_f32();
+    assert!(distance.x <= expected);
+    assert!(distance.x >= expected - cut_off - 1.0);
+    assert_eq!(distance.y, 0.0);
+    assert!(duration < Duration::from_secs(2));
+}
+
+#[test]
+fn test_fling_is_frame_rate_independent() {
+    let mut fast = start_fling();
+    let mut slow = start_fling();
+
+    let mut fast_distance = Vector2D::zero();
+    for frame in 1..=6 {
+        fast_distance += fast.step(Duration::from_millis(150 + frame * 10)).unwrap();
+    }
+    let mut slow_distance = Vector2D::zero();
+    for frame in 1..=2 {
+        slow_distance += slow.step(Duration::from_millis(150 + frame * 30)).unwrap();
+    }
+
+    assert!((fast_distance - slow_distance).length() < 1e-3);
+}
+
+#[test]
+fn test_resting_pointer_does_not_fling() {
+    let mut fling = Fling::<euclid::UnknownUnit>::default();
+    fling.track(Vector2D::new(16.0, 0.0), Duration::from_millis(0));
+    fling.track(Vector2D::new(16.0, 0.0), Duration::from_millis(16));
+
+    // The pointer stood still for a while before it was released
+    assert!(!fling.release(Duration::from_millis(500)));
+    assert!(fling.step(Duration::from_millis(516)).is_none());
+}
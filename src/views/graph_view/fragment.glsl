@@ -1,10 +0,0 @@
-// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
-// SPDX-License-Identifier: MIT
-
-precision mediump float;
-
-varying vec4 f_color;
-
-void main(void) {
-    gl_FragColor = f_color;
-}
@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::BoardId;
+use crate::graph::Graph;
+use std::collections::HashMap;
+
+/// The states that the player can reach within a few moves, their short-term options
+pub struct Horizon {
+    max_moves: u32,
+    /// The state that the horizon was found from
+    center: Option<BoardId>,
+    /// The fewest moves that reach each state within the horizon
+    states: HashMap<BoardId, u32>,
+}
+
+impl Horizon {
+    pub fn new(max_moves: u32) -> Self {
+        Self {
+            max_moves,
+            center: None,
+            states: HashMap::new(),
+        }
+    }
+
+    pub fn max_moves(&self) -> u32 {
+        self.max_moves
+    }
+
+    /// Follow the player to a state, the horizon is only searched again when the state changed
+    pub fn update(&mut self, graph: &Graph, state: BoardId) {
+        if self.center == Some(state) {
+            return;
+        }
+        self.center = Some(state);
+        self.states = graph.within(state, self.max_moves);
+    }
+
+    /// The states within the horizon with the fewest moves that reach them, except the center
+    pub fn states(&self) -> impl Iterator<Item = (BoardId, u32)> + '_ {
+        self.states
+            .iter()
+            .map(|(state, moves)| (*state, *moves))
+            .filter(|(_state, moves)| *moves > 0)
+    }
+}
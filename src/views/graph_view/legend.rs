@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::ColorRange;
+use crate::views::graph_view::palette;
+use crate::views::utils::get_document;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlDivElement, HtmlElement};
+
+/// Explains what the colors of the graph's nodes mean
+pub struct Legend {
+    legend_div: HtmlDivElement,
+    title: HtmlElement,
+    min_label: HtmlElement,
+    max_label: HtmlElement,
+}
+
+impl Legend {
+    pub fn new(legend_div: HtmlDivElement) -> Result<Self, JsValue> {
+        let document = get_document()?;
+        let title: HtmlElement = document.create_element("span")?.dyn_into()?;
+        let gradient: HtmlElement = document.create_element("div")?.dyn_into()?;
+        let min_label: HtmlElement = document.create_element("span")?.dyn_into()?;
+        let max_label: HtmlElement = document.create_element("span")?.dyn_into()?;
+
+        title.set_class_name("graph-legend-title");
+        gradient.set_class_name("graph-legend-gradient");
+        gradient
+            .style()
+            .set_property("background", &palette::to_css_gradient())?;
+        min_label.set_class_name("graph-legend-min");
+        max_label.set_class_name("graph-legend-max");
+
+        legend_div.append_child(&title)?;
+        legend_div.append_child(&gradient)?;
+        legend_div.append_child(&min_label)?;
+        legend_div.append_child(&max_label)?;
+
+        Ok(Self {
+            legend_div,
+            title,
+            min_label,
+            max_label,
+        })
+    }
+
+    /// Show the range of the current coloring, or hide the legend when the nodes are not colored
+    pub fn set_range(&self, range: Option<&ColorRange>) {
+        let Some(range) = range else {
+            self.legend_div.set_hidden(true);
+            return;
+        };
+        self.legend_div.set_hidden(false);
+        self.title.set_text_content(Some(range.title));
+        self.min_label
+            .set_text_content(Some(&range.min.to_string()));
+        self.max_label
+            .set_text_content(Some(&range.max.to_string()));
+    }
+}
@@ -3,22 +3,48 @@
 
 use crate::board::BoardId;
 use crate::graph::Graph;
-use crate::views::frame_scheduler::{FrameScheduler, OnFrameCb};
-use crate::views::graph_view::arrangement::Arrangement;
+use crate::views::clock::Clock;
+use crate::views::frame_scheduler::{Frame, FrameScheduler, OnFrameCb};
+use crate::views::graph_view::arrangement::{Arrangement, ColorMode, Layout, Scene, POINT_STRIDE};
 use crate::views::graph_view::controls::{ControlEvent, Controls};
-use crate::views::graph_view::renderer::Renderer;
+use crate::views::graph_view::fling::Fling;
+use crate::views::graph_view::horizon::Horizon;
+use crate::views::graph_view::legend::Legend;
+use crate::views::graph_view::renderer::{create_renderer, GraphRenderer, PendingRenderer};
+use crate::views::graph_view::spatial_index::SpatialIndex;
+use crate::views::graph_view::traffic::Traffic;
+use crate::views::graph_view::transition::Transition;
+use crate::views::graph_view::visits::Visits;
+use crate::views::motion::Motion;
 use crate::views::resize_observer::ResizeObserver;
-use crate::views::utils::get_element_of_type;
+use crate::views::theme::Theme;
+use crate::views::utils::Coordinates;
 use euclid::{Scale, Size2D, Transform2D, Vector2D};
+use keyframe::functions::EaseInOutCubic;
+use keyframe::EasingFunction;
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::{Rc, Weak};
 use std::time::Duration;
 use wasm_bindgen::JsValue;
-use web_sys::HtmlCanvasElement;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlCanvasElement, HtmlDivElement};
 
 pub mod arrangement;
 mod controls;
+mod fling;
+mod horizon;
+mod legend;
+mod palette;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod raster;
 mod renderer;
+mod spatial_index;
+mod svg;
+mod traffic;
+mod transition;
+mod visits;
 
 /// This represents the view's content coordinate space, dynamic axes depending on the content size
 struct ContentSpace;
@@ -60,40 +86,147 @@ impl ClipSpace {
             .pre_translate(ContentSpace::PADDING)
             .then_translate(translation)
     }
+
+    /// Limit the translation so that at least part of the content remains on the canvas
+    pub fn clamp_translation(
+        canvas_size: Size2D<f32, CanvasSpace>,
+        content_size: Size2D<f32, ContentSpace>,
+        zoom: Scale<f32, ClipSpace, ClipSpace>,
+        translation: Vector2D<f32, ClipSpace>,
+    ) -> Vector2D<f32, ClipSpace> {
+        if content_size.is_empty() || canvas_size.is_empty() {
+            return translation;
+        }
+
+        // The translation is the position of the content's bottom left corner, bound it on either side
+        let extent =
+            Self::transform_from_content(canvas_size, content_size, zoom, Vector2D::zero())
+                .transform_vector(content_size.to_vector());
+        let clamp = |position: f32, extent: f32| {
+            let margin = MINIMUM_VISIBLE_CONTENT.min(extent);
+            position.min(1.0 - margin).max(-1.0 - extent + margin)
+        };
+        Vector2D::new(
+            clamp(translation.x, extent.x),
+            clamp(translation.y, extent.y),
+        )
+    }
 }
 
 /// This represents the Canvas coordinate system, where the canvas is represented in [0, pixel size]
 struct CanvasSpace;
 
-/// The velocity of a user scrolling
-const _ZOOM_SPEED: Scale<f32, ClipSpace, ClipSpace> = Scale::new(1.0);
+/// How much the zoom changes per scrolled canvas pixel, exponentially so that zooming in and out are symmetric
+const ZOOM_SPEED: f32 = 0.002;
 
 /// The minimum zoom level, this fits the whole contents into the clip-space, with some padding.
-const _ZOOM_MINIMUM: Scale<f32, ClipSpace, ClipSpace> = Scale::new(1.0);
+const ZOOM_MINIMUM: Scale<f32, ClipSpace, ClipSpace> = Scale::new(1.0);
 
 /// The maximum zoom level
-const _ZOOM_MAXIMUM: Scale<f32, ClipSpace, ClipSpace> = Scale::new(5.0);
+const ZOOM_MAXIMUM: Scale<f32, ClipSpace, ClipSpace> = Scale::new(5.0);
+
+/// How much of the content remains visible when it is dragged away, in clip-space units
+const MINIMUM_VISIBLE_CONTENT: f32 = 0.2;
+
+/// How long it takes to animate back to the fitted view
+const VIEW_RESET_DURATION: Duration = Duration::from_millis(400);
+
+/// An animation from the current view back to the fitted view
+struct ViewReset {
+    from_zoom: Scale<f32, ClipSpace, ClipSpace>,
+    from_translation: Vector2D<f32, ClipSpace>,
+    start_time: Option<Duration>,
+}
+
+/// How long a visited node remains visible in the trail, the fading itself is done on the GPU
+const TRAIL_FADE_DURATION: Duration = Duration::from_secs(8);
+
+/// The size of a node that is highlighted from another view
+const LINKED_HIGHLIGHT_SIZE: f32 = 6.0;
+
+/// The size of the nodes along the path of a replay
+const REPLAY_PATH_SIZE: f32 = 4.0;
+
+/// The size of the node where the best solve was, it is as large as the active node
+const GHOST_SIZE: f32 = 6.0;
+
+/// The smallest size of the nodes within the reachability horizon
+const HORIZON_SIZE: f32 = 3.0;
+
+/// How much of the active color is blended into the nodes that are one move away, it fades with
+/// each further move
+const HORIZON_OPACITY: f32 = 0.5;
+
+/// The size of the nodes along the optimal path to the solution
+const SOLUTION_PATH_SIZE: f32 = 3.5;
+
+/// How far from a node's center the pointer can be to hit it, in canvas pixels
+const PICK_RADIUS: f32 = 8.0;
+
+/// The interactions of the user with the nodes in the graph
+pub enum GraphEvent {
+    /// A node was clicked
+    Selected(BoardId),
+    /// The pointer moved onto a node, or off all nodes
+    Hovered(Option<BoardId>),
+}
+
+/// The callback type for the view to call when the user interacts with a node
+pub type OnGraphEventCb = dyn FnMut(GraphEvent);
 
 pub struct GraphView {
-    _self_ref: Weak<RefCell<Self>>,
+    self_ref: Weak<RefCell<Self>>,
+    on_graph_event_cb: Box<OnGraphEventCb>,
     frame_scheduler: FrameScheduler,
-    _resize_observer: ResizeObserver,
-    _controls: Rc<RefCell<Controls>>,
+    resize_observer: ResizeObserver,
+    controls: Rc<RefCell<Controls>>,
     canvas: HtmlCanvasElement,
+    legend: Legend,
+    motion: Motion,
+    color_mode: ColorMode,
+    theme: Theme,
     canvas_needs_size_update: bool,
     canvas_size: Size2D<f32, CanvasSpace>,
     content_size: Size2D<f32, ContentSpace>,
     canvas_to_clip: Transform2D<f32, CanvasSpace, ClipSpace>,
     zoom: Scale<f32, ClipSpace, ClipSpace>,
     translation: Vector2D<f32, ClipSpace>,
+    view_reset: Option<ViewReset>,
+    fling: Fling<CanvasSpace>,
+    content_to_clip: Transform2D<f32, ContentSpace, ClipSpace>,
     view_transform: [f32; 9],
-    renderer: Renderer,
+    active_state: Option<BoardId>,
+    trail: HashMap<BoardId, Duration>,
+    traffic: Traffic,
+    visits: Visits,
+    layout: Box<dyn Arrangement>,
+    arrangement: Option<Scene>,
+    /// The nodes of the arrangement by their position, for finding the node under the pointer
+    spatial_index: Option<SpatialIndex>,
+    transition: Option<Transition>,
+    hovered: Option<BoardId>,
+    highlighted: Option<BoardId>,
+    /// The states of a replay, and the index of the state that it has been played back to
+    replay: Option<(Vec<BoardId>, usize)>,
+    /// The optimal path from the active state to the solution, None if it isn't shown
+    solution_path: Option<Vec<BoardId>>,
+    /// Where the best solve was after as many moves as the player made
+    ghost: Option<BoardId>,
+    /// The states within a few moves of the active state, None if they aren't shown
+    horizon: Option<Horizon>,
+    renderer: Box<dyn GraphRenderer>,
 }
 
 impl GraphView {
-    pub fn new(canvas_id: &str) -> Result<Rc<RefCell<Self>>, JsValue> {
-        let canvas: HtmlCanvasElement = get_element_of_type(canvas_id)?;
-        let renderer: Renderer = Renderer::new(&canvas)?;
+    pub fn new(
+        canvas: HtmlCanvasElement,
+        legend_div: HtmlDivElement,
+        clock: Rc<dyn Clock>,
+        motion: Motion,
+        on_graph_event_cb: Box<OnGraphEventCb>,
+    ) -> Result<Rc<RefCell<Self>>, JsValue> {
+        let legend = Legend::new(legend_div)?;
+        let canvas_for_renderer = canvas.clone();
 
         let view = Rc::new_cyclic(|self_ref| {
             let self_ref_for_on_frame_cb = self_ref.clone();
@@ -101,15 +234,19 @@ impl GraphView {
             let self_ref_for_mouse_event_cb = self_ref.clone();
 
             RefCell::new(Self {
-                _self_ref: self_ref.clone(),
-                frame_scheduler: FrameScheduler::new(Box::new(move |timestamp: Duration| {
-                    self_ref_for_on_frame_cb
-                        .upgrade()
-                        .unwrap()
-                        .borrow_mut()
-                        .draw(timestamp);
-                }) as Box<OnFrameCb>),
-                _resize_observer: ResizeObserver::new(
+                self_ref: self_ref.clone(),
+                on_graph_event_cb,
+                frame_scheduler: FrameScheduler::new(
+                    clock,
+                    Box::new(move |frame: Frame| {
+                        self_ref_for_on_frame_cb
+                            .upgrade()
+                            .unwrap()
+                            .borrow_mut()
+                            .draw(frame.timestamp);
+                    }) as Box<OnFrameCb>,
+                ),
+                resize_observer: ResizeObserver::new(
                     &canvas,
                     Box::new(move |width, height| {
                         self_ref_for_resize_observer_cb
@@ -119,7 +256,7 @@ impl GraphView {
                             .resize(width, height);
                     }),
                 ),
-                _controls: Controls::new(
+                controls: Controls::new(
                     &canvas,
                     Box::new(move |event: ControlEvent| {
                         self_ref_for_mouse_event_cb
@@ -131,21 +268,82 @@ impl GraphView {
                 )
                 .expect("Could not create graph controls"),
                 canvas,
+                legend,
+                motion,
+                color_mode: ColorMode::default(),
+                theme: Theme::default(),
                 canvas_needs_size_update: false,
                 canvas_size: Size2D::zero(),
                 content_size: Size2D::zero(),
                 canvas_to_clip: Transform2D::identity(),
                 zoom: Scale::identity(),
                 translation: ClipSpace::CLIP_SPACE_OFFSET,
+                view_reset: None,
+                fling: Fling::default(),
+                content_to_clip: Transform2D::identity(),
                 view_transform: [0.0; 9],
-                renderer,
+                active_state: None,
+                trail: HashMap::new(),
+                traffic: Traffic::default(),
+                visits: Visits::load(),
+                layout: Layout::default().create(),
+                arrangement: None,
+                spatial_index: None,
+                transition: None,
+                hovered: None,
+                highlighted: None,
+                replay: None,
+                ghost: None,
+                horizon: None,
+                solution_path: None,
+                renderer: Box::new(PendingRenderer),
             })
         });
 
+        // Picking the renderer might involve waiting for the GPU, the view draws nothing until then
+        let view_for_renderer = Rc::downgrade(&view);
+        spawn_local(async move {
+            match create_renderer(canvas_for_renderer).await {
+                Ok(renderer) => {
+                    if let Some(view) = view_for_renderer.upgrade() {
+                        view.borrow_mut().set_renderer(renderer);
+                    }
+                }
+                Err(error) => log::error!("Unable to create a graph renderer: {error:?}"),
+            }
+        });
+
         view.borrow_mut().recalculate_view_transform();
         Ok(view)
     }
 
+    /// Stop listening to the canvas, and stop drawing
+    pub fn destroy(&mut self) -> Result<(), JsValue> {
+        self.controls.borrow().detach();
+        self.resize_observer.disconnect();
+        self.fling.stop();
+        self.transition = None;
+        self.view_reset = None;
+        self.frame_scheduler.cancel()
+    }
+
+    /// Replace the renderer, and provide it with everything that the previous renderer was given
+    fn set_renderer(&mut self, renderer: Box<dyn GraphRenderer>) {
+        self.renderer = renderer;
+        if !self.canvas_size.is_empty() {
+            self.renderer.set_viewport(
+                self.canvas_size.width as i32,
+                self.canvas_size.height as i32,
+            );
+        }
+        if let Some(arrangement) = &self.arrangement {
+            let points = arrangement.points.clone();
+            self.upload_points(&points);
+            self.upload_edges(&points);
+        }
+        self.schedule_draw();
+    }
+
     fn resize(&mut self, width: f64, height: f64) {
         self.canvas_needs_size_update = true;
         self.renderer.set_viewport(width as i32, height as i32);
@@ -159,75 +357,537 @@ impl GraphView {
         self.frame_scheduler.schedule().unwrap();
     }
 
-    fn draw(&mut self, _timestamp: Duration) {
+    /// Drive an animation of the view every frame, until the tick returns false. This replaces a
+    /// running animation of the same name.
+    fn animate(
+        &mut self,
+        name: &'static str,
+        mut tick: impl FnMut(&mut Self, Duration) -> bool + 'static,
+    ) {
+        let self_ref = self.self_ref.clone();
+        self.frame_scheduler
+            .animate(
+                name,
+                Box::new(move |frame: Frame| {
+                    self_ref
+                        .upgrade()
+                        .is_some_and(|view| tick(&mut view.borrow_mut(), frame.timestamp))
+                }),
+            )
+            .unwrap();
+    }
+
+    fn draw(&mut self, timestamp: Duration) {
         if self.canvas_needs_size_update {
             self.canvas_needs_size_update = false;
             self.canvas.set_width(self.canvas_size.width as u32);
             self.canvas.set_height(self.canvas_size.height as u32);
         }
 
-        self.renderer.draw(&self.view_transform)
+        // The animations have been driven through this frame already
+        self.renderer.draw(
+            &self.view_transform,
+            timestamp.as_secs_f32(),
+            TRAIL_FADE_DURATION.as_secs_f32(),
+        );
     }
 
-    pub fn set_data(&mut self, graph: &Graph, active_state: BoardId) {
-        // Create an arrangement from the graph data
-        let arrangement = Arrangement::new(graph, active_state);
+    /// Morph the points towards the latest arrangement
+    /// @returns whether the transition is still running
+    fn animate_transition(&mut self, timestamp: Duration) -> bool {
+        let Some(transition) = &mut self.transition else {
+            return false;
+        };
+        let (width, height) = transition.size_at(timestamp);
+        let (points, finished) = transition.points_at(timestamp);
+        self.content_size = ContentSpace::add_padding(Size2D::new(width, height));
+        self.recalculate_view_transform();
+        self.upload_points(&points);
+        self.upload_edges(&points);
+        if finished {
+            self.transition = None;
+        }
+        !finished
+    }
 
+    fn upload_points(&mut self, points: &[f32]) {
+        let points = self.mark_points(points);
         // Upload the data to the GPU
-        let vertices_array = unsafe { js_sys::Float32Array::view(&arrangement.points) };
-        self.renderer.set_data(&vertices_array);
+        self.renderer.set_data(&points);
+    }
 
-        // Store the content's size with padding applied
-        self.content_size = ContentSpace::add_padding(Size2D::new(
-            arrangement.width as f32,
-            arrangement.height as f32,
-        ));
+    /// Apply the marks of the paths and the linked highlight to the points, on a copy if needed
+    fn mark_points<'a>(&self, points: &'a [f32]) -> Cow<'a, [f32]> {
+        let Some(indices) = self
+            .arrangement
+            .as_ref()
+            .map(|arrangement| &arrangement.indices)
+        else {
+            return Cow::Borrowed(points);
+        };
+
+        // Mark the optimal path to the solution
+        let mut marks = Vec::new();
+        for state in self.solution_path.iter().flatten() {
+            if Some(*state) == self.active_state {
+                continue;
+            }
+            marks.extend(
+                indices
+                    .get(state)
+                    .map(|index| (*index, SOLUTION_PATH_SIZE, self.theme.graph_solution_path)),
+            );
+        }
+
+        // Mark the path of a replay, the states that were played back stand out like the trajectory
+        if let Some((states, position)) = &self.replay {
+            for (index, state) in states.iter().enumerate() {
+                if Some(*state) == self.active_state {
+                    continue;
+                }
+                let color = match index <= *position {
+                    true => self.theme.trajectory,
+                    false => self.theme.graph_linked_highlight,
+                };
+                marks.extend(
+                    indices
+                        .get(state)
+                        .map(|index| (*index, REPLAY_PATH_SIZE, color)),
+                );
+            }
+        }
+        // The ghost looks like a translucent active node, by blending the active color into the node's color
+        let ghost = self
+            .ghost
+            .filter(|ghost| Some(*ghost) != self.active_state)
+            .and_then(|ghost| indices.get(&ghost).copied());
+
+        // Emphasize the node that another view linked to
+        marks.extend(self.highlighted.and_then(|id| {
+            let index = *indices.get(&id)?;
+            Some((
+                index,
+                LINKED_HIGHLIGHT_SIZE,
+                self.theme.graph_linked_highlight,
+            ))
+        }));
+
+        // The horizon is a halo, which fades with the moves it takes to get there
+        let horizon: Vec<(usize, f32)> = self
+            .horizon
+            .iter()
+            .flat_map(|horizon| {
+                let fade = 1.0 / horizon.max_moves() as f32;
+                horizon.states().filter_map(move |(state, moves)| {
+                    let index = *indices.get(&state)?;
+                    Some((index, HORIZON_OPACITY * (1.0 - (moves - 1) as f32 * fade)))
+                })
+            })
+            .collect();
+
+        if marks.is_empty() && ghost.is_none() && horizon.is_empty() {
+            return Cow::Borrowed(points);
+        }
+        let mut copy = points.to_vec();
+        let active = self.theme.graph_active.to_normalized();
+        for (index, opacity) in horizon {
+            let offset = index * POINT_STRIDE;
+            copy[offset + 2] = copy[offset + 2].max(HORIZON_SIZE);
+            for (component, active) in copy[offset + 3..offset + 6].iter_mut().zip(active) {
+                *component += (active - *component) * opacity;
+            }
+            copy[offset + 6..offset + 9].copy_from_slice(&active);
+        }
+        if let Some(index) = ghost {
+            let offset = index * POINT_STRIDE;
+            copy[offset + 2] = GHOST_SIZE;
+            for (component, active) in copy[offset + 3..offset + 6].iter_mut().zip(active) {
+                *component = (*component + active) / 2.0;
+            }
+            copy[offset + 6..offset + 9].copy_from_slice(&active);
+        }
+        let outline = self.theme.graph_highlight_outline.to_normalized();
+        for (index, size, color) in marks {
+            let offset = index * POINT_STRIDE;
+            copy[offset + 2] = size;
+            copy[offset + 3..offset + 6].copy_from_slice(&color.to_normalized());
+            copy[offset + 6..offset + 9].copy_from_slice(&outline);
+        }
+        Cow::Owned(copy)
+    }
+
+    /// Draw the current arrangement as SVG, with its edges and highlights
+    /// @returns None until the first state is arranged
+    pub fn export_svg(&self) -> Option<String> {
+        let arrangement = self.arrangement.as_ref()?;
+        let mut edges = arrangement.edges(&self.traffic, &self.theme);
+        if let Some(path) = &self.solution_path {
+            edges.extend(arrangement.path_edges(path, self.theme.graph_solution_path));
+        }
+        let points = self.mark_points(&arrangement.points);
+        Some(svg::to_svg(arrangement, &points, &edges))
+    }
+
+    /// Highlight a node on behalf of another view, e.g. the resulting state of a hovered move
+    pub fn highlight(&mut self, state: Option<BoardId>) {
+        if self.highlighted == state {
+            return;
+        }
+        self.highlighted = state;
+        self.reupload_points();
+    }
+
+    /// Mark where the best solve was after as many moves as the player made
+    pub fn show_ghost(&mut self, state: Option<BoardId>) {
+        if self.ghost == state {
+            return;
+        }
+        self.ghost = state;
+        self.reupload_points();
+    }
+
+    /// Surround the states that can be reached within a number of moves with a halo, this follows the
+    /// player's moves. None or zero moves hides it.
+    pub fn show_horizon(&mut self, graph: &Graph, max_moves: Option<u32>) {
+        self.horizon = max_moves
+            .filter(|max_moves| *max_moves > 0)
+            .map(Horizon::new);
+        self.update_horizon(graph);
+        self.reupload_points();
+    }
+
+    fn update_horizon(&mut self, graph: &Graph) {
+        if let (Some(horizon), Some(active_state)) = (&mut self.horizon, self.active_state) {
+            horizon.update(graph, active_state);
+        }
+    }
+
+    /// Mark the path of a replay, and how far it has been played back
+    pub fn show_replay(&mut self, replay: Option<(Vec<BoardId>, usize)>) {
+        self.replay = replay;
+        self.reupload_points();
+    }
+
+    /// Overlay the optimal path from the active state to the solution, this follows the player's moves
+    pub fn show_solution_path(&mut self, graph: &Graph, enabled: bool) {
+        self.solution_path = enabled.then(Vec::new);
+        self.arrange(graph);
+    }
+
+    /// Upload the edges that the player has traversed and the solution path, between the given points
+    fn upload_edges(&mut self, points: &[f32]) {
+        let Some(arrangement) = &self.arrangement else {
+            return;
+        };
+        let edges = arrangement.edges_at(points, &self.traffic, &self.theme);
+        let overlay = match &self.solution_path {
+            Some(path) => arrangement.path_edges_at(points, path, self.theme.graph_solution_path),
+            None => Vec::new(),
+        };
+        self.renderer.set_edge_data(&edges);
+        self.renderer.set_overlay_data(&overlay);
+    }
+
+    fn reupload_points(&mut self) {
+        // A running transition applies the marks and moves the edges on its next frame, otherwise reupload
+        if self.transition.is_none() {
+            if let Some(points) = self.arrangement.as_ref().map(|a| a.points.clone()) {
+                self.upload_points(&points);
+                self.upload_edges(&points);
+            }
+        }
+        self.schedule_draw();
+    }
+
+    fn trail_is_fading(&self, timestamp: Duration) -> bool {
+        self.trail
+            .values()
+            .any(|visited_at| timestamp.saturating_sub(*visited_at) < TRAIL_FADE_DURATION)
+    }
+
+    pub fn set_data(&mut self, graph: &Graph, active_state: BoardId) {
+        // Add the state we are leaving to the trail, and forget the states that have faded out completely
+        let now = self.frame_scheduler.clock().now();
+        self.trail
+            .retain(|_, visited_at| now.saturating_sub(*visited_at) < TRAIL_FADE_DURATION);
+        let previous_state = self.active_state.replace(active_state);
+        // Showing the same state again, e.g. after a setting changed, is not a visit
+        if previous_state != Some(active_state) {
+            if let Some(previous_state) = previous_state {
+                self.trail.insert(previous_state, now);
+                // The trail fades on the GPU, it only needs frames until it's gone
+                self.animate("trail", |view, timestamp| view.trail_is_fading(timestamp));
+
+                // Only count actual moves as traffic, not jumps between unconnected states
+                let is_move = graph.get(previous_state).is_some_and(|node| {
+                    graph
+                        .neighbors(node)
+                        .any(|(_edge, neighbor)| neighbor.id == active_state)
+                });
+                if is_move {
+                    self.traffic.record(previous_state, active_state);
+                }
+            }
+
+            self.visits.record(active_state);
+
+            // Highlights from other views refer to the previous state's neighborhood
+            self.highlighted = None;
+        }
+        self.update_horizon(graph);
+        self.arrange(graph);
+    }
+
+    pub fn set_theme(&mut self, graph: &Graph, theme: Theme) {
+        self.theme = theme;
+        self.arrange(graph);
+    }
+
+    /// Change the metric that determines the color of the nodes
+    pub fn set_color_mode(&mut self, graph: &Graph, color_mode: ColorMode) {
+        self.color_mode = color_mode;
+        self.arrange(graph);
+    }
+
+    /// Change how the nodes are placed, they morph from their current places into the new layout
+    pub fn set_layout(&mut self, graph: &Graph, layout: Box<dyn Arrangement>) {
+        self.layout = layout;
+        self.arrange(graph);
+    }
+
+    fn arrange(&mut self, graph: &Graph) {
+        let Some(active_state) = self.active_state else {
+            // Nothing to arrange until the first state is set
+            return;
+        };
+
+        if let Some(path) = &mut self.solution_path {
+            *path = graph.solution_path(active_state);
+        }
+
+        // Place the nodes, and style their points
+        let arranged = self.layout.arrange(graph, active_state);
+        let arrangement = Scene::new(
+            graph,
+            &arranged,
+            active_state,
+            &self.trail,
+            &self.visits,
+            self.color_mode,
+            &self.theme,
+        );
+        self.legend.set_range(arrangement.color_range.as_ref());
+
+        // Morph from the previous arrangement, or show the arrangement straight away if it's the
+        // first one or if motion is reduced
+        match &self.arrangement {
+            Some(previous) if !self.motion.is_reduced() => {
+                let running = self.transition.as_ref();
+                self.transition = Some(Transition::new(previous, &arrangement, running));
+                self.animate("transition", Self::animate_transition);
+            }
+            _ => self.transition = None,
+        }
+
+        // Store the content's size with padding applied, a transition morphs towards it
+        if self.transition.is_none() {
+            self.content_size =
+                ContentSpace::add_padding(Size2D::new(arrangement.width, arrangement.height));
+        }
+        self.spatial_index = Some(SpatialIndex::new(&arrangement.points));
+        self.arrangement = Some(arrangement);
+        self.reupload_points();
         self.recalculate_view_transform();
         self.schedule_draw();
     }
 
     fn handle_pointer_event(&mut self, event: ControlEvent) {
         match event {
-            ControlEvent::Down(_coordinates) => {}
-            ControlEvent::Move(coordinates) => {
-                self.handle_translation(Vector2D::new(coordinates.x as f32, -coordinates.y as f32))
+            ControlEvent::Down(_coordinates) => self.fling.stop(),
+            ControlEvent::Move(delta) => {
+                let translation = Vector2D::new(delta.x as f32, -delta.y as f32);
+                self.fling
+                    .track(translation, self.frame_scheduler.clock().now());
+                self.handle_translation(translation)
+            }
+            ControlEvent::Up() => {
+                if self.motion.is_reduced() {
+                    // The content stops where it was released
+                    self.fling.stop();
+                } else if self.fling.release(self.frame_scheduler.clock().now()) {
+                    self.animate("fling", Self::animate_fling);
+                }
+            }
+            ControlEvent::Click(coordinates) => {
+                if let Some(id) = self.pick(coordinates) {
+                    (self.on_graph_event_cb)(GraphEvent::Selected(id));
+                }
+            }
+            ControlEvent::DoubleClick(_coordinates) => self.reset_view(),
+            ControlEvent::Hover(coordinates) => self.hover(coordinates),
+            ControlEvent::Zoom(coordinates, movement) => {
+                self.accumulate_zoom(movement as f32, coordinates)
+            }
+        }
+    }
+
+    /// Find the node at the given canvas coordinates
+    fn pick(&mut self, coordinates: Coordinates) -> Option<BoardId> {
+        let index = match (&self.spatial_index, &self.transition) {
+            (Some(spatial_index), None) => {
+                // The canvas y-axis points down, while the clip-space y-axis points up
+                let clip = self.canvas_to_clip.transform_point(euclid::Point2D::new(
+                    coordinates.x as f32,
+                    self.canvas_size.height - coordinates.y as f32,
+                ));
+                let content = self.content_to_clip.inverse()?.transform_point(clip);
+                // Content space is scaled equally along both axes
+                let radius = PICK_RADIUS * self.canvas_to_clip.m11 / self.content_to_clip.m11;
+                spatial_index.nearest([content.x, content.y], radius)?
+            }
+            // The points are moving, only the picking buffer knows where they are drawn. It has its
+            // origin at the bottom left, while the canvas starts at the top left.
+            _ => {
+                let x = coordinates.x as i32;
+                let y = self.canvas_size.height as i32 - 1 - coordinates.y as i32;
+                self.renderer.pick(&self.view_transform, x, y)?
+            }
+        };
+        self.arrangement.as_ref()?.ids.get(index).copied()
+    }
+
+    /// Show which node is under the pointer, through the cursor and a tooltip
+    fn hover(&mut self, coordinates: Coordinates) {
+        let hovered = self.pick(coordinates);
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            (self.on_graph_event_cb)(GraphEvent::Hovered(hovered));
+        }
+        let tooltip = hovered.and_then(|id| {
+            let arrangement = self.arrangement.as_ref()?;
+            Some(
+                match arrangement.distances_to_solution[arrangement.indices[&id]] {
+                    // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+                    Some(distance) => match distance.saturating_sub(1) {
+                        1 => "1 move to solution".to_string(),
+                        moves => format!("{moves} moves to solution"),
+                    },
+                    None => "No way to the solution".to_string(),
+                },
+            )
+        });
+        let style = self.canvas.style();
+        match tooltip {
+            Some(tooltip) => {
+                self.canvas.set_title(&tooltip);
+                style.set_property("cursor", "pointer").unwrap();
+            }
+            None => {
+                self.canvas.set_title("");
+                style.remove_property("cursor").unwrap();
             }
-            ControlEvent::Up() => {}
         }
     }
 
-    fn _accumulate_zoom(&mut self, zoom_movement: f32, target_x: f32, target_y: f32) {
-        let target_begin = self
-            .canvas_to_clip
-            .transform_vector(Vector2D::new(target_x, target_y));
-        let transform_begin = ClipSpace::transform_from_content(
+    /// Zoom in or out, while keeping the content under the target coordinates in place
+    fn accumulate_zoom(&mut self, zoom_movement: f32, target: Coordinates) {
+        if self.content_size.is_empty() {
+            return;
+        }
+        self.view_reset = None;
+        self.fling.stop();
+
+        // Scrolling down zooms out
+        let zoom = Scale::new(
+            (self.zoom.get() * (-zoom_movement * ZOOM_SPEED).exp())
+                .clamp(ZOOM_MINIMUM.get(), ZOOM_MAXIMUM.get()),
+        );
+
+        // The canvas y-axis points down, while the clip-space y-axis points up
+        let target = self.canvas_to_clip.transform_point(euclid::Point2D::new(
+            target.x as f32,
+            self.canvas_size.height - target.y as f32,
+        ));
+
+        // The content scales linearly with the zoom, so scale the translation's offset from the target along
+        let ratio = zoom.get() / self.zoom.get();
+        let translation = target.to_vector() + (self.translation - target.to_vector()) * ratio;
+
+        self.zoom = zoom;
+        self.translation = ClipSpace::clamp_translation(
             self.canvas_size,
             self.content_size,
             self.zoom,
-            self.translation,
-        )
-        .inverse()
-        .unwrap();
-        let _target_content = transform_begin.transform_vector(target_begin);
-
-        // Convert zoom movement from canvas pixels to clip space delta
-        let _zoom_movement_clip = self
-            .canvas_to_clip
-            .transform_vector(Vector2D::new(0.0, zoom_movement));
-
-        // TODO(Menno 10.05.2025) Apply the zoom and readjust the translation so that the target x and y remain at the
-        //  same content space point.
+            translation,
+        );
         self.recalculate_view_transform();
         self.schedule_draw();
     }
 
     fn handle_translation(&mut self, translation: Vector2D<f32, CanvasSpace>) {
-        // TODO(Menno 04.05.2025) Clamp this translation
-        self.translation += self.canvas_to_clip.transform_vector(translation);
+        // The user takes over from a running reset
+        self.view_reset = None;
+        self.translation = ClipSpace::clamp_translation(
+            self.canvas_size,
+            self.content_size,
+            self.zoom,
+            self.translation + self.canvas_to_clip.transform_vector(translation),
+        );
         self.recalculate_view_transform();
         self.schedule_draw();
     }
 
+    /// Animate back to the view that fits the whole content
+    fn reset_view(&mut self) {
+        self.fling.stop();
+        self.view_reset = Some(ViewReset {
+            from_zoom: self.zoom,
+            from_translation: self.translation,
+            start_time: None,
+        });
+        self.animate("view_reset", Self::animate_view_reset);
+    }
+
+    /// Keep the content moving after a drag was released
+    /// @returns whether the content is still flinging
+    fn animate_fling(&mut self, timestamp: Duration) -> bool {
+        let Some(displacement) = self.fling.step(timestamp) else {
+            return false;
+        };
+        let previous_translation = self.translation;
+        self.handle_translation(displacement);
+        // Hitting the clamped edge stops the content dead
+        if self.translation == previous_translation {
+            self.fling.stop();
+        }
+        self.fling.is_flinging()
+    }
+
+    /// Apply the view reset animation for the given frame timestamp
+    /// @returns whether the reset is still running
+    fn animate_view_reset(&mut self, timestamp: Duration) -> bool {
+        let Some(reset) = &mut self.view_reset else {
+            return false;
+        };
+        let elapsed = timestamp.saturating_sub(*reset.start_time.get_or_insert(timestamp));
+        let duration = self.motion.scale_duration(VIEW_RESET_DURATION);
+        let progress = match duration.is_zero() {
+            true => 1.0,
+            false => (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0),
+        };
+        let eased = EaseInOutCubic.y(progress) as f32;
+
+        self.zoom = Scale::new(reset.from_zoom.get() + (1.0 - reset.from_zoom.get()) * eased);
+        self.translation = reset
+            .from_translation
+            .lerp(ClipSpace::CLIP_SPACE_OFFSET, eased);
+        if progress >= 1.0 {
+            self.view_reset = None;
+        }
+        self.recalculate_view_transform();
+        self.view_reset.is_some()
+    }
+
     fn recalculate_view_transform(&mut self) {
         let transform = ClipSpace::transform_from_content(
             self.canvas_size,
@@ -235,6 +895,7 @@ impl GraphView {
             self.zoom,
             self.translation,
         );
+        self.content_to_clip = transform;
         let [m11, m12, m21, m22, m31, m32] = transform.to_array();
         self.view_transform = [m11, m12, 0.0, m21, m22, 0.0, m31, m32, 1.0];
     }
@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+/// Evenly spaced samples of the perceptually uniform viridis color map, from low to high
+const VIRIDIS: [[f32; 3]; 9] = [
+    [0.267, 0.005, 0.329],
+    [0.279, 0.175, 0.483],
+    [0.230, 0.322, 0.546],
+    [0.173, 0.449, 0.558],
+    [0.128, 0.567, 0.551],
+    [0.153, 0.680, 0.506],
+    [0.361, 0.784, 0.388],
+    [0.667, 0.862, 0.196],
+    [0.993, 0.906, 0.144],
+];
+
+/// Sample the gradient at position t, in range [0.0, 1.0]
+pub fn sample(t: f32) -> [f32; 3] {
+    let position = t.clamp(0.0, 1.0) * (VIRIDIS.len() - 1) as f32;
+    let index = (position.floor() as usize).min(VIRIDIS.len() - 2);
+    let fraction = position - index as f32;
+    let (low, high) = (VIRIDIS[index], VIRIDIS[index + 1]);
+    [0, 1, 2].map(|channel| low[channel] + (high[channel] - low[channel]) * fraction)
+}
+
+/// Describe the gradient as a CSS linear-gradient, for use in HTML elements
+pub fn to_css_gradient() -> String {
+    let stops = VIRIDIS
+        .iter()
+        .map(|[r, g, b]| format!("rgb({:.0} {:.0} {:.0})", r * 255.0, g * 255.0, b * 255.0))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("linear-gradient(to right, {stops})")
+}
@@ -0,0 +1,123 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Raster pictures of the arrangement, drawn without a browser, e.g. for documentation images
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::BoardId;
+use crate::graph::Graph;
+use crate::views::graph_view::arrangement::{
+    Arrangement, BinnedLayout, ColorMode, Scene, EDGE_VERTEX_STRIDE, POINT_STRIDE, SHAPE_DIAMOND,
+};
+use crate::views::graph_view::svg::{edge_opacity, picture_size, to_picture_space, LINE_WIDTH};
+use crate::views::graph_view::visits::Visits;
+use crate::views::theme::Theme;
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use tiny_skia::{Color, FillRule, Paint, Path, PathBuilder, Pixmap, Stroke, Transform};
+
+fn to_color([red, green, blue]: [f32; 3], alpha: f32) -> Color {
+    Color::from_rgba(red, green, blue, alpha).unwrap_or(Color::BLACK)
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(color);
+    paint.anti_alias = true;
+    paint
+}
+
+fn diamond(x: f32, y: f32, radius: f32) -> Option<Path> {
+    let mut builder = PathBuilder::new();
+    builder.move_to(x, y - radius);
+    builder.line_to(x + radius, y);
+    builder.line_to(x, y + radius);
+    builder.line_to(x - radius, y);
+    builder.close();
+    builder.finish()
+}
+
+/// Draw the arrangement as PNG, with the same shapes as its SVG
+/// @param points the points of the arrangement, with any marks applied
+/// @param edges the vertices of the edges, drawn in order beneath the points
+pub fn to_png(arrangement: &Scene, points: &[f32], edges: &[f32]) -> Result<Vec<u8>> {
+    let (width, height) = picture_size(arrangement);
+    let mut pixmap = Pixmap::new(width.ceil() as u32, height.ceil() as u32)
+        .context("The arrangement is too large to draw")?;
+    let stroke = Stroke {
+        width: LINE_WIDTH,
+        ..Stroke::default()
+    };
+
+    for edge in edges.chunks_exact(EDGE_VERTEX_STRIDE * 2) {
+        let (x1, y1) = to_picture_space(height, edge[0], edge[1]);
+        let (x2, y2) = to_picture_space(
+            height,
+            edge[EDGE_VERTEX_STRIDE],
+            edge[EDGE_VERTEX_STRIDE + 1],
+        );
+        let mut builder = PathBuilder::new();
+        builder.move_to(x1, y1);
+        builder.line_to(x2, y2);
+        let Some(line) = builder.finish() else {
+            continue;
+        };
+        let color = to_color([edge[3], edge[4], edge[5]], edge_opacity(edge[2]));
+        pixmap.stroke_path(&line, &paint(color), &stroke, Transform::identity(), None);
+    }
+
+    for point in points.chunks_exact(POINT_STRIDE) {
+        let (x, y) = to_picture_space(height, point[0], point[1]);
+        let radius = point[2] * 0.5;
+        let shape = match point[10] == SHAPE_DIAMOND {
+            true => diamond(x, y, radius),
+            false => PathBuilder::from_circle(x, y, radius),
+        };
+        let Some(shape) = shape else {
+            continue;
+        };
+        let fill = to_color([point[3], point[4], point[5]], 1.0);
+        let outline = to_color([point[6], point[7], point[8]], 1.0);
+        pixmap.fill_path(
+            &shape,
+            &paint(fill),
+            FillRule::Winding,
+            Transform::identity(),
+            None,
+        );
+        pixmap.stroke_path(
+            &shape,
+            &paint(outline),
+            &stroke,
+            Transform::identity(),
+            None,
+        );
+    }
+
+    pixmap
+        .encode_png()
+        .map_err(|error| anyhow!("Failed to encode PNG: {error}"))
+}
+
+/// Draw the graph like the graph view arranges it, with the optimal path from the active state to
+/// the solution, in the default theme
+pub fn render_graph_png(graph: &Graph, active_state: BoardId) -> Result<Vec<u8>> {
+    let theme = Theme::default();
+    let arranged = BinnedLayout::default().arrange(graph, active_state);
+    let arrangement = Scene::new(
+        graph,
+        &arranged,
+        active_state,
+        &HashMap::new(),
+        &Visits::default(),
+        ColorMode::default(),
+        &theme,
+    );
+    let edges = arrangement.path_edges(
+        &graph.solution_path(active_state),
+        theme.graph_solution_path,
+    );
+    to_png(&arrangement, &arrangement.points, &edges)
+}
@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::{Scene, SHAPE_CIRCLE, SHAPE_DIAMOND};
+use crate::views::graph_view::raster::to_png;
+use std::collections::{HashMap, HashSet};
+use tiny_skia::Pixmap;
+
+/// A point at content coordinates, with a size, a color, an outline color, and a shape
+fn point(x: f32, y: f32, shape: f32) -> [f32; 11] {
+    [x, y, 4.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -1.0, shape]
+}
+
+#[test]
+fn test_png_export() {
+    let mut points = Vec::new();
+    points.extend_from_slice(&point(0.0, 0.0, SHAPE_CIRCLE));
+    points.extend_from_slice(&point(1.0, 0.0, SHAPE_DIAMOND));
+    points.extend_from_slice(&point(1.0, 1.0, SHAPE_CIRCLE));
+    let arrangement = Scene {
+        width: 2.0,
+        height: 2.0,
+        points: points.clone(),
+        ids: vec![1, 2, 3],
+        indices: HashMap::from([(1, 0), (2, 1), (3, 2)]),
+        distances_to_solution: vec![Some(0), Some(1), Some(1)],
+        color_range: None,
+        one_way: HashSet::new(),
+    };
+    let edges = arrangement.path_edges(&[1, 2, 3], crate::views::theme::Rgb(0, 128, 255));
+
+    let png = to_png(&arrangement, &points, &edges).unwrap();
+    let pixmap = Pixmap::decode_png(&png).unwrap();
+    // The picture has the same size as the SVG
+    assert_eq!((pixmap.width(), pixmap.height()), (36, 36));
+
+    // The first point is filled in the bottom left corner, half a unit from the border
+    let center = pixmap.pixel(6, 30).unwrap();
+    assert_eq!(
+        (center.red(), center.green(), center.blue(), center.alpha()),
+        (255, 0, 0, 255)
+    );
+    // The edge between the first two points is drawn beneath them
+    let edge = pixmap.pixel(12, 30).unwrap();
+    assert!(edge.alpha() > 0 && edge.blue() > edge.red());
+    // Nothing is drawn where there are no points or edges
+    assert_eq!(pixmap.pixel(6, 6).unwrap().alpha(), 0);
+}
@@ -0,0 +1,12 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+precision mediump float;
+
+varying float f_weight;
+varying vec3 f_color;
+
+void main(void) {
+    // WebGL lines are always one pixel wide, so heavier traffic is expressed through opacity
+    gl_FragColor = vec4(f_color, mix(0.15, 0.9, f_weight));
+}
@@ -0,0 +1,42 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+// The WebGPU counterpart of edge_vertex.glsl and edge_fragment.glsl
+
+struct Uniforms {
+    view_transform: mat3x3<f32>,
+    viewport_size: vec2<f32>,
+    time: f32,
+    trail_duration: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+struct Vertex {
+    @location(0) coordinates: vec2<f32>,
+    @location(1) weight: f32,
+    @location(2) color: vec3<f32>,
+}
+
+struct Fragment {
+    @builtin(position) position: vec4<f32>,
+    @location(0) weight: f32,
+    @location(1) color: vec3<f32>,
+}
+
+@vertex
+fn vertex_main(vertex: Vertex) -> Fragment {
+    var fragment: Fragment;
+    fragment.weight = vertex.weight;
+    fragment.color = vertex.color;
+    let transformed_vertex = uniforms.view_transform * vec3<f32>(vertex.coordinates, 1.0);
+    fragment.position = vec4<f32>(transformed_vertex.xy, 0.0, 1.0);
+    return fragment;
+}
+
+@fragment
+fn fragment_main(fragment: Fragment) -> @location(0) vec4<f32> {
+    // Lines are always one pixel wide, so heavier traffic is expressed through opacity
+    let alpha = mix(0.15, 0.9, fragment.weight);
+    return vec4<f32>(fragment.color * alpha, alpha);
+}
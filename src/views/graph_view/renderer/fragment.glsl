@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+precision mediump float;
+
+varying vec4 f_color;
+varying vec4 f_outline_color;
+varying vec2 f_corner;
+varying float f_point_size;
+varying float f_shape;
+
+// The width of the outline, in pixels
+const float OUTLINE_WIDTH = 1.0;
+
+// The shapes of the points, these match the arrangement's shape values
+const float SHAPE_DIAMOND = 1.0;
+
+void main(void) {
+    // The distance from the point's center in pixels, circles use the euclidean distance, diamonds the manhattan distance
+    float radius = f_point_size * 0.5;
+    float relative_distance = length(f_corner);
+    if (f_shape == SHAPE_DIAMOND) {
+        relative_distance = abs(f_corner.x) + abs(f_corner.y);
+    }
+    float distance = relative_distance * radius;
+    if (distance > radius) {
+        discard;
+    }
+
+    // Smooth the shape's edge over a single pixel, and blend the outline in the same way
+    float coverage = 1.0 - smoothstep(radius - 1.0, radius, distance);
+    float outline = smoothstep(radius - OUTLINE_WIDTH - 1.0, radius - OUTLINE_WIDTH, distance);
+    vec4 color = mix(f_color, f_outline_color, outline);
+    gl_FragColor = vec4(color.rgb, color.a * coverage);
+}
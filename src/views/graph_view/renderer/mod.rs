@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+mod webgl;
+mod webgpu;
+
+use crate::views::graph_view::renderer::webgl::WebGlRenderer;
+use crate::views::graph_view::renderer::webgpu::WebGpuRenderer;
+use wasm_bindgen::JsValue;
+use web_sys::HtmlCanvasElement;
+
+/// Draws the arranged graph onto a canvas, the points and edges are laid out as described by the arrangement
+pub trait GraphRenderer {
+    fn set_data(&mut self, points: &[f32]);
+
+    fn set_edge_data(&mut self, edges: &[f32]);
+
+    /// Set the highlighted edges, these are drawn on top of the other edges
+    fn set_overlay_data(&mut self, edges: &[f32]);
+
+    fn set_viewport(&mut self, width: i32, height: i32);
+
+    /// Find the point at the given pixel, the origin is the bottom left of the canvas
+    /// @returns the index of the point, if any
+    fn pick(&mut self, view_transform: &[f32; 9], x: i32, y: i32) -> Option<usize>;
+
+    /// Draw the edges and points, the time and trail duration are in seconds, and drive the trail fading
+    fn draw(&mut self, view_transform: &[f32; 9], time: f32, trail_duration: f32);
+}
+
+/// Stands in while the actual renderer is being created, it draws nothing
+pub struct PendingRenderer;
+
+impl GraphRenderer for PendingRenderer {
+    fn set_data(&mut self, _points: &[f32]) {}
+
+    fn set_edge_data(&mut self, _edges: &[f32]) {}
+
+    fn set_overlay_data(&mut self, _edges: &[f32]) {}
+
+    fn set_viewport(&mut self, _width: i32, _height: i32) {}
+
+    fn pick(&mut self, _view_transform: &[f32; 9], _x: i32, _y: i32) -> Option<usize> {
+        None
+    }
+
+    fn draw(&mut self, _view_transform: &[f32; 9], _time: f32, _trail_duration: f32) {}
+}
+
+/// Create the best renderer that the browser supports, WebGPU is preferred over WebGL2.
+/// A canvas only ever holds one kind of context, so WebGL2 is only set up once WebGPU turned out to be unusable.
+pub async fn create_renderer(canvas: HtmlCanvasElement) -> Result<Box<dyn GraphRenderer>, JsValue> {
+    if webgpu::is_available() {
+        match WebGpuRenderer::new(&canvas).await {
+            Ok(renderer) => {
+                log::info!("Rendering the graph with WebGPU");
+                return Ok(Box::new(renderer));
+            }
+            Err(error) => log::warn!("Falling back to WebGL2, WebGPU is unusable: {error:#}"),
+        }
+    }
+    Ok(Box::new(WebGlRenderer::new(&canvas)?))
+}
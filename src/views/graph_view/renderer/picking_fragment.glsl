@@ -0,0 +1,13 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+precision highp float;
+
+varying highp float f_pick_id;
+
+void main(void) {
+    // Encode the ID into the red, green, and blue bytes, with the least significant byte in red
+    float id = floor(f_pick_id + 0.5);
+    vec3 bytes = vec3(mod(id, 256.0), mod(floor(id / 256.0), 256.0), floor(id / 65536.0));
+    gl_FragColor = vec4(bytes / 255.0, 1.0);
+}
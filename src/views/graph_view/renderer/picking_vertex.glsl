@@ -0,0 +1,21 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+uniform mat3 view_transform;
+uniform vec2 viewport_size;
+attribute vec2 corner;
+attribute vec2 coordinates;
+attribute float point_size;
+attribute float pick_id;
+
+varying highp float f_pick_id;
+
+// Enlarge the points a bit, so that small points remain easy to hit
+const float PICK_MARGIN = 4.0;
+
+void main(void) {
+    f_pick_id = pick_id;
+    vec3 center = view_transform * vec3(coordinates, 1.0);
+    vec2 offset = corner * (point_size + PICK_MARGIN) / viewport_size;
+    gl_Position = vec4(center.xy + offset, center.z, 1.0);
+}
@@ -0,0 +1,89 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+// The WebGPU counterpart of vertex.glsl and fragment.glsl, each point is an instance of a quad
+
+struct Uniforms {
+    view_transform: mat3x3<f32>,
+    viewport_size: vec2<f32>,
+    time: f32,
+    trail_duration: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+struct Point {
+    @location(0) corner: vec2<f32>,
+    @location(1) coordinates: vec2<f32>,
+    @location(2) point_size: f32,
+    @location(3) color: vec3<f32>,
+    @location(4) outline_color: vec3<f32>,
+    @location(5) visited_at: f32,
+    @location(6) shape: f32,
+}
+
+struct Fragment {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) outline_color: vec4<f32>,
+    @location(2) corner: vec2<f32>,
+    @location(3) point_size: f32,
+    @location(4) shape: f32,
+}
+
+const TRAIL_COLOR = vec3<f32>(1.0, 0.6, 0.3);
+
+// An additional pixel around each point, which is used to smooth its edge
+const ANTIALIAS_MARGIN = 1.0;
+
+// The width of the outline, in pixels
+const OUTLINE_WIDTH = 1.0;
+
+// The shapes of the points, these match the arrangement's shape values
+const SHAPE_DIAMOND = 1.0;
+
+@vertex
+fn vertex_main(point: Point) -> Fragment {
+    // Nodes that were recently visited start out in the trail color, and decay back to their own color
+    var trail = 0.0;
+    if (point.visited_at >= 0.0) {
+        trail = clamp(1.0 - (uniforms.time - point.visited_at) / uniforms.trail_duration, 0.0, 1.0);
+    }
+
+    var fragment: Fragment;
+    fragment.color = vec4<f32>(mix(point.color, TRAIL_COLOR, trail), 1.0);
+    fragment.outline_color = vec4<f32>(mix(point.outline_color, TRAIL_COLOR, trail), 1.0);
+    fragment.corner = point.corner;
+    fragment.point_size = point.point_size + trail * 2.0 + ANTIALIAS_MARGIN;
+    fragment.shape = point.shape;
+
+    // Span the quad around the point's center, the point size is in pixels
+    let center = uniforms.view_transform * vec3<f32>(point.coordinates, 1.0);
+    let offset = point.corner * fragment.point_size / uniforms.viewport_size;
+    // WebGPU's clip space depth is [0, 1], the points are drawn at the near plane
+    fragment.position = vec4<f32>(center.xy + offset, 0.0, 1.0);
+    return fragment;
+}
+
+@fragment
+fn fragment_main(fragment: Fragment) -> @location(0) vec4<f32> {
+    // The distance from the point's center in pixels, circles use the euclidean distance, diamonds the manhattan distance
+    let radius = fragment.point_size * 0.5;
+    var relative_distance = length(fragment.corner);
+    if (fragment.shape == SHAPE_DIAMOND) {
+        relative_distance = abs(fragment.corner.x) + abs(fragment.corner.y);
+    }
+    let distance = relative_distance * radius;
+    if (distance > radius) {
+        discard;
+    }
+
+    // Smooth the shape's edge over a single pixel, and blend the outline in the same way
+    let coverage = 1.0 - smoothstep(radius - 1.0, radius, distance);
+    let outline = smoothstep(radius - OUTLINE_WIDTH - 1.0, radius - OUTLINE_WIDTH, distance);
+    let color = mix(fragment.color, fragment.outline_color, outline);
+
+    // The canvas expects premultiplied alpha
+    let alpha = color.a * coverage;
+    return vec4<f32>(color.rgb * alpha, alpha);
+}
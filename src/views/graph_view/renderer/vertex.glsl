@@ -0,0 +1,44 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+uniform mat3 view_transform;
+uniform vec2 viewport_size;
+uniform float time;
+uniform float trail_duration;
+attribute vec2 corner;
+attribute vec2 coordinates;
+attribute float point_size;
+attribute vec3 color;
+attribute vec3 outline_color;
+attribute float visited_at;
+attribute float shape;
+
+varying vec4 f_color;
+varying vec4 f_outline_color;
+varying vec2 f_corner;
+varying float f_point_size;
+varying float f_shape;
+
+const vec3 TRAIL_COLOR = vec3(1.0, 0.6, 0.3);
+
+// An additional pixel around each point, which is used to smooth its edge
+const float ANTIALIAS_MARGIN = 1.0;
+
+void main(void) {
+    // Nodes that were recently visited start out in the trail color, and decay back to their own color
+    float trail = 0.0;
+    if (visited_at >= 0.0) {
+        trail = clamp(1.0 - (time - visited_at) / trail_duration, 0.0, 1.0);
+    }
+
+    f_color = vec4(mix(color, TRAIL_COLOR, trail), 1.0);
+    f_outline_color = vec4(mix(outline_color, TRAIL_COLOR, trail), 1.0);
+    f_corner = corner;
+    f_point_size = point_size + trail * 2.0 + ANTIALIAS_MARGIN;
+    f_shape = shape;
+
+    // Span the quad around the point's center, the point size is in pixels
+    vec3 center = view_transform * vec3(coordinates, 1.0);
+    vec2 offset = corner * f_point_size / viewport_size;
+    gl_Position = vec4(center.xy + offset, center.z, 1.0);
+}
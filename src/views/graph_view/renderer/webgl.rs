@@ -0,0 +1,503 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::{EDGE_VERTEX_STRIDE, POINT_STRIDE};
+use crate::views::graph_view::renderer::GraphRenderer;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    HtmlCanvasElement, WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram,
+    WebGlRenderbuffer, WebGlShader, WebGlUniformLocation, WebGlVertexArrayObject,
+};
+
+/// An offscreen pass that renders the index of each point into a color attachment,
+/// so that the point under the cursor can be found by reading back a single pixel.
+struct PickingPass {
+    shaders: WebGlProgram,
+    view_transform_location: WebGlUniformLocation,
+    viewport_size_location: WebGlUniformLocation,
+    vao: WebGlVertexArrayObject,
+    id_buffer: WebGlBuffer,
+    id_count: i32,
+    framebuffer: WebGlFramebuffer,
+    renderbuffer: WebGlRenderbuffer,
+    /// The view transform that the current picking buffer was rendered with, None if it needs rendering
+    rendered_transform: Option<[f32; 9]>,
+}
+
+/// The WebGL2 implementation of the graph renderer, this is available in practically every browser
+pub struct WebGlRenderer {
+    gl: WebGl2RenderingContext,
+    shaders: WebGlProgram,
+    view_transform_location: WebGlUniformLocation,
+    time_location: WebGlUniformLocation,
+    trail_duration_location: WebGlUniformLocation,
+    viewport_size_location: WebGlUniformLocation,
+    viewport_size: [f32; 2],
+    vao: WebGlVertexArrayObject,
+    vertex_buffer: WebGlBuffer,
+    vertex_count: i32,
+    edge_shaders: WebGlProgram,
+    edge_view_transform_location: WebGlUniformLocation,
+    edge_vao: WebGlVertexArrayObject,
+    edge_buffer: WebGlBuffer,
+    edge_vertex_count: i32,
+    overlay_vao: WebGlVertexArrayObject,
+    overlay_buffer: WebGlBuffer,
+    overlay_vertex_count: i32,
+    picking: PickingPass,
+}
+
+fn create_context(canvas: &HtmlCanvasElement) -> Result<WebGl2RenderingContext, JsValue> {
+    let gl: WebGl2RenderingContext = canvas
+        .get_context("webgl2")?
+        .unwrap()
+        .dyn_into::<WebGl2RenderingContext>()?;
+    Ok(gl)
+}
+
+fn create_shader(
+    gl: &WebGl2RenderingContext,
+    shader_type: u32,
+    source: &str,
+) -> Result<WebGlShader, JsValue> {
+    let shader = gl
+        .create_shader(shader_type)
+        .ok_or_else(|| JsValue::from_str("Unable to create shader object"))?;
+
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+
+    if gl
+        .get_shader_parameter(&shader, WebGl2RenderingContext::COMPILE_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        Ok(shader)
+    } else {
+        Err(JsValue::from_str(
+            &gl.get_shader_info_log(&shader)
+                .unwrap_or_else(|| "Unknown error creating shader".into()),
+        ))
+    }
+}
+
+fn setup_shaders(
+    gl: &WebGl2RenderingContext,
+    vertex_shader_source: &str,
+    fragment_shader_source: &str,
+) -> Result<WebGlProgram, JsValue> {
+    let vertex_shader = create_shader(
+        gl,
+        WebGl2RenderingContext::VERTEX_SHADER,
+        vertex_shader_source,
+    )
+    .expect("Failed to compile vertex shader");
+    let fragment_shader = create_shader(
+        gl,
+        WebGl2RenderingContext::FRAGMENT_SHADER,
+        fragment_shader_source,
+    )
+    .expect("Failed to compile fragment shader");
+
+    let shader_program = gl.create_program().unwrap();
+    gl.attach_shader(&shader_program, &vertex_shader);
+    gl.attach_shader(&shader_program, &fragment_shader);
+    gl.link_program(&shader_program);
+
+    if gl
+        .get_program_parameter(&shader_program, WebGl2RenderingContext::LINK_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        // Set the shader program as active.
+        gl.use_program(Some(&shader_program));
+        Ok(shader_program)
+    } else {
+        Err(JsValue::from_str(
+            &gl.get_program_info_log(&shader_program)
+                .unwrap_or_else(|| "Unknown error linking program".into()),
+        ))
+    }
+}
+
+fn get_uniform_location(
+    gl: &WebGl2RenderingContext,
+    shader_program: &WebGlProgram,
+    name: &str,
+) -> Result<WebGlUniformLocation, JsValue> {
+    gl.get_uniform_location(shader_program, name)
+        .ok_or(JsValue::from_str(&format!(
+            "Can't retrieve {name} uniform location from shaders"
+        )))
+}
+
+/// The corners of the quad that every point is drawn on, as a triangle strip
+const QUAD_CORNERS: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+
+fn setup_quad_buffer(gl: &WebGl2RenderingContext) -> WebGlBuffer {
+    let quad_buffer = gl.create_buffer().unwrap();
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&quad_buffer));
+    let corners_array = unsafe { js_sys::Float32Array::view(&QUAD_CORNERS) };
+    gl.buffer_data_with_array_buffer_view(
+        WebGl2RenderingContext::ARRAY_BUFFER,
+        &corners_array,
+        WebGl2RenderingContext::STATIC_DRAW,
+    );
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, None);
+    quad_buffer
+}
+
+/// Point the named attribute at the currently bound buffer, the divisor is 1 for per-instance attributes
+fn setup_attribute(
+    gl: &WebGl2RenderingContext,
+    shader_program: &WebGlProgram,
+    name: &str,
+    size: i32,
+    stride: i32,
+    offset: i32,
+    divisor: u32,
+) {
+    let location: u32 = gl.get_attrib_location(shader_program, name) as u32;
+    gl.vertex_attrib_pointer_with_i32(
+        location,
+        size,
+        WebGl2RenderingContext::FLOAT,
+        false,
+        stride,
+        offset,
+    );
+    gl.enable_vertex_attrib_array(location);
+    gl.vertex_attrib_divisor(location, divisor);
+}
+
+fn setup_vao(
+    gl: &WebGl2RenderingContext,
+    shader_program: &WebGlProgram,
+    quad_buffer: &WebGlBuffer,
+) -> (WebGlVertexArrayObject, WebGlBuffer) {
+    const STRIDE: i32 = POINT_STRIDE as i32 * 4;
+
+    let vertex_array_object = gl.create_vertex_array().unwrap();
+    gl.bind_vertex_array(Some(&vertex_array_object));
+
+    // Every instance is drawn on the same quad
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(quad_buffer));
+    setup_attribute(gl, shader_program, "corner", 2, 0, 0, 0);
+
+    // Each point is an instance
+    let vertex_buffer = gl.create_buffer().unwrap();
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vertex_buffer));
+    setup_attribute(gl, shader_program, "coordinates", 2, STRIDE, 0, 1);
+    setup_attribute(gl, shader_program, "point_size", 1, STRIDE, 2 * 4, 1);
+    setup_attribute(gl, shader_program, "color", 3, STRIDE, 3 * 4, 1);
+    setup_attribute(gl, shader_program, "outline_color", 3, STRIDE, 6 * 4, 1);
+    setup_attribute(gl, shader_program, "visited_at", 1, STRIDE, 9 * 4, 1);
+    setup_attribute(gl, shader_program, "shape", 1, STRIDE, 10 * 4, 1);
+
+    gl.bind_vertex_array(None);
+    (vertex_array_object, vertex_buffer)
+}
+
+fn setup_edge_vao(
+    gl: &WebGl2RenderingContext,
+    shader_program: &WebGlProgram,
+) -> (WebGlVertexArrayObject, WebGlBuffer) {
+    const STRIDE: i32 = EDGE_VERTEX_STRIDE as i32 * 4;
+
+    let vertex_array_object = gl.create_vertex_array().unwrap();
+    gl.bind_vertex_array(Some(&vertex_array_object));
+
+    let vertex_buffer = gl.create_buffer().unwrap();
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vertex_buffer));
+
+    setup_attribute(gl, shader_program, "coordinates", 2, STRIDE, 0, 0);
+    setup_attribute(gl, shader_program, "weight", 1, STRIDE, 2 * 4, 0);
+    setup_attribute(gl, shader_program, "color", 3, STRIDE, 3 * 4, 0);
+
+    gl.bind_vertex_array(None);
+    (vertex_array_object, vertex_buffer)
+}
+
+fn setup_picking_pass(
+    gl: &WebGl2RenderingContext,
+    vertex_buffer: &WebGlBuffer,
+    quad_buffer: &WebGlBuffer,
+) -> Result<PickingPass, JsValue> {
+    const STRIDE: i32 = POINT_STRIDE as i32 * 4;
+
+    let shaders = setup_shaders(
+        gl,
+        include_str!("picking_vertex.glsl"),
+        include_str!("picking_fragment.glsl"),
+    )?;
+    let view_transform_location = get_uniform_location(gl, &shaders, "view_transform")?;
+    let viewport_size_location = get_uniform_location(gl, &shaders, "viewport_size")?;
+
+    let vao = gl.create_vertex_array().unwrap();
+    gl.bind_vertex_array(Some(&vao));
+
+    // The quad, coordinates and point size are shared with the regular points
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(quad_buffer));
+    setup_attribute(gl, &shaders, "corner", 2, 0, 0, 0);
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(vertex_buffer));
+    setup_attribute(gl, &shaders, "coordinates", 2, STRIDE, 0, 1);
+    setup_attribute(gl, &shaders, "point_size", 1, STRIDE, 2 * 4, 1);
+
+    // The IDs live in their own buffer
+    let id_buffer = gl.create_buffer().unwrap();
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&id_buffer));
+    setup_attribute(gl, &shaders, "pick_id", 1, 4, 0, 1);
+    gl.bind_vertex_array(None);
+    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, None);
+
+    let framebuffer = gl
+        .create_framebuffer()
+        .ok_or(JsValue::from_str("Unable to create picking framebuffer"))?;
+    let renderbuffer = gl
+        .create_renderbuffer()
+        .ok_or(JsValue::from_str("Unable to create picking renderbuffer"))?;
+    gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(&framebuffer));
+    gl.bind_renderbuffer(WebGl2RenderingContext::RENDERBUFFER, Some(&renderbuffer));
+    gl.renderbuffer_storage(
+        WebGl2RenderingContext::RENDERBUFFER,
+        WebGl2RenderingContext::RGBA8,
+        1,
+        1,
+    );
+    gl.framebuffer_renderbuffer(
+        WebGl2RenderingContext::FRAMEBUFFER,
+        WebGl2RenderingContext::COLOR_ATTACHMENT0,
+        WebGl2RenderingContext::RENDERBUFFER,
+        Some(&renderbuffer),
+    );
+    gl.bind_renderbuffer(WebGl2RenderingContext::RENDERBUFFER, None);
+    gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+
+    Ok(PickingPass {
+        shaders,
+        view_transform_location,
+        viewport_size_location,
+        vao,
+        id_buffer,
+        id_count: 0,
+        framebuffer,
+        renderbuffer,
+        rendered_transform: None,
+    })
+}
+
+impl WebGlRenderer {
+    pub fn new(canvas: &HtmlCanvasElement) -> Result<Self, JsValue> {
+        let gl: WebGl2RenderingContext = create_context(canvas)?;
+        let shaders: WebGlProgram = setup_shaders(
+            &gl,
+            include_str!("vertex.glsl"),
+            include_str!("fragment.glsl"),
+        )?;
+        let view_transform_location = get_uniform_location(&gl, &shaders, "view_transform")?;
+        let time_location = get_uniform_location(&gl, &shaders, "time")?;
+        let trail_duration_location = get_uniform_location(&gl, &shaders, "trail_duration")?;
+        let viewport_size_location = get_uniform_location(&gl, &shaders, "viewport_size")?;
+        let quad_buffer = setup_quad_buffer(&gl);
+        let (vao, vertex_buffer) = setup_vao(&gl, &shaders, &quad_buffer);
+
+        let edge_shaders: WebGlProgram = setup_shaders(
+            &gl,
+            include_str!("edge_vertex.glsl"),
+            include_str!("edge_fragment.glsl"),
+        )?;
+        let edge_view_transform_location =
+            get_uniform_location(&gl, &edge_shaders, "view_transform")?;
+        let (edge_vao, edge_buffer) = setup_edge_vao(&gl, &edge_shaders);
+        let (overlay_vao, overlay_buffer) = setup_edge_vao(&gl, &edge_shaders);
+        let picking = setup_picking_pass(&gl, &vertex_buffer, &quad_buffer)?;
+
+        // Edges are translucent and points have smoothed edges, blend them on top of the background
+        gl.enable(WebGl2RenderingContext::BLEND);
+        gl.blend_func(
+            WebGl2RenderingContext::SRC_ALPHA,
+            WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
+        );
+
+        // Create instance
+        Ok(WebGlRenderer {
+            gl,
+            shaders,
+            view_transform_location,
+            time_location,
+            trail_duration_location,
+            viewport_size_location,
+            viewport_size: [1.0, 1.0],
+            vao,
+            vertex_buffer,
+            vertex_count: 0,
+            edge_shaders,
+            edge_view_transform_location,
+            edge_vao,
+            edge_buffer,
+            edge_vertex_count: 0,
+            overlay_vao,
+            overlay_buffer,
+            overlay_vertex_count: 0,
+            picking,
+        })
+    }
+
+    fn upload(&self, buffer: &WebGlBuffer, data: &js_sys::Float32Array) {
+        self.gl
+            .bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(buffer));
+        self.gl.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            data,
+            WebGl2RenderingContext::DYNAMIC_DRAW,
+        );
+        self.gl
+            .bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, None);
+    }
+}
+
+impl GraphRenderer for WebGlRenderer {
+    fn set_data(&mut self, points: &[f32]) {
+        let data = unsafe { js_sys::Float32Array::view(points) };
+        self.upload(&self.vertex_buffer, &data);
+        self.vertex_count = (points.len() / POINT_STRIDE) as i32;
+
+        // Each point is identified by its index, offset by one so that 0 means "no point"
+        if self.picking.id_count != self.vertex_count {
+            let ids: Vec<f32> = (1..=self.vertex_count).map(|id| id as f32).collect();
+            let ids_array = unsafe { js_sys::Float32Array::view(&ids) };
+            self.upload(&self.picking.id_buffer, &ids_array);
+            self.picking.id_count = self.vertex_count;
+        }
+        self.picking.rendered_transform = None;
+    }
+
+    fn set_edge_data(&mut self, edges: &[f32]) {
+        let data = unsafe { js_sys::Float32Array::view(edges) };
+        self.upload(&self.edge_buffer, &data);
+        self.edge_vertex_count = (edges.len() / EDGE_VERTEX_STRIDE) as i32;
+    }
+
+    fn set_overlay_data(&mut self, edges: &[f32]) {
+        let data = unsafe { js_sys::Float32Array::view(edges) };
+        self.upload(&self.overlay_buffer, &data);
+        self.overlay_vertex_count = (edges.len() / EDGE_VERTEX_STRIDE) as i32;
+    }
+
+    fn set_viewport(&mut self, width: i32, height: i32) {
+        self.gl.viewport(0, 0, width, height);
+        self.viewport_size = [width.max(1) as f32, height.max(1) as f32];
+
+        // The picking buffer matches the canvas pixel for pixel
+        self.gl.bind_renderbuffer(
+            WebGl2RenderingContext::RENDERBUFFER,
+            Some(&self.picking.renderbuffer),
+        );
+        self.gl.renderbuffer_storage(
+            WebGl2RenderingContext::RENDERBUFFER,
+            WebGl2RenderingContext::RGBA8,
+            width.max(1),
+            height.max(1),
+        );
+        self.gl
+            .bind_renderbuffer(WebGl2RenderingContext::RENDERBUFFER, None);
+        self.picking.rendered_transform = None;
+    }
+
+    fn pick(&mut self, view_transform: &[f32; 9], x: i32, y: i32) -> Option<usize> {
+        let gl = &self.gl;
+        gl.bind_framebuffer(
+            WebGl2RenderingContext::FRAMEBUFFER,
+            Some(&self.picking.framebuffer),
+        );
+
+        // Only rerender the picking buffer if the points or the view changed since the last pick
+        if self.picking.rendered_transform.as_ref() != Some(view_transform) {
+            gl.disable(WebGl2RenderingContext::BLEND);
+            gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+            gl.use_program(Some(&self.picking.shaders));
+            gl.bind_vertex_array(Some(&self.picking.vao));
+            gl.uniform_matrix3fv_with_f32_array(
+                Some(&self.picking.view_transform_location),
+                false,
+                view_transform,
+            );
+            gl.uniform2fv_with_f32_array(
+                Some(&self.picking.viewport_size_location),
+                &self.viewport_size,
+            );
+            gl.draw_arrays_instanced(
+                WebGl2RenderingContext::TRIANGLE_STRIP,
+                0,
+                4,
+                self.vertex_count,
+            );
+            gl.bind_vertex_array(None);
+            gl.use_program(None);
+            gl.enable(WebGl2RenderingContext::BLEND);
+            self.picking.rendered_transform = Some(*view_transform);
+        }
+
+        let mut pixel = [0u8; 4];
+        let result = gl.read_pixels_with_opt_u8_array(
+            x,
+            y,
+            1,
+            1,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            Some(&mut pixel),
+        );
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+        result.ok()?;
+
+        let id = pixel[0] as usize | (pixel[1] as usize) << 8 | (pixel[2] as usize) << 16;
+        id.checked_sub(1)
+    }
+
+    fn draw(&mut self, view_transform: &[f32; 9], time: f32, trail_duration: f32) {
+        self.gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+
+        // Draw edges underneath the points
+        self.gl.use_program(Some(&self.edge_shaders));
+        self.gl.bind_vertex_array(Some(&self.edge_vao));
+        self.gl.uniform_matrix3fv_with_f32_array(
+            Some(&self.edge_view_transform_location),
+            false,
+            view_transform,
+        );
+        self.gl
+            .draw_arrays(WebGl2RenderingContext::LINES, 0, self.edge_vertex_count);
+        self.gl.bind_vertex_array(Some(&self.overlay_vao));
+        self.gl
+            .draw_arrays(WebGl2RenderingContext::LINES, 0, self.overlay_vertex_count);
+
+        // Prepare state
+        self.gl.use_program(Some(&self.shaders));
+        self.gl.bind_vertex_array(Some(&self.vao));
+
+        // Update uniforms
+        self.gl.uniform_matrix3fv_with_f32_array(
+            Some(&self.view_transform_location),
+            false,
+            view_transform,
+        );
+        self.gl.uniform1f(Some(&self.time_location), time);
+        self.gl
+            .uniform1f(Some(&self.trail_duration_location), trail_duration);
+        self.gl
+            .uniform2fv_with_f32_array(Some(&self.viewport_size_location), &self.viewport_size);
+
+        // Draw a quad instance per point
+        self.gl.draw_arrays_instanced(
+            WebGl2RenderingContext::TRIANGLE_STRIP,
+            0,
+            4,
+            self.vertex_count,
+        );
+
+        // Reset state
+        self.gl.bind_vertex_array(None);
+        self.gl.use_program(None);
+    }
+}
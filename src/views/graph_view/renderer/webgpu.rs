@@ -0,0 +1,400 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::{EDGE_VERTEX_STRIDE, POINT_STRIDE};
+use crate::views::graph_view::renderer::GraphRenderer;
+use crate::views::utils::get_window;
+use anyhow::{anyhow, Context, Result};
+use web_sys::HtmlCanvasElement;
+
+/// The corners of the quad that every point is drawn on, as a triangle strip
+const QUAD_CORNERS: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+
+/// The size of the uniforms, a padded 3x3 matrix followed by the viewport size, time and trail duration
+const UNIFORMS_SIZE: usize = 16;
+
+/// Enlarge the points a bit when picking, so that small points remain easy to hit
+const PICK_MARGIN: f32 = 4.0;
+
+/// Check whether the browser exposes the WebGPU API at all
+pub fn is_available() -> bool {
+    get_window()
+        .ok()
+        .and_then(|window| js_sys::Reflect::has(&window.navigator(), &"gpu".into()).ok())
+        .unwrap_or(false)
+}
+
+fn to_bytes(data: &[f32]) -> Vec<u8> {
+    data.iter().flat_map(|value| value.to_ne_bytes()).collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn create_surface(
+    instance: &wgpu::Instance,
+    canvas: &HtmlCanvasElement,
+) -> Result<wgpu::Surface<'static>> {
+    Ok(instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn create_surface(
+    _instance: &wgpu::Instance,
+    _canvas: &HtmlCanvasElement,
+) -> Result<wgpu::Surface<'static>> {
+    Err(anyhow!("Canvas surfaces are only available in the browser"))
+}
+
+/// A vertex buffer that grows to fit the data written to it
+struct DynamicBuffer {
+    label: &'static str,
+    buffer: wgpu::Buffer,
+    count: u32,
+}
+
+impl DynamicBuffer {
+    fn new(device: &wgpu::Device, label: &'static str) -> Self {
+        Self {
+            label,
+            buffer: Self::create(device, label, 4),
+            count: 0,
+        }
+    }
+
+    fn create(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn write(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[f32], stride: usize) {
+        let bytes = to_bytes(data);
+        if bytes.len() as u64 > self.buffer.size() {
+            self.buffer = Self::create(device, self.label, bytes.len().next_power_of_two() as u64);
+        }
+        queue.write_buffer(&self.buffer, 0, &bytes);
+        self.count = (data.len() / stride) as u32;
+    }
+}
+
+/// The WebGPU implementation of the graph renderer, this scales to much larger graphs than WebGL2
+pub struct WebGpuRenderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    point_pipeline: wgpu::RenderPipeline,
+    edge_pipeline: wgpu::RenderPipeline,
+    quad_buffer: wgpu::Buffer,
+    points: DynamicBuffer,
+    edges: DynamicBuffer,
+    overlay: DynamicBuffer,
+    /// A copy of the points, WebGPU can only read back asynchronously, so points are picked on the CPU
+    picking_points: Vec<f32>,
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    topology: wgpu::PrimitiveTopology,
+    buffers: &[wgpu::VertexBufferLayout],
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vertex_main"),
+            compilation_options: Default::default(),
+            buffers,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fragment_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: Default::default(),
+        multiview: None,
+        cache: None,
+    })
+}
+
+impl WebGpuRenderer {
+    pub async fn new(canvas: &HtmlCanvasElement) -> Result<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::BROWSER_WEBGPU,
+            ..Default::default()
+        });
+
+        // Only claim the canvas once a device is available, as a canvas can't switch to WebGL2 afterward
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions::default())
+            .await
+            .context("No WebGPU adapter available")?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default())
+            .await
+            .context("Unable to create a WebGPU device")?;
+        let surface = create_surface(&instance, canvas)?;
+
+        let capabilities = surface.get_capabilities(&adapter);
+        let format = *capabilities
+            .formats
+            .first()
+            .ok_or(anyhow!("The canvas supports no texture formats"))?;
+        let alpha_mode = if capabilities
+            .alpha_modes
+            .contains(&wgpu::CompositeAlphaMode::PreMultiplied)
+        {
+            wgpu::CompositeAlphaMode::PreMultiplied
+        } else {
+            capabilities.alpha_modes[0]
+        };
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: canvas.width().max(1),
+            height: canvas.height().max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            desired_maximum_frame_latency: 2,
+            alpha_mode,
+            view_formats: Vec::new(),
+        };
+        surface.configure(&device, &surface_config);
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("uniforms"),
+            size: (UNIFORMS_SIZE * 4) as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let point_shader = device.create_shader_module(wgpu::include_wgsl!("points.wgsl"));
+        let point_pipeline = create_pipeline(
+            &device,
+            &layout,
+            &point_shader,
+            format,
+            wgpu::PrimitiveTopology::TriangleStrip,
+            &[
+                wgpu::VertexBufferLayout {
+                    array_stride: 2 * 4,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: (POINT_STRIDE * 4) as u64,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        1 => Float32x2,
+                        2 => Float32,
+                        3 => Float32x3,
+                        4 => Float32x3,
+                        5 => Float32,
+                        6 => Float32
+                    ],
+                },
+            ],
+        );
+
+        let edge_shader = device.create_shader_module(wgpu::include_wgsl!("edges.wgsl"));
+        let edge_pipeline = create_pipeline(
+            &device,
+            &layout,
+            &edge_shader,
+            format,
+            wgpu::PrimitiveTopology::LineList,
+            &[wgpu::VertexBufferLayout {
+                array_stride: (EDGE_VERTEX_STRIDE * 4) as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32, 2 => Float32x3],
+            }],
+        );
+
+        let quad_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("quad"),
+            size: (QUAD_CORNERS.len() * 4) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&quad_buffer, 0, &to_bytes(&QUAD_CORNERS));
+
+        Ok(Self {
+            points: DynamicBuffer::new(&device, "points"),
+            edges: DynamicBuffer::new(&device, "edges"),
+            overlay: DynamicBuffer::new(&device, "overlay"),
+            device,
+            queue,
+            surface,
+            surface_config,
+            uniform_buffer,
+            bind_group,
+            point_pipeline,
+            edge_pipeline,
+            quad_buffer,
+            picking_points: Vec::new(),
+        })
+    }
+}
+
+impl GraphRenderer for WebGpuRenderer {
+    fn set_data(&mut self, points: &[f32]) {
+        self.points
+            .write(&self.device, &self.queue, points, POINT_STRIDE);
+        self.picking_points = points.to_vec();
+    }
+
+    fn set_edge_data(&mut self, edges: &[f32]) {
+        self.edges
+            .write(&self.device, &self.queue, edges, EDGE_VERTEX_STRIDE);
+    }
+
+    fn set_overlay_data(&mut self, edges: &[f32]) {
+        self.overlay
+            .write(&self.device, &self.queue, edges, EDGE_VERTEX_STRIDE);
+    }
+
+    fn set_viewport(&mut self, width: i32, height: i32) {
+        self.surface_config.width = width.max(1) as u32;
+        self.surface_config.height = height.max(1) as u32;
+        self.surface.configure(&self.device, &self.surface_config);
+    }
+
+    fn pick(&mut self, view_transform: &[f32; 9], x: i32, y: i32) -> Option<usize> {
+        let width = self.surface_config.width as f32;
+        let height = self.surface_config.height as f32;
+
+        // Find the nearest point that covers the pixel, comparing in pixels
+        let m = view_transform;
+        self.picking_points
+            .chunks_exact(POINT_STRIDE)
+            .enumerate()
+            .filter_map(|(index, point)| {
+                let clip_x = m[0] * point[0] + m[3] * point[1] + m[6];
+                let clip_y = m[1] * point[0] + m[4] * point[1] + m[7];
+                let dx = (clip_x + 1.0) * 0.5 * width - x as f32;
+                let dy = (clip_y + 1.0) * 0.5 * height - y as f32;
+                let distance = (dx * dx + dy * dy).sqrt();
+                let radius = (point[2] + PICK_MARGIN) * 0.5;
+                (distance <= radius).then_some((index, distance))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    fn draw(&mut self, view_transform: &[f32; 9], time: f32, trail_duration: f32) {
+        // Each column of the 3x3 matrix is padded to 4 floats
+        let m = view_transform;
+        let uniforms: [f32; UNIFORMS_SIZE] = [
+            m[0],
+            m[1],
+            m[2],
+            0.0,
+            m[3],
+            m[4],
+            m[5],
+            0.0,
+            m[6],
+            m[7],
+            m[8],
+            0.0,
+            self.surface_config.width as f32,
+            self.surface_config.height as f32,
+            time,
+            trail_duration,
+        ];
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, &to_bytes(&uniforms));
+
+        let frame = match self.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(error) => {
+                log::warn!("Unable to get the next WebGPU frame: {error}");
+                return;
+            }
+        };
+        let view = frame.texture.create_view(&Default::default());
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_bind_group(0, &self.bind_group, &[]);
+
+            // Draw edges underneath the points
+            if self.edges.count > 0 {
+                pass.set_pipeline(&self.edge_pipeline);
+                pass.set_vertex_buffer(0, self.edges.buffer.slice(..));
+                pass.draw(0..self.edges.count, 0..1);
+            }
+            if self.overlay.count > 0 {
+                pass.set_pipeline(&self.edge_pipeline);
+                pass.set_vertex_buffer(0, self.overlay.buffer.slice(..));
+                pass.draw(0..self.overlay.count, 0..1);
+            }
+
+            // Draw a quad instance per point
+            if self.points.count > 0 {
+                pass.set_pipeline(&self.point_pipeline);
+                pass.set_vertex_buffer(0, self.quad_buffer.slice(..));
+                pass.set_vertex_buffer(1, self.points.buffer.slice(..));
+                pass.draw(0..4, 0..self.points.count);
+            }
+        }
+        self.queue.submit([encoder.finish()]);
+        frame.present();
+    }
+}
@@ -0,0 +1,160 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! A quadtree over the arranged points, to find the point under the pointer without reading back
+//! the GPU's picking buffer
+
+#[cfg(test)]
+mod unittest;
+
+use crate::views::graph_view::arrangement::POINT_STRIDE;
+
+/// The number of points that a cell holds before it is split into quadrants
+const CELL_CAPACITY: usize = 8;
+
+/// Cells stop splitting at this depth, e.g. when many points share the same coordinates
+const MAX_DEPTH: u32 = 16;
+
+struct Cell {
+    min: [f32; 2],
+    max: [f32; 2],
+    /// The index of the first of the cell's four quadrants, if it was split
+    first_quadrant: Option<usize>,
+    /// The indices of the points in this cell, if it is a leaf
+    points: Vec<usize>,
+}
+
+impl Cell {
+    fn new(min: [f32; 2], max: [f32; 2]) -> Self {
+        Self {
+            min,
+            max,
+            first_quadrant: None,
+            points: Vec::new(),
+        }
+    }
+
+    /// The squared distance from a position to the nearest edge of the cell, zero inside of it
+    fn distance_squared(&self, [x, y]: [f32; 2]) -> f32 {
+        let dx = (self.min[0] - x).max(0.0).max(x - self.max[0]);
+        let dy = (self.min[1] - y).max(0.0).max(y - self.max[1]);
+        dx * dx + dy * dy
+    }
+
+    fn quadrant_of(&self, [x, y]: [f32; 2]) -> usize {
+        let center = self.center();
+        (x >= center[0]) as usize + 2 * (y >= center[1]) as usize
+    }
+
+    fn center(&self) -> [f32; 2] {
+        [
+            (self.min[0] + self.max[0]) / 2.0,
+            (self.min[1] + self.max[1]) / 2.0,
+        ]
+    }
+}
+
+/// Finds the points nearest to content space positions, in logarithmic time
+pub struct SpatialIndex {
+    positions: Vec<[f32; 2]>,
+    /// The root is the first cell, the quadrants of each cell are stored next to each other
+    cells: Vec<Cell>,
+}
+
+impl SpatialIndex {
+    /// Index the coordinates of the points, these are laid out like the arrangement's points
+    pub fn new(points: &[f32]) -> Self {
+        let positions: Vec<[f32; 2]> = points
+            .chunks_exact(POINT_STRIDE)
+            .map(|point| [point[0], point[1]])
+            .collect();
+        let (min, max) = positions.iter().fold(
+            ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            |(min, max), [x, y]| {
+                (
+                    [min[0].min(*x), min[1].min(*y)],
+                    [max[0].max(*x), max[1].max(*y)],
+                )
+            },
+        );
+
+        let mut index = Self {
+            positions,
+            cells: vec![Cell::new(min, max)],
+        };
+        for point in 0..index.positions.len() {
+            index.insert(point);
+        }
+        index
+    }
+
+    fn insert(&mut self, point: usize) {
+        let position = self.positions[point];
+        let mut cell = 0;
+        let mut depth = 0;
+        while let Some(first_quadrant) = self.cells[cell].first_quadrant {
+            cell = first_quadrant + self.cells[cell].quadrant_of(position);
+            depth += 1;
+        }
+        self.cells[cell].points.push(point);
+        if self.cells[cell].points.len() > CELL_CAPACITY && depth < MAX_DEPTH {
+            self.split(cell);
+        }
+    }
+
+    /// Divide a leaf into quadrants, and move its points into them
+    fn split(&mut self, cell: usize) {
+        let Cell { min, max, .. } = self.cells[cell];
+        let center = self.cells[cell].center();
+        let first_quadrant = self.cells.len();
+        self.cells.extend([
+            Cell::new(min, center),
+            Cell::new([center[0], min[1]], [max[0], center[1]]),
+            Cell::new([min[0], center[1]], [center[0], max[1]]),
+            Cell::new(center, max),
+        ]);
+        self.cells[cell].first_quadrant = Some(first_quadrant);
+        for point in std::mem::take(&mut self.cells[cell].points) {
+            let quadrant = self.cells[cell].quadrant_of(self.positions[point]);
+            self.cells[first_quadrant + quadrant].points.push(point);
+        }
+    }
+
+    /// Find the point nearest to a position, within a maximum distance
+    /// @returns the index of the point, if any is close enough
+    pub fn nearest(&self, position: [f32; 2], max_distance: f32) -> Option<usize> {
+        let mut best: Option<(f32, usize)> = None;
+        let mut best_distance_squared = max_distance * max_distance;
+        let mut stack = vec![0];
+        while let Some(cell) = stack.pop() {
+            let cell = &self.cells[cell];
+            if cell.distance_squared(position) > best_distance_squared {
+                continue;
+            }
+            match cell.first_quadrant {
+                Some(first_quadrant) => {
+                    // Visit the quadrant that holds the position first, it likely has the nearest point
+                    let nearest_quadrant = cell.quadrant_of(position);
+                    stack.extend(
+                        (0..4)
+                            .filter(|quadrant| *quadrant != nearest_quadrant)
+                            .map(|quadrant| first_quadrant + quadrant),
+                    );
+                    stack.push(first_quadrant + nearest_quadrant);
+                }
+                None => {
+                    for point in &cell.points {
+                        let [x, y] = self.positions[*point];
+                        let distance_squared =
+                            (x - position[0]).powi(2) + (y - position[1]).powi(2);
+                        if distance_squared <= best_distance_squared {
+                            best_distance_squared = distance_squared;
+                            best = Some((distance_squared, *point));
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(_distance_squared, point)| point)
+    }
+}
@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::POINT_STRIDE;
+use crate::views::graph_view::spatial_index::SpatialIndex;
+use proptest::prelude::*;
+
+/// Lay out coordinates like the arrangement's points
+fn to_points(positions: &[(f32, f32)]) -> Vec<f32> {
+    positions
+        .iter()
+        .flat_map(|(x, y)| {
+            let mut point = [0.0; POINT_STRIDE];
+            point[0] = *x;
+            point[1] = *y;
+            point
+        })
+        .collect()
+}
+
+#[test]
+fn test_nearest() {
+    // A grid of 20 by 20 points, enough to split the tree a few times
+    let positions: Vec<(f32, f32)> = (0..400)
+        .map(|index| ((index % 20) as f32, (index / 20) as f32))
+        .collect();
+    let index = SpatialIndex::new(&to_points(&positions));
+
+    assert_eq!(index.nearest([3.2, 5.1], 0.5), Some(5 * 20 + 3));
+    assert_eq!(index.nearest([19.4, 19.4], 1.0), Some(399));
+    // Nothing is close enough
+    assert_eq!(index.nearest([3.5, 5.5], 0.5), None);
+    assert_eq!(index.nearest([-5.0, 0.0], 1.0), None);
+    assert_eq!(SpatialIndex::new(&[]).nearest([0.0, 0.0], 1.0), None);
+}
+
+proptest! {
+    #[test]
+    fn prop_nearest_matches_linear_search(
+        positions in prop::collection::vec((0.0f32..50.0, 0.0f32..50.0), 1..200),
+        target in (0.0f32..50.0, 0.0f32..50.0),
+    ) {
+        let index = SpatialIndex::new(&to_points(&positions));
+        let distance = |(x, y): (f32, f32)| (x - target.0).powi(2) + (y - target.1).powi(2);
+        let nearest = index.nearest([target.0, target.1], 100.0).unwrap();
+        let best = positions.iter().map(|position| distance(*position)).fold(f32::INFINITY, f32::min);
+        prop_assert_eq!(distance(positions[nearest]), best);
+    }
+}
@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Vector pictures of the arrangement, these stay sharp at any size, unlike screenshots of the canvas
+
+#[cfg(test)]
+mod unittest;
+
+use crate::views::graph_view::arrangement::{
+    Scene, EDGE_VERTEX_STRIDE, POINT_STRIDE, SHAPE_DIAMOND,
+};
+use crate::views::graph_view::ContentSpace;
+
+/// The number of pixels that a single unit of content space spans
+const SCALE: f32 = 12.0;
+
+/// The width of the lines and outlines in pixels, like the renderer's
+pub(super) const LINE_WIDTH: f32 = 1.0;
+
+/// The size of a picture of the arrangement in pixels, including padding
+pub(super) fn picture_size(arrangement: &Scene) -> (f32, f32) {
+    (
+        (arrangement.width + ContentSpace::PADDING.x * 2.0) * SCALE,
+        (arrangement.height + ContentSpace::PADDING.y * 2.0) * SCALE,
+    )
+}
+
+/// Map content coordinates into a picture of the given height. Content space grows upward, like the
+/// canvas' clip space, but pictures grow downward.
+pub(super) fn to_picture_space(picture_height: f32, x: f32, y: f32) -> (f32, f32) {
+    (
+        (x + ContentSpace::PADDING.x) * SCALE,
+        picture_height - (y + ContentSpace::PADDING.y) * SCALE,
+    )
+}
+
+/// Heavier traffic is more opaque, like in the renderer's edge shader
+pub(super) fn edge_opacity(weight: f32) -> f32 {
+    0.15 + (0.9 - 0.15) * weight
+}
+
+/// Turn normalized color components back into CSS
+fn to_css([red, green, blue]: [f32; 3]) -> String {
+    let [red, green, blue] = [red, green, blue].map(|component| (component * 255.0).round() as u8);
+    format!("rgb({red},{green},{blue})")
+}
+
+/// Draw the arrangement as SVG
+/// @param points the points of the arrangement, with any marks applied
+/// @param edges the vertices of the edges, drawn in order beneath the points
+pub fn to_svg(arrangement: &Scene, points: &[f32], edges: &[f32]) -> String {
+    let (width, height) = picture_size(arrangement);
+    let to_svg_space = |x: f32, y: f32| to_picture_space(height, x, y);
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+
+    for edge in edges.chunks_exact(EDGE_VERTEX_STRIDE * 2) {
+        let (x1, y1) = to_svg_space(edge[0], edge[1]);
+        let (x2, y2) = to_svg_space(edge[EDGE_VERTEX_STRIDE], edge[EDGE_VERTEX_STRIDE + 1]);
+        let opacity = edge_opacity(edge[2]);
+        svg.push_str(&format!(
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}" stroke-opacity="{opacity}" stroke-width="{LINE_WIDTH}"/>"#,
+            to_css([edge[3], edge[4], edge[5]])
+        ));
+    }
+
+    for point in points.chunks_exact(POINT_STRIDE) {
+        let (x, y) = to_svg_space(point[0], point[1]);
+        let radius = point[2] * 0.5;
+        let fill = to_css([point[3], point[4], point[5]]);
+        let outline = to_css([point[6], point[7], point[8]]);
+        let style = format!(r#"fill="{fill}" stroke="{outline}" stroke-width="{LINE_WIDTH}""#);
+        match point[10] == SHAPE_DIAMOND {
+            true => svg.push_str(&format!(
+                r#"<polygon points="{x},{} {},{y} {x},{} {},{y}" {style}/>"#,
+                y - radius,
+                x + radius,
+                y + radius,
+                x - radius
+            )),
+            false => svg.push_str(&format!(
+                r#"<circle cx="{x}" cy="{y}" r="{radius}" {style}/>"#
+            )),
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
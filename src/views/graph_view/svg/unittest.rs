@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::{Scene, SHAPE_CIRCLE, SHAPE_DIAMOND};
+use crate::views::graph_view::svg::to_svg;
+use std::collections::{HashMap, HashSet};
+
+/// A point at content coordinates, with a size, a color, an outline color, and a shape
+fn point(x: f32, y: f32, shape: f32) -> [f32; 11] {
+    [x, y, 4.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -1.0, shape]
+}
+
+#[test]
+fn test_svg_export() {
+    let mut points = Vec::new();
+    points.extend_from_slice(&point(0.0, 0.0, SHAPE_CIRCLE));
+    points.extend_from_slice(&point(1.0, 0.0, SHAPE_DIAMOND));
+    points.extend_from_slice(&point(1.0, 1.0, SHAPE_CIRCLE));
+    let arrangement = Scene {
+        width: 2.0,
+        height: 2.0,
+        points: points.clone(),
+        ids: vec![1, 2, 3],
+        indices: HashMap::from([(1, 0), (2, 1), (3, 2)]),
+        distances_to_solution: vec![Some(0), Some(1), Some(1)],
+        color_range: None,
+        one_way: HashSet::from([(3, 2)]),
+    };
+    let edges = arrangement.path_edges(&[1, 2, 3], crate::views::theme::Rgb(0, 128, 255));
+
+    let svg = to_svg(&arrangement, &points, &edges);
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="36" height="36""#));
+    assert!(svg.ends_with("</svg>"));
+    assert_eq!(svg.matches("<circle ").count(), 2);
+    assert_eq!(svg.matches("<polygon ").count(), 1);
+    // The one-way edge has an arrowhead of two lines
+    assert_eq!(svg.matches("<line ").count(), 2 + 2);
+
+    // The first point is in the bottom left corner, half a unit from the border
+    assert!(
+        svg.contains(r#"<circle cx="6" cy="30" r="2" fill="rgb(255,0,0)" stroke="rgb(0,0,255)""#)
+    );
+    // Edges at full weight are the most opaque
+    assert!(svg.contains(
+        r#"x1="6" y1="30" x2="18" y2="30" stroke="rgb(0,128,255)" stroke-opacity="0.9""#
+    ));
+}
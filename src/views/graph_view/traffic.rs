@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::BoardId;
+use std::collections::HashMap;
+
+/// Counts how often each edge of the graph has been traversed during this session, in either direction
+#[derive(Default)]
+pub struct Traffic {
+    counts: HashMap<(BoardId, BoardId), u32>,
+    max_count: u32,
+}
+
+impl Traffic {
+    /// Edges are undirected for traffic purposes, so the key is ordered
+    fn key(a: BoardId, b: BoardId) -> (BoardId, BoardId) {
+        (a.min(b), a.max(b))
+    }
+
+    /// Record a traversal between two neighboring states
+    pub fn record(&mut self, from: BoardId, to: BoardId) {
+        let count = self.counts.entry(Self::key(from, to)).or_insert(0);
+        *count += 1;
+        self.max_count = self.max_count.max(*count);
+    }
+
+    /// The highest traversal count of any edge
+    pub fn max_count(&self) -> u32 {
+        self.max_count
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&(BoardId, BoardId), u32)> {
+        self.counts.iter().map(|(key, count)| (key, *count))
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::views::graph_view::arrangement::{Scene, POINT_STRIDE, POINT_TWEENABLE};
+use keyframe::functions::EaseInOutCubic;
+use keyframe::EasingFunction;
+use std::time::Duration;
+
+/// How long it takes to morph from one arrangement into the next
+const TRANSITION_DURATION: Duration = Duration::from_millis(300);
+
+/// A tween between two arrangements, where each node morphs from its previous point into its new point.
+/// The content size morphs along, so that switching between layouts of different sizes doesn't jump.
+pub struct Transition {
+    from: Vec<f32>,
+    to: Vec<f32>,
+    from_size: (f32, f32),
+    to_size: (f32, f32),
+    start_time: Option<Duration>,
+    /// The points and the content size that were shown last, a transition that interrupts this one
+    /// continues from there
+    shown: Vec<f32>,
+    shown_size: (f32, f32),
+}
+
+impl Transition {
+    /// @param running the transition into the previous arrangement, if it hasn't finished yet
+    pub fn new(previous: &Scene, next: &Scene, running: Option<&Transition>) -> Self {
+        // The nodes morph from where they are shown, which is partway if a transition is interrupted
+        let (previous_points, from_size) = match running {
+            Some(running) => (&running.shown, running.shown_size),
+            None => (&previous.points, (previous.width, previous.height)),
+        };
+
+        // Nodes that were not part of the previous arrangement start out at their new point
+        let mut from = next.points.clone();
+        for (index, id) in next.ids.iter().enumerate() {
+            if let Some(previous_index) = previous.indices.get(id) {
+                let target = index * POINT_STRIDE;
+                let source = previous_index * POINT_STRIDE;
+                from[target..target + POINT_TWEENABLE]
+                    .copy_from_slice(&previous_points[source..source + POINT_TWEENABLE]);
+            }
+        }
+
+        Self {
+            shown: from.clone(),
+            shown_size: from_size,
+            from,
+            to: next.points.clone(),
+            from_size,
+            to_size: (next.width, next.height),
+            start_time: None,
+        }
+    }
+
+    /// The eased progress of the transition at the given frame timestamp, in range [0.0, 1.0]
+    fn progress_at(&mut self, timestamp: Duration) -> f32 {
+        let elapsed = timestamp.saturating_sub(*self.start_time.get_or_insert(timestamp));
+        let progress = (elapsed.as_secs_f64() / TRANSITION_DURATION.as_secs_f64()).min(1.0);
+        EaseInOutCubic.y(progress) as f32
+    }
+
+    /// Interpolate the content size for the given frame timestamp
+    pub fn size_at(&mut self, timestamp: Duration) -> (f32, f32) {
+        let eased = self.progress_at(timestamp);
+        self.shown_size = (
+            self.from_size.0 + (self.to_size.0 - self.from_size.0) * eased,
+            self.from_size.1 + (self.to_size.1 - self.from_size.1) * eased,
+        );
+        self.shown_size
+    }
+
+    /// Interpolate the points for the given frame timestamp
+    /// @returns the interpolated points, and whether the transition has finished
+    pub fn points_at(&mut self, timestamp: Duration) -> (Vec<f32>, bool) {
+        let eased = self.progress_at(timestamp);
+        if eased >= 1.0 {
+            self.shown = self.to.clone();
+            return (self.to.clone(), true);
+        }
+
+        let points: Vec<f32> = self
+            .from
+            .iter()
+            .zip(self.to.iter())
+            .enumerate()
+            .map(|(index, (from, to))| {
+                if index % POINT_STRIDE < POINT_TWEENABLE {
+                    from + (to - from) * eased
+                } else {
+                    *to
+                }
+            })
+            .collect();
+        self.shown = points.clone();
+        (points, false)
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::graph_view::arrangement::{Scene, POINT_STRIDE, SHAPE_CIRCLE};
+use crate::views::graph_view::transition::Transition;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// An arrangement of a single node, at the given x coordinate
+fn scene(x: f32) -> Scene {
+    let mut points = vec![0.0; POINT_STRIDE];
+    points[0] = x;
+    points[POINT_STRIDE - 1] = SHAPE_CIRCLE;
+    Scene {
+        width: x.max(1.0),
+        height: 1.0,
+        points,
+        ids: vec![1],
+        indices: HashMap::from([(1, 0)]),
+        distances_to_solution: vec![Some(0)],
+        color_range: None,
+        one_way: HashSet::new(),
+    }
+}
+
+#[test]
+fn test_interrupted_transition() {
+    let (start, middle, end) = (scene(0.0), scene(10.0), scene(0.0));
+    let mut transition = Transition::new(&start, &middle, None);
+    assert_eq!(transition.points_at(Duration::ZERO).0[0], 0.0);
+    let (points, finished) = transition.points_at(Duration::from_millis(150));
+    assert!(!finished);
+    let shown = points[0];
+    assert!(shown > 0.0 && shown < 10.0);
+
+    // The next transition starts where the node is shown, instead of where it was headed
+    let mut next = Transition::new(&middle, &end, Some(&transition));
+    assert_eq!(next.points_at(Duration::ZERO).0[0], shown);
+    assert_eq!(
+        next.points_at(Duration::from_secs(1)),
+        (end.points.clone(), true)
+    );
+
+    // Without a running transition, it starts from the previous arrangement
+    let mut next = Transition::new(&middle, &end, None);
+    assert_eq!(next.points_at(Duration::ZERO).0[0], 10.0);
+}
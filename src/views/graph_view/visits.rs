@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::BoardId;
+use crate::views::utils::get_window;
+use std::collections::HashMap;
+use web_sys::Storage;
+
+/// The local storage key of the visit counts
+const STORAGE_KEY: &str = "wiggers-graaf-visits";
+
+fn get_storage() -> Option<Storage> {
+    get_window().ok()?.local_storage().ok()?
+}
+
+/// Counts how often each state has been visited, these counts are persisted across sessions
+#[derive(Default)]
+pub struct Visits {
+    counts: HashMap<BoardId, u32>,
+}
+
+impl Visits {
+    /// Load the counts of previous sessions, corrupt entries are skipped
+    pub fn load() -> Self {
+        let stored = get_storage().and_then(|storage| storage.get_item(STORAGE_KEY).ok()?);
+        let counts = stored
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|entry| {
+                let (id, count) = entry.split_once(':')?;
+                Some((BoardId::from_str_radix(id, 16).ok()?, count.parse().ok()?))
+            })
+            .collect();
+        Self { counts }
+    }
+
+    fn save(&self) {
+        let Some(storage) = get_storage() else {
+            return;
+        };
+        let serialized = self
+            .counts
+            .iter()
+            .map(|(id, count)| format!("{id:x}:{count}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        if let Err(error) = storage.set_item(STORAGE_KEY, &serialized) {
+            log::warn!("Failed to store visits: {error:?}");
+        }
+    }
+
+    /// Record a visit to a state, and persist the new counts
+    pub fn record(&mut self, state: BoardId) {
+        // Other games on the page may have recorded visits since these were loaded
+        if get_storage().is_some() {
+            *self = Self::load();
+        }
+        *self.counts.entry(state).or_insert(0) += 1;
+        self.save();
+    }
+
+    pub fn count(&self, state: &BoardId) -> u32 {
+        self.counts.get(state).copied().unwrap_or(0)
+    }
+}
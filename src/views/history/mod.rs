@@ -0,0 +1,243 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::BoardId;
+
+/// States are forgotten beyond this many, first those of other branches, then the oldest
+const MAX_LENGTH: usize = 1000;
+
+struct Entry {
+    state: BoardId,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// The child that was visited last, redo steps into it
+    latest_child: Option<usize>,
+}
+
+/// A line of play that branches off from the current line, it was left when the player undid and
+/// played a different move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Branch {
+    /// The position on the current line that the branch starts from
+    pub position: usize,
+    /// The number of moves along the branch, following its latest moves
+    pub moves: usize,
+    /// The state at the end of the branch
+    pub tip: BoardId,
+    /// Identifies the branch, until the history changes
+    pub id: usize,
+}
+
+/// The states that were visited, as a tree of the lines that were played. Undoing and playing a
+/// different move starts a new branch, the old line can still be returned to.
+pub struct History {
+    entries: Vec<Entry>,
+    root: usize,
+    /// The index of the current state's entry
+    current: usize,
+}
+
+impl History {
+    pub fn new(state: BoardId) -> Self {
+        Self {
+            entries: vec![Entry {
+                state,
+                parent: None,
+                children: Vec::new(),
+                latest_child: None,
+            }],
+            root: 0,
+            current: 0,
+        }
+    }
+
+    /// Visit a new state, this branches off if states were undone
+    pub fn visit(&mut self, state: BoardId) {
+        if self.entries[self.current].state == state {
+            return;
+        }
+        // Playing the same move again follows the existing branch
+        let existing = self.entries[self.current]
+            .children
+            .iter()
+            .copied()
+            .find(|child| self.entries[*child].state == state);
+        let entry = existing.unwrap_or_else(|| {
+            self.entries.push(Entry {
+                state,
+                parent: Some(self.current),
+                children: Vec::new(),
+                latest_child: None,
+            });
+            let entry = self.entries.len() - 1;
+            self.entries[self.current].children.push(entry);
+            entry
+        });
+        self.entries[self.current].latest_child = Some(entry);
+        self.current = entry;
+        if self.entries.len() > MAX_LENGTH {
+            self.forget_one();
+        }
+    }
+
+    /// Step back to the previous state, if there is one
+    pub fn undo(&mut self) -> Option<BoardId> {
+        self.current = self.entries[self.current].parent?;
+        Some(self.entries[self.current].state)
+    }
+
+    /// Step back to the latest earlier state that matches, like undoing repeatedly
+    /// @returns the states that are stepped back through, ending with the matching state,
+    ///          or nothing if no earlier state matches
+    pub fn undo_until(&mut self, matches: impl Fn(BoardId) -> bool) -> Vec<BoardId> {
+        let mut states = Vec::new();
+        let mut entry = self.current;
+        while let Some(parent) = self.entries[entry].parent {
+            entry = parent;
+            states.push(self.entries[entry].state);
+            if matches(self.entries[entry].state) {
+                self.current = entry;
+                return states;
+            }
+        }
+        Vec::new()
+    }
+
+    /// Step forward to the state that was last undone, if there is one
+    pub fn redo(&mut self) -> Option<BoardId> {
+        self.current = self.entries[self.current].latest_child?;
+        Some(self.entries[self.current].state)
+    }
+
+    /// The entries of the current line, from the first state through the current one, and on along
+    /// the latest moves that were undone
+    fn line(&self) -> Vec<usize> {
+        let mut line = vec![self.current];
+        while let Some(parent) = self.entries[line[line.len() - 1]].parent {
+            line.push(parent);
+        }
+        line.reverse();
+        while let Some(child) = self.entries[line[line.len() - 1]].latest_child {
+            line.push(child);
+        }
+        line
+    }
+
+    /// The number of states that can be stepped through along the current line
+    pub fn len(&self) -> usize {
+        self.line().len()
+    }
+
+    /// The index of the current state, along the current line
+    pub fn position(&self) -> usize {
+        self.depth(self.current)
+    }
+
+    /// Step to any state of the current line, without forgetting the others until a new state is visited
+    pub fn seek(&mut self, position: usize) -> Option<BoardId> {
+        self.current = *self.line().get(position)?;
+        Some(self.entries[self.current].state)
+    }
+
+    /// The lines that branch off from the current line, in the order that they were played
+    pub fn branches(&self) -> Vec<Branch> {
+        let line = self.line();
+        let mut branches: Vec<Branch> = line
+            .iter()
+            .enumerate()
+            .flat_map(|(position, entry)| {
+                let latest_child = self.entries[*entry].latest_child;
+                self.entries[*entry]
+                    .children
+                    .iter()
+                    .filter(move |child| Some(**child) != latest_child)
+                    .map(move |child| (position, *child))
+            })
+            .map(|(position, child)| {
+                let tip = self.tip(child);
+                Branch {
+                    position,
+                    moves: self.depth(tip) - position,
+                    tip: self.entries[tip].state,
+                    id: child,
+                }
+            })
+            .collect();
+        branches.sort_by_key(|branch| branch.id);
+        branches
+    }
+
+    /// Switch to a branch, and step to its end. The branch becomes the current line.
+    pub fn switch_to(&mut self, id: usize) -> Option<BoardId> {
+        if !self.branches().iter().any(|branch| branch.id == id) {
+            return None;
+        }
+        let mut entry = id;
+        while let Some(parent) = self.entries[entry].parent {
+            self.entries[parent].latest_child = Some(entry);
+            entry = parent;
+        }
+        self.current = self.tip(id);
+        Some(self.entries[self.current].state)
+    }
+
+    /// The last entry along the latest moves from an entry
+    fn tip(&self, entry: usize) -> usize {
+        std::iter::successors(Some(entry), |entry| self.entries[*entry].latest_child)
+            .last()
+            .expect("The entry itself is the first")
+    }
+
+    fn depth(&self, entry: usize) -> usize {
+        std::iter::successors(Some(entry), |entry| self.entries[*entry].parent).count() - 1
+    }
+
+    /// Forget a state to make room, the oldest of another branch or else the first state
+    fn forget_one(&mut self) {
+        let mut on_line = vec![false; self.entries.len()];
+        self.line()
+            .into_iter()
+            .for_each(|entry| on_line[entry] = true);
+        let leaf = (0..self.entries.len())
+            .find(|entry| self.entries[*entry].children.is_empty() && !on_line[*entry]);
+        let forgotten = match leaf {
+            Some(leaf) => leaf,
+            None => {
+                // The current line is all there is, it starts at the next state from now on
+                let root = self.root;
+                let next = self.entries[root].latest_child.expect("The line is long");
+                self.entries[next].parent = None;
+                self.root = next;
+                root
+            }
+        };
+        if let Some(parent) = self.entries[forgotten].parent {
+            let parent = &mut self.entries[parent];
+            parent.children.retain(|child| *child != forgotten);
+            if parent.latest_child == Some(forgotten) {
+                parent.latest_child = parent.children.last().copied();
+            }
+        }
+        self.entries.remove(forgotten);
+
+        // The entries after the forgotten one moved down
+        let shift = |index: &mut usize| {
+            if *index > forgotten {
+                *index -= 1;
+            }
+        };
+        for entry in &mut self.entries {
+            entry
+                .parent
+                .iter_mut()
+                .chain(entry.children.iter_mut())
+                .chain(entry.latest_child.iter_mut())
+                .for_each(shift);
+        }
+        shift(&mut self.root);
+        shift(&mut self.current);
+    }
+}
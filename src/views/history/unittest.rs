@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::history::{Branch, History, MAX_LENGTH};
+
+#[test]
+fn test_undo_redo() {
+    let mut history = History::new(1);
+    assert_eq!(history.undo(), None);
+    history.visit(2);
+    history.visit(3);
+    // Revisiting the current state is not a step
+    history.visit(3);
+
+    assert_eq!(history.undo(), Some(2));
+    assert_eq!(history.undo(), Some(1));
+    assert_eq!(history.undo(), None);
+    assert_eq!(history.redo(), Some(2));
+    assert_eq!(history.redo(), Some(3));
+    assert_eq!(history.redo(), None);
+
+    // Visiting a new state forgets what was undone
+    history.undo();
+    history.visit(4);
+    assert_eq!(history.redo(), None);
+    assert_eq!(history.undo(), Some(2));
+}
+
+#[test]
+fn test_length_is_limited() {
+    let mut history = History::new(0);
+    for state in 1..=MAX_LENGTH as u64 {
+        history.visit(state);
+    }
+    let undone = std::iter::from_fn(|| history.undo()).count();
+    assert_eq!(undone, MAX_LENGTH - 1);
+}
+
+#[test]
+fn test_undo_until() {
+    let mut history = History::new(1);
+    for state in 2..=5 {
+        history.visit(state);
+    }
+    // The current state doesn't count, the latest earlier match does
+    assert_eq!(history.undo_until(|state| state % 2 == 1), [4, 3]);
+    assert!(history.undo_until(|state| state > 5).is_empty());
+    assert_eq!(history.redo(), Some(4));
+}
+
+#[test]
+fn test_seek() {
+    let mut history = History::new(1);
+    history.visit(2);
+    history.visit(3);
+    assert_eq!((history.len(), history.position()), (3, 2));
+
+    assert_eq!(history.seek(0), Some(1));
+    assert_eq!(history.seek(3), None);
+    assert_eq!(history.position(), 0);
+    // Seeking keeps the later states, until the game branches off
+    assert_eq!(history.seek(1), Some(2));
+    assert_eq!(history.redo(), Some(3));
+    history.seek(1);
+    history.visit(4);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.redo(), None);
+}
+
+#[test]
+fn test_branches() {
+    let mut history = History::new(1);
+    history.visit(2);
+    history.visit(3);
+    history.visit(4);
+    assert!(history.branches().is_empty());
+
+    // Undoing and playing a different move keeps the old line as a branch
+    history.undo();
+    history.undo();
+    history.visit(5);
+    history.visit(6);
+    let branches = history.branches();
+    assert_eq!(
+        branches,
+        [Branch {
+            position: 1,
+            moves: 2,
+            tip: 4,
+            id: branches[0].id,
+        }]
+    );
+    assert_eq!(history.len(), 4);
+
+    // Playing the same move again follows the existing branch, it becomes the current line
+    history.undo_until(|state| state == 2);
+    history.visit(3);
+    assert_eq!(history.redo(), Some(4));
+    assert_eq!(history.branches()[0].tip, 6);
+
+    // Switching to a branch steps to its end
+    let branch = history.branches()[0];
+    assert_eq!(history.switch_to(branch.id), Some(6));
+    assert_eq!(history.position(), 3);
+    assert_eq!(history.branches()[0].tip, 4);
+    assert_eq!(history.undo(), Some(5));
+    assert_eq!(history.switch_to(usize::MAX), None);
+}
+
+#[test]
+fn test_branches_are_forgotten_first() {
+    let mut history = History::new(0);
+    history.visit(1);
+    history.undo();
+    for state in 2..=MAX_LENGTH as u64 {
+        history.visit(state);
+    }
+    // The branch to state 1 made room for the last state
+    assert!(history.branches().is_empty());
+    assert_eq!(history.len(), MAX_LENGTH);
+    assert_eq!(history.seek(0), Some(0));
+}
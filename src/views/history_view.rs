@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::event_listener::EventListener;
+use crate::views::history::{Branch, History};
+use crate::views::utils::get_document;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Event, HtmlCanvasElement, HtmlElement, HtmlInputElement};
+
+pub enum HistoryEvent {
+    /// The slider is being dragged over a position of the history
+    Scrubbed(usize),
+    /// The slider was let go at a position of the history
+    Released(usize),
+    /// A branch of the history was picked from the list
+    BranchSelected(usize),
+}
+
+pub type OnHistoryEventCb = dyn FnMut(HistoryEvent);
+
+/// A slider under the board that scrubs through the states that were visited during this session,
+/// followed by a collapsible list of the branches that the player left
+pub struct HistoryView {
+    slider: HtmlInputElement,
+    branches_details: HtmlElement,
+    branches_summary: HtmlElement,
+    branches_list: HtmlElement,
+    listeners: Vec<EventListener>,
+    /// The listeners of the branches' buttons, they are replaced along with the list
+    branch_listeners: RefCell<Vec<EventListener>>,
+    on_history_event_cb: Rc<RefCell<Box<OnHistoryEventCb>>>,
+}
+
+impl HistoryView {
+    pub fn new(
+        board_canvas: &HtmlCanvasElement,
+        on_history_event_cb: Box<OnHistoryEventCb>,
+    ) -> Result<Self, JsValue> {
+        let document = get_document()?;
+        let slider: HtmlInputElement = document.create_element("input")?.dyn_into()?;
+        slider.set_type("range");
+        slider.class_list().add_1("history-scrubber")?;
+        slider.set_attribute("aria-label", "Move history")?;
+        slider.set_min("0");
+        slider.set_max("0");
+        slider.set_step("1");
+
+        // The slider reports while it is dragged, and once more when it is let go
+        let on_history_event_cb = Rc::new(RefCell::new(on_history_event_cb));
+        let listen = |event_type: &'static str, to_event: fn(usize) -> HistoryEvent| {
+            let slider_clone = slider.clone();
+            let on_history_event_cb = on_history_event_cb.clone();
+            EventListener::new(&slider, event_type, move |_event: Event| {
+                if let Ok(position) = slider_clone.value().parse() {
+                    (on_history_event_cb.borrow_mut())(to_event(position));
+                }
+            })
+        };
+        let listeners = vec![
+            listen("input", HistoryEvent::Scrubbed)?,
+            listen("change", HistoryEvent::Released)?,
+        ];
+
+        let branches_details: HtmlElement = document.create_element("details")?.dyn_into()?;
+        branches_details.class_list().add_1("history-branches")?;
+        branches_details.set_hidden(true);
+        let branches_summary: HtmlElement = document.create_element("summary")?.dyn_into()?;
+        let branches_list: HtmlElement = document.create_element("ul")?.dyn_into()?;
+        branches_details.append_child(&branches_summary)?;
+        branches_details.append_child(&branches_list)?;
+
+        board_canvas.after_with_node_2(&slider, &branches_details)?;
+        Ok(Self {
+            slider,
+            branches_details,
+            branches_summary,
+            branches_list,
+            listeners,
+            branch_listeners: RefCell::new(Vec::new()),
+            on_history_event_cb,
+        })
+    }
+
+    /// Take the slider and the branches off the page
+    pub fn remove(&mut self) {
+        self.listeners.clear();
+        self.branch_listeners.borrow_mut().clear();
+        self.slider.remove();
+        self.branches_details.remove();
+    }
+
+    /// Move the slider to the current state of the history
+    pub fn set_data(&self, history: &History) {
+        self.slider
+            .set_max(&history.len().saturating_sub(1).to_string());
+        self.slider.set_value_as_number(history.position() as f64);
+
+        let branches = history.branches();
+        self.branches_details.set_hidden(branches.is_empty());
+        self.branches_summary
+            .set_text_content(Some(&match branches.len() {
+                1 => "1 other line".to_string(),
+                count => format!("{count} other lines"),
+            }));
+        self.branches_list.set_inner_html("");
+        self.branch_listeners.borrow_mut().clear();
+        for branch in branches {
+            self.add_branch(&branch)
+                .expect("Failed to add history branch");
+        }
+    }
+
+    /// List a branch, with a button that switches to it
+    fn add_branch(&self, branch: &Branch) -> Result<(), JsValue> {
+        let document = get_document()?;
+        let item = document.create_element("li")?;
+        let button: HtmlElement = document.create_element("button")?.dyn_into()?;
+        button.set_text_content(Some(&format!(
+            "After move {}, {} moves",
+            branch.position, branch.moves
+        )));
+        let id = branch.id;
+        let on_history_event_cb = self.on_history_event_cb.clone();
+        let listener = EventListener::new(&button, "click", move |_event: Event| {
+            (on_history_event_cb.borrow_mut())(HistoryEvent::BranchSelected(id));
+        })?;
+        self.branch_listeners.borrow_mut().push(listener);
+        item.append_child(&button)?;
+        self.branches_list.append_child(&item)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::utils::get_document;
+
+/// The writing direction of the page's locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    pub fn is_mirrored(&self) -> bool {
+        *self == Direction::RightToLeft
+    }
+}
+
+/// Read the locale direction flag of the page, i.e. the dir attribute of the document root.
+/// Pages without this flag are considered to be left-to-right.
+pub fn get_direction() -> Direction {
+    let dir = get_document()
+        .ok()
+        .and_then(|document| document.document_element())
+        .and_then(|root| root.get_attribute("dir"));
+
+    match dir.as_deref() {
+        Some("rtl") => Direction::RightToLeft,
+        _ => Direction::LeftToRight,
+    }
+}
@@ -1,34 +1,98 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
+mod audio;
 mod board_view;
+mod clock;
+mod command_bus;
+mod context_menu;
+mod event_listener;
+mod events;
 mod frame_scheduler;
+mod ghost;
+pub mod graph_handle;
+pub mod graph_loader;
 pub mod graph_view;
+pub(crate) mod history;
+mod history_view;
+mod i18n;
+mod motion;
 mod moves_view;
+pub mod options;
+mod permalink;
 pub mod pointer_handler;
+mod replay;
 mod resize_observer;
+mod settings;
+mod speedrun;
+mod stats_view;
+pub mod theme;
 mod utils;
 
-use crate::board::BoardId;
+use crate::board::{BoardId, SlideMove};
+use crate::builder::LazyExpansion;
+use crate::graph::metrics::Metrics;
 use crate::graph::Graph;
+use crate::record::{GameRecord, Step};
+use crate::simulate::difficulty::{estimate_difficulty, SEED, WALKS_PER_STATE};
+use crate::simulate::shuffle::shuffle;
+use crate::views::audio::{Audio, Sound};
 use crate::views::board_view::visual_board::DragMove;
-use crate::views::moves_view::{MoveInfo, MovesView};
+use crate::views::board_view::{ImageFormat, PieceLabels, MOVE_DURATION};
+use crate::views::clock::{Clock, PerformanceClock};
+use crate::views::command_bus::{Command, CommandBus};
+use crate::views::context_menu::MenuAction;
+use crate::views::events::{Events, GameEvent, PasteError};
+use crate::views::ghost::Ghost;
+use crate::views::graph_loader::yield_to_event_loop;
+use crate::views::graph_view::arrangement::{ColorMode, Layout};
+use crate::views::history::History;
+use crate::views::history_view::{HistoryEvent, HistoryView};
+use crate::views::motion::{prefers_reduced_motion, Motion};
+use crate::views::moves_view::{collect_moves, MoveEffectiveness, MoveInfo, MovesView};
+use crate::views::permalink::Permalink;
+use crate::views::replay::Replay;
+use crate::views::settings::Settings;
+use crate::views::speedrun::{BestTimes, RunState, Speedrun};
+use crate::views::stats_view::StatsView;
+use crate::views::theme::Theme;
+use crate::views::utils::{cancel_timeout, get_element_of_type, get_window};
 use crate::{board, graph};
-pub(crate) use board_view::BoardView;
-pub(crate) use graph_view::GraphView;
-use std::cell::RefCell;
+use anyhow::anyhow;
+pub(crate) use board_view::{BoardEvent, BoardView};
+use futures::future::join_all;
+pub(crate) use graph_view::{GraphEvent, GraphView};
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
 use std::rc::{Rc, Weak};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
-use wasm_bindgen::JsValue;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{HtmlCanvasElement, HtmlDivElement};
+
+/// Jumps of up to this many moves are animated move by move, longer jumps cross-fade the board
+const MAX_ANIMATED_JUMP_LENGTH: u32 = 6;
+
+/// Moves that are requested while another move animates are made after it, up to this many
+const MAX_QUEUED_MOVES: usize = 2;
+
+/// The time between the steps of a replay that is playing, in milliseconds
+const REPLAY_STEP_INTERVAL: i32 = 700;
+
+/// The moves of a shuffle are quick, they only show which way the board was scrambled
+const SHUFFLE_MOVE_DURATION: Duration = Duration::from_millis(60);
 
 /**
- * TODO(Menno 12.08.2025) I'm adding this just to be done with async borrow checking stuff,
- *  please revisit this if I ever rewrite this app to be less... naive C++ brained.
- *
  * A RAII guard to "lock" a bool, a poor man's work around to not have to use a mutex.
  * Using a mutex would cause borrowing across an await, and by using a bool I have the freedom to
  * drop the borrow after setting it to "locked".
+ *
+ * The views no longer call into each other while handling their events, they go through the command
+ * bus, so a lock is only held across the awaits of an ongoing move. Everything else just checks it.
  */
 struct BoolGuard<'a> {
     target: &'a AtomicBool,
@@ -53,84 +117,731 @@ impl<'a> Drop for BoolGuard<'a> {
     }
 }
 
+/// The IDs of the page's elements that the views are attached to
+pub struct ElementIds<'a> {
+    pub meta_canvas: &'a str,
+    pub meta_legend_div: &'a str,
+    pub board_canvas: &'a str,
+    pub moves_div: &'a str,
+    pub restart_div: &'a str,
+    pub solve_div: &'a str,
+    pub temperature_div: &'a str,
+    pub stats_div: &'a str,
+}
+
+/// The page's elements that the views are attached to
+pub struct Elements {
+    pub meta_canvas: HtmlCanvasElement,
+    pub meta_legend_div: HtmlDivElement,
+    pub board_canvas: HtmlCanvasElement,
+    pub moves_div: HtmlDivElement,
+    pub restart_div: HtmlDivElement,
+    pub solve_div: HtmlDivElement,
+    pub temperature_div: HtmlDivElement,
+    pub stats_div: HtmlDivElement,
+}
+
+impl Elements {
+    /// Look up the elements in the page's document
+    pub fn find(ids: &ElementIds) -> Result<Self, JsValue> {
+        Ok(Self {
+            meta_canvas: get_element_of_type(ids.meta_canvas)?,
+            meta_legend_div: get_element_of_type(ids.meta_legend_div)?,
+            board_canvas: get_element_of_type(ids.board_canvas)?,
+            moves_div: get_element_of_type(ids.moves_div)?,
+            restart_div: get_element_of_type(ids.restart_div)?,
+            solve_div: get_element_of_type(ids.solve_div)?,
+            temperature_div: get_element_of_type(ids.temperature_div)?,
+            stats_div: get_element_of_type(ids.stats_div)?,
+        })
+    }
+
+    /// Take the elements from an object with the same keys as this struct, like `{meta_canvas: canvas, ...}`
+    pub fn from_options(options: &JsValue) -> Result<Self, JsValue> {
+        fn get<T: JsCast>(options: &JsValue, key: &str) -> Result<T, JsValue> {
+            js_sys::Reflect::get(options, &key.into())?
+                .dyn_into::<T>()
+                .map_err(|_value| {
+                    JsValue::from_str(&format!(
+                        "Option {key} is missing or not of type {}",
+                        std::any::type_name::<T>()
+                    ))
+                })
+        }
+        Ok(Self {
+            meta_canvas: get(options, "meta_canvas")?,
+            meta_legend_div: get(options, "meta_legend_div")?,
+            board_canvas: get(options, "board_canvas")?,
+            moves_div: get(options, "moves_div")?,
+            restart_div: get(options, "restart_div")?,
+            solve_div: get(options, "solve_div")?,
+            temperature_div: get(options, "temperature_div")?,
+            stats_div: get(options, "stats_div")?,
+        })
+    }
+}
+
 pub struct StatefulViews {
-    graph: Graph,
+    /// The graph grows as the player goes, if it's expanded lazily
+    graph: RefCell<Graph>,
+    expansion: Option<LazyExpansion>,
+    /// Whether the player's moves were simulated, to estimate the difficulty of the graph's states
+    difficulty_estimated: bool,
     graph_view: Rc<RefCell<GraphView>>,
     board_view: Rc<RefCell<BoardView>>,
     moves_view: Rc<RefCell<MovesView>>,
-    move_lock: AtomicBool,
+    stats_view: StatsView,
+    /// Shared, so that moves that await can hold the lock without holding a borrow of the views
+    move_lock: Rc<AtomicBool>,
+    /// Moves to make once the ongoing move has finished
+    queued_moves: RefCell<VecDeque<SlideMove>>,
+    /// The state that the puzzle starts from, and restarts to
+    start_state: BoardId,
+    active_state: Cell<BoardId>,
+    history: RefCell<History>,
+    history_view: RefCell<HistoryView>,
+    /// The state that was shown when the player started scrubbing through the history
+    scrub_origin: Cell<Option<BoardId>>,
+    record: RefCell<GameRecord>,
+    /// The time at which the recorded game started
+    record_epoch: Cell<Duration>,
+    clock: Rc<dyn Clock>,
+    replay: RefCell<Option<Replay>>,
+    symmetry_folding: Cell<bool>,
+    settings: Cell<Settings>,
+    motion: Motion,
+    /// The page's URL, unless another game on the page links its state there
+    permalink: RefCell<Option<Permalink>>,
+    audio: RefCell<Audio>,
+    /// The timed run of this game, if speedrun mode is on
+    speedrun: RefCell<Option<Speedrun>>,
+    best_times: RefCell<BestTimes>,
+    /// The player's attempt, compared with the best solve
+    ghost: RefCell<Ghost>,
+    ghost_shown: Cell<bool>,
+    events: Events,
 }
 
 impl StatefulViews {
     pub fn new(
         graph: Graph,
-        meta_canvas_id: &str,
-        board_canvas_id: &str,
-        moves_div_id: &str,
-        restart_div_id: &str,
-        solve_div_id: &str,
+        expansion: Option<LazyExpansion>,
+        start_state: BoardId,
+        elements: Elements,
     ) -> Result<Rc<RefCell<Self>>, JsValue> {
+        // All views are animated by the same clock
+        let clock: Rc<dyn Clock> = Rc::new(PerformanceClock);
+        let events = Events::default();
+        let motion = Motion::default();
+        let ghost = Ghost::new(start_state, &graph);
         Ok(Rc::new_cyclic(|self_ref: &Weak<RefCell<Self>>| {
-            let self_ref_clone_for_board_view = self_ref.clone();
-            let self_ref_clone_for_moves_view = self_ref.clone();
+            // The views publish their commands on the bus, only the bus refers back to this
+            let self_ref = self_ref.clone();
+            let commands = CommandBus::new(
+                clock.clone(),
+                Box::new(move |commands| {
+                    StatefulViews::apply(
+                        &self_ref
+                            .upgrade()
+                            .expect("Could not reference StatefulViews"),
+                        commands,
+                    );
+                }),
+            );
+            let commands_for_graph_view = commands.clone();
+            let commands_for_board_view = commands.clone();
+            let commands_for_history_view = commands.clone();
+            let history_view = HistoryView::new(
+                &elements.board_canvas,
+                Box::new(move |event| {
+                    let command = match event {
+                        HistoryEvent::Scrubbed(position) => Command::ScrubHistory(position),
+                        HistoryEvent::Released(position) => Command::ResumeFromHistory(position),
+                        HistoryEvent::BranchSelected(id) => Command::SwitchBranch(id),
+                    };
+                    commands_for_history_view.publish(command);
+                }),
+            )
+            .expect("Couldn't create HistoryView");
             RefCell::new(Self {
-                graph,
-                graph_view: GraphView::new(meta_canvas_id).expect("Couldn't create GraphView"),
+                graph: RefCell::new(graph),
+                expansion,
+                difficulty_estimated: false,
+                graph_view: GraphView::new(
+                    elements.meta_canvas,
+                    elements.meta_legend_div,
+                    clock.clone(),
+                    motion.clone(),
+                    Box::new(move |event| {
+                        let command = match event {
+                            GraphEvent::Selected(state) => Command::JumpTo(state),
+                            GraphEvent::Hovered(state) => Command::LinkState(state),
+                        };
+                        commands_for_graph_view.publish(command);
+                    }),
+                )
+                .expect("Couldn't create GraphView"),
                 board_view: BoardView::new(
-                    board_canvas_id,
-                    Box::new(move |drag_move| {
-                        self_ref_clone_for_board_view
-                            .upgrade()
-                            .expect("Could not reference StatefulViews")
-                            .borrow_mut()
-                            .do_drag_move(&drag_move)
+                    elements.board_canvas,
+                    clock.clone(),
+                    motion.clone(),
+                    Box::new(move |event| {
+                        let command = match event {
+                            BoardEvent::DragMove(drag_move) => Command::DragMove(drag_move),
+                            BoardEvent::Hovered(piece) => Command::RevealPiece(piece),
+                            BoardEvent::MenuAction(action) => match action {
+                                MenuAction::Restart => Command::Restart,
+                                MenuAction::Undo => Command::Undo,
+                                MenuAction::CopyPosition => Command::CopyPosition,
+                                MenuAction::ShowHint => Command::ShowHint,
+                                MenuAction::Flip => Command::FlipBoard,
+                            },
+                        };
+                        commands_for_board_view.publish(command);
                     }),
                 )
                 .expect("Couldn't create BoardView"),
                 moves_view: MovesView::new(
-                    moves_div_id,
-                    restart_div_id,
-                    solve_div_id,
-                    self_ref_clone_for_moves_view,
+                    elements.moves_div,
+                    elements.restart_div,
+                    elements.solve_div,
+                    elements.temperature_div,
+                    events.clone(),
+                    commands,
                 )
                 .expect("Couldn't create MovesView"),
-                move_lock: AtomicBool::new(false),
+                stats_view: StatsView::new(elements.stats_div),
+                move_lock: Rc::new(AtomicBool::new(false)),
+                queued_moves: RefCell::new(VecDeque::with_capacity(MAX_QUEUED_MOVES)),
+                start_state,
+                active_state: Cell::new(start_state),
+                history: RefCell::new(History::new(start_state)),
+                history_view: RefCell::new(history_view),
+                scrub_origin: Cell::new(None),
+                record: RefCell::new(GameRecord::new(start_state)),
+                record_epoch: Cell::new(clock.now()),
+                clock: clock.clone(),
+                replay: RefCell::new(None),
+                symmetry_folding: Cell::new(false),
+                settings: Cell::new(Settings::load()),
+                motion,
+                permalink: RefCell::new(Permalink::claim()),
+                audio: RefCell::new(Audio::default()),
+                speedrun: RefCell::new(None),
+                best_times: RefCell::new(BestTimes::load()),
+                ghost: RefCell::new(ghost),
+                ghost_shown: Cell::new(false),
+                events,
             })
         }))
     }
 
-    pub fn preview_move(&self, move_info: Option<MoveInfo>) {
-        let Some(_lock) = BoolGuard::lock(&self.move_lock) else {
-            // No preview, a move is ongoing
+    /// Apply the commands that the views published during a frame, at this point none of the views are borrowed
+    fn apply(self_ref: &Rc<RefCell<Self>>, commands: Vec<Command>) {
+        for command in commands {
+            match command {
+                Command::JumpTo(state) => {
+                    self_ref.borrow().audio.borrow_mut().play(Sound::Click);
+                    Self::jump_to(self_ref, state)
+                }
+                Command::PreviewMove(move_info) => self_ref.borrow().preview_move(move_info),
+                Command::LinkState(state) => self_ref.borrow().link_state(state),
+                Command::DoMove(move_info) => {
+                    let self_ref = self_ref.clone();
+                    spawn_local(async move { Self::do_move(&self_ref, &move_info).await });
+                }
+                Command::HighlightPiece(piece) => self_ref
+                    .borrow()
+                    .board_view
+                    .borrow_mut()
+                    .highlight_piece(piece),
+                Command::RevealPiece(piece) => {
+                    self_ref.borrow().moves_view.borrow().reveal_piece(piece)
+                }
+                Command::DragMove(drag_move) => self_ref.borrow().do_drag_move(&drag_move),
+                Command::Restart => Self::restart(self_ref),
+                Command::Undo => {
+                    Self::undo(self_ref);
+                }
+                Command::CopyPosition => {
+                    let copied = self_ref.borrow().copy_position();
+                    spawn_local(async move {
+                        let result = match copied {
+                            Ok(promise) => JsFuture::from(promise).await,
+                            Err(error) => Err(error),
+                        };
+                        if let Err(error) = result {
+                            log::warn!("Failed to copy position: {error:?}");
+                        }
+                    });
+                }
+                Command::ShowHint => self_ref.borrow().show_hint(),
+                Command::FlipBoard => {
+                    let self_ref = self_ref.clone();
+                    spawn_local(async move {
+                        if let Err(error) = Self::flip_board(&self_ref).await {
+                            log::info!("Not flipping the board: {error}");
+                        }
+                    });
+                }
+                Command::AutoSolveStarted => self_ref.borrow().invalidate_attempt(),
+                Command::ScrubHistory(position) => self_ref.borrow().scrub_history(position),
+                Command::ResumeFromHistory(position) => {
+                    self_ref.borrow().resume_from_history(position)
+                }
+                Command::SwitchBranch(id) => {
+                    Self::step_through_history(self_ref, |history| history.switch_to(id));
+                }
+            }
+        }
+    }
+
+    /// Show a state of the history right away, the board and graph follow the slider live
+    fn scrub_history(&self, position: usize) {
+        if self.move_lock.load(Relaxed) {
+            // Refuse to scrub, a move is ongoing
+            self.history_view.borrow().set_data(&self.history.borrow());
+            return;
+        }
+        if self.scrub_origin.get().is_none() {
+            self.stop_replay();
+            self.scrub_origin.set(Some(self.active_state.get()));
+        }
+        let state = self.history.borrow_mut().seek(position);
+        if let Some(state) = state {
+            self.set_state(state);
+        }
+    }
+
+    /// Continue from the state that the slider was let go at, the history is kept until the next move
+    /// branches off from there
+    fn resume_from_history(&self, position: usize) {
+        self.scrub_history(position);
+        let Some(origin) = self.scrub_origin.take() else {
             return;
         };
+        let state = self.active_state.get();
+        if state != origin {
+            // Scrubbing is jumping, like stepping through the history
+            self.invalidate_attempt();
+            self.record_step(Step::Jump(state));
+        }
+    }
+
+    fn preview_move(&self, move_info: Option<MoveInfo>) {
+        if self.move_lock.load(Relaxed) {
+            // No preview, a move is ongoing
+            return;
+        }
         self.board_view
             .borrow_mut()
             .preview_move(move_info.map(|move_info| move_info.slide_move));
+
+        // Point out where this move leads to in the graph
+        self.graph_view
+            .borrow_mut()
+            .highlight(move_info.map(|move_info| move_info.resulting_id));
+    }
+
+    /// Copy the active state to the clipboard in notation, see Board::to_notation
+    /// @returns the promise of the clipboard, which resolves once the position is written
+    pub fn copy_position(&self) -> Result<js_sys::Promise, JsValue> {
+        Ok(get_window()?
+            .navigator()
+            .clipboard()
+            .write_text(&self.active_board().to_notation()))
+    }
+
+    /// Jump to the position on the clipboard, which is in notation or a layout.
+    /// If that fails, a PasteRejected event tells the hosting page why.
+    pub async fn paste_position(self_ref: &Rc<RefCell<Self>>) -> anyhow::Result<()> {
+        let state = match Self::read_clipboard_position(self_ref).await {
+            Ok(state) => state,
+            Err(reason) => {
+                self_ref
+                    .borrow()
+                    .events
+                    .emit(GameEvent::PasteRejected(reason));
+                return Err(anyhow!("{reason}"));
+            }
+        };
+        Self::jump_to(self_ref, state);
+        Ok(())
+    }
+
+    async fn read_clipboard_position(self_ref: &Rc<RefCell<Self>>) -> Result<BoardId, PasteError> {
+        let read = get_window()
+            .map_err(|_error| PasteError::Unreadable)?
+            .navigator()
+            .clipboard()
+            .read_text();
+        let text = JsFuture::from(read)
+            .await
+            .ok()
+            .and_then(|text| text.as_string())
+            .ok_or(PasteError::Unreadable)?;
+        let board = board::parse_position(&text).map_err(|error| {
+            log::info!("Rejected pasted position: {error}");
+            PasteError::Invalid
+        })?;
+        let state = board::to_id(&board);
+        let self_ref = self_ref.borrow();
+        if !self_ref.graph.borrow().contains(state) {
+            return Err(PasteError::Unknown);
+        }
+        // Jumping would be refused, the clipboard was read while a move was ongoing
+        if self_ref.move_lock.load(Relaxed) {
+            return Err(PasteError::MoveOngoing);
+        }
+        Ok(state)
+    }
+
+    /// Preview the best move from the active state, and point out its button
+    fn show_hint(&self) {
+        let best_move = collect_moves(&self.graph.borrow(), self.active_state.get())
+            .ok()
+            .and_then(|moves| moves.into_iter().next())
+            .filter(|move_info| move_info.effectiveness == MoveEffectiveness::Positive);
+        let Some(best_move) = best_move else {
+            // The puzzle is solved, or can't be
+            return;
+        };
+        self.invalidate_attempt();
+        self.preview_move(Some(best_move));
+        self.link_state(Some(best_move.resulting_id));
+    }
+
+    /// Point out the move that leads to a state hovered in the graph, if the state is a neighbor
+    fn link_state(&self, state: Option<BoardId>) {
+        self.moves_view.borrow().highlight_move(state);
+    }
+
+    pub fn show_piece_trajectory(&self, enabled: bool) {
+        self.board_view.borrow_mut().show_trajectory(enabled);
     }
 
-    fn do_drag_move(&self, drag_move: &DragMove) -> graph::Node {
-        let new_state = drag_move.resulting_id;
+    pub fn show_board_grid(&self, enabled: bool) {
+        self.board_view.borrow_mut().show_grid(enabled);
+    }
+
+    pub fn set_fixed_timestep(&self, step: Option<Duration>) {
+        self.board_view.borrow_mut().set_fixed_timestep(step);
+    }
+
+    pub fn set_piece_labels(&self, labels: PieceLabels) {
+        self.board_view.borrow_mut().set_piece_labels(labels);
+    }
 
-        // TODO(Menno 16.08.2025) This duplicates code from set_state
+    pub fn show_horizon(&self, max_moves: Option<u32>) {
         self.graph_view
             .borrow_mut()
-            .set_data(&self.graph, new_state);
+            .show_horizon(&self.graph.borrow(), max_moves);
+    }
+
+    pub fn show_solution_path(&self, enabled: bool) {
+        self.graph_view
+            .borrow_mut()
+            .show_solution_path(&self.graph.borrow(), enabled);
+    }
+
+    pub fn set_graph_color_mode(&mut self, color_mode: ColorMode) {
+        if color_mode == ColorMode::Difficulty && !self.difficulty_estimated {
+            // Simulating the walks takes a while, so it waits until the difficulty is shown
+            estimate_difficulty(self.graph.get_mut(), WALKS_PER_STATE, SEED);
+            self.difficulty_estimated = true;
+        }
+        self.graph_view
+            .borrow_mut()
+            .set_color_mode(&self.graph.borrow(), color_mode);
+    }
+
+    pub fn set_graph_layout(&self, layout: Layout) {
+        self.graph_view
+            .borrow_mut()
+            .set_layout(&self.graph.borrow(), layout.create());
+    }
+
+    /// Draw the graph as it is currently arranged, as SVG
+    pub fn export_graph_svg(&self) -> Option<String> {
+        self.graph_view.borrow().export_svg()
+    }
+
+    /// Time the game from the first move to the solution, the run starts over at a restart
+    pub fn set_speedrun(&self, enabled: bool) {
+        *self.speedrun.borrow_mut() = enabled.then(Speedrun::default);
+    }
+
+    /// The speedrun, if speedrun mode is on, and the best time of this puzzle
+    pub fn speedrun(&self) -> Option<(Speedrun, Option<Duration>)> {
+        let speedrun = self.speedrun.borrow().clone()?;
+        Some((speedrun, self.best_times.borrow().get(self.start_state)))
+    }
+
+    /// The time on the speedrun's clock, if speedrun mode is on and the run counts
+    pub fn speedrun_time(&self) -> Option<Duration> {
+        self.speedrun.borrow().as_ref()?.elapsed(self.clock.now())
+    }
+
+    /// Overlay where the best solve of this puzzle was after as many moves as the player made
+    pub fn show_ghost(&self, enabled: bool) {
+        self.ghost_shown.set(enabled);
+        self.update_ghost();
+    }
+
+    fn update_ghost(&self) {
+        let state = match self.ghost_shown.get() {
+            true => self.ghost.borrow().state(),
+            false => None,
+        };
+        self.graph_view.borrow_mut().show_ghost(state);
+    }
+
+    /// The player jumped or got help, so the speedrun no longer counts, nor can this become the best solve
+    fn invalidate_attempt(&self) {
+        if let Some(speedrun) = self.speedrun.borrow_mut().as_mut() {
+            speedrun.invalidate();
+        }
+        self.ghost.borrow_mut().invalidate();
+        self.update_ghost();
+    }
+
+    fn restart_attempt(&self) {
+        if let Some(speedrun) = self.speedrun.borrow_mut().as_mut() {
+            *speedrun = Speedrun::default();
+        }
+        self.ghost.borrow_mut().restart();
+        self.update_ghost();
+    }
+
+    /// Count a move of the speedrun and of the attempt, and keep their results if they reached the solution
+    fn count_move(
+        &self,
+        previous_state: BoardId,
+        slide_move: &SlideMove,
+        new_state: BoardId,
+        may_start: bool,
+    ) {
+        let solved = self
+            .graph
+            .borrow()
+            .get(new_state)
+            .is_some_and(|node| board::is_solution(&node.board));
+        {
+            let mut ghost = self.ghost.borrow_mut();
+            ghost.record_move(
+                &self.graph.borrow(),
+                self.clock.now(),
+                previous_state,
+                *slide_move,
+                may_start,
+            );
+            if solved && ghost.finish(&self.graph.borrow()) {
+                ghost.save();
+            }
+        }
+        self.update_ghost();
+
+        // The solved states are at distance 1, they lead to the "fake" solution at distance 0
+        let moves = |state| {
+            self.graph
+                .borrow()
+                .get(state)
+                .and_then(|node| node.distance_to_solution())
+                .map(|distance| distance.saturating_sub(1))
+        };
+        let time = match self.speedrun.borrow_mut().as_mut() {
+            Some(speedrun) if may_start || speedrun.state() != RunState::Ready => speedrun
+                .record_move(
+                    self.clock.now(),
+                    moves(previous_state),
+                    moves(new_state),
+                    solved,
+                ),
+            _ => None,
+        };
+        if let Some(time) = time {
+            let best = self.best_times.borrow_mut().record(self.start_state, time);
+            self.events.emit(GameEvent::SpeedrunFinished { time, best });
+        }
+    }
+
+    /// Apply the hosting page's customizations
+    pub fn configure(&self, theme: Option<Theme>, auto_solve_interval: Option<Duration>) {
+        if let Some(theme) = theme {
+            self.set_theme(theme);
+        }
+        if let Some(interval) = auto_solve_interval {
+            self.moves_view
+                .borrow_mut()
+                .set_auto_solve_interval(interval);
+        }
+    }
+
+    /// Apply the user's preferences to the views
+    fn apply_settings(&self) {
+        let settings = self.settings.get();
+        self.moves_view
+            .borrow_mut()
+            .set_shape_coded(settings.shape_coded_moves);
+        self.moves_view
+            .borrow_mut()
+            .set_group_by_piece(settings.group_moves_by_piece);
+        self.moves_view
+            .borrow_mut()
+            .set_auto_solve_through_neutral(settings.auto_solve_through_neutral);
         self.moves_view
             .borrow_mut()
-            .set_data(&self.graph, new_state);
+            .set_collapse_chains(settings.collapse_move_chains);
+        self.audio.borrow_mut().set_muted(settings.muted);
+        self.board_view
+            .borrow_mut()
+            .set_spring_back(settings.spring_back);
+        self.motion.set_reduced(
+            settings
+                .reduced_motion
+                .unwrap_or_else(prefers_reduced_motion),
+        );
+    }
+
+    /// Change the user's preferences, and remember them for the next session
+    fn update_settings(&self, update: impl FnOnce(&mut Settings)) {
+        let mut settings = self.settings.get();
+        update(&mut settings);
+        settings.save();
+        self.settings.set(settings);
+        self.apply_settings();
+    }
 
-        // Return the new node to the BoardView
-        self.graph.map.get(&new_state).expect("Invalid ID").clone()
+    /// Mark how effective moves are with shapes, in addition to colors
+    pub fn set_shape_coded_moves(&self, enabled: bool) {
+        self.update_settings(|settings| settings.shape_coded_moves = enabled);
+    }
+
+    /// List the moves by the piece that they move, instead of by how they change the distance
+    pub fn set_group_moves_by_piece(&self, enabled: bool) {
+        self.update_settings(|settings| settings.group_moves_by_piece = enabled);
+        self.moves_view
+            .borrow_mut()
+            .set_data(&self.graph.borrow(), self.active_state.get());
+    }
+
+    /// List each position that the moves end in once, by the shortest move that leads there
+    pub fn set_collapse_move_chains(&self, enabled: bool) {
+        self.update_settings(|settings| settings.collapse_move_chains = enabled);
+        self.moves_view
+            .borrow_mut()
+            .set_data(&self.graph.borrow(), self.active_state.get());
+    }
+
+    /// Let auto-solve make moves that keep the distance, when no move gets closer
+    pub fn set_auto_solve_through_neutral(&self, enabled: bool) {
+        self.update_settings(|settings| settings.auto_solve_through_neutral = enabled);
+        self.moves_view
+            .borrow_mut()
+            .set_data(&self.graph.borrow(), self.active_state.get());
+    }
+
+    /// Replace the graph that grows around the player by the whole graph, once it has loaded. The game
+    /// goes on from the active state, after the ongoing moves have finished.
+    pub async fn complete_graph(self_ref: &Rc<RefCell<Self>>, graph: Graph) -> Result<(), JsValue> {
+        let mut graph = Some(graph);
+        loop {
+            {
+                let mut views = self_ref.borrow_mut();
+                let views = &mut *views;
+                if !views.move_lock.load(Relaxed) {
+                    let graph = graph.take().expect("The graph is only replaced once");
+                    let state = match graph.contains(views.active_state.get()) {
+                        true => views.active_state.get(),
+                        false => views.start_state,
+                    };
+                    *views.ghost.get_mut() = Ghost::new(views.start_state, &graph);
+                    *views.graph.get_mut() = graph;
+                    views.expansion = None;
+                    if views.difficulty_estimated {
+                        estimate_difficulty(views.graph.get_mut(), WALKS_PER_STATE, SEED);
+                    }
+                    views.set_state(state);
+                    return Ok(());
+                }
+            }
+            // The ongoing moves refer to the states of the graph that is replaced
+            yield_to_event_loop().await?;
+        }
+    }
+
+    /// Unregister all listeners and observers, and cancel the scheduled frames and timeouts
+    pub fn destroy(&self) -> Result<(), JsValue> {
+        if let Some(timeout_id) = self.replay.take().and_then(|replay| replay.timeout_id) {
+            cancel_timeout(timeout_id);
+        }
+        self.events.clear();
+        self.permalink.take();
+        self.audio.borrow_mut().close();
+        self.history_view.borrow_mut().remove();
+        self.graph_view.borrow_mut().destroy()?;
+        self.board_view.borrow_mut().destroy()?;
+        self.moves_view.borrow_mut().destroy()
+    }
+
+    /// Call back the hosting page whenever a game event happens
+    pub fn subscribe(&self, callback: js_sys::Function) {
+        self.events.subscribe(callback);
+    }
+
+    /// Silence the sound effects
+    pub fn set_muted(&self, muted: bool) {
+        self.update_settings(|settings| settings.muted = muted);
+    }
+
+    /// Replace movement with instant changes, or follow the system's preference when unset
+    pub fn set_reduced_motion(&self, enabled: Option<bool>) {
+        self.update_settings(|settings| settings.reduced_motion = enabled);
+    }
+
+    /// Let released pieces spring back to their place, instead of easing back
+    pub fn set_spring_back(&self, enabled: bool) {
+        self.update_settings(|settings| settings.spring_back = enabled);
+    }
+
+    /// Recolor every view
+    pub fn set_theme(&self, theme: Theme) {
+        self.board_view.borrow_mut().set_theme(theme);
+        self.graph_view
+            .borrow_mut()
+            .set_theme(&self.graph.borrow(), theme);
+        self.moves_view.borrow_mut().set_theme(theme);
+    }
+
+    /// Treat mirrored positions as equivalent, this normalizes permalinks to the canonical position
+    pub fn set_symmetry_folding(&self, enabled: bool) {
+        self.symmetry_folding.set(enabled);
+        self.update_views(self.active_state.get());
+    }
+    fn do_drag_move(&self, drag_move: &DragMove) {
+        let new_state = self.graph.borrow().node(drag_move.resulting_index).id;
+        let previous_state = self.active_state.get();
+        self.stop_replay();
+        self.history.borrow_mut().visit(new_state);
+        self.set_state(new_state);
+        self.acknowledge_move(previous_state, &drag_move.slide_move, new_state);
+        self.count_move(previous_state, &drag_move.slide_move, new_state, true);
     }
 
     pub async fn do_move(self_ref: &Rc<RefCell<Self>>, move_info: &MoveInfo) {
         // Set lock to true, and check if it was already set to true
         if self_ref.borrow().move_lock.swap(true, Relaxed) {
-            // Ignore further moves until previous move has finished
+            // Make this move once the previous move has finished
+            self_ref.borrow().enqueue_move(move_info.slide_move);
             return;
         };
+        self_ref.borrow().stop_replay();
+
+        Self::animate_move(self_ref, move_info).await;
+        Self::finish_moving(self_ref).await;
+    }
 
+    async fn animate_move(self_ref: &Rc<RefCell<Self>>, move_info: &MoveInfo) {
         let move_done = self_ref
             .borrow()
             .board_view
@@ -140,29 +851,692 @@ impl StatefulViews {
 
         // Steps to take after move finished
         let self_ref = self_ref.borrow();
+        let previous_state = self_ref.active_state.get();
+        self_ref.history.borrow_mut().visit(move_info.resulting_id);
         self_ref.set_state(move_info.resulting_id);
-        self_ref.move_lock.store(false, Relaxed);
+        self_ref.acknowledge_move(
+            previous_state,
+            &move_info.slide_move,
+            move_info.resulting_id,
+        );
+        self_ref.count_move(
+            previous_state,
+            &move_info.slide_move,
+            move_info.resulting_id,
+            true,
+        );
     }
 
-    pub fn restart(self_ref: &Rc<RefCell<Self>>) {
+    /// Make the queued moves that are still possible, and then release the move lock
+    async fn finish_moving(self_ref: &Rc<RefCell<Self>>) {
+        loop {
+            let next_move = self_ref.borrow().dequeue_move();
+            let Some(move_info) = next_move else {
+                break;
+            };
+            Self::animate_move(self_ref, &move_info).await;
+        }
+        self_ref.borrow().move_lock.store(false, Relaxed);
+    }
+
+    fn enqueue_move(&self, slide_move: SlideMove) {
+        let mut queued_moves = self.queued_moves.borrow_mut();
+        if queued_moves.len() < MAX_QUEUED_MOVES {
+            queued_moves.push_back(slide_move);
+        } else {
+            log::debug!("Ignoring move {slide_move}, too many moves are queued");
+        }
+    }
+
+    /// Take the next queued move that is possible from the active state, the impossible ones are dropped
+    fn dequeue_move(&self) -> Option<MoveInfo> {
+        let mut queued_moves = self.queued_moves.borrow_mut();
+        while let Some(slide_move) = queued_moves.pop_front() {
+            let move_info = collect_moves(&self.graph.borrow(), self.active_state.get())
+                .ok()?
+                .into_iter()
+                .find(|move_info| move_info.slide_move == slide_move);
+            match move_info {
+                Some(move_info) => return Some(move_info),
+                None => log::debug!("Dropping queued move {slide_move}, it is no longer possible"),
+            }
+        }
+        None
+    }
+
+    /// Tell screen reader users and the hosting page which move was made, and play its sound effect
+    fn acknowledge_move(
+        &self,
+        previous_state: BoardId,
+        slide_move: &SlideMove,
+        new_state: BoardId,
+    ) {
+        let (board, distance) = {
+            let graph = self.graph.borrow();
+            let node = graph.get(new_state).expect("Invalid ID");
+            // States past a one-way cell may not be able to reach the solution
+            (node.board, node.distance_to_solution())
+        };
+        self.moves_view.borrow().announce_move(slide_move, distance);
+        self.record_step(Step::Move(*slide_move));
+        self.events
+            .emit(GameEvent::MoveMade(*slide_move, new_state));
+
+        let progress = board.progress;
+        let previous_progress = self
+            .graph
+            .borrow()
+            .get(previous_state)
+            .map(|node| node.board.progress);
+        if previous_progress.is_some_and(|previous| previous.reached() < progress.reached()) {
+            self.events.emit(GameEvent::CheckpointReached {
+                state: new_state,
+                reached: progress.reached(),
+                count: progress.checkpoints().len(),
+            });
+        }
+
+        let solved = board::is_solution(&board);
+        if solved {
+            self.events.emit(GameEvent::Solved(new_state));
+        }
+        let sound = match solved {
+            true => Sound::Fanfare,
+            false => Sound::Slide,
+        };
+        self.audio.borrow_mut().play(sound);
+    }
+
+    /// Start at the state from the page's permalink, or at the start position if there is none
+    pub fn start(self_ref: &Rc<RefCell<Self>>) {
+        self_ref.borrow().apply_settings();
+
+        let linked_state = self_ref
+            .borrow()
+            .permalink
+            .borrow()
+            .as_ref()
+            .and_then(|permalink| permalink.read());
+        let linked_state = linked_state.filter(|state| {
+            let is_known = self_ref.borrow().graph.borrow().contains(*state);
+            if !is_known {
+                log::warn!("Ignoring permalink to unknown position {state:016x}");
+            }
+            is_known
+        });
+        let state = linked_state.unwrap_or(self_ref.borrow().start_state);
         let self_ref = self_ref.borrow();
+        *self_ref.history.borrow_mut() = History::new(state);
+        *self_ref.record.borrow_mut() = GameRecord::new(state);
+        self_ref.record_epoch.set(self_ref.clock.now());
+        self_ref.set_state(state);
+    }
+
+    fn record_step(&self, step: Step) {
+        if self
+            .replay
+            .borrow()
+            .as_ref()
+            .is_some_and(|replay| !replay.solution)
+        {
+            // Replayed steps aren't part of this game
+            return;
+        }
+        let time = self.clock.now().saturating_sub(self.record_epoch.get());
+        self.record.borrow_mut().push(time, step);
+    }
+
+    /// The record of this game in text form, see the record module for its format
+    pub fn export_record(&self) -> String {
+        self.record.borrow().to_string()
+    }
+
+    /// Continue from the end of a recorded game, its states become the history to undo through
+    pub fn import_record(&self, text: &str) -> anyhow::Result<()> {
+        let record: GameRecord = text.parse()?;
+        let states = record.replay(&self.graph.borrow())?;
+        if self.move_lock.load(Relaxed) {
+            return Err(anyhow!("Can't import a game while a move is ongoing"));
+        }
+        self.stop_replay();
+        self.invalidate_attempt();
+
+        let mut history = History::new(record.start);
+        states.iter().for_each(|state| history.visit(*state));
+        *self.history.borrow_mut() = history;
+        // The game continues where the record left off
+        self.record_epoch
+            .set(self.clock.now().saturating_sub(record.duration()));
+        *self.record.borrow_mut() = record;
+        self.set_state(*states.last().expect("Replay always includes the start"));
+        Ok(())
+    }
+
+    /// Measure the whole graph, this searches the graph many times over so it is slow
+    pub fn metrics(&self) -> Metrics {
+        Metrics::new(&self.graph.borrow())
+    }
+
+    pub fn graph(&self) -> Ref<'_, Graph> {
+        self.graph.borrow()
+    }
+
+    pub fn active_state(&self) -> BoardId {
+        self.active_state.get()
+    }
+
+    pub fn active_board(&self) -> board::Board {
+        self.graph
+            .borrow()
+            .get(self.active_state.get())
+            .expect("The active state is in the graph")
+            .board
+    }
+
+    /// Draw a picture of the current board
+    /// @returns the picture as a data URL
+    pub fn export_board_image(
+        &self,
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+    ) -> Result<String, JsValue> {
+        self.board_view.borrow().export_image(format, width, height)
+    }
+
+    /// Make a move from the active state, given in notation like "B2⮕B1"
+    pub fn do_move_notation(self_ref: &Rc<RefCell<Self>>, notation: &str) -> anyhow::Result<()> {
+        let slide_move: SlideMove = notation.parse()?;
+        let move_info = {
+            let self_ref = self_ref.borrow();
+            let moves = collect_moves(&self_ref.graph.borrow(), self_ref.active_state.get())?;
+            moves
+                .into_iter()
+                .find(|move_info| move_info.slide_move == slide_move)
+                .ok_or_else(|| match self_ref.explain_move(&slide_move) {
+                    Some(rejection) => anyhow!("Move {slide_move} is not possible, {rejection}"),
+                    None => anyhow!("Move {slide_move} is not possible in this position"),
+                })?
+        };
+        let self_ref = self_ref.clone();
+        spawn_local(async move { Self::do_move(&self_ref, &move_info).await });
+        Ok(())
+    }
+
+    /// Find out why a move can't be made from the active state, see board::explain_move
+    pub fn explain_move(&self, slide_move: &SlideMove) -> Option<board::MoveRejection> {
+        board::explain_move(&self.active_board(), slide_move)
+    }
+
+    /// Switch to the mirror image of the active state, the pieces slide across into their places
+    /// @returns an error if the mirror image can't be reached in this puzzle, or a move is ongoing
+    pub async fn flip_board(self_ref: &Rc<RefCell<Self>>) -> anyhow::Result<()> {
+        let (state, animations_done) = {
+            let self_ref = self_ref.borrow();
+            let mirrored = self_ref.active_board().mirrored();
+            let state = board::to_id(&mirrored);
+            if !self_ref.graph.borrow().contains(state) {
+                return Err(anyhow!("The mirror image can't be reached in this puzzle"));
+            }
+            if self_ref.move_lock.swap(true, Relaxed) {
+                return Err(anyhow!("A move is ongoing"));
+            }
+            self_ref.stop_replay();
+            self_ref.invalidate_attempt();
+            let animations_done = self_ref.board_view.borrow_mut().flip(&mirrored);
+            (state, animations_done)
+        };
+        join_all(animations_done).await;
+        {
+            let self_ref = self_ref.borrow();
+            self_ref.history.borrow_mut().visit(state);
+            self_ref.set_state(state);
+            self_ref.record_step(Step::Jump(state));
+        }
+        Self::finish_moving(self_ref).await;
+        Ok(())
+    }
+
+    /// Jump to a state, after checking that it exists
+    pub fn go_to(self_ref: &Rc<RefCell<Self>>, state: BoardId) -> anyhow::Result<()> {
+        if !self_ref.borrow().graph.borrow().contains(state) {
+            return Err(anyhow!("Unknown position {state:016x}"));
+        }
+        Self::jump_to(self_ref, state);
+        Ok(())
+    }
+
+    /// Step back to the previous state, returns false if there is none or a move is ongoing
+    pub fn undo(self_ref: &Rc<RefCell<Self>>) -> bool {
+        Self::step_through_history(self_ref, History::undo)
+    }
+
+    /// Step forward to the state that was last undone, returns false if there is none or a move is ongoing
+    pub fn redo(self_ref: &Rc<RefCell<Self>>) -> bool {
+        Self::step_through_history(self_ref, History::redo)
+    }
+
+    /// Step back through the history to the latest visited state that lies on a shortest path from the
+    /// start to the solution, animating the way back through the visited states
+    /// @returns false if no such state was visited, the active state is already on a shortest path, or a move
+    /// is ongoing
+    pub fn back_to_best_known(self_ref: &Rc<RefCell<Self>>) -> bool {
+        let (path, target) = {
+            let self_ref = self_ref.borrow();
+            if self_ref.move_lock.load(Relaxed) {
+                return false;
+            }
+            // There's no better state to go back to, and the history is left as it is
+            let active_state = self_ref.active_state.get();
+            if self_ref
+                .graph
+                .borrow()
+                .is_on_shortest_path(self_ref.start_state, active_state)
+            {
+                return false;
+            }
+            self_ref.stop_replay();
+            let states = self_ref.history.borrow_mut().undo_until(|state| {
+                self_ref
+                    .graph
+                    .borrow()
+                    .is_on_shortest_path(self_ref.start_state, state)
+            });
+            let Some(target) = states.last().copied() else {
+                return false;
+            };
+            // Retrace the visited states, jumps between them are animated if they are short
+            let mut path = Vec::new();
+            let mut current = self_ref.active_state.get();
+            for state in states {
+                match self_ref
+                    .graph
+                    .borrow()
+                    .find_path(current, state, MAX_ANIMATED_JUMP_LENGTH)
+                {
+                    Some(steps) => path.extend(steps),
+                    None => {
+                        path.clear();
+                        break;
+                    }
+                }
+                current = state;
+            }
+            (path, target)
+        };
+        if path.is_empty() {
+            Self::travel_to(self_ref, target);
+        } else {
+            spawn_local(Self::follow_path(self_ref.clone(), path, MOVE_DURATION));
+        }
+        true
+    }
+
+    fn step_through_history(
+        self_ref: &Rc<RefCell<Self>>,
+        step: impl FnOnce(&mut History) -> Option<BoardId>,
+    ) -> bool {
+        let state = {
+            let self_ref = self_ref.borrow();
+            if self_ref.move_lock.load(Relaxed) {
+                return false;
+            }
+            self_ref.stop_replay();
+            let state = step(&mut self_ref.history.borrow_mut());
+            state
+        };
+        let Some(state) = state else {
+            return false;
+        };
+        Self::travel_to(self_ref, state);
+        true
+    }
+
+    pub fn restart(self_ref: &Rc<RefCell<Self>>) {
         // TODO(Menno 24.08.2025) Restart should cancel ongoing moves
-        let Some(_lock) = BoolGuard::lock(&self_ref.move_lock) else {
-            // Refuse to restart, a move is ongoing
+        self_ref.borrow().queued_moves.borrow_mut().clear();
+        if self_ref.borrow().move_lock.load(Relaxed) {
+            // The jump would be refused, so the attempt goes on
+            return;
+        }
+        let start_state = self_ref.borrow().start_state;
+        Self::jump_to(self_ref, start_state);
+        self_ref.borrow().events.emit(GameEvent::Restart);
+        // A restart starts a new attempt
+        self_ref.borrow().restart_attempt();
+    }
+
+    /// Play back a recorded game from its start, the record of the current game continues afterward
+    pub fn start_replay(self_ref: &Rc<RefCell<Self>>, text: &str) -> anyhow::Result<()> {
+        let self_ref = self_ref.borrow();
+        let record: GameRecord = text.parse()?;
+        let states = record.replay(&self_ref.graph.borrow())?;
+        if self_ref.move_lock.load(Relaxed) {
+            return Err(anyhow!("Can't start a replay while a move is ongoing"));
+        }
+        self_ref.stop_replay();
+        self_ref.invalidate_attempt();
+
+        *self_ref.replay.borrow_mut() = Some(Replay::new(states));
+        self_ref.set_state(record.start);
+        self_ref.show_replay_progress();
+        Ok(())
+    }
+
+    /// Scramble the board with a number of quick random moves, ending in a position that takes a
+    /// number of moves to solve
+    pub fn shuffle(
+        self_ref: &Rc<RefCell<Self>>,
+        moves: usize,
+        solve_moves: RangeInclusive<u32>,
+    ) -> anyhow::Result<()> {
+        let path = {
+            let self_ref = self_ref.borrow();
+            if self_ref.move_lock.load(Relaxed) {
+                return Err(anyhow!("Can't shuffle while a move is ongoing"));
+            }
+            let seed = (js_sys::Math::random() * u64::MAX as f64) as u64;
+            let path = shuffle(
+                &self_ref.graph.borrow(),
+                self_ref.active_state.get(),
+                moves,
+                solve_moves.clone(),
+                seed,
+            )
+            .ok_or_else(|| {
+                anyhow!(
+                    "No shuffle of {moves} moves ends in a position that takes {} to {} moves to solve",
+                    solve_moves.start(),
+                    solve_moves.end()
+                )
+            })?;
+            self_ref.stop_replay();
+            self_ref.invalidate_attempt();
+            if let Some(last) = path.last() {
+                let state = self_ref.graph.borrow().node(last.neighbor).id;
+                self_ref.history.borrow_mut().visit(state);
+            }
+            path
+        };
+        spawn_local(Self::follow_path(
+            self_ref.clone(),
+            path,
+            SHUFFLE_MOVE_DURATION,
+        ));
+        Ok(())
+    }
+
+    /// Play the remaining optimal path to the solution with animations, then continue the game there.
+    /// This plays like a replay, which closes at its end.
+    pub fn solve_from_here(self_ref: &Rc<RefCell<Self>>) -> anyhow::Result<()> {
+        {
+            let self_ref = self_ref.borrow();
+            if self_ref.move_lock.load(Relaxed) {
+                return Err(anyhow!("Can't solve while a move is ongoing"));
+            }
+            let states = self_ref
+                .graph
+                .borrow()
+                .solution_path(self_ref.active_state.get());
+            if states.len() < 2 {
+                return Err(anyhow!("There are no moves left to solve this position"));
+            }
+            self_ref.stop_replay();
+            self_ref.invalidate_attempt();
+            *self_ref.replay.borrow_mut() = Some(Replay::solution(states));
+        }
+        Self::set_replay_playing(self_ref, true);
+        Ok(())
+    }
+
+    /// Show the state at a position of the replay, returns false if that isn't possible right now
+    pub fn replay_seek(self_ref: &Rc<RefCell<Self>>, position: usize) -> bool {
+        let state = {
+            let self_ref = self_ref.borrow();
+            if self_ref.move_lock.load(Relaxed) {
+                return false;
+            }
+            let mut replay = self_ref.replay.borrow_mut();
+            replay.as_mut().and_then(|replay| replay.seek(position))
+        };
+        let Some(state) = state else {
+            return false;
+        };
+        Self::travel_to(self_ref, state);
+        self_ref.borrow().show_replay_progress();
+        true
+    }
+
+    /// Step forward or back through the replay, returns false if that isn't possible right now
+    pub fn replay_step(self_ref: &Rc<RefCell<Self>>, forward: bool) -> bool {
+        let position = self_ref
+            .borrow()
+            .replay
+            .borrow()
+            .as_ref()
+            .map(Replay::position);
+        let position = match (position, forward) {
+            (Some(position), true) => position + 1,
+            (Some(position), false) if position > 0 => position - 1,
+            _ => return false,
+        };
+        Self::replay_seek(self_ref, position)
+    }
+
+    pub fn set_replay_playing(self_ref: &Rc<RefCell<Self>>, playing: bool) {
+        {
+            let self_ref = self_ref.borrow();
+            let mut replay = self_ref.replay.borrow_mut();
+            let Some(replay) = replay.as_mut() else {
+                return;
+            };
+            replay.playing = playing;
+            if let Some(timeout_id) = replay.timeout_id.take() {
+                cancel_timeout(timeout_id);
+            }
+        }
+        if playing {
+            Self::schedule_replay_step(self_ref);
+        }
+        self_ref.borrow().show_replay_progress();
+    }
+
+    fn schedule_replay_step(self_ref: &Rc<RefCell<Self>>) {
+        let self_ref_clone = self_ref.clone();
+        let timeout_id = get_window()
+            .and_then(|window| {
+                window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    Closure::<dyn FnMut()>::new(move || Self::advance_replay(&self_ref_clone))
+                        .into_js_value()
+                        .unchecked_ref(),
+                    REPLAY_STEP_INTERVAL,
+                )
+            })
+            .expect("Failed to schedule replay step");
+        if let Some(replay) = self_ref.borrow().replay.borrow_mut().as_mut() {
+            replay.timeout_id = Some(timeout_id);
+        }
+    }
+
+    /// Take the next step of a playing replay, and pause at its end
+    fn advance_replay(self_ref: &Rc<RefCell<Self>>) {
+        let (playing, moving) = {
+            let self_ref = self_ref.borrow();
+            let mut replay = self_ref.replay.borrow_mut();
+            let Some(replay) = replay.as_mut() else {
+                return;
+            };
+            replay.timeout_id = None;
+            (replay.playing, self_ref.move_lock.load(Relaxed))
+        };
+        if !playing {
+            return;
+        }
+        if moving {
+            // Wait for the previous step to finish animating
+            Self::schedule_replay_step(self_ref);
+        } else if Self::replay_step(self_ref, true) {
+            Self::schedule_replay_step(self_ref);
+        } else if self_ref
+            .borrow()
+            .replay
+            .borrow()
+            .as_ref()
+            .is_some_and(|replay| replay.solution)
+        {
+            // The solution was reached, the final move emitted the solved event
+            self_ref.borrow().stop_replay();
+        } else {
+            Self::set_replay_playing(self_ref, false);
+        }
+    }
+
+    /// Close the replay, the game continues from the state it shows
+    pub fn stop_replay(&self) {
+        let Some(replay) = self.replay.take() else {
             return;
         };
-        self_ref.set_state(board::to_id(&board::get_start_board()));
+        if let Some(timeout_id) = replay.timeout_id {
+            cancel_timeout(timeout_id);
+        }
+        self.graph_view.borrow_mut().show_replay(None);
+
+        let state = self.active_state.get();
+        if replay.solution {
+            // The moves of the solution are part of the game, they can be undone one by one
+            let (states, position) = replay.path();
+            let mut history = self.history.borrow_mut();
+            states[1..=position]
+                .iter()
+                .for_each(|state| history.visit(*state));
+        } else {
+            self.history.borrow_mut().visit(state);
+            self.record_step(Step::Jump(state));
+        }
+        self.events.emit(GameEvent::ReplayEnded);
+    }
+
+    fn show_replay_progress(&self) {
+        if let Some(replay) = self.replay.borrow().as_ref() {
+            self.graph_view
+                .borrow_mut()
+                .show_replay(Some(replay.path()));
+            self.events.emit(replay.progress());
+        }
+    }
+
+    /// Jump to any state, and remember it in the history
+    pub fn jump_to(self_ref: &Rc<RefCell<Self>>, state: BoardId) {
+        {
+            let self_ref = self_ref.borrow();
+            if self_ref.move_lock.load(Relaxed) {
+                // Refuse to jump, a move is ongoing
+                return;
+            }
+            self_ref.stop_replay();
+            self_ref.invalidate_attempt();
+            self_ref.history.borrow_mut().visit(state);
+        }
+        Self::travel_to(self_ref, state);
+    }
+
+    /// Go to any state, nearby states are reached by animating the moves in between
+    fn travel_to(self_ref: &Rc<RefCell<Self>>, state: BoardId) {
+        let path = {
+            let self_ref = self_ref.borrow();
+            let graph = self_ref.graph.borrow();
+            graph.find_path(self_ref.active_state.get(), state, MAX_ANIMATED_JUMP_LENGTH)
+        };
+        if let Some(path) = path.filter(|path| !path.is_empty()) {
+            spawn_local(Self::follow_path(self_ref.clone(), path, MOVE_DURATION));
+            return;
+        }
+
+        let self_ref = self_ref.borrow();
+        if self_ref.move_lock.load(Relaxed) {
+            // Refuse to jump, a move is ongoing
+            return;
+        }
+        self_ref.set_state(state);
+        self_ref.record_step(Step::Jump(state));
+    }
+
+    /// Make a sequence of moves, animating each one in turn
+    async fn follow_path(
+        self_ref: Rc<RefCell<Self>>,
+        path: Vec<graph::Edge>,
+        move_duration: Duration,
+    ) {
+        let move_lock = self_ref.borrow().move_lock.clone();
+        let Some(_lock) = BoolGuard::lock(&move_lock) else {
+            // Refuse to jump, a move is ongoing
+            return;
+        };
+
+        for edge in path {
+            let neighbor = self_ref.borrow().graph.borrow().node(edge.neighbor).id;
+            let previous_state = self_ref.borrow().active_state.get();
+            let move_done = self_ref
+                .borrow()
+                .board_view
+                .borrow_mut()
+                .do_move_for(&edge.slide_move, move_duration);
+            move_done.await.expect("Unable to finish move");
+            self_ref.borrow().set_state(neighbor);
+            self_ref
+                .borrow()
+                .acknowledge_move(previous_state, &edge.slide_move, neighbor);
+            // Moves through the history count, but only the player's own moves start an attempt
+            self_ref
+                .borrow()
+                .count_move(previous_state, &edge.slide_move, neighbor, false);
+        }
+        Self::finish_moving(&self_ref).await;
     }
 
     fn set_state(&self, new_state: BoardId) {
+        self.update_views(new_state);
+
+        let index = self.graph.borrow().index_of(new_state).expect("Invalid ID");
+        self.board_view
+            .borrow_mut()
+            .transition_to(&self.graph.borrow().neighborhood(index));
+    }
+
+    /// Update everything except the BoardView to show the new state
+    fn update_views(&self, new_state: BoardId) {
+        if let Some(expansion) = &self.expansion {
+            if let Err(error) = expansion.expand(&mut self.graph.borrow_mut(), new_state) {
+                log::error!("Failed to expand the graph: {error}");
+            }
+        }
+        if self.active_state.replace(new_state) != new_state {
+            self.events.emit(GameEvent::StateChanged(new_state));
+        }
+        let symmetry_folding = self.symmetry_folding.get();
+        self.history_view.borrow().set_data(&self.history.borrow());
+
         self.graph_view
             .borrow_mut()
-            .set_data(&self.graph, new_state);
+            .set_data(&self.graph.borrow(), new_state);
         self.moves_view
             .borrow_mut()
-            .set_data(&self.graph, new_state);
+            .set_data(&self.graph.borrow(), new_state);
+        self.stats_view
+            .set_data(&self.graph.borrow(), new_state, symmetry_folding);
 
-        let node = self.graph.map.get(&new_state).expect("Invalid ID");
-        self.board_view.borrow_mut().transition_to(node);
+        let linked_state = match symmetry_folding {
+            true => self
+                .graph
+                .borrow()
+                .get(new_state)
+                .map_or(new_state, |node| board::to_canonical_id(&node.board)),
+            false => new_state,
+        };
+        if let Some(permalink) = self.permalink.borrow().as_ref() {
+            if let Err(error) = permalink.write(linked_state) {
+                log::warn!("Failed to update permalink: {error:?}");
+            }
+        }
     }
 }
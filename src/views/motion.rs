@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! How much the views animate. With reduced motion, pieces and nodes jump to their new places
+//! instead of sliding, fades are kept as they don't move anything.
+
+use crate::views::utils::get_window;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Whether the system asks to minimize motion, through the prefers-reduced-motion media query
+pub fn prefers_reduced_motion() -> bool {
+    get_window()
+        .ok()
+        .and_then(|window| {
+            window
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()?
+        })
+        .is_some_and(|media_query_list| media_query_list.matches())
+}
+
+/// The factor that a game scales its animation durations by, zero disables movement.
+/// Clones share the same factor.
+#[derive(Clone)]
+pub struct Motion {
+    scale: Rc<Cell<f64>>,
+}
+
+impl Default for Motion {
+    fn default() -> Self {
+        Self {
+            scale: Rc::new(Cell::new(1.0)),
+        }
+    }
+}
+
+impl Motion {
+    pub fn set_reduced(&self, enabled: bool) {
+        self.scale.set(if enabled { 0.0 } else { 1.0 });
+    }
+
+    pub fn is_reduced(&self) -> bool {
+        self.scale.get() == 0.0
+    }
+
+    /// Scale the duration of an animation that moves things
+    pub fn scale_duration(&self, duration: Duration) -> Duration {
+        duration.mul_f64(self.scale.get())
+    }
+}
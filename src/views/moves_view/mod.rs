@@ -1,78 +1,329 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
-use crate::board::{BoardId, SlideMove};
+use crate::board;
+use crate::board::{BoardId, SlideDirection, SlideMove};
+use crate::error::Error;
 use crate::graph::Graph;
-use crate::views::utils::{get_document, get_element_of_type, get_window};
-use crate::views::StatefulViews;
-use itertools::Itertools;
-use std::cell::RefCell;
+use crate::views::command_bus::{Command, CommandBus};
+use crate::views::events::{Events, GameEvent};
+use crate::views::moves_view::temperature::{get_temperature, TemperatureGauge};
+use crate::views::theme::Theme;
+use crate::views::utils::{cancel_timeout, get_document, get_window};
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::rc::{Rc, Weak};
+use std::time::Duration;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
-use wasm_bindgen_futures::spawn_local;
-use web_sys::{Document, HtmlDivElement};
+use web_sys::{
+    Document, Element, HtmlDivElement, HtmlElement, ScrollIntoViewOptions, ScrollLogicalPosition,
+};
+
+mod temperature;
+#[cfg(test)]
+mod unittest;
+
+/// How long a touch must be held on a move button to preview the move, in milliseconds
+const LONG_PRESS_DURATION: i32 = 300;
+
+/// The default time between the moves of auto-solve, in milliseconds
+const AUTO_SOLVE_INTERVAL: i32 = 200;
+
+/// The moves are listed in groups by how they change the distance to the solution, best first
+const MOVE_GROUPS: [(MoveEffectiveness, &str); 3] = [
+    (MoveEffectiveness::Positive, "Closer to the solution"),
+    (MoveEffectiveness::Neutral, "Just as far"),
+    (MoveEffectiveness::Negative, "Further from the solution"),
+];
+
+/// What the moves of a group have in common
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveGroupKind {
+    /// The moves change the distance alike, this is the index into MOVE_GROUPS
+    Effectiveness(usize),
+    /// The moves move the piece at these coordinates
+    Piece(board::Coordinates),
+}
+
+/// A group of moves in the list, under a collapsible header
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MoveGroup {
+    kind: MoveGroupKind,
+    title: String,
+    moves: Vec<MoveInfo>,
+    expanded: bool,
+}
+
+/// Divide the moves into groups, either by how they change the distance or by the piece that they move.
+/// The groups and the moves within them keep the order of the moves, so the best move comes first.
+/// @param expanded_groups which of the MOVE_GROUPS are expanded, the piece groups always start expanded
+fn group_moves(
+    moves: &[MoveInfo],
+    by_piece: bool,
+    expanded_groups: [bool; MOVE_GROUPS.len()],
+) -> Vec<MoveGroup> {
+    if by_piece {
+        let mut groups: Vec<MoveGroup> = Vec::new();
+        for move_info in moves {
+            let kind = MoveGroupKind::Piece(move_info.slide_move.start);
+            match groups.iter_mut().find(|group| group.kind == kind) {
+                Some(group) => group.moves.push(*move_info),
+                None => groups.push(MoveGroup {
+                    kind,
+                    title: format!("Piece at {}", move_info.slide_move.start),
+                    moves: vec![*move_info],
+                    expanded: true,
+                }),
+            }
+        }
+        return groups;
+    }
+
+    MOVE_GROUPS
+        .into_iter()
+        .enumerate()
+        .map(|(index, (effectiveness, title))| MoveGroup {
+            kind: MoveGroupKind::Effectiveness(index),
+            title: title.to_string(),
+            moves: moves
+                .iter()
+                .filter(|move_info| move_info.effectiveness == effectiveness)
+                .copied()
+                .collect(),
+            expanded: expanded_groups[index],
+        })
+        .filter(|group| !group.moves.is_empty())
+        .collect()
+}
+
+/// Pick the next move of auto-solve, which follows a shortest path into a solved state
+/// @param current_distance the distance of the current state, solved states are one move from the "fake" solution
+/// @param through_neutral when no move gets closer, make a move that keeps the distance instead,
+///                        if it leads to a state that auto-solve didn't visit yet
+/// @returns the move, or None when the puzzle is solved or there is no way forward
+fn pick_auto_solve_move(
+    moves: &[MoveInfo],
+    current_distance: u32,
+    through_neutral: bool,
+    visited: &HashSet<BoardId>,
+) -> Option<MoveInfo> {
+    if current_distance <= 1 {
+        return None;
+    }
+    let closest = moves
+        .iter()
+        .min_by_key(|move_info| move_info.resulting_distance.unwrap_or(u32::MAX))?;
+    if closest
+        .resulting_distance
+        .is_some_and(|distance| distance < current_distance)
+    {
+        return Some(*closest);
+    }
+    moves
+        .iter()
+        .find(|move_info| {
+            through_neutral
+                && move_info.effectiveness == MoveEffectiveness::Neutral
+                && !visited.contains(&move_info.resulting_id)
+        })
+        .copied()
+}
+
+/// The progress of a long press on a move button, touch devices preview moves this way
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LongPress {
+    Idle,
+    /// The touch started, the preview starts at the timeout with this ID
+    Pending(i32),
+    /// The move is previewed, releasing the touch makes it
+    Previewing,
+    /// The move was made on release, the click that follows the release is ignored
+    Committed,
+}
 
 /**
- * Get the move indicator color for a given delta-distance
+ * Get the move indicator symbol, this tells moves apart without relying on color
  * @param effectiveness How the move impacts resulting distance to the solution
- * @return The move-indicator color
+ * @return The move-indicator symbol
  */
-fn get_color(effectiveness: MoveEffectiveness) -> &'static str {
+fn get_symbol(effectiveness: MoveEffectiveness) -> &'static str {
     match effectiveness {
-        MoveEffectiveness::Positive => "#009d77",
-        MoveEffectiveness::Neutral => "#4B7BFF",
-        MoveEffectiveness::Negative => "#ff443a",
+        MoveEffectiveness::Positive => "▲",
+        MoveEffectiveness::Neutral => "▬",
+        MoveEffectiveness::Negative => "▼",
     }
 }
 
-fn collect_moves(graph: &Graph, state: BoardId) -> Vec<MoveInfo> {
-    let state = graph
-        .map
-        .get(&state)
-        .expect("Could not find state in graph");
+/// Count steps in words, e.g. "one step" or "3 steps"
+fn describe_steps(steps: u32) -> String {
+    match steps {
+        1 => "one step".into(),
+        steps => format!("{steps} steps"),
+    }
+}
 
-    let current_distance = state
-        .distance_to_solution
-        .expect("Incomplete state, missing distance field");
+/// Describe a move for screen readers, e.g. "Move piece at B2 down one step, 97 steps remaining"
+/// @param verb the verb that starts the description, e.g. "Moved" for a move that was made already
+/// @param resulting_distance the distance to the solution after the move, None if it's out of reach
+fn describe_move(verb: &str, slide_move: &SlideMove, resulting_distance: Option<u32>) -> String {
+    let direction = match slide_move.direction {
+        SlideDirection::Up => "up",
+        SlideDirection::Down => "down",
+        SlideDirection::Left => "left",
+        SlideDirection::Right => "right",
+    };
+    let remaining = match resulting_distance {
+        Some(0) => "puzzle solved".into(),
+        Some(distance) => format!("{} remaining", describe_steps(distance)),
+        None => "the solution is out of reach".into(),
+    };
+    format!(
+        "{verb} piece at {} {direction} {}, {remaining}",
+        slide_move.start,
+        describe_steps(slide_move.distance as u32)
+    )
+}
 
-    state
-        .edges
-        .iter()
-        .filter_map(|edge| {
-            let neighbor = graph.map.get(&edge.neighbor).expect("Invalid neighbor ID");
-            let resulting_distance = neighbor
-                .distance_to_solution
-                .expect("Incomplete neighbour, missing distance field");
-            let effectiveness = match resulting_distance.cmp(&current_distance) {
+/// Create a live region at the end of the page, screen readers read out any text that is put inside
+fn create_announcer() -> Result<HtmlDivElement, JsValue> {
+    let document = get_document()?;
+    let announcer_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
+    announcer_div.class_list().add_1("visually-hidden")?;
+    announcer_div.set_attribute("aria-live", "polite")?;
+    announcer_div.set_attribute("role", "status")?;
+    document
+        .body()
+        .ok_or(JsValue::from_str("Document has no body"))?
+        .append_child(&announcer_div)?;
+    Ok(announcer_div)
+}
+
+/// Color the move indicator, and mark it with a symbol if shape coding is enabled
+fn style_indicator(
+    indicator: &HtmlElement,
+    effectiveness: MoveEffectiveness,
+    theme: &Theme,
+    shape_coded: bool,
+) -> Result<(), JsValue> {
+    indicator.style().set_property(
+        "background-color",
+        &theme.move_color(effectiveness).to_css(1.0),
+    )?;
+    indicator.set_text_content(shape_coded.then(|| get_symbol(effectiveness)));
+    Ok(())
+}
+
+/// List the moves from a state, best first. The moves into states that can't reach the solution,
+/// like those past a one-way cell, come last.
+/// @returns the moves, or an error if the state is unknown
+pub fn collect_moves(graph: &Graph, id: BoardId) -> Result<Vec<MoveInfo>, Error> {
+    let state = graph.get(id).ok_or(Error::UnknownState(id))?;
+    let current_distance = state.distance_to_solution();
+
+    let mut moves = Vec::new();
+    for (edge, neighbor) in graph.neighbors(state) {
+        let resulting_distance = neighbor.distance_to_solution();
+        let effectiveness = match (resulting_distance, current_distance) {
+            (Some(resulting), Some(current)) => match resulting.cmp(&current) {
                 Ordering::Less => MoveEffectiveness::Positive,
                 Ordering::Equal => MoveEffectiveness::Neutral,
                 Ordering::Greater => MoveEffectiveness::Negative,
-            };
+            },
+            (None, Some(_)) => MoveEffectiveness::Negative,
+            (Some(_), None) => MoveEffectiveness::Positive,
+            (None, None) => MoveEffectiveness::Neutral,
+        };
+
+        // Hide our "fake" solution moves
+        // TODO(Menno 28.06.2025) We could get rid of these fake moves by altering the solver
+        if resulting_distance == Some(0) {
+            continue;
+        }
+
+        moves.push(MoveInfo {
+            slide_move: edge.slide_move,
+            resulting_id: neighbor.id,
+            resulting_distance,
+            effectiveness,
+        });
+    }
+    moves.sort_by_key(|move_info| move_info.resulting_distance.unwrap_or(u32::MAX));
+    Ok(moves)
+}
 
-            // Hide our "fake" solution moves
-            // TODO(Menno 28.06.2025) We could get rid of these fake moves by altering the solver
-            if resulting_distance == 0 {
-                return None;
+/// Collapse the moves that end in the same position, so that each end position is listed once.
+/// Of the moves to a position the shortest slide is kept, it's the simplest to describe.
+/// @param moves sorted best first, like collect_moves returns them
+pub fn collapse_move_chains(moves: Vec<MoveInfo>) -> Vec<MoveInfo> {
+    let mut kept: Vec<MoveInfo> = Vec::with_capacity(moves.len());
+    for move_info in moves {
+        match kept
+            .iter_mut()
+            .find(|kept_move| kept_move.resulting_id == move_info.resulting_id)
+        {
+            Some(kept_move) => {
+                if move_info.slide_move.distance < kept_move.slide_move.distance {
+                    *kept_move = move_info;
+                }
             }
+            None => kept.push(move_info),
+        }
+    }
+    kept
+}
 
-            Some(MoveInfo {
-                slide_move: edge.slide_move,
-                resulting_id: edge.neighbor,
-                resulting_distance,
-                effectiveness,
-            })
+/// Create a collapsible group of moves with a header that counts them
+/// @returns the group, its header, and the element to put its move buttons in
+fn create_move_group(
+    title: &str,
+    count: usize,
+    expanded: bool,
+    document: &Document,
+) -> Result<(Element, Element, HtmlDivElement), JsValue> {
+    let group = document.create_element("details")?;
+    let summary = document.create_element("summary")?;
+    let list_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
+
+    group.class_list().add_1("game-moves-group")?;
+    summary.class_list().add_1("game-moves-group-title")?;
+    summary.set_text_content(Some(&format!("{title} ({count})")));
+    list_div.class_list().add_1("game-moves-group-list")?;
+    if expanded {
+        group.set_attribute("open", "")?;
+    }
+    group.append_with_node_2(&summary, &list_div)?;
+    Ok((group, summary, list_div))
+}
+
+/// Highlight the piece on the board while the header of its group is hovered
+fn link_piece(summary: &Element, piece: board::Coordinates, commands: &CommandBus) {
+    let commands_clone = commands.clone();
+    let on_enter = Closure::<dyn FnMut()>::new(move || {
+        commands_clone.publish(Command::HighlightPiece(Some(piece)));
+    });
+    let commands_clone = commands.clone();
+    let on_leave = Closure::<dyn FnMut()>::new(move || {
+        commands_clone.publish(Command::HighlightPiece(None));
+    });
+    summary
+        .add_event_listener_with_callback("mouseenter", on_enter.into_js_value().unchecked_ref())
+        .and_then(|_| {
+            summary.add_event_listener_with_callback(
+                "mouseleave",
+                on_leave.into_js_value().unchecked_ref(),
+            )
         })
-        .sorted_by(|a, b| a.resulting_distance.cmp(&b.resulting_distance))
-        .collect()
+        .expect("Failed to link move group to its piece");
 }
 
 /// Create a div that acts as a button that executes the corresponding move
+/// The contents are filled in by update_move_button, the handlers act on the move that it holds at the time
 fn create_move_button(
-    move_info: MoveInfo,
+    move_info: Rc<Cell<MoveInfo>>,
     document: &Document,
-    parent_ref: Rc<RefCell<StatefulViews>>,
+    commands: &CommandBus,
 ) -> Result<HtmlDivElement, JsValue> {
     let move_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
     let indicator_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
@@ -80,60 +331,72 @@ fn create_move_button(
     let description_span = document.create_element("span")?;
 
     indicator_div.class_list().add_1("game-move-indicator")?;
-    indicator_div
-        .style()
-        .set_property("background-color", get_color(move_info.effectiveness))?;
+    // The indicator repeats what the description says, screen readers can skip it
+    indicator_div.set_attribute("aria-hidden", "true")?;
 
-    coordinates_span.append_with_str_1(move_info.slide_move.to_string().as_str())?;
     coordinates_span.class_list().add_1("game-coordinates")?;
 
     description_span
         .class_list()
         .add_1("game-move-description")?;
     description_span.append_with_node_1(&coordinates_span)?;
-    description_span
-        .append_with_str_1(format!("{} steps left", move_info.resulting_distance).as_str())?;
+    // The text with the remaining steps
+    description_span.append_with_str_1("")?;
 
     move_div.append_with_node_2(&indicator_div, &description_span)?;
     move_div.class_list().add_1("game-move")?;
 
-    let move_info_copy = move_info;
+    // Act like a button for keyboards and screen readers
+    move_div.set_attribute("role", "button")?;
+    move_div.set_attribute("tabindex", "0")?;
+    let long_press = add_long_press_preview(&move_div, move_info.clone(), commands);
+
+    let move_div_clone = move_div.clone();
+    let long_press_clone = long_press.clone();
+    move_div.set_onkeydown(Some(
+        Closure::<dyn FnMut(web_sys::KeyboardEvent)>::new(move |event: web_sys::KeyboardEvent| {
+            if event.key() == "Enter" || event.key() == " " {
+                event.prevent_default();
+                long_press_clone.set(LongPress::Idle);
+                move_div_clone.click();
+            }
+        })
+        .into_js_value()
+        .unchecked_ref(),
+    ));
+
+    let move_info_clone = move_info.clone();
     let move_div_clone = move_div.clone();
-    let parent_ref_clone = parent_ref.clone();
+    let commands_clone = commands.clone();
     move_div.set_onclick(Some(
         Closure::<dyn FnMut(web_sys::PointerEvent)>::new(move |_event| {
+            if long_press.replace(LongPress::Idle) == LongPress::Committed {
+                // The release of the long press made this move already
+                return;
+            }
             move_div_clone
                 .class_list()
                 .add_1("clicked")
                 .expect("Failed to add clicked class to move div");
-
-            // TODO(Menno 24.08.2025) I don't quite understand why this second clone is needed,
-            //  but the compiler says so. And I'm too deep into this to question it. Please refactor.
-            let parent_ref_clone = parent_ref_clone.clone();
-            spawn_local(
-                async move { StatefulViews::do_move(&parent_ref_clone, &move_info_copy).await },
-            );
+            commands_clone.publish(Command::DoMove(move_info_clone.get()));
         })
         .into_js_value()
         .unchecked_ref(),
     ));
 
-    let move_info_copy = move_info;
-    let parent_ref_clone = parent_ref.clone();
+    let commands_clone = commands.clone();
     move_div.set_onmouseenter(Some(
         Closure::<dyn FnMut(web_sys::MouseEvent)>::new(move |_event| {
-            parent_ref_clone
-                .borrow_mut()
-                .preview_move(Some(move_info_copy));
+            commands_clone.publish(Command::PreviewMove(Some(move_info.get())));
         })
         .into_js_value()
         .unchecked_ref(),
     ));
 
-    let parent_ref_clone = parent_ref.clone();
+    let commands_clone = commands.clone();
     move_div.set_onmouseleave(Some(
         Closure::<dyn FnMut(web_sys::MouseEvent)>::new(move |_event| {
-            parent_ref_clone.borrow_mut().preview_move(None);
+            commands_clone.publish(Command::PreviewMove(None));
         })
         .into_js_value()
         .unchecked_ref(),
@@ -142,50 +405,224 @@ fn create_move_button(
     Ok(move_div)
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+/// Show a move on a button that was made by create_move_button
+fn update_move_button(
+    move_div: &HtmlDivElement,
+    move_info: MoveInfo,
+    theme: &Theme,
+    shape_coded: bool,
+) -> Result<(), JsValue> {
+    let missing = || JsValue::from_str("Move button is incomplete");
+    let indicator: HtmlElement = move_div
+        .first_element_child()
+        .ok_or_else(missing)?
+        .dyn_into()?;
+    let description_span = move_div.last_element_child().ok_or_else(missing)?;
+    let coordinates_span = description_span.first_element_child().ok_or_else(missing)?;
+    let steps_text = description_span.last_child().ok_or_else(missing)?;
+
+    style_indicator(&indicator, move_info.effectiveness, theme, shape_coded)?;
+    coordinates_span.set_text_content(Some(&move_info.slide_move.to_string()));
+    steps_text.set_text_content(Some(&match move_info.resulting_distance {
+        Some(distance) => format!("{distance} steps left"),
+        None => "out of reach".to_string(),
+    }));
+    move_div.set_attribute(
+        "aria-label",
+        &describe_move("Move", &move_info.slide_move, move_info.resulting_distance),
+    )?;
+    // The button may have shown another move before
+    move_div
+        .class_list()
+        .remove_3("clicked", "highlight", "linked")
+}
+
+/// Make the element's children the given elements in order, removing any others.
+/// Children that are already in place aren't touched, so only the changes cause work for the browser.
+fn place_children(parent: &Element, children: &[Element]) -> Result<(), JsValue> {
+    let mut occupant = parent.first_element_child();
+    for child in children {
+        if occupant.as_ref() == Some(child) {
+            occupant = child.next_element_sibling();
+        } else {
+            parent.insert_before(child, occupant.as_ref().map(|occupant| occupant.as_ref()))?;
+        }
+    }
+    while let Some(extra) = occupant {
+        occupant = extra.next_element_sibling();
+        extra.remove();
+    }
+    Ok(())
+}
+
+/// Preview the move while a touch is held on its button, and make it when the touch is released.
+/// This stands in for hovering on touch devices, mice hover and click as usual.
+fn add_long_press_preview(
+    move_div: &HtmlDivElement,
+    move_info: Rc<Cell<MoveInfo>>,
+    commands: &CommandBus,
+) -> Rc<Cell<LongPress>> {
+    let long_press = Rc::new(Cell::new(LongPress::Idle));
+
+    let long_press_clone = long_press.clone();
+    let commands_clone = commands.clone();
+    let move_info_clone = move_info.clone();
+    move_div.set_onpointerdown(Some(
+        Closure::<dyn FnMut(web_sys::PointerEvent)>::new(move |event: web_sys::PointerEvent| {
+            if event.pointer_type() != "touch" {
+                return;
+            }
+            let long_press_for_timeout = long_press_clone.clone();
+            let commands_for_timeout = commands_clone.clone();
+            let move_info_for_timeout = move_info_clone.clone();
+            let timeout_id = get_window().and_then(|window| {
+                window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    Closure::<dyn FnMut()>::new(move || {
+                        if let LongPress::Pending(_) = long_press_for_timeout.get() {
+                            long_press_for_timeout.set(LongPress::Previewing);
+                            commands_for_timeout
+                                .publish(Command::PreviewMove(Some(move_info_for_timeout.get())));
+                        }
+                    })
+                    .into_js_value()
+                    .unchecked_ref(),
+                    LONG_PRESS_DURATION,
+                )
+            });
+            match timeout_id {
+                Ok(timeout_id) => long_press_clone.set(LongPress::Pending(timeout_id)),
+                Err(error) => log::warn!("Failed to start long press: {error:?}"),
+            }
+        })
+        .into_js_value()
+        .unchecked_ref(),
+    ));
+
+    let long_press_clone = long_press.clone();
+    let commands_clone = commands.clone();
+    move_div.set_onpointerup(Some(
+        Closure::<dyn FnMut(web_sys::PointerEvent)>::new(move |_event| {
+            match long_press_clone.get() {
+                LongPress::Pending(timeout_id) => {
+                    // A short tap, the click that follows makes the move
+                    cancel_timeout(timeout_id);
+                    long_press_clone.set(LongPress::Idle);
+                }
+                LongPress::Previewing => {
+                    long_press_clone.set(LongPress::Committed);
+                    commands_clone.publish(Command::PreviewMove(None));
+                    commands_clone.publish(Command::DoMove(move_info.get()));
+                }
+                LongPress::Idle | LongPress::Committed => {}
+            }
+        })
+        .into_js_value()
+        .unchecked_ref(),
+    ));
+
+    // The touch left the button or turned into scrolling, this abandons the long press
+    let long_press_clone = long_press.clone();
+    let commands_clone = commands.clone();
+    let abandon = Closure::<dyn FnMut(web_sys::PointerEvent)>::new(move |_event| {
+        match long_press_clone.get() {
+            LongPress::Pending(timeout_id) => cancel_timeout(timeout_id),
+            LongPress::Previewing => commands_clone.publish(Command::PreviewMove(None)),
+            LongPress::Idle | LongPress::Committed => return,
+        }
+        long_press_clone.set(LongPress::Idle);
+    })
+    .into_js_value();
+    move_div.set_onpointercancel(Some(abandon.unchecked_ref()));
+    move_div.set_onpointerleave(Some(abandon.unchecked_ref()));
+
+    // Don't open the context menu that browsers show on a long press
+    let long_press_clone = long_press.clone();
+    move_div.set_oncontextmenu(Some(
+        Closure::<dyn FnMut(web_sys::MouseEvent)>::new(move |event: web_sys::MouseEvent| {
+            if long_press_clone.get() != LongPress::Idle {
+                event.prevent_default();
+            }
+        })
+        .into_js_value()
+        .unchecked_ref(),
+    ));
+
+    long_press
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum MoveEffectiveness {
     Positive,
     Neutral,
     Negative,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveInfo {
     pub slide_move: SlideMove,
     pub resulting_id: BoardId,
-    pub resulting_distance: u32,
+    /// The distance to the solution after the move, None if the solution is out of reach from there
+    pub resulting_distance: Option<u32>,
     pub effectiveness: MoveEffectiveness,
 }
 
+#[derive(Clone)]
 struct MoveButton {
-    move_info: MoveInfo,
+    /// Shared with the button's event handlers, buttons are reused for the same move from another state
+    move_info: Rc<Cell<MoveInfo>>,
     div: HtmlDivElement,
 }
 
+/// The elements of a group of moves in the list
+struct MoveGroupElements {
+    kind: MoveGroupKind,
+    group: Element,
+    summary: Element,
+    list_div: HtmlDivElement,
+}
+
 pub struct MovesView {
-    // TODO(Menno 17.08.2025) At this point I'm convinced my architecture sucks, and I know how to fix it,
-    //  but that's gonna be a fair bit of work. Dear future me, please remove this ref.
-    parent_ref: Weak<RefCell<StatefulViews>>,
+    /// Moves and restarts are requested from StatefulViews through here
+    commands: CommandBus,
     self_ref: Weak<RefCell<MovesView>>,
+    /// The move that auto-solve makes next
     best_move: Option<MoveButton>,
+    buttons: Vec<MoveButton>,
     auto_solve_enabled: bool,
+    auto_solve_through_neutral: bool,
+    /// The states that auto-solve passed, so that neutral moves don't go in circles
+    auto_solve_visited: HashSet<BoardId>,
     auto_solve_toggle_div: HtmlDivElement,
     auto_solve_timeout_id: Option<i32>,
+    /// The time between the moves of auto-solve, in milliseconds
+    auto_solve_interval: i32,
     restart_button_div: HtmlDivElement,
     moves_div: HtmlDivElement,
+    /// A visually hidden live region, which screen readers announce committed moves from
+    announcer_div: HtmlDivElement,
+    temperature_gauge: TemperatureGauge,
+    events: Events,
+    theme: Theme,
+    shape_coded: bool,
+    /// Which of the MOVE_GROUPS are expanded, this is kept when the list is rebuilt
+    expanded_groups: Rc<Cell<[bool; MOVE_GROUPS.len()]>>,
+    group_by_piece: bool,
+    collapse_chains: bool,
+    /// The listed groups, in order
+    groups: Vec<MoveGroupElements>,
 }
 
 impl MovesView {
     pub fn new(
-        moves_div_id: &str,
-        restart_div_id: &str,
-        solve_div_id: &str,
-        parent_ref: Weak<RefCell<StatefulViews>>,
+        moves_div: HtmlDivElement,
+        restart_div: HtmlDivElement,
+        solve_div: HtmlDivElement,
+        temperature_div: HtmlDivElement,
+        events: Events,
+        commands: CommandBus,
     ) -> Result<Rc<RefCell<Self>>, JsValue> {
         Ok(Rc::new_cyclic(|self_ref: &Weak<RefCell<Self>>| {
             let self_ref_clone = self_ref.clone();
-            let restart_div = get_element_of_type::<HtmlDivElement>(restart_div_id)
-                .expect("Failed to find restart button div");
             restart_div.set_onclick(Some(
                 Closure::<dyn FnMut(web_sys::PointerEvent)>::new(move |_event| {
                     MovesView::restart(&self_ref_clone.upgrade().unwrap());
@@ -195,8 +632,6 @@ impl MovesView {
             ));
 
             let self_ref_clone = self_ref.clone();
-            let solve_div = get_element_of_type::<HtmlDivElement>(solve_div_id)
-                .expect("Failed to find auto solve button div");
             solve_div.set_onclick(Some(
                 Closure::<dyn FnMut(web_sys::PointerEvent)>::new(move |_event| {
                     self_ref_clone
@@ -210,50 +645,134 @@ impl MovesView {
             ));
 
             RefCell::new(Self {
-                parent_ref,
+                commands,
                 self_ref: self_ref.clone(),
                 best_move: None,
+                buttons: Vec::new(),
                 auto_solve_enabled: false,
+                auto_solve_through_neutral: false,
+                auto_solve_visited: HashSet::new(),
                 auto_solve_toggle_div: solve_div,
                 auto_solve_timeout_id: None,
+                auto_solve_interval: AUTO_SOLVE_INTERVAL,
                 restart_button_div: restart_div,
-                moves_div: get_element_of_type(moves_div_id).expect("Failed to find moves div"),
+                moves_div,
+                announcer_div: create_announcer().expect("Failed to create move announcer"),
+                temperature_gauge: TemperatureGauge::new(temperature_div)
+                    .expect("Failed to create temperature gauge"),
+                events,
+                theme: Theme::default(),
+                shape_coded: false,
+                // Only the best moves are shown at first
+                expanded_groups: Rc::new(Cell::new([true, false, false])),
+                group_by_piece: false,
+                collapse_chains: false,
+                groups: Vec::new(),
             })
         }))
     }
 
-    pub fn set_data(&mut self, graph: &Graph, active_state: BoardId) {
-        // Clear the contents
-        self.moves_div.set_inner_html("");
+    /// Stop auto-solve, drop the pending commands, and clear the list and the buttons' handlers
+    pub fn destroy(&mut self) -> Result<(), JsValue> {
+        self.auto_solve_enabled = false;
+        self.cancel_best_move()?;
+        self.commands.cancel()?;
+        self.restart_button_div.set_onclick(None);
+        self.auto_solve_toggle_div.set_onclick(None);
+        self.moves_div.replace_children_with_node_0();
+        self.announcer_div.remove();
         self.best_move = None;
+        self.buttons.clear();
+        self.groups.clear();
+        Ok(())
+    }
 
-        let moves = collect_moves(graph, active_state);
+    /// List the moves of a state. The elements of the previous list are reused where they fit,
+    /// as states can have dozens of moves, and many of them are possible from the next state too.
+    pub fn set_data(&mut self, graph: &Graph, active_state: BoardId) {
+        self.best_move = None;
+        let mut old_buttons: HashMap<SlideMove, MoveButton> = self
+            .buttons
+            .drain(..)
+            .map(|button| (button.move_info.get().slide_move, button))
+            .collect();
+        let mut old_groups = std::mem::take(&mut self.groups);
+        if !self.auto_solve_enabled {
+            self.auto_solve_visited.clear();
+        }
+        self.auto_solve_visited.insert(active_state);
+
+        let mut moves = collect_moves(graph, active_state).unwrap_or_else(|error| {
+            log::error!("Failed to collect moves: {error}");
+            Vec::new()
+        });
+        if self.collapse_chains {
+            moves = collapse_move_chains(moves);
+        }
+        self.temperature_gauge
+            .set_temperature(get_temperature(&moves))
+            .expect("Failed to update temperature gauge");
+        let next_move = graph
+            .get(active_state)
+            .and_then(|state| state.distance_to_solution())
+            .and_then(|distance| {
+                pick_auto_solve_move(
+                    &moves,
+                    distance,
+                    self.auto_solve_through_neutral,
+                    &self.auto_solve_visited,
+                )
+            });
 
         let document = get_document().expect("Failed to get document");
-        for move_info in moves {
-            let parent_ref = self.parent_ref.upgrade().unwrap().clone();
-            let move_button = create_move_button(move_info, &document, parent_ref)
-                .expect("Failed to create move button");
-            self.moves_div
-                .append_child(&move_button)
-                .expect("Failed to append move button to list");
-
-            // Store the first move, we consider this to be the best move
-            if self.best_move.is_none() {
-                self.best_move = Some(MoveButton {
-                    move_info,
-                    div: move_button,
-                });
+        for group in group_moves(&moves, self.group_by_piece, self.expanded_groups.get()) {
+            let elements = match old_groups.iter().position(|old| old.kind == group.kind) {
+                Some(index) => old_groups.swap_remove(index),
+                None => self
+                    .create_group_elements(&group, &document)
+                    .expect("Failed to create move group"),
+            };
+            elements.summary.set_text_content(Some(&format!(
+                "{} ({})",
+                group.title,
+                group.moves.len()
+            )));
+
+            let mut group_buttons = Vec::with_capacity(group.moves.len());
+            for move_info in group.moves {
+                let button = old_buttons
+                    .remove(&move_info.slide_move)
+                    .unwrap_or_else(|| {
+                        let move_info = Rc::new(Cell::new(move_info));
+                        let div = create_move_button(move_info.clone(), &document, &self.commands)
+                            .expect("Failed to create move button");
+                        MoveButton { move_info, div }
+                    });
+                button.move_info.set(move_info);
+                update_move_button(&button.div, move_info, &self.theme, self.shape_coded)
+                    .expect("Failed to update move button");
+                group_buttons.push(button.div.clone().into());
+
+                if next_move.map(|next_move| next_move.slide_move) == Some(move_info.slide_move) {
+                    self.best_move = Some(button.clone());
+                }
+                self.buttons.push(button);
             }
+            place_children(&elements.list_div, &group_buttons)
+                .expect("Failed to place move buttons");
+            self.groups.push(elements);
         }
+        let group_elements: Vec<Element> = self
+            .groups
+            .iter()
+            .map(|group| group.group.clone())
+            .collect();
+        place_children(&self.moves_div, &group_elements).expect("Failed to place move groups");
 
         // If we are in auto-solve mode, we soon start the next move.
         if self.auto_solve_enabled {
-            if self.best_move.is_some()
-                && self.best_move.as_ref().unwrap().move_info.effectiveness
-                    == MoveEffectiveness::Positive
-            {
-                // Keep going until we run out of good moves to make
+            if self.best_move.is_some() {
+                // Keep going until the puzzle is solved
                 self.queue_best_move().expect("Failed to queue best move");
             } else {
                 self.set_auto_solve(false)
@@ -262,8 +781,137 @@ impl MovesView {
         }
     }
 
+    /// Change the time between the moves of auto-solve
+    pub fn set_auto_solve_interval(&mut self, interval: Duration) {
+        self.auto_solve_interval = interval.as_millis().try_into().unwrap_or(i32::MAX);
+    }
+
+    /// Let auto-solve make moves that keep the distance, when no move gets closer.
+    /// This applies when the list is rebuilt.
+    pub fn set_auto_solve_through_neutral(&mut self, enabled: bool) {
+        self.auto_solve_through_neutral = enabled;
+    }
+
+    /// Group the moves by the piece that they move, instead of by how they change the distance.
+    /// This applies when the list is rebuilt.
+    pub fn set_group_by_piece(&mut self, enabled: bool) {
+        self.group_by_piece = enabled;
+    }
+
+    /// List each position that the moves end in once, see collapse_move_chains.
+    /// This applies when the list is rebuilt.
+    pub fn set_collapse_chains(&mut self, enabled: bool) {
+        self.collapse_chains = enabled;
+    }
+
+    fn create_group_elements(
+        &self,
+        group: &MoveGroup,
+        document: &Document,
+    ) -> Result<MoveGroupElements, JsValue> {
+        let (group_element, summary, list_div) =
+            create_move_group(&group.title, group.moves.len(), group.expanded, document)?;
+        match group.kind {
+            MoveGroupKind::Effectiveness(index) => self.track_expansion(&group_element, index),
+            MoveGroupKind::Piece(piece) => link_piece(&summary, piece, &self.commands),
+        }
+        Ok(MoveGroupElements {
+            kind: group.kind,
+            group: group_element,
+            summary,
+            list_div,
+        })
+    }
+
+    /// Expand the group of the moves of a piece that is hovered on the board, and scroll to it
+    pub fn reveal_piece(&self, piece: Option<board::Coordinates>) {
+        for elements in &self.groups {
+            let MoveGroupKind::Piece(group_piece) = elements.kind else {
+                continue;
+            };
+            let group = &elements.group;
+            let result = if Some(group_piece) == piece {
+                let options = ScrollIntoViewOptions::new();
+                options.set_block(ScrollLogicalPosition::Nearest);
+                group.scroll_into_view_with_scroll_into_view_options(&options);
+                group
+                    .set_attribute("open", "")
+                    .and_then(|_| group.class_list().add_1("linked"))
+            } else {
+                group.class_list().remove_1("linked")
+            };
+            result.expect("Failed to update linked class of move group");
+        }
+    }
+
+    /// Remember when the player expands or collapses a group, for the next time the list is rebuilt
+    fn track_expansion(&self, group: &Element, group_index: usize) {
+        let expanded_groups = self.expanded_groups.clone();
+        let group_clone = group.clone();
+        group
+            .add_event_listener_with_callback(
+                "toggle",
+                Closure::<dyn FnMut()>::new(move || {
+                    let mut expanded = expanded_groups.get();
+                    expanded[group_index] = group_clone.has_attribute("open");
+                    expanded_groups.set(expanded);
+                })
+                .into_js_value()
+                .unchecked_ref(),
+            )
+            .expect("Failed to track expansion of move group");
+    }
+
+    /// Recolor the move indicators
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.restyle_indicators();
+    }
+
+    /// Mark the move indicators with symbols, in addition to their colors
+    pub fn set_shape_coded(&mut self, enabled: bool) {
+        self.shape_coded = enabled;
+        self.restyle_indicators();
+    }
+
+    fn restyle_indicators(&self) {
+        for button in &self.buttons {
+            let indicator: HtmlElement = button
+                .div
+                .first_element_child()
+                .and_then(|child| child.dyn_into().ok())
+                .expect("Move button has no indicator");
+            style_indicator(
+                &indicator,
+                button.move_info.get().effectiveness,
+                &self.theme,
+                self.shape_coded,
+            )
+            .expect("Failed to restyle move indicator");
+        }
+    }
+
+    /// Let screen readers announce a move that was made
+    pub fn announce_move(&self, slide_move: &SlideMove, resulting_distance: Option<u32>) {
+        let announcement = describe_move("Moved", slide_move, resulting_distance);
+        self.announcer_div.set_text_content(Some(&announcement));
+    }
+
+    /// Mark the move button that leads to the given state, if there is one
+    pub fn highlight_move(&self, state: Option<BoardId>) {
+        for button in &self.buttons {
+            let class_list = button.div.class_list();
+            let result = if Some(button.move_info.get().resulting_id) == state {
+                class_list.add_1("linked")
+            } else {
+                class_list.remove_1("linked")
+            };
+            result.expect("Failed to update linked class of move button");
+        }
+    }
+
     fn restart(self_ref: &Rc<RefCell<Self>>) {
-        let parent_ref = {
+        {
             let mut self_deref = self_ref.borrow_mut();
             self_deref
                 .set_auto_solve(false)
@@ -292,10 +940,8 @@ impl MovesView {
                     200,
                 )
                 .expect_throw("Failed to remove clicked class from restart button");
-
-            self_deref.parent_ref.upgrade().unwrap()
-        };
-        StatefulViews::restart(&parent_ref);
+        }
+        self_ref.borrow().commands.publish(Command::Restart);
     }
 
     fn toggle_auto_solve(&mut self) {
@@ -308,10 +954,19 @@ impl MovesView {
             // do nothing
             return Ok(());
         }
+        if enable && self.best_move.is_none() {
+            // The puzzle is solved already, or there is no way forward
+            return Ok(());
+        }
 
         self.auto_solve_enabled = enable;
+        self.events.emit(match enable {
+            true => GameEvent::AutoSolveStarted,
+            false => GameEvent::AutoSolveStopped,
+        });
 
         if self.auto_solve_enabled {
+            self.commands.publish(Command::AutoSolveStarted);
             self.auto_solve_toggle_div.class_list().add_1("clicked")?;
             // Start chain of moves
             self.queue_best_move()?;
@@ -330,12 +985,11 @@ impl MovesView {
             let self_ref_clone = self.self_ref.upgrade().unwrap().clone();
             let id = get_window()?.set_timeout_with_callback_and_timeout_and_arguments_0(
                 Closure::<dyn FnMut()>::new(move || {
-                    let self_ref_clone = self_ref_clone.clone();
-                    spawn_local(async move { MovesView::do_best_move(&self_ref_clone).await });
+                    self_ref_clone.borrow().do_best_move();
                 })
                 .into_js_value()
                 .unchecked_ref(),
-                200,
+                self.auto_solve_interval,
             )?;
             self.auto_solve_timeout_id = Some(id);
         }
@@ -352,21 +1006,17 @@ impl MovesView {
         Ok(())
     }
 
-    async fn do_best_move(self_ref: &Rc<RefCell<Self>>) {
-        let (parent_ref, move_info) = {
-            let self_ref = self_ref.borrow();
-            let Some(best_move) = &self_ref.best_move else {
-                // Nothing to do
-                return;
-            };
-            best_move
-                .div
-                .class_list()
-                .add_1("clicked")
-                .expect("Couldn't add clicked class to move");
-            (self_ref.parent_ref.upgrade().unwrap(), best_move.move_info)
+    fn do_best_move(&self) {
+        let Some(best_move) = &self.best_move else {
+            // Nothing to do
+            return;
         };
-
-        StatefulViews::do_move(&parent_ref, &move_info).await;
+        best_move
+            .div
+            .class_list()
+            .add_1("clicked")
+            .expect("Couldn't add clicked class to move");
+        self.commands
+            .publish(Command::DoMove(best_move.move_info.get()));
     }
 }
@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::moves_view::{MoveEffectiveness, MoveInfo};
+use crate::views::utils::get_document;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::HtmlDivElement;
+
+/// The fraction of the available moves that bring the player closer to the solution.
+/// A hot position is forgiving, most moves are good, while in a cold position most moves are bad.
+pub fn get_temperature(moves: &[MoveInfo]) -> Option<f32> {
+    if moves.is_empty() {
+        return None;
+    }
+    let positive = moves
+        .iter()
+        .filter(|move_info| move_info.effectiveness == MoveEffectiveness::Positive)
+        .count();
+    Some(positive as f32 / moves.len() as f32)
+}
+
+fn get_description(temperature: f32) -> &'static str {
+    match temperature {
+        t if t >= 0.75 => "Hot",
+        t if t >= 0.5 => "Warm",
+        t if t >= 0.25 => "Cool",
+        _ => "Cold",
+    }
+}
+
+/// A hot/cold gauge that shows the temperature of the current position
+pub struct TemperatureGauge {
+    gauge_div: HtmlDivElement,
+    fill_div: HtmlDivElement,
+}
+
+impl TemperatureGauge {
+    pub fn new(gauge_div: HtmlDivElement) -> Result<Self, JsValue> {
+        let document = get_document()?;
+        let track_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
+        let fill_div: HtmlDivElement = document.create_element("div")?.dyn_into()?;
+
+        track_div.class_list().add_1("game-temperature-track")?;
+        fill_div.class_list().add_1("game-temperature-fill")?;
+        track_div.append_child(&fill_div)?;
+        gauge_div.append_child(&track_div)?;
+
+        Ok(Self {
+            gauge_div,
+            fill_div,
+        })
+    }
+
+    pub fn set_temperature(&self, temperature: Option<f32>) -> Result<(), JsValue> {
+        let temperature = temperature.unwrap_or(0.0);
+        self.fill_div
+            .style()
+            .set_property("width", &format!("{:.0}%", temperature * 100.0))?;
+        self.gauge_div.set_title(get_description(temperature));
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{Coordinates, SlideDirection, SlideMove};
+use crate::views::moves_view::{
+    collapse_move_chains, describe_move, group_moves, pick_auto_solve_move, MoveEffectiveness,
+    MoveGroupKind, MoveInfo,
+};
+use std::collections::HashSet;
+
+#[test]
+fn test_describe_move() {
+    let slide_move = SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    assert_eq!(
+        describe_move("Move", &slide_move, Some(97)),
+        "Move piece at B2 down one step, 97 steps remaining"
+    );
+
+    let slide_move = SlideMove {
+        start: Coordinates { x: 0, y: 0 },
+        direction: SlideDirection::Right,
+        distance: 2,
+    };
+    assert_eq!(
+        describe_move("Moved", &slide_move, Some(1)),
+        "Moved piece at A1 right 2 steps, one step remaining"
+    );
+    assert_eq!(
+        describe_move("Moved", &slide_move, Some(0)),
+        "Moved piece at A1 right 2 steps, puzzle solved"
+    );
+    assert_eq!(
+        describe_move("Moved", &slide_move, None),
+        "Moved piece at A1 right 2 steps, the solution is out of reach"
+    );
+}
+
+#[test]
+fn test_group_moves() {
+    let move_info = |x, direction, resulting_distance, effectiveness| MoveInfo {
+        slide_move: SlideMove {
+            start: Coordinates { x, y: 0 },
+            direction,
+            distance: 1,
+        },
+        resulting_id: resulting_distance as u64,
+        resulting_distance: Some(resulting_distance),
+        effectiveness,
+    };
+    let moves = [
+        move_info(0, SlideDirection::Up, 9, MoveEffectiveness::Positive),
+        move_info(1, SlideDirection::Up, 10, MoveEffectiveness::Neutral),
+        move_info(0, SlideDirection::Right, 11, MoveEffectiveness::Negative),
+        move_info(1, SlideDirection::Left, 11, MoveEffectiveness::Negative),
+    ];
+
+    let groups = group_moves(&moves, false, [true, false, false]);
+    let summary: Vec<(MoveGroupKind, usize, bool)> = groups
+        .iter()
+        .map(|group| (group.kind, group.moves.len(), group.expanded))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (MoveGroupKind::Effectiveness(0), 1, true),
+            (MoveGroupKind::Effectiveness(1), 1, false),
+            (MoveGroupKind::Effectiveness(2), 2, false),
+        ]
+    );
+
+    // The pieces are in the order of their best moves, and empty groups are left out
+    let groups = group_moves(&moves, true, [true, false, false]);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].title, "Piece at A1");
+    assert_eq!(groups[0].moves, [moves[0], moves[2]]);
+    assert_eq!(
+        groups[1].kind,
+        MoveGroupKind::Piece(Coordinates { x: 1, y: 0 })
+    );
+    assert_eq!(groups[1].moves, [moves[1], moves[3]]);
+    assert!(group_moves(&moves[1..2], false, [true, false, false])
+        .iter()
+        .all(|group| group.kind == MoveGroupKind::Effectiveness(1)));
+}
+
+#[test]
+fn test_collapse_move_chains() {
+    let move_info = |y, distance, resulting_distance| MoveInfo {
+        slide_move: SlideMove {
+            start: Coordinates { x: 0, y },
+            direction: SlideDirection::Right,
+            distance,
+        },
+        resulting_id: (y as u64) << 8 | distance as u64,
+        resulting_distance: Some(resulting_distance),
+        effectiveness: MoveEffectiveness::Neutral,
+    };
+    let moves = vec![
+        move_info(0, 2, 9),
+        move_info(1, 2, 10),
+        move_info(1, 1, 10),
+        move_info(0, 1, 11),
+        move_info(2, 1, 12),
+    ];
+    // The moves of a chain end in different positions, they are all kept
+    assert_eq!(collapse_move_chains(moves.clone()), moves);
+
+    // Of the moves that end in the same position, the shortest slide is kept in place
+    let mut same_end = moves.clone();
+    same_end[2].resulting_id = same_end[1].resulting_id;
+    assert_eq!(
+        collapse_move_chains(same_end.clone()),
+        [same_end[0], same_end[2], same_end[3], same_end[4]]
+    );
+}
+
+#[test]
+fn test_pick_auto_solve_move() {
+    let move_info = |x, resulting_distance, effectiveness| MoveInfo {
+        slide_move: SlideMove {
+            start: Coordinates { x, y: 0 },
+            direction: SlideDirection::Up,
+            distance: 1,
+        },
+        resulting_id: x as u64,
+        resulting_distance,
+        effectiveness,
+    };
+    let closer = move_info(0, Some(1), MoveEffectiveness::Positive);
+    let neutral = move_info(1, Some(2), MoveEffectiveness::Neutral);
+    let further = move_info(2, Some(3), MoveEffectiveness::Negative);
+    let trapped = move_info(3, None, MoveEffectiveness::Negative);
+    let mut visited = HashSet::new();
+
+    // The final move into the solved state is made, then auto-solve stops
+    assert_eq!(
+        pick_auto_solve_move(&[further, neutral, closer], 2, false, &visited),
+        Some(closer)
+    );
+    // A move past a one-way cell never gets closer to the solution
+    assert_eq!(
+        pick_auto_solve_move(&[trapped, further], 2, false, &visited),
+        None
+    );
+    assert_eq!(
+        pick_auto_solve_move(&[neutral, further], 1, true, &visited),
+        None
+    );
+
+    // Neutral moves are only made when enabled, and not back to a visited state
+    assert_eq!(
+        pick_auto_solve_move(&[neutral, further], 2, false, &visited),
+        None
+    );
+    assert_eq!(
+        pick_auto_solve_move(&[neutral, further], 2, true, &visited),
+        Some(neutral)
+    );
+    visited.insert(neutral.resulting_id);
+    assert_eq!(
+        pick_auto_solve_move(&[neutral, further], 2, true, &visited),
+        None
+    );
+}
@@ -0,0 +1,69 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board;
+use crate::builder::WiggersGraafBuilder;
+use wasm_bindgen::JsValue;
+
+/// Read how the hosting page customizes a game, given as an object like
+/// `{puzzle, start_position, theme, auto_solve_speed, lazy_depth}`. Every option may be left out, a string
+/// instead of an object is taken as the puzzle for compatibility with the former puzzle parameter.
+pub fn builder_from_js(options: &JsValue) -> Result<WiggersGraafBuilder, JsValue> {
+    let builder = WiggersGraafBuilder::new();
+    if options.is_undefined() || options.is_null() {
+        return Ok(builder);
+    }
+    let parse_puzzle = |puzzle: &str| {
+        board::parse_position(puzzle)
+            .map_err(|error| JsValue::from_str(&format!("This puzzle is not valid: {error}")))
+    };
+    if let Some(puzzle) = options.as_string() {
+        return Ok(builder.puzzle(parse_puzzle(&puzzle)?));
+    }
+
+    let get = |key: &str| -> Result<Option<JsValue>, JsValue> {
+        let value = js_sys::Reflect::get(options, &key.into())?;
+        Ok((!value.is_undefined() && !value.is_null()).then_some(value))
+    };
+    let get_string = |key: &str| -> Result<Option<String>, JsValue> {
+        get(key)?
+            .map(|value| {
+                value
+                    .as_string()
+                    .ok_or(JsValue::from_str(&format!("Option {key} is not a string")))
+            })
+            .transpose()
+    };
+
+    let mut builder = builder;
+    if let Some(puzzle) = get_string("puzzle")? {
+        builder = builder.puzzle(parse_puzzle(&puzzle)?);
+    }
+    if let Some(position) = get_string("start_position")? {
+        builder = builder.start_position(board::parse_position(&position).map_err(|error| {
+            JsValue::from_str(&format!("This start position is not valid: {error}"))
+        })?);
+    }
+    if let Some(theme) = get_string("theme")? {
+        builder = builder.theme(
+            theme
+                .parse()
+                .map_err(|error: anyhow::Error| JsValue::from_str(&error.to_string()))?,
+        );
+    }
+    if let Some(speed) = get("auto_solve_speed")? {
+        builder = builder.auto_solve_speed(
+            speed
+                .as_f64()
+                .ok_or(JsValue::from_str("Option auto_solve_speed is not a number"))?,
+        );
+    }
+    if let Some(depth) = get("lazy_depth")? {
+        let depth = depth
+            .as_f64()
+            .filter(|depth| depth.fract() == 0.0 && *depth >= 0.0)
+            .ok_or(JsValue::from_str("Option lazy_depth is not a whole number"))?;
+        builder = builder.lazy(depth as u32);
+    }
+    Ok(builder)
+}
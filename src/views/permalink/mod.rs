@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Permalinks store the current state in the URL fragment, e.g. #position=1a2b3c4d5e6f7a8b
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::BoardId;
+use crate::views::utils::get_window;
+use std::cell::Cell;
+use wasm_bindgen::JsValue;
+
+const POSITION_PREFIX: &str = "#position=";
+
+thread_local! {
+    /// Whether a game on the page has claimed the URL fragment
+    static CLAIMED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The right to use the page's URL for permalinks. A page can hold several games, only the first
+/// of them gets permalinks, so that they don't overwrite each other's. The claim is released when
+/// dropped.
+pub struct Permalink {
+    _private: (),
+}
+
+impl Permalink {
+    /// Claim the URL fragment, if no other game holds it
+    pub fn claim() -> Option<Self> {
+        (!CLAIMED.replace(true)).then_some(Self { _private: () })
+    }
+
+    /// Read the state from the page's URL, if it contains one
+    pub fn read(&self) -> Option<BoardId> {
+        let hash = get_window().ok()?.location().hash().ok()?;
+        BoardId::from_str_radix(hash.strip_prefix(POSITION_PREFIX)?, 16).ok()
+    }
+
+    /// Store the state in the page's URL, without adding a browser history entry
+    pub fn write(&self, state: BoardId) -> Result<(), JsValue> {
+        get_window()?.history()?.replace_state_with_url(
+            &JsValue::NULL,
+            "",
+            Some(&format!("{POSITION_PREFIX}{state:016x}")),
+        )
+    }
+}
+
+impl Drop for Permalink {
+    fn drop(&mut self) {
+        CLAIMED.set(false);
+    }
+}
@@ -0,0 +1,15 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::permalink::Permalink;
+
+#[test]
+fn test_only_one_game_claims_the_url() {
+    let first = Permalink::claim();
+    assert!(first.is_some());
+    assert!(Permalink::claim().is_none());
+
+    // The next game gets the URL once the first one is gone
+    drop(first);
+    assert!(Permalink::claim().is_some());
+}
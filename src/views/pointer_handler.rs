@@ -1,34 +1,46 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
+use crate::views::event_listener::EventListener;
 use crate::views::utils;
-use crate::views::utils::Coordinates;
+use crate::views::utils::{Coordinates, Delta};
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::time::Duration;
-use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{AddEventListenerOptions, Element, HtmlElement};
 
 /// The callback type for the handler to call on a pointer event
 pub type OnMouseEventCb = dyn FnMut(PointerEvent) -> bool;
 
+/// The height of a line of text, to convert line based wheel deltas into pixels
+const WHEEL_LINE_HEIGHT: f64 = 16.0;
+
 #[derive(Debug)]
-pub struct MouseWheel {}
+pub struct MouseWheel {
+    pub coordinates: Coordinates,
+    /// The scrolled distance in canvas pixels
+    pub delta: Delta,
+    /// Whether the wheel should zoom instead of scroll, browsers also report trackpad pinches as ctrl+wheel
+    pub zoom: bool,
+}
 
 #[derive(Debug)]
 pub enum PointerEvent {
     Down((i32, Duration, Coordinates)),
     Up((i32, Duration, Coordinates)),
     Move((i32, Duration, Coordinates)),
+    Leave(i32),
     TouchMove(),
     Wheel(MouseWheel),
+    /// The browser's context menu was requested, by a right click or a long press
+    ContextMenu(Coordinates),
 }
 
 pub struct MouseHandler {
     target: Element,
-    device_pixel_ratio: f64,
     on_event_cb: Box<OnMouseEventCb>,
+    listeners: Vec<EventListener>,
 }
 
 impl MouseHandler {
@@ -38,74 +50,90 @@ impl MouseHandler {
     ) -> Result<Rc<RefCell<MouseHandler>>, JsValue> {
         let self_ref = Rc::new(RefCell::new(Self {
             target: target.clone().into(),
-            device_pixel_ratio: web_sys::window().unwrap().device_pixel_ratio(),
             on_event_cb,
+            listeners: Vec::new(),
         }));
 
-        Self::add_listener(target, "wheel", self_ref.clone(), Self::handle_wheel);
-        Self::add_listener(
-            target,
-            "pointerdown",
-            self_ref.clone(),
-            Self::handle_pointerdown,
-        );
-        Self::add_listener(
-            target,
-            "pointerup",
-            self_ref.clone(),
-            Self::handle_pointerup,
-        );
-        Self::add_listener(
-            target,
-            "pointercancel",
-            self_ref.clone(),
-            Self::handle_pointerup,
-        );
-        Self::add_listener(
-            target,
-            "pointermove",
-            self_ref.clone(),
-            Self::handle_pointermove,
-        );
-        Self::add_listener(
-            target,
-            "touchmove",
-            self_ref.clone(),
-            Self::handle_touchmove,
-        );
+        // The listeners refer to the handler weakly, as the handler owns them
+        let weak_ref = Rc::downgrade(&self_ref);
+        let listeners = vec![
+            Self::add_listener(target, "wheel", weak_ref.clone(), Self::handle_wheel)?,
+            Self::add_listener(
+                target,
+                "pointerdown",
+                weak_ref.clone(),
+                Self::handle_pointerdown,
+            )?,
+            Self::add_listener(
+                target,
+                "pointerup",
+                weak_ref.clone(),
+                Self::handle_pointerup,
+            )?,
+            Self::add_listener(
+                target,
+                "pointercancel",
+                weak_ref.clone(),
+                Self::handle_pointerup,
+            )?,
+            Self::add_listener(
+                target,
+                "pointermove",
+                weak_ref.clone(),
+                Self::handle_pointermove,
+            )?,
+            Self::add_listener(
+                target,
+                "pointerleave",
+                weak_ref.clone(),
+                Self::handle_pointerleave,
+            )?,
+            Self::add_listener(
+                target,
+                "touchmove",
+                weak_ref.clone(),
+                Self::handle_touchmove,
+            )?,
+            Self::add_listener(target, "contextmenu", weak_ref, Self::handle_contextmenu)?,
+        ];
+        self_ref.borrow_mut().listeners = listeners;
 
         Ok(self_ref)
     }
 
-    fn add_listener<
-        TEvent: AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi + 'static,
-    >(
+    /// Stop listening to the target's events
+    pub fn detach(&mut self) {
+        self.listeners.clear();
+    }
+
+    fn add_listener<TEvent: AsRef<web_sys::Event> + JsCast + 'static>(
         target: &web_sys::EventTarget,
-        event_name: &str,
-        self_ref: Rc<RefCell<Self>>,
+        event_name: &'static str,
+        self_ref: Weak<RefCell<Self>>,
         event_mapping_fn: fn(&TEvent, &Element, f64) -> PointerEvent,
-    ) {
+    ) -> Result<EventListener, JsValue> {
         let options = AddEventListenerOptions::new();
         options.set_passive(false);
-        target
-            .add_event_listener_with_callback_and_add_event_listener_options(
-                event_name,
-                Closure::<dyn FnMut(TEvent)>::new(Box::new(move |browser_event: TEvent| {
-                    let mut self_mut = self_ref.borrow_mut();
-                    let device_pixel_ratio = self_mut.device_pixel_ratio;
-                    let event =
-                        event_mapping_fn(&browser_event, &self_mut.target, device_pixel_ratio);
-                    if (self_mut.on_event_cb)(event) {
-                        let browser_event = browser_event.as_ref();
-                        browser_event.prevent_default();
-                        browser_event.stop_propagation();
-                    }
-                }))
-                .into_js_value()
-                .unchecked_ref(),
-                &options,
-            )
-            .expect("Couldn't register event listener");
+        EventListener::with_options(
+            target,
+            event_name,
+            &options,
+            move |browser_event: TEvent| {
+                let Some(self_ref) = self_ref.upgrade() else {
+                    return;
+                };
+                let mut self_mut = self_ref.borrow_mut();
+                // The ratio changes when the window moves to a monitor with another density
+                let device_pixel_ratio =
+                    web_sys::window().map_or(1.0, |window| window.device_pixel_ratio());
+                let event = event_mapping_fn(&browser_event, &self_mut.target, device_pixel_ratio);
+                if (self_mut.on_event_cb)(event) {
+                    let browser_event: &web_sys::Event = browser_event.as_ref();
+                    browser_event.prevent_default();
+                    browser_event.stop_propagation();
+                }
+            },
+        )
     }
 
     pub fn handle_pointerdown(
@@ -147,6 +175,14 @@ impl MouseHandler {
         ))
     }
 
+    pub fn handle_pointerleave(
+        event: &web_sys::PointerEvent,
+        _target: &Element,
+        _device_pixel_ratio: f64,
+    ) -> PointerEvent {
+        PointerEvent::Leave(event.pointer_id())
+    }
+
     pub fn handle_touchmove(
         _event: &web_sys::TouchEvent,
         _target: &Element,
@@ -155,12 +191,32 @@ impl MouseHandler {
         PointerEvent::TouchMove()
     }
 
-    pub fn handle_wheel(
-        _event: &web_sys::MouseScrollEvent,
+    pub fn handle_contextmenu(
+        event: &web_sys::MouseEvent,
         _target: &Element,
-        _device_pixel_ratio: f64,
+        device_pixel_ratio: f64,
+    ) -> PointerEvent {
+        PointerEvent::ContextMenu(
+            Coordinates::new(event.offset_x() as f64, event.offset_y() as f64) * device_pixel_ratio,
+        )
+    }
+
+    pub fn handle_wheel(
+        event: &web_sys::WheelEvent,
+        target: &Element,
+        device_pixel_ratio: f64,
     ) -> PointerEvent {
-        // TODO(Menno 02.08.2025) Implement scroll handling
-        PointerEvent::Wheel(MouseWheel {})
+        // Normalize the delta into CSS pixels, depending on the unit the browser reports it in
+        let unit = match event.delta_mode() {
+            web_sys::WheelEvent::DOM_DELTA_LINE => WHEEL_LINE_HEIGHT,
+            web_sys::WheelEvent::DOM_DELTA_PAGE => target.client_height() as f64,
+            _ => 1.0,
+        };
+        PointerEvent::Wheel(MouseWheel {
+            coordinates: Coordinates::new(event.offset_x() as f64, event.offset_y() as f64)
+                * device_pixel_ratio,
+            delta: Delta::new(event.delta_x(), event.delta_y()) * unit * device_pixel_ratio,
+            zoom: event.ctrl_key(),
+        })
     }
 }
@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::BoardId;
+use crate::views::events::GameEvent;
+
+/// A recorded game that is being played back
+pub struct Replay {
+    /// The start state followed by the state after each recorded step
+    states: Vec<BoardId>,
+    position: usize,
+    /// This plays the optimal path from the game's state, its moves are part of the game,
+    /// and it closes once it reaches the solution
+    pub solution: bool,
+    pub playing: bool,
+    /// The pending timeout of the next step, while playing
+    pub timeout_id: Option<i32>,
+}
+
+impl Replay {
+    pub fn new(states: Vec<BoardId>) -> Self {
+        Self {
+            states,
+            position: 0,
+            solution: false,
+            playing: false,
+            timeout_id: None,
+        }
+    }
+
+    /// Play the states of an optimal path to the solution, starting with the active state
+    pub fn solution(states: Vec<BoardId>) -> Self {
+        Self {
+            solution: true,
+            ..Self::new(states)
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Move to another position in the recording
+    /// @returns the state at that position, or None if it is out of range or already shown
+    pub fn seek(&mut self, position: usize) -> Option<BoardId> {
+        if position == self.position {
+            return None;
+        }
+        let state = *self.states.get(position)?;
+        self.position = position;
+        Some(state)
+    }
+
+    /// The states, and the index of the state that has been played back to
+    pub fn path(&self) -> (Vec<BoardId>, usize) {
+        (self.states.clone(), self.position)
+    }
+
+    pub fn progress(&self) -> GameEvent {
+        GameEvent::ReplayProgress {
+            position: self.position,
+            length: self.states.len(),
+            playing: self.playing,
+        }
+    }
+}
@@ -1,9 +1,14 @@
 // SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
 // SPDX-License-Identifier: MIT
 
+use crate::views::utils::get_window;
+use std::rc::{Rc, Weak};
 use wasm_bindgen::closure::Closure;
-use wasm_bindgen::JsCast;
-use web_sys::{Element, ResizeObserverEntry, ResizeObserverSize};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    AddEventListenerOptions, Element, ResizeObserverBoxOptions, ResizeObserverEntry,
+    ResizeObserverOptions, ResizeObserverSize,
+};
 
 /// Get the content's (width, height) in device pixels
 fn get_size(entry: ResizeObserverEntry) -> (f64, f64) {
@@ -29,30 +34,97 @@ fn get_size(entry: ResizeObserverEntry) -> (f64, f64) {
     )
 }
 
+/// Whether the browser reports sizes in device pixels, it then also reports changes of the device
+/// pixel ratio by itself
+fn supports_device_pixel_size() -> bool {
+    let Ok(entry) = js_sys::Reflect::get(&js_sys::global(), &"ResizeObserverEntry".into()) else {
+        return false;
+    };
+    js_sys::Reflect::get(&entry, &"prototype".into())
+        .and_then(|prototype| js_sys::Reflect::has(&prototype, &"devicePixelContentBoxSize".into()))
+        .unwrap_or(false)
+}
+
+/// Call back once whenever the device pixel ratio changes, e.g. when the window moves to a monitor
+/// with another density, or when the page is zoomed. A media query only matches the current ratio,
+/// so each change sets up a new query for the next. Watching stops once the callback is dropped.
+pub fn watch_pixel_ratio(on_change_cb: Weak<dyn Fn(f64)>) -> Result<(), JsValue> {
+    let window = get_window()?;
+    let query = format!("(resolution: {}dppx)", window.device_pixel_ratio());
+    let Some(media_query_list) = window.match_media(&query)? else {
+        return Ok(());
+    };
+    let options = AddEventListenerOptions::new();
+    options.set_once(true);
+    media_query_list.add_event_listener_with_callback_and_add_event_listener_options(
+        "change",
+        Closure::once_into_js(move || {
+            let Some(callback) = on_change_cb.upgrade() else {
+                return;
+            };
+            if let Ok(window) = get_window() {
+                callback(window.device_pixel_ratio());
+            }
+            if let Err(error) = watch_pixel_ratio(on_change_cb) {
+                log::warn!("Stopped watching the device pixel ratio: {error:?}");
+            }
+        })
+        .unchecked_ref(),
+        &options,
+    )
+}
+
 /// The callback type for the observer to call on a resize event
 pub type OnResizeCb = dyn FnMut(f64, f64);
 
-/// An observer that tracks the content size of the target in device pixels
+/// An observer that tracks the content size of the target in device pixels, including changes of
+/// the device pixel ratio
 pub struct ResizeObserver {
     observer: web_sys::ResizeObserver,
+    _on_resize_closure: Closure<dyn FnMut(js_sys::Array)>,
+    /// Observes the target anew when the device pixel ratio changes, if the browser doesn't
+    reobserve: Option<Rc<dyn Fn(f64)>>,
 }
 
 impl ResizeObserver {
     /// Register the on_resize_cb for resize events on target
     pub fn new(target: &Element, mut on_resize_cb: Box<OnResizeCb>) -> Self {
-        let instance = Self {
-            observer: web_sys::ResizeObserver::new(
-                Closure::new(move |entries: js_sys::Array| {
-                    let entry: ResizeObserverEntry = entries.at(0).dyn_into().unwrap();
-                    let (width, height) = get_size(entry);
-                    on_resize_cb(width, height);
-                })
-                .into_js_value()
-                .unchecked_ref(),
-            )
-            .unwrap(),
+        let on_resize_closure = Closure::new(move |entries: js_sys::Array| {
+            let entry: ResizeObserverEntry = entries.at(0).dyn_into().unwrap();
+            let (width, height) = get_size(entry);
+            on_resize_cb(width, height);
+        });
+        let mut instance = Self {
+            observer: web_sys::ResizeObserver::new(on_resize_closure.as_ref().unchecked_ref())
+                .unwrap(),
+            _on_resize_closure: on_resize_closure,
+            reobserve: None,
         };
-        instance.observer.observe(target);
+        if supports_device_pixel_size() {
+            let options = ResizeObserverOptions::new();
+            options.set_box(ResizeObserverBoxOptions::DevicePixelContentBox);
+            instance.observer.observe_with_options(target, &options);
+        } else {
+            // The content box keeps its size when the pixel ratio changes, observing the target anew
+            // reports its size in device pixels again
+            instance.observer.observe(target);
+            let observer = instance.observer.clone();
+            let target = target.clone();
+            let reobserve: Rc<dyn Fn(f64)> = Rc::new(move |_device_pixel_ratio: f64| {
+                observer.unobserve(&target);
+                observer.observe(&target);
+            });
+            if let Err(error) = watch_pixel_ratio(Rc::downgrade(&reobserve)) {
+                log::warn!("Unable to watch the device pixel ratio: {error:?}");
+            }
+            instance.reobserve = Some(reobserve);
+        }
         instance
     }
+
+    /// Stop observing, the callback isn't called anymore
+    pub fn disconnect(&mut self) {
+        self.observer.disconnect();
+        self.reobserve = None;
+    }
 }
@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::views::utils::get_window;
+use web_sys::Storage;
+
+/// The local storage key of the settings
+const STORAGE_KEY: &str = "wiggers-graaf-settings";
+
+fn get_storage() -> Option<Storage> {
+    get_window().ok()?.local_storage().ok()?
+}
+
+/// The user's preferences, these are persisted across sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings {
+    /// Mark how effective moves are with shapes, in addition to colors
+    pub shape_coded_moves: bool,
+    /// Silence the sound effects
+    pub muted: bool,
+    /// List the moves by the piece that they move
+    pub group_moves_by_piece: bool,
+    /// Let auto-solve make moves that keep the distance, when no move gets closer
+    pub auto_solve_through_neutral: bool,
+    /// List each position that the moves end in once, by the shortest move that leads there
+    pub collapse_move_chains: bool,
+    /// Replace movement with instant changes, follows the system's preference when unset
+    pub reduced_motion: Option<bool>,
+    /// Let released pieces spring back to their place, instead of easing back
+    pub spring_back: bool,
+}
+
+impl Settings {
+    /// Load the settings of previous sessions, unknown or corrupt entries are skipped
+    pub fn load() -> Self {
+        let stored = get_storage().and_then(|storage| storage.get_item(STORAGE_KEY).ok()?);
+        Self::deserialize(&stored.unwrap_or_default())
+    }
+
+    pub fn save(&self) {
+        let Some(storage) = get_storage() else {
+            return;
+        };
+        if let Err(error) = storage.set_item(STORAGE_KEY, &self.serialize()) {
+            log::warn!("Failed to store settings: {error:?}");
+        }
+    }
+
+    fn serialize(&self) -> String {
+        format!(
+            "shape-coded-moves={},muted={},group-moves-by-piece={},auto-solve-through-neutral={},collapse-move-chains={},reduced-motion={},spring-back={}",
+            self.shape_coded_moves,
+            self.muted,
+            self.group_moves_by_piece,
+            self.auto_solve_through_neutral,
+            self.collapse_move_chains,
+            self.reduced_motion
+                .map_or("system".to_string(), |enabled| enabled.to_string()),
+            self.spring_back
+        )
+    }
+
+    fn deserialize(stored: &str) -> Self {
+        let mut settings = Self::default();
+        for (key, value) in stored.split(',').filter_map(|entry| entry.split_once('=')) {
+            match key {
+                "shape-coded-moves" => {
+                    settings.shape_coded_moves = value.parse().unwrap_or_default()
+                }
+                "muted" => settings.muted = value.parse().unwrap_or_default(),
+                "group-moves-by-piece" => {
+                    settings.group_moves_by_piece = value.parse().unwrap_or_default()
+                }
+                "auto-solve-through-neutral" => {
+                    settings.auto_solve_through_neutral = value.parse().unwrap_or_default()
+                }
+                "collapse-move-chains" => {
+                    settings.collapse_move_chains = value.parse().unwrap_or_default()
+                }
+                "reduced-motion" => settings.reduced_motion = value.parse().ok(),
+                "spring-back" => settings.spring_back = value.parse().unwrap_or_default(),
+                _ => log::warn!("Ignoring unknown setting \"{key}\""),
+            }
+        }
+        settings
+    }
+}
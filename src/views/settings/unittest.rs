@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::settings::Settings;
+
+#[test]
+fn test_round_trip() {
+    let settings = Settings {
+        shape_coded_moves: true,
+        muted: true,
+        group_moves_by_piece: true,
+        auto_solve_through_neutral: true,
+        collapse_move_chains: true,
+        reduced_motion: Some(false),
+        spring_back: true,
+    };
+    assert_eq!(Settings::deserialize(&settings.serialize()), settings);
+
+    // Without a preference the system's is followed
+    let settings = Settings {
+        reduced_motion: None,
+        ..settings
+    };
+    assert_eq!(Settings::deserialize(&settings.serialize()), settings);
+}
+
+#[test]
+fn test_corrupt_entries_are_skipped() {
+    assert_eq!(Settings::deserialize(""), Settings::default());
+    assert_eq!(
+        Settings::deserialize("bogus,shape-coded-moves=maybe,unknown=1"),
+        Settings::default()
+    );
+}
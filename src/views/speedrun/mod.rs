@@ -0,0 +1,205 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::BoardId;
+use crate::views::utils::get_window;
+use std::collections::HashMap;
+use std::time::Duration;
+use web_sys::Storage;
+
+/// The local storage key of the best times
+const STORAGE_KEY: &str = "wiggers-graaf-best-times";
+
+/// A split is taken at every multiple of this many moves to the solution
+const MILESTONE_INTERVAL: u32 = 10;
+
+fn get_storage() -> Option<Storage> {
+    get_window().ok()?.local_storage().ok()?
+}
+
+/// The phase of a speedrun
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    /// The timer starts at the first move
+    Ready,
+    Running {
+        started: Duration,
+    },
+    Finished {
+        time: Duration,
+    },
+    /// The player got help, e.g. a hint or auto-solve, the run no longer counts
+    Invalidated,
+}
+
+impl RunState {
+    /// The name of the state, as seen by JavaScript
+    pub fn name(&self) -> &'static str {
+        match self {
+            RunState::Ready => "ready",
+            RunState::Running { .. } => "running",
+            RunState::Finished { .. } => "finished",
+            RunState::Invalidated => "invalidated",
+        }
+    }
+}
+
+/// The time at which a run first got within a number of moves of the solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Split {
+    pub moves: u32,
+    pub time: Duration,
+}
+
+/// Times a run from the first move to the solution, taking splits along the way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Speedrun {
+    state: RunState,
+    splits: Vec<Split>,
+    /// The number of moves to the solution at which the next split is taken
+    next_milestone: u32,
+}
+
+impl Default for Speedrun {
+    fn default() -> Self {
+        Self {
+            state: RunState::Ready,
+            splits: Vec::new(),
+            next_milestone: 0,
+        }
+    }
+}
+
+impl Speedrun {
+    pub fn state(&self) -> RunState {
+        self.state
+    }
+
+    pub fn splits(&self) -> &[Split] {
+        &self.splits
+    }
+
+    /// The time on the clock, it runs from the first move until the solution is reached
+    pub fn elapsed(&self, now: Duration) -> Option<Duration> {
+        match self.state {
+            RunState::Ready => Some(Duration::ZERO),
+            RunState::Running { started } => Some(now.saturating_sub(started)),
+            RunState::Finished { time } => Some(time),
+            RunState::Invalidated => None,
+        }
+    }
+
+    /// Count a move, which went from and to the given numbers of moves to the solution. These are
+    /// None for the states that can't reach the solution, they don't take splits.
+    /// @param solved whether the move reached the solution, which finishes the run
+    /// @returns the time of the run, if this move finished it
+    pub fn record_move(
+        &mut self,
+        now: Duration,
+        from: Option<u32>,
+        to: Option<u32>,
+        solved: bool,
+    ) -> Option<Duration> {
+        let started = match self.state {
+            RunState::Ready => {
+                self.state = RunState::Running { started: now };
+                // The first milestone is the multiple of the interval just below the start
+                self.next_milestone = from.map_or(0, |from| {
+                    from.saturating_sub(1) / MILESTONE_INTERVAL * MILESTONE_INTERVAL
+                });
+                now
+            }
+            RunState::Running { started } => started,
+            RunState::Finished { .. } | RunState::Invalidated => return None,
+        };
+
+        let time = now.saturating_sub(started);
+        if let Some(to) = to {
+            while self.next_milestone > 0 && to <= self.next_milestone {
+                self.splits.push(Split {
+                    moves: self.next_milestone,
+                    time,
+                });
+                self.next_milestone -= MILESTONE_INTERVAL;
+            }
+        }
+        if !solved {
+            return None;
+        }
+        self.splits.push(Split { moves: 0, time });
+        self.state = RunState::Finished { time };
+        Some(time)
+    }
+
+    pub fn invalidate(&mut self) {
+        self.state = RunState::Invalidated;
+    }
+}
+
+/// The fastest finished run of each puzzle, by the state that it starts from.
+/// These are persisted across sessions.
+#[derive(Default)]
+pub struct BestTimes {
+    times: HashMap<BoardId, Duration>,
+}
+
+impl BestTimes {
+    /// Load the best times of previous sessions, corrupt entries are skipped
+    pub fn load() -> Self {
+        let stored = get_storage().and_then(|storage| storage.get_item(STORAGE_KEY).ok()?);
+        Self::deserialize(&stored.unwrap_or_default())
+    }
+
+    fn save(&self) {
+        let Some(storage) = get_storage() else {
+            return;
+        };
+        if let Err(error) = storage.set_item(STORAGE_KEY, &self.serialize()) {
+            log::warn!("Failed to store best times: {error:?}");
+        }
+    }
+
+    fn serialize(&self) -> String {
+        self.times
+            .iter()
+            .map(|(start, time)| format!("{start:x}:{}", time.as_millis()))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn deserialize(stored: &str) -> Self {
+        let times = stored
+            .split(',')
+            .filter_map(|entry| {
+                let (start, millis) = entry.split_once(':')?;
+                Some((
+                    BoardId::from_str_radix(start, 16).ok()?,
+                    Duration::from_millis(millis.parse().ok()?),
+                ))
+            })
+            .collect();
+        Self { times }
+    }
+
+    pub fn get(&self, start: BoardId) -> Option<Duration> {
+        self.times.get(&start).copied()
+    }
+
+    /// Record a finished run, and persist it if it is the best so far
+    /// @returns whether the run is a new best time
+    pub fn record(&mut self, start: BoardId, time: Duration) -> bool {
+        // Other games on the page may have recorded times since these were loaded
+        if get_storage().is_some() {
+            *self = Self::load();
+        }
+        if self.get(start).is_some_and(|best| best <= time) {
+            return false;
+        }
+        self.times.insert(start, time);
+        self.save();
+        true
+    }
+}
@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::views::speedrun::{BestTimes, RunState, Speedrun, Split};
+use std::time::Duration;
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+
+#[test]
+fn test_run() {
+    let mut run = Speedrun::default();
+    assert_eq!(run.elapsed(secs(5)), Some(Duration::ZERO));
+
+    // The timer starts at the first move
+    assert_eq!(run.record_move(secs(10), Some(21), Some(20), false), None);
+    assert_eq!(run.state(), RunState::Running { started: secs(10) });
+    assert_eq!(run.elapsed(secs(12)), Some(secs(2)));
+    assert_eq!(run.record_move(secs(13), Some(20), Some(21), false), None);
+    // Getting back within 20 moves doesn't take another split
+    assert_eq!(run.record_move(secs(14), Some(21), Some(20), false), None);
+    assert_eq!(run.record_move(secs(20), Some(20), Some(11), false), None);
+    assert_eq!(
+        run.record_move(secs(25), Some(11), Some(0), true),
+        Some(secs(15))
+    );
+
+    assert_eq!(run.state(), RunState::Finished { time: secs(15) });
+    assert_eq!(run.elapsed(secs(100)), Some(secs(15)));
+    assert_eq!(
+        run.splits(),
+        [
+            Split {
+                moves: 20,
+                time: Duration::ZERO,
+            },
+            Split {
+                moves: 10,
+                time: secs(15),
+            },
+            Split {
+                moves: 0,
+                time: secs(15),
+            },
+        ]
+    );
+    // Moves after the finish don't count
+    assert_eq!(run.record_move(secs(30), Some(0), Some(1), false), None);
+    assert_eq!(run.splits().len(), 3);
+}
+
+#[test]
+fn test_run_into_trap() {
+    let mut run = Speedrun::default();
+    assert_eq!(run.record_move(secs(1), Some(12), Some(11), false), None);
+    // A state that can't reach the solution neither finishes the run nor takes a split
+    assert_eq!(run.record_move(secs(2), Some(11), None, false), None);
+    assert_eq!(run.record_move(secs(3), None, None, false), None);
+    assert_eq!(run.state(), RunState::Running { started: secs(1) });
+    assert!(run.splits().is_empty());
+}
+
+#[test]
+fn test_invalidate() {
+    let mut run = Speedrun::default();
+    run.record_move(secs(1), Some(5), Some(4), false);
+    run.invalidate();
+    assert_eq!(run.elapsed(secs(2)), None);
+    assert_eq!(run.record_move(secs(3), Some(1), Some(0), true), None);
+    assert_eq!(run.state(), RunState::Invalidated);
+}
+
+#[test]
+fn test_best_times_round_trip() {
+    let best_times = BestTimes::deserialize("c0ffee:61500,bogus,1:x");
+    assert_eq!(best_times.get(0xc0ffee), Some(Duration::from_millis(61500)));
+    assert_eq!(best_times.times.len(), 1);
+    assert_eq!(
+        BestTimes::deserialize(&best_times.serialize()).times,
+        best_times.times
+    );
+}
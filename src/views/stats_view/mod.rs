@@ -0,0 +1,78 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod unittest;
+
+use crate::board::{get_annotated_positions, to_id, Board, BoardId};
+use crate::graph::Graph;
+use crate::views::utils::get_document;
+use web_sys::HtmlDivElement;
+
+/// Find the annotated position that this board is the mirror image of, if any.
+/// Positions that are their own mirror image are recognized as themselves, not as a mirror image.
+fn find_mirrored_annotation(board: &Board) -> Option<&'static str> {
+    let id = to_id(board);
+    let mirrored_id = to_id(&board.mirrored());
+    get_annotated_positions()
+        .into_iter()
+        .find(|(_, annotated)| {
+            let annotated_id = to_id(annotated);
+            annotated_id != id && annotated_id == mirrored_id
+        })
+        .map(|(name, _)| name)
+}
+
+/// Count the moves that are left, the solved states are at distance 1, they lead to the "fake"
+/// solution at distance 0
+fn describe_moves_to_solution(distance: u32) -> String {
+    match distance.saturating_sub(1) {
+        1 => "1 move to solution".into(),
+        moves => format!("{moves} moves to solution"),
+    }
+}
+
+/// A panel with textual information about the current state
+pub struct StatsView {
+    stats_div: HtmlDivElement,
+}
+
+impl StatsView {
+    pub fn new(stats_div: HtmlDivElement) -> Self {
+        Self { stats_div }
+    }
+
+    pub fn set_data(&self, graph: &Graph, active_state: BoardId, symmetry_folding: bool) {
+        let mut lines = Vec::new();
+        let node = graph.get(active_state);
+        if let Some(distance) = node.and_then(|node| node.distance_to_solution()) {
+            lines.push(describe_moves_to_solution(distance));
+        }
+        if node.is_some_and(|node| node.dead_end_entry.is_some()) {
+            lines.push("This is a dead end, the way out is the way in".into());
+        }
+        match graph.checkpoints(active_state).len() {
+            0 => {}
+            1 => lines.push("Every solution passes through 1 bottleneck".into()),
+            count => lines.push(format!("Every solution passes through {count} bottlenecks")),
+        }
+        if symmetry_folding {
+            if let Some(name) = node.and_then(|node| find_mirrored_annotation(&node.board)) {
+                lines.push(format!("This is the mirror image of {name}"));
+            }
+        }
+
+        // Replace the contents with a paragraph per line
+        self.stats_div.set_inner_html("");
+        let document = get_document().expect("Failed to get document");
+        for line in lines {
+            let paragraph = document
+                .create_element("p")
+                .expect("Failed to create stats paragraph");
+            paragraph.set_text_content(Some(&line));
+            self.stats_div
+                .append_child(&paragraph)
+                .expect("Failed to append stats paragraph");
+        }
+    }
+}
@@ -0,0 +1,27 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board::{get_annotated_positions, get_start_board, parse_position};
+use crate::views::stats_view::{describe_moves_to_solution, find_mirrored_annotation};
+
+#[test]
+fn test_describe_moves_to_solution() {
+    assert_eq!(describe_moves_to_solution(82), "81 moves to solution");
+    assert_eq!(describe_moves_to_solution(2), "1 move to solution");
+    assert_eq!(describe_moves_to_solution(1), "0 moves to solution");
+}
+
+#[test]
+fn test_find_mirrored_annotation() {
+    // The start position is its own mirror image
+    assert_eq!(find_mirrored_annotation(&get_start_board()), None);
+
+    // The other classic layouts are recognized by their mirror image, but not as themselves
+    for (name, board) in get_annotated_positions().into_iter().skip(1) {
+        assert_ne!(board.mirrored(), board, "{name}");
+        assert_eq!(find_mirrored_annotation(&board.mirrored()), Some(name));
+        assert_eq!(find_mirrored_annotation(&board), None, "{name}");
+    }
+    let mirrored = parse_position("GBBA/HBBA/IFDC/JFDC/.EE.").unwrap();
+    assert_eq!(find_mirrored_annotation(&mirrored), Some("Yi Lu Jin Jun"));
+}
@@ -0,0 +1,223 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+use crate::board;
+use crate::views::moves_view::MoveEffectiveness;
+use anyhow::{anyhow, Error};
+use std::str::FromStr;
+
+/// A color as red, green and blue components in [0, 255]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Format as a CSS color with the given opacity
+    pub fn to_css(self, alpha: f64) -> String {
+        format!("rgba({},{},{},{alpha})", self.0, self.1, self.2)
+    }
+
+    /// Convert to components in [0.0, 1.0], as used by the graph's shaders
+    pub fn to_normalized(self) -> [f32; 3] {
+        [self.0, self.1, self.2].map(|component| component as f32 / 255.0)
+    }
+}
+
+/// The piece sizes that the theme has a color for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceKind {
+    Size1x1,
+    Size1x2,
+    Size2x1,
+    Size2x2,
+    /// Pieces of an unexpected size, e.g. in custom puzzles
+    Unknown,
+}
+
+impl PieceKind {
+    /// Look up the kind of a piece by its size, an unexpected size is logged
+    pub fn of(size: &board::Size) -> Self {
+        match (size.x, size.y) {
+            (1, 1) => Self::Size1x1,
+            (1, 2) => Self::Size1x2,
+            (2, 1) => Self::Size2x1,
+            (2, 2) => Self::Size2x2,
+            _ => {
+                log::warn!("Unknown Piece size: (x: {}, y: {})", size.x, size.y);
+                Self::Unknown
+            }
+        }
+    }
+}
+
+/// The colors that the board, graph, and moves views are drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// The piece colors by size, pieces of an unexpected size stand out in the last color
+    pub piece_1x1: Rgb,
+    pub piece_1x2: Rgb,
+    pub piece_2x1: Rgb,
+    pub piece_2x2: Rgb,
+    pub piece_unknown: Rgb,
+    pub piece_opacity: f64,
+    pub piece_highlight_opacity: f64,
+    /// The letters or numbers on the pieces, when they are labeled
+    pub piece_label: Rgb,
+    pub axis: Rgb,
+    pub trajectory: Rgb,
+    /// The move indicator colors, by how a move affects the distance to the solution
+    pub move_positive: Rgb,
+    pub move_neutral: Rgb,
+    pub move_negative: Rgb,
+    /// The active node, it should stand out from every color of the graph's gradient
+    pub graph_active: Rgb,
+    /// The nodes when they are not colored by a metric
+    pub graph_uniform: Rgb,
+    /// The nodes that can't reach the solution, when the other nodes are colored by a metric
+    pub graph_trap: Rgb,
+    /// The outline of highlighted nodes, which separates them from the nodes around them
+    pub graph_highlight_outline: Rgb,
+    /// The node that another view links to
+    pub graph_linked_highlight: Rgb,
+    /// The edges that the player has traversed
+    pub graph_edge: Rgb,
+    /// The optimal path from the active node to the solution, when it is shown
+    pub graph_solution_path: Rgb,
+}
+
+impl Theme {
+    /// The original colors (palette from https://mycolor.space/?hex=%23754BFF&sub=1)
+    pub const LIGHT: Theme = Theme {
+        piece_1x1: Rgb(75, 123, 255),
+        piece_1x2: Rgb(117, 75, 255),
+        piece_2x1: Rgb(75, 213, 255),
+        piece_2x2: Rgb(255, 207, 75),
+        piece_unknown: Rgb(255, 0, 255),
+        piece_opacity: 0.8,
+        piece_highlight_opacity: 1.0,
+        piece_label: Rgb(26, 26, 26),
+        axis: Rgb(179, 179, 179),
+        trajectory: Rgb(255, 68, 58),
+        move_positive: Rgb(0, 157, 119),
+        move_neutral: Rgb(75, 123, 255),
+        move_negative: Rgb(255, 68, 58),
+        graph_active: Rgb(255, 69, 59),
+        graph_uniform: Rgb(0, 0, 0),
+        graph_trap: Rgb(128, 128, 128),
+        graph_highlight_outline: Rgb(255, 255, 255),
+        graph_linked_highlight: Rgb(74, 122, 255),
+        graph_edge: Rgb(51, 51, 51),
+        graph_solution_path: Rgb(0, 157, 119),
+    };
+
+    /// Lighter variants of the original colors, for dark page backgrounds
+    pub const DARK: Theme = Theme {
+        piece_1x1: Rgb(125, 160, 255),
+        piece_1x2: Rgb(160, 130, 255),
+        piece_2x1: Rgb(125, 225, 255),
+        piece_2x2: Rgb(255, 220, 125),
+        piece_unknown: Rgb(255, 0, 255),
+        piece_opacity: 0.85,
+        piece_highlight_opacity: 1.0,
+        piece_label: Rgb(20, 20, 20),
+        axis: Rgb(110, 110, 110),
+        trajectory: Rgb(255, 105, 95),
+        move_positive: Rgb(40, 200, 150),
+        move_neutral: Rgb(125, 160, 255),
+        move_negative: Rgb(255, 105, 95),
+        graph_active: Rgb(255, 105, 95),
+        graph_uniform: Rgb(230, 230, 230),
+        graph_trap: Rgb(110, 110, 110),
+        graph_highlight_outline: Rgb(0, 0, 0),
+        graph_linked_highlight: Rgb(125, 160, 255),
+        graph_edge: Rgb(200, 200, 200),
+        graph_solution_path: Rgb(40, 200, 150),
+    };
+
+    /// Saturated, fully opaque colors
+    pub const HIGH_CONTRAST: Theme = Theme {
+        piece_1x1: Rgb(0, 80, 255),
+        piece_1x2: Rgb(110, 0, 230),
+        piece_2x1: Rgb(0, 190, 255),
+        piece_2x2: Rgb(255, 190, 0),
+        piece_unknown: Rgb(255, 0, 255),
+        piece_opacity: 1.0,
+        piece_highlight_opacity: 1.0,
+        piece_label: Rgb(0, 0, 0),
+        axis: Rgb(0, 0, 0),
+        trajectory: Rgb(230, 0, 0),
+        move_positive: Rgb(0, 130, 0),
+        move_neutral: Rgb(0, 0, 230),
+        move_negative: Rgb(220, 0, 0),
+        graph_active: Rgb(255, 0, 0),
+        graph_uniform: Rgb(0, 0, 0),
+        graph_trap: Rgb(0, 0, 0),
+        graph_highlight_outline: Rgb(255, 255, 255),
+        graph_linked_highlight: Rgb(0, 0, 255),
+        graph_edge: Rgb(0, 0, 0),
+        graph_solution_path: Rgb(0, 130, 0),
+    };
+
+    /// The Okabe-Ito palette, which remains distinguishable with the common kinds of color blindness
+    pub const COLORBLIND_SAFE: Theme = Theme {
+        piece_1x1: Rgb(0, 114, 178),
+        piece_1x2: Rgb(204, 121, 167),
+        piece_2x1: Rgb(86, 180, 233),
+        piece_2x2: Rgb(230, 159, 0),
+        piece_unknown: Rgb(0, 0, 0),
+        piece_opacity: 0.85,
+        piece_highlight_opacity: 1.0,
+        piece_label: Rgb(0, 0, 0),
+        axis: Rgb(179, 179, 179),
+        trajectory: Rgb(213, 94, 0),
+        move_positive: Rgb(0, 158, 115),
+        move_neutral: Rgb(0, 114, 178),
+        move_negative: Rgb(213, 94, 0),
+        graph_active: Rgb(213, 94, 0),
+        graph_uniform: Rgb(0, 0, 0),
+        graph_trap: Rgb(128, 128, 128),
+        graph_highlight_outline: Rgb(255, 255, 255),
+        graph_linked_highlight: Rgb(0, 114, 178),
+        graph_edge: Rgb(51, 51, 51),
+        graph_solution_path: Rgb(0, 158, 115),
+    };
+
+    /// Look up the color of a kind of piece
+    pub fn piece_color(&self, kind: PieceKind) -> Rgb {
+        match kind {
+            PieceKind::Size1x1 => self.piece_1x1,
+            PieceKind::Size1x2 => self.piece_1x2,
+            PieceKind::Size2x1 => self.piece_2x1,
+            PieceKind::Size2x2 => self.piece_2x2,
+            PieceKind::Unknown => self.piece_unknown,
+        }
+    }
+
+    /// Get the move indicator color for how a move impacts the resulting distance to the solution
+    pub fn move_color(&self, effectiveness: MoveEffectiveness) -> Rgb {
+        match effectiveness {
+            MoveEffectiveness::Positive => self.move_positive,
+            MoveEffectiveness::Neutral => self.move_neutral,
+            MoveEffectiveness::Negative => self.move_negative,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::LIGHT
+    }
+}
+
+impl FromStr for Theme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "light" => Ok(Theme::LIGHT),
+            "dark" => Ok(Theme::DARK),
+            "high-contrast" => Ok(Theme::HIGH_CONTRAST),
+            "colorblind-safe" => Ok(Theme::COLORBLIND_SAFE),
+            _ => Err(anyhow!("Unknown theme \"{s}\"")),
+        }
+    }
+}
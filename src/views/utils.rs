@@ -51,8 +51,24 @@ pub fn get_element_of_type<T: JsCast>(id: &str) -> Result<T, JsValue> {
     })
 }
 
+/// Cancel a timeout that was set on the window, failures are only logged
+pub fn cancel_timeout(timeout_id: i32) {
+    match get_window() {
+        Ok(window) => window.clear_timeout_with_handle(timeout_id),
+        Err(error) => log::warn!("Failed to cancel timeout: {error:?}"),
+    }
+}
+
 /// Convert DOMHighResTimeStamp into a Duration
 pub fn dom_high_res_timestamp_to_duration(timestamp: f64) -> Duration {
     // The DOMHighResTimeStamp is in milliseconds, convert it to a std time Duration
     Duration::from_micros((timestamp * 1000.0) as u64)
 }
+
+/// Get the current time as a Duration, this uses the same clock as the animation frame timestamps
+pub fn get_timestamp() -> Result<Duration, JsValue> {
+    let performance = get_window()?
+        .performance()
+        .ok_or(JsValue::from_str("Unable to access the performance timer"))?;
+    Ok(dom_high_res_timestamp_to_duration(performance.now()))
+}
@@ -12,9 +12,17 @@ import init, {WiggersGraaf} from "../pkg/wiggers_graaf.js";
 
 const GAME_CONTROL_RESTART_ID = "game-control-restart";
 const GAME_CONTROL_SOLVE_ID = "game-control-solve";
+const GAME_CONTROL_SOLVE_FROM_HERE_ID = "game-control-solve-from-here";
+const GAME_CONTROL_BACK_TO_BEST_ID = "game-control-back-to-best";
+const GAME_CONTROL_COPY_ID = "game-control-copy";
+const GAME_CONTROL_REPLAY_ID = "game-control-replay";
+const REPLAY_CONTROLS_ID = "replay-controls";
+const GAME_TEMPERATURE_ID = "game-temperature";
 const META_CANVAS_ID = "meta-canvas";
+const META_LEGEND_DIV_ID = "meta-legend";
 const GAME_CANVAS_ID = "game-canvas";
 const GAME_MOVES_DIV_ID = "game-moves"
+const META_STATS_DIV_ID = "meta-stats";
 
 let wiggers_graaf;
 
@@ -27,6 +35,65 @@ function registerSpector() {
 
 init().then(() => {
     registerSpector();
-    wiggers_graaf = new WiggersGraaf(META_CANVAS_ID, GAME_CANVAS_ID, GAME_MOVES_DIV_ID, GAME_CONTROL_RESTART_ID, GAME_CONTROL_SOLVE_ID);
+    // A custom puzzle can be given as a layout, e.g. ?puzzle=ABBC/ABBC/DEEF/DGHF/I..J,
+    // or in notation, e.g. ?puzzle=ABBC/ABBC/DEEF/DGHF/I2J+4x5+2x2@B1
+    const puzzle = new URLSearchParams(window.location.search).get("puzzle") ?? undefined;
+    try {
+        wiggers_graaf = new WiggersGraaf(META_CANVAS_ID, META_LEGEND_DIV_ID, GAME_CANVAS_ID, GAME_MOVES_DIV_ID, GAME_CONTROL_RESTART_ID, GAME_CONTROL_SOLVE_ID, GAME_TEMPERATURE_ID, META_STATS_DIV_ID, {puzzle});
+    } catch (error) {
+        lazyAnimation.cancel();
+        window.alert(error);
+        return;
+    }
     lazyAnimation.cancel();
+
+    document.getElementById(GAME_CONTROL_SOLVE_FROM_HERE_ID).onclick = () => {
+        try {
+            wiggers_graaf.solve_from_here();
+        } catch (error) {
+            console.info("Unable to solve from here", error);
+        }
+    };
+    document.getElementById(GAME_CONTROL_BACK_TO_BEST_ID).onclick = () => wiggers_graaf.back_to_best_known();
+    document.getElementById(GAME_CONTROL_COPY_ID).onclick = () => {
+        navigator.clipboard.writeText(wiggers_graaf.export_record())
+            .catch((error) => console.warn("Failed to copy game record", error));
+    };
+    registerReplayControls();
 });
+
+function registerReplayControls() {
+    const controls = document.getElementById(REPLAY_CONTROLS_ID);
+    const play = document.getElementById("replay-play");
+    const scrubber = document.getElementById("replay-scrubber");
+    let playing = false;
+
+    document.getElementById(GAME_CONTROL_REPLAY_ID).onclick = () => {
+        const record = window.prompt("Paste a copied game to replay it");
+        if (record) {
+            try {
+                wiggers_graaf.start_replay(record);
+            } catch (error) {
+                window.alert(`Unable to replay this game: ${error}`);
+            }
+        }
+    };
+    play.onclick = () => playing ? wiggers_graaf.replay_pause() : wiggers_graaf.replay_play();
+    document.getElementById("replay-step-back").onclick = () => wiggers_graaf.replay_step_back();
+    document.getElementById("replay-step-forward").onclick = () => wiggers_graaf.replay_step_forward();
+    document.getElementById("replay-exit").onclick = () => wiggers_graaf.stop_replay();
+    scrubber.oninput = () => wiggers_graaf.replay_seek(scrubber.valueAsNumber);
+
+    wiggers_graaf.on_event((event) => {
+        if (event.type === "replay_progress") {
+            controls.hidden = false;
+            playing = event.playing;
+            play.textContent = playing ? "⏸" : "▶";
+            play.ariaLabel = playing ? "Pause" : "Play";
+            scrubber.max = event.length - 1;
+            scrubber.value = event.position;
+        } else if (event.type === "replay_ended") {
+            controls.hidden = true;
+        }
+    });
+}
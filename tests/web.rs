@@ -0,0 +1,640 @@
+// SPDX-FileCopyrightText: 2025 Menno van der Graaf <mennovandergraaf@hotmail.com>
+// SPDX-License-Identifier: MIT
+
+//! Browser tests of the views, these build the game on a synthetic page and drive it like a player would.
+//! Run them with `wasm-pack test --headless --chrome` or `--firefox`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Function, Object, Promise, Reflect};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{
+    Document, Event, HtmlCanvasElement, HtmlElement, HtmlInputElement, PointerEvent,
+    PointerEventInit,
+};
+use wiggers_graaf::board::{
+    get_start_board, make_move, to_id, Coordinates, SlideDirection, SlideMove,
+};
+use wiggers_graaf::WiggersGraaf;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Long enough for a move to animate, and for the events to be delivered
+const SETTLE_TIME_MS: i32 = 500;
+
+/// The CSS size of the board canvas, so that pointer events can aim at its tiles
+const BOARD_WIDTH: f64 = 400.0;
+const BOARD_HEIGHT: f64 = 500.0;
+
+fn document() -> Document {
+    web_sys::window().unwrap().document().unwrap()
+}
+
+fn hex(board: &wiggers_graaf::board::Board) -> String {
+    format!("{:016x}", to_id(board))
+}
+
+/// The move that the tests make, the piece at B2 slides down into the empty row
+fn first_move() -> SlideMove {
+    SlideMove {
+        start: Coordinates { x: 1, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    }
+}
+
+/// Replace the page with fresh elements for the views, and build the game on them
+fn mount(puzzle: Option<&str>) -> Result<WiggersGraaf, JsValue> {
+    mount_with_options(puzzle.map_or(JsValue::UNDEFINED, JsValue::from))
+}
+
+/// Like mount, with the constructor's options
+fn mount_with_options(options: JsValue) -> Result<WiggersGraaf, JsValue> {
+    let document = document();
+    let body = document.body().unwrap();
+    body.set_inner_html("");
+    // A game writes its position into the URL, which the next game would start from
+    let window = web_sys::window().unwrap();
+    let path = window.location().pathname()?;
+    window
+        .history()?
+        .replace_state_with_url(&JsValue::NULL, "", Some(&path))?;
+
+    for (tag, id) in [
+        ("canvas", "meta-canvas"),
+        ("div", "meta-legend"),
+        ("canvas", "game-canvas"),
+        ("div", "game-moves"),
+        ("div", "game-control-restart"),
+        ("div", "game-control-solve"),
+        ("div", "game-temperature"),
+        ("div", "meta-stats"),
+    ] {
+        let element = document.create_element(tag)?;
+        element.set_id(id);
+        body.append_child(&element)?;
+    }
+    let board_canvas = board_canvas();
+    board_canvas
+        .style()
+        .set_property("width", &format!("{BOARD_WIDTH}px"))?;
+    board_canvas
+        .style()
+        .set_property("height", &format!("{BOARD_HEIGHT}px"))?;
+
+    WiggersGraaf::new(
+        "meta-canvas",
+        "meta-legend",
+        "game-canvas",
+        "game-moves",
+        "game-control-restart",
+        "game-control-solve",
+        "game-temperature",
+        "meta-stats",
+        options,
+    )
+}
+
+fn board_canvas() -> HtmlCanvasElement {
+    document()
+        .get_element_by_id("game-canvas")
+        .unwrap()
+        .dyn_into()
+        .unwrap()
+}
+
+/// Collect the types and states of the game's events, in the order that they are emitted
+fn record_events(game: &WiggersGraaf) -> Rc<RefCell<Vec<(String, Option<String>)>>> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events_clone = events.clone();
+    let callback = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        let field = |key: &str| Reflect::get(&event, &key.into()).ok()?.as_string();
+        events_clone
+            .borrow_mut()
+            .push((field("type").unwrap(), field("state")));
+    });
+    game.on_event(callback.into_js_value().unchecked_into::<Function>());
+    events
+}
+
+async fn sleep(milliseconds: i32) {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, milliseconds)
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+/// Send a pointer event to the board canvas, at coordinates relative to its top left corner
+fn dispatch_pointer(kind: &str, x: f64, y: f64) {
+    let canvas = board_canvas();
+    let rect = canvas.get_bounding_client_rect();
+    let init = PointerEventInit::new();
+    init.set_pointer_id(1);
+    init.set_pointer_type("mouse");
+    init.set_is_primary(true);
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_buttons(u16::from(kind != "pointerup"));
+    init.set_client_x((rect.left() + x) as i32);
+    init.set_client_y((rect.top() + y) as i32);
+    let event = PointerEvent::new_with_event_init_dict(kind, &init).unwrap();
+    canvas.dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_start() {
+    let game = mount(None).unwrap();
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+    assert!(!game.undo());
+
+    // The buttons are attached to the views
+    let moves: HtmlElement = document()
+        .get_element_by_id("game-moves")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    assert!(moves.child_element_count() > 0);
+}
+
+#[wasm_bindgen_test]
+async fn test_puzzle() {
+    let layout = "ABBC/ABBC/DEEF/DGHF/I..J";
+    let game = mount(Some(layout)).unwrap();
+    assert_eq!(game.get_state(), hex(&layout.parse().unwrap()));
+
+    assert!(mount(Some("ABBC/ABBC")).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_move_notation() {
+    let game = mount(None).unwrap();
+    let events = record_events(&game);
+    let moved = hex(&make_move(&get_start_board(), &first_move()).unwrap());
+
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), moved);
+    assert_eq!(
+        *events.borrow(),
+        [
+            ("state_changed".to_string(), Some(moved.clone())),
+            ("move_made".to_string(), Some(moved.clone())),
+        ]
+    );
+
+    // The piece can't move down twice
+    assert!(game.do_move_notation("B2>B1").is_err());
+    assert!(game.do_move_notation("nonsense").is_err());
+    assert_eq!(
+        game.explain_move("C1>C2").unwrap(),
+        Some("there is no piece to move".to_string())
+    );
+    assert_eq!(game.explain_move("B1>C1").unwrap(), None);
+
+    assert!(game.undo());
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+    assert!(game.redo());
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), moved);
+}
+
+#[wasm_bindgen_test]
+async fn test_drag() {
+    let game = mount(None).unwrap();
+    let events = record_events(&game);
+    // Wait for the board to be laid out in its canvas
+    sleep(SETTLE_TIME_MS).await;
+
+    // Drag the piece at B2 down by one tile, the bottom row is y = 0
+    let tile_width = BOARD_WIDTH / 4.0;
+    let tile_height = BOARD_HEIGHT / 5.0;
+    let x = 1.5 * tile_width;
+    let y = 3.5 * tile_height;
+    dispatch_pointer("pointerdown", x, y);
+    for step in 1..=4 {
+        dispatch_pointer("pointermove", x, y + tile_height * step as f64 / 4.0);
+    }
+    dispatch_pointer("pointerup", x, y + tile_height);
+    sleep(SETTLE_TIME_MS).await;
+
+    let moved = hex(&make_move(&get_start_board(), &first_move()).unwrap());
+    assert_eq!(game.get_state(), moved);
+    assert!(events
+        .borrow()
+        .contains(&("move_made".to_string(), Some(moved))));
+}
+
+#[wasm_bindgen_test]
+async fn test_restart() {
+    let game = mount(None).unwrap();
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    let events = record_events(&game);
+
+    let restart: HtmlElement = document()
+        .get_element_by_id("game-control-restart")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    restart.click();
+    sleep(SETTLE_TIME_MS).await;
+
+    let start = hex(&get_start_board());
+    assert_eq!(game.get_state(), start);
+    let events = events.borrow();
+    assert_eq!(events.first(), Some(&("restart".to_string(), None)));
+    assert!(events.contains(&("state_changed".to_string(), Some(start))));
+}
+
+#[wasm_bindgen_test]
+async fn test_history_scrubber() {
+    let game = mount(None).unwrap();
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    let moved = game.get_state();
+
+    let slider: HtmlInputElement = document()
+        .query_selector(".history-scrubber")
+        .unwrap()
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    assert_eq!(slider.max(), "1");
+    assert_eq!(slider.value(), "1");
+
+    // Dragging the slider shows the earlier state, letting go resumes play from there
+    let scrub_to = |position: &str, event_type: &str| {
+        slider.set_value(position);
+        slider
+            .dispatch_event(&Event::new(event_type).unwrap())
+            .unwrap();
+    };
+    scrub_to("0", "input");
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+    scrub_to("1", "input");
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), moved);
+    scrub_to("0", "change");
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+    assert!(game.redo());
+}
+
+#[wasm_bindgen_test]
+async fn test_with_elements() {
+    // Build on the elements of a first game, without looking them up by ID
+    mount(None).unwrap();
+    let options = Object::new();
+    for (key, id) in [
+        ("meta_canvas", "meta-canvas"),
+        ("meta_legend_div", "meta-legend"),
+        ("board_canvas", "game-canvas"),
+        ("moves_div", "game-moves"),
+        ("restart_div", "game-control-restart"),
+        ("solve_div", "game-control-solve"),
+        ("temperature_div", "game-temperature"),
+        ("stats_div", "meta-stats"),
+    ] {
+        let element = document().get_element_by_id(id).unwrap();
+        element.remove_attribute("id").unwrap();
+        Reflect::set(&options, &key.into(), &element).unwrap();
+    }
+    Reflect::set(
+        &options,
+        &"puzzle".into(),
+        &"ABBC/ABBC/DEEF/DGHF/I..J".into(),
+    )
+    .unwrap();
+    let game = WiggersGraaf::with_elements(&options).unwrap();
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+
+    // The stats div is not a canvas
+    let stats = Reflect::get(&options, &"stats_div".into()).unwrap();
+    Reflect::set(&options, &"board_canvas".into(), &stats).unwrap();
+    assert!(WiggersGraaf::with_elements(&options).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_queued_moves() {
+    let game = mount(None).unwrap();
+    let events = record_events(&game);
+
+    // The second move is requested while the first one animates, it is made afterward
+    game.do_move_notation("B2>B1").unwrap();
+    game.do_move_notation("C2>C1").unwrap();
+    sleep(2 * SETTLE_TIME_MS).await;
+
+    let second_move = SlideMove {
+        start: Coordinates { x: 2, y: 1 },
+        direction: SlideDirection::Down,
+        distance: 1,
+    };
+    let board = make_move(&get_start_board(), &first_move()).unwrap();
+    let board = make_move(&board, &second_move).unwrap();
+    assert_eq!(game.get_state(), hex(&board));
+    let moves_made = events
+        .borrow()
+        .iter()
+        .filter(|(kind, _state)| kind == "move_made")
+        .count();
+    assert_eq!(moves_made, 2);
+}
+
+#[wasm_bindgen_test]
+async fn test_long_press() {
+    let game = mount(None).unwrap();
+    let events = record_events(&game);
+    let button: HtmlElement = document()
+        .query_selector("#game-moves .game-move")
+        .unwrap()
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+
+    // Holding a touch previews the move, releasing it makes the move, the click that follows is ignored
+    let touch = |kind: &str| {
+        let init = PointerEventInit::new();
+        init.set_pointer_id(2);
+        init.set_pointer_type("touch");
+        init.set_bubbles(true);
+        let event = PointerEvent::new_with_event_init_dict(kind, &init).unwrap();
+        button.dispatch_event(&event).unwrap();
+    };
+    touch("pointerdown");
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+    touch("pointerup");
+    button.click();
+    sleep(2 * SETTLE_TIME_MS).await;
+
+    assert_ne!(game.get_state(), hex(&get_start_board()));
+    let moves_made = events
+        .borrow()
+        .iter()
+        .filter(|(kind, _state)| kind == "move_made")
+        .count();
+    assert_eq!(moves_made, 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_solve_from_here() {
+    // The piece at B4 slides aside, then the big piece slides down into the solution
+    let game = mount(Some("ACDE/FGHI/.BB./.BB./.J..")).unwrap();
+    let events = record_events(&game);
+
+    game.solve_from_here().unwrap();
+    sleep(6 * SETTLE_TIME_MS).await;
+
+    let events = events.borrow();
+    let kinds: Vec<&str> = events.iter().map(|(kind, _state)| kind.as_str()).collect();
+    assert_eq!(kinds.iter().filter(|kind| **kind == "move_made").count(), 2);
+    assert!(kinds.contains(&"solved"));
+    assert_eq!(kinds.last(), Some(&"replay_ended"));
+    let solved = events
+        .iter()
+        .find(|(kind, _state)| kind == "solved")
+        .and_then(|(_kind, state)| state.clone());
+    assert_eq!(Some(game.get_state()), solved);
+
+    // Nothing is left to solve, and the moves can be undone
+    assert!(game.solve_from_here().is_err());
+    assert!(game.undo());
+}
+
+#[wasm_bindgen_test]
+async fn test_shuffle() {
+    let game = mount(None).unwrap();
+    let events = record_events(&game);
+
+    // The start takes over eighty moves to solve, a few random moves can't get close to the solution
+    assert!(game.shuffle(4, 0, 20).is_err());
+    game.shuffle(10, 0, 1000).unwrap();
+    sleep(4 * SETTLE_TIME_MS).await;
+
+    let moves = events
+        .borrow()
+        .iter()
+        .filter(|(kind, _state)| kind == "move_made")
+        .count();
+    assert_eq!(moves, 10);
+    assert_ne!(game.get_state(), hex(&get_start_board()));
+}
+
+#[wasm_bindgen_test]
+async fn test_flip_board() {
+    let game = mount(None).unwrap();
+    game.do_move_notation("A1>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+
+    // The pieces slide across into the mirror image, which the standard puzzle can reach
+    let moved = make_move(&get_start_board(), &"A1>B1".parse().unwrap()).unwrap();
+    JsFuture::from(game.flip_board()).await.unwrap();
+    assert_eq!(game.get_state(), hex(&moved.mirrored()));
+}
+
+#[wasm_bindgen_test]
+async fn test_back_to_best_known() {
+    let game = mount(None).unwrap();
+    assert!(!game.back_to_best_known());
+
+    // The start is on every shortest path, so there is always an earlier state to go back to
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    game.do_move_notation("C2>C1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    assert!(game.back_to_best_known());
+    sleep(2 * SETTLE_TIME_MS).await;
+    let visited = [
+        hex(&get_start_board()),
+        hex(&make_move(&get_start_board(), &first_move()).unwrap()),
+    ];
+    assert!(visited.contains(&game.get_state()));
+
+    // Back on a shortest path, there's nothing better to go back to
+    assert!(!game.back_to_best_known());
+    assert!(visited.contains(&game.get_state()));
+}
+
+#[wasm_bindgen_test]
+async fn test_context_menu() {
+    let game = mount(None).unwrap();
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+
+    dispatch_pointer("contextmenu", BOARD_WIDTH / 2.0, BOARD_HEIGHT / 2.0);
+    let menu: HtmlElement = document()
+        .query_selector(".context-menu")
+        .unwrap()
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    assert!(!menu.hidden());
+
+    let items = document().query_selector_all(".context-menu-item").unwrap();
+    let undo: HtmlElement = (0..items.length())
+        .filter_map(|index| items.item(index)?.dyn_into::<HtmlElement>().ok())
+        .find(|item| item.text_content().as_deref() == Some("Undo"))
+        .unwrap();
+    undo.click();
+    sleep(SETTLE_TIME_MS).await;
+
+    assert!(menu.hidden());
+    assert_eq!(game.get_state(), hex(&get_start_board()));
+}
+
+#[wasm_bindgen_test]
+async fn test_graph_handle() {
+    let game = mount(None).unwrap();
+    let graph = game.graph();
+    let start = hex(&get_start_board());
+
+    let node = graph.node(&start).unwrap();
+    assert_eq!(Reflect::get(&node, &"id".into()).unwrap(), start);
+    assert_eq!(Reflect::get(&node, &"moves_to_start".into()).unwrap(), 0);
+    assert!(graph.node("0").unwrap().is_undefined());
+
+    let neighbors = graph.neighbors(&start).unwrap();
+    assert!(neighbors.length() > 0);
+    let neighbor = Reflect::get(&neighbors.get(0), &"node".into()).unwrap();
+    assert_eq!(
+        Reflect::get(&neighbor, &"moves_to_start".into()).unwrap(),
+        1
+    );
+
+    assert!(graph.nodes_at_distance(0).unwrap().length() > 0);
+    assert!(graph.node_count() > 1000);
+}
+
+#[wasm_bindgen_test]
+async fn test_destroy() {
+    let game = mount(None).unwrap();
+    let count_menus = || {
+        document()
+            .query_selector_all(".context-menu")
+            .unwrap()
+            .length()
+    };
+    let menus = count_menus();
+    game.destroy().unwrap();
+    sleep(SETTLE_TIME_MS).await;
+
+    assert_eq!(count_menus(), menus - 1);
+    let moves = document().get_element_by_id("game-moves").unwrap();
+    assert_eq!(moves.child_element_count(), 0);
+    // The board doesn't respond anymore
+    dispatch_pointer("contextmenu", BOARD_WIDTH / 2.0, BOARD_HEIGHT / 2.0);
+    assert_eq!(count_menus(), menus - 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_two_games() {
+    let first = mount(None).unwrap();
+    let options = Object::new();
+    for (key, tag) in [
+        ("meta_canvas", "canvas"),
+        ("meta_legend_div", "div"),
+        ("board_canvas", "canvas"),
+        ("moves_div", "div"),
+        ("restart_div", "div"),
+        ("solve_div", "div"),
+        ("temperature_div", "div"),
+        ("stats_div", "div"),
+    ] {
+        let element = document().create_element(tag).unwrap();
+        document().body().unwrap().append_child(&element).unwrap();
+        Reflect::set(&options, &key.into(), &element).unwrap();
+    }
+    let second = WiggersGraaf::with_elements(&options).unwrap();
+
+    // The games keep their own states
+    first.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    assert_ne!(first.get_state(), hex(&get_start_board()));
+    assert_eq!(second.get_state(), hex(&get_start_board()));
+}
+
+#[wasm_bindgen_test]
+async fn test_options() {
+    let start = make_move(&get_start_board(), &first_move()).unwrap();
+    let options = Object::new();
+    Reflect::set(
+        &options,
+        &"start_position".into(),
+        &start.to_notation().into(),
+    )
+    .unwrap();
+    Reflect::set(&options, &"theme".into(), &"dark".into()).unwrap();
+    Reflect::set(&options, &"auto_solve_speed".into(), &10.into()).unwrap();
+    let game = mount_with_options(options.clone().into()).unwrap();
+    assert_eq!(game.get_state(), hex(&start));
+
+    // Restarting returns to the configured start position
+    game.set_state(&hex(&get_start_board())).unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    game.restart();
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_state(), hex(&start));
+
+    Reflect::set(&options, &"auto_solve_speed".into(), &(-1).into()).unwrap();
+    assert!(mount_with_options(options.into()).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_speedrun() {
+    // The piece at B1 slides aside, then the big piece slides down into the solution
+    let game = mount(Some("ACDE/FGHI/.BB./.BB./.J..")).unwrap();
+    let events = record_events(&game);
+    assert_eq!(game.get_speedrun_time(), None);
+    game.set_speedrun(true);
+    assert_eq!(game.get_speedrun_time(), Some(0.0));
+
+    game.do_move_notation("B1>A1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    let speedrun = game.get_speedrun().unwrap();
+    let get = |key: &str| Reflect::get(&speedrun, &key.into()).unwrap();
+    assert_eq!(get("state"), "finished");
+    assert!(get("best_time").as_f64().is_some());
+    assert_eq!(get("splits").unchecked_into::<js_sys::Array>().length(), 1);
+    assert!(events
+        .borrow()
+        .iter()
+        .any(|(kind, _state)| kind == "speedrun_finished"));
+
+    // Restarting starts a new run, which no longer counts once the game is solved for the player
+    game.restart();
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(game.get_speedrun_time(), Some(0.0));
+    game.solve_from_here().unwrap();
+    assert_eq!(game.get_speedrun_time(), None);
+}
+
+#[wasm_bindgen_test]
+async fn test_lazy() {
+    let options = Object::new();
+    Reflect::set(&options, &"lazy_depth".into(), &2.into()).unwrap();
+    let game = mount_with_options(options.into()).unwrap();
+    let graph = game.graph();
+    let expanded = graph.node_count();
+    assert!(expanded < 1000);
+
+    // The graph grows around the states that the player reaches
+    game.do_move_notation("B2>B1").unwrap();
+    sleep(SETTLE_TIME_MS).await;
+    assert_eq!(
+        game.get_state(),
+        hex(&make_move(&get_start_board(), &first_move()).unwrap())
+    );
+    assert!(graph.node_count() > expanded);
+}